@@ -0,0 +1,182 @@
+// Generated by nyacore-autosplitter. Do not edit by hand.
+// Regenerate with `cargo test --features typescript`.
+
+/** An achievement (or completion goal) unlocked once all its flags are set */
+export type Achievement = { flags: number[]; id: string; name: string };
+
+/** Completion of every tracked achievement */
+export type AchievementProgress = { achievements: AchievementStatus[]; total: number; /** Unlocked achievements */ unlocked: number };
+
+/** Completion of one achievement */
+export type AchievementStatus = { id: string; name: string; /** Flags of the achievement that are set */ progress: number; total: number; unlocked: boolean };
+
+/** An event flag reading algorithm */
+export type AlgorithmCapability = { description: string; id: string };
+
+/** A segment boundary */
+export type Anchor = { /** Only count the edge while this flag is set ("first load after X") */ after_flag?: number | null; on: AnchorEvent };
+
+/** Edge that marks a segment boundary */
+export type AnchorEvent = { flag_id: number; type: "flag_set" } | { type: "load_start" } | { type: "load_end" } | { type: "blackscreen_start" } | { type: "blackscreen_end" } | { area: number; block?: number | null; type: "area_entered" };
+
+/** An Arena opponent a host can offer as a split choice */
+export type ArenaOpponent = { /** Event flag set once the fight has been won */ flag_id: number; id: string; name: string };
+
+/** A canonical attribute and the game's own name for it */
+export type AttributeMapping = { canonical: CanonicalAttribute; name: string };
+
+/** Sound files played on run events (requires the `audio` feature)  Paths may point to WAV, OGG Vorbis or MP3 files. Events without a sound are silent. */
+export type AudioCueConfig = { /** Falls back to `split` when not set */ boss_defeated?: string | null; /** Practice events of watched boss fights (see `boss_phases`) */ boss_phase?: string | null; enabled?: boolean; gold?: string | null; rule_violation?: string | null; split?: string | null; /** Playback volume (1.0 = original) */ volume?: number };
+
+/** Host-adjustable autosplitter settings */
+export type AutosplitterConfig = { /** Achievements tracked in `AutosplitterState::achievements`; empty uses the attached game's built-in table (see `achievements::builtin`) */ achievements?: Achievement[]; /** Announce splits with text-to-speech (requires the `tts` feature) */ announce_splits?: boolean; audio_cues?: AudioCueConfig; /** Bosses whose HP thresholds and phase flags are logged as practice events (see `boss_phases`) */ boss_phases?: BossPhaseWatch[]; /** Directory where a verification bundle (zip of the run log, splits, config and diagnostics) is written at the end of each attempt */ bundle_dir?: string | null; /** Directory where VOD chapter files (FFmpeg metadata and YouTube timestamps) are written at the end of each attempt */ chapters_dir?: string | null; /** Comparison (e.g., personal best) IGT per split, keyed by boss or trigger id; used for the deltas in split announcements */ comparison_igt_ms?: Record<string, number>; /** Boss flags read through both the game's reader and the cross-check reader (`set_cross_check`); they split only when both agree. Empty checks every boss flag while a cross-check reader is set. */ cross_check_flags?: number[]; /** Directory of game definitions and achievement tables used instead of the data compiled into the library (see `data_dir`) */ data_dir?: string | null; /** Seconds of per-tick watched flag values kept for diagnostics (`get_flag_history_json`, verification bundles); 0 disables */ flag_history_seconds?: number; /** Delay between process searches while the game is not running */ idle_backoff_initial_ms?: number; /** Cap for the exponential idle backoff */ idle_backoff_max_ms?: number; /** Attach in a limited mode when the game's memory cannot be read, tracking only the process and its window (see `limited_access`) */ low_privilege_fallback?: boolean; /** Batching and rate limiting of `poll_output` messages */ output?: OutputConfig; /** Interval between player position samples for the position trail (0 disables) */ position_trail_interval_ms?: number; /** Average memory reads per tick above which a warning is logged and `read_stats.over_budget` is set (0 disables) */ read_budget_per_tick?: number; /** Caps on the memory reads of the session (see `memory::sandbox`) */ read_limits?: ReadLimits; /** Wall-clock time the VOD recording started (ms since the Unix epoch); chapters are aligned to the start of the attempt when not set */ recording_started_unix_ms?: number | null; /** Entries kept in the run log; when it is full the oldest are dropped (0 keeps every entry) */ run_log_max_entries?: number; /** Template for the display name of boss splits and split triggers, e.g. `"{area} - {name} ({kill_count})"` (see `split_names`); None keeps the configured names */ split_name_template?: string | null; /** After a split, custom split triggers firing within this window are consumed without splitting, so one game event setting several watched flags cannot cascade into several splits (0 disables; see `CustomTrigger::suppression_ms`) */ split_suppression_ms?: number; /** Interval between stat snapshots written to the run log (0 disables) */ stat_snapshot_interval_ms?: number; /** Elden Ring: pause split evaluation in the Colosseum and while other players are in the world (warm-up matches can toggle boss flags) */ suspend_during_pvp?: boolean; /** Segments timed between a start and an end anchor (flag edges, loads, blackscreens, area entry), logged as `segment_timed` splits */ timing_rules?: TimingRule[]; /** Mark boss flags set without IGT advancing or while the player was not loaded as suspicious (see `kill_validation`) */ validate_boss_kills?: boolean };
+
+/** Autosplitter state (serializable for FFI) */
+export type AutosplitterState = { /** AC6: timing of the current or last mission attempt (see `ac6_missions`) */ ac6_mission?: MissionTiming | null; /** Completion of the tracked achievements (None without an achievement table) */ achievements?: AchievementProgress | null; /** Game-specific values read each tick (e.g., "igt", "last_mission_rank") */ attributes?: Record<string, number>; boss_kill_counts?: Record<string, number>; /** Metadata of the configured bosses that have any, by boss id */ boss_metadata?: Record<string, Record<string, unknown>>; /** Defeated bosses ordered by `split_index`, each boss at most once; kept across re-attaching and cleared on reset */ bosses_defeated: DefeatedBoss[]; /** Grouped bosses whose split lost to a higher-priority trigger of their group; they do not split again until reset */ bosses_suppressed?: string[]; /** Cross-checked flags the two readers currently disagree on */ flag_disagreements?: FlagDisagreement[]; /** Frame rate of the attached game (games with a frame counter; None until a full window was sampled) */ frame_rate?: FrameRate | null; game_id: string; /** Sekiro: the Reflection of Strength or gauntlet being fought */ gauntlet?: GauntletProgress | null; /** Attached, but no save/character is loaded; flags are not evaluated */ in_main_menu?: boolean; /** Set while attached without memory access (see `limited_access`) */ limited_access?: LimitedAccess | null; /** The Steam overlay is open and capturing input; hosts should ignore split/reset hotkeys while it is set */ overlay_open?: boolean; process_attached: boolean; process_id?: number | null; /** Elden Ring: "colosseum" or "online" while a multiplayer session is active */ pvp_session?: string | null; /** Boss flag read plan of the attached game (diagnostics; None when flags are read one by one) */ read_plan?: ReadPlanStats | null; /** Memory reads of the attached game (None until the second tick) */ read_stats?: ReadStats | null; /** Memory reads denied by `read_limits` since attaching */ reads_denied?: number; running: boolean; /** Defeated bosses whose flags failed kill validation (subset of `bosses_defeated`) */ suspicious_bosses?: string[]; triggers_matched: number[] };
+
+/** A boss whose fight is reported in practice events */
+export type BossPhaseWatch = { boss_id: string; hp: ValuePointer; /** HP percentages reported when the HP drops to or below them */ hp_thresholds?: number[]; /** None takes the HP read when the fight starts as the max HP */ max_hp?: ValuePointer | null; phase_flags?: PhaseFlag[] };
+
+/** A file listed in the manifest */
+export type BundleFile = { crc32: number; name: string; size: number };
+
+/** Contents of `manifest.json` */
+export type BundleManifest = { arch: string; created_unix_ms: number; /** Other files of the bundle, in archive order */ files: BundleFile[]; format_version: number; game_id: string; library_version: string; os: string };
+
+/** Attribute concept shared between games */
+export type CanonicalAttribute = "strength" | "dexterity" | "intelligence" | "faith" | "death_count" | "player_loaded" | "loading" | "blackscreen" | "igt" | "level" | "health" | "focus" | "stamina" | "equip_load" | "luck" | "attack_power" | "ng_level" | "last_warp_point" | "frame_count";
+
+/** Everything the library supports */
+export type Capabilities = { algorithms: AlgorithmCapability[]; engines: EngineCapability[]; games: GameCapability[]; version: string };
+
+/** Comparison operator for attribute triggers */
+export type CompareOp = "eq" | "ne" | "gt" | "ge" | "lt" | "le";
+
+/** Custom split trigger checked alongside boss flags Matched trigger indices are reported in `AutosplitterState::triggers_matched` */
+export type CustomTrigger = { condition: TriggerCondition; /** Mutual-exclusion group: when several triggers of a group fire on the same tick, only the one with the highest `priority` splits */ group?: string | null; id: string; kind?: TriggerKind; metadata?: Record<string, unknown>; name: string; /** Rank within the group (higher wins; ties go to the earlier trigger) */ priority?: number; /** Overrides `AutosplitterConfig::split_suppression_ms` for this trigger (0 = never suppressed) */ suppression_ms?: number | null };
+
+/** A defeated boss in `AutosplitterState::bosses_defeated` */
+export type DefeatedBoss = { boss_id: string; /** Run log time of the defeat (ms since the log started) */ elapsed_ms: number; igt_ms?: number | null; /** Position of the boss in the configured boss list */ split_index: number };
+
+/** Timeline of the synthetic game */
+export type DemoScript = { /** Start a new attempt this long after the last step (None plays once) */ restart_after_ms?: number | null; steps: DemoStep[] };
+
+/** A scripted action, `at_ms` after the start of the attempt */
+export type DemoStep = { action: "set_flag"; flag_id: number } | { action: "clear_flag"; flag_id: number } | { action: "die" } | { action: "level_up"; levels: number };
+
+/** A place a host can offer as a DS2 position split */
+export type Ds2Landmark = { /** Kill counter offset of the boss that must be dead */ after_kill: number; id: string; name: string };
+
+/** Dark Souls 3 flag id parts */
+export type Ds3FlagId = { area: number; block: number; group: number; index: number; section: number };
+
+/** A generic engine usable from GameData or ASL configurations */
+export type EngineCapability = { /** Flag algorithm used by this engine */ algorithm: string; /** Identifier used in `[autosplitter] engine = "..."` */ id: string; uses_kill_counters: boolean };
+
+/** Elden Ring flag id parts */
+export type ErFlagId = { id: number; kind: "global" } | { area: number; block: number; kind: "dungeon"; local: number } | { kind: "overworld"; level: number; local: number; map: number; x: number; y: number };
+
+/** A flag the two readers currently disagree on */
+export type FlagDisagreement = { flag_id: number; /** Value read by the game's own reader */ primary: boolean; /** Value read by the cross-check reader */ secondary: boolean };
+
+/** Consecutive ticks with the same watched flags */
+export type FlagHistorySegment = { flags: number[]; ticks: FlagHistoryTick[] };
+
+/** Flag values on one tick */
+export type FlagHistoryTick = { /** Milliseconds since the run log was started */ elapsed_ms: number; igt_ms?: number | null; /** One character per flag of the segment, in order: '1' set, '0' unset */ values: string };
+
+/** Inclusive range of flag ids, optionally with a step (e.g., every 10th flag) */
+export type FlagRange = { first: number; last: number; step?: number };
+
+/** How many flags of a flag set must be set */
+export type FlagSetMode = "any" | "all" | { at_least: number };
+
+/** Frame rate of the attached game */
+export type FrameRate = { /** Frames per second over the last `FRAME_RATE_WINDOW_MS` */ fps: number; /** Highest rolling average since attaching */ max_fps: number; /** Lowest rolling average since attaching */ min_fps: number };
+
+/** A built-in game implementation */
+export type GameCapability = { /** Canonical attributes the game supports and its own names for them */ attributes: AttributeMapping[]; /** Readable values and detections (e.g., "event_flags", "igt", "position") */ features: string[]; /** Identifier accepted by `autosplitter_start` (e.g., "DarkSouls3") */ id: string; name: string; process_names: string[] };
+
+/** A gauntlet a host can offer as a category */
+export type Gauntlet = { /** Bosses in fight order */ bosses: ReflectionBoss[]; /** Event flag set while this gauntlet is being fought */ flag_id: number; id: string; name: string };
+
+/** Where the player is inside a reflection */
+export type GauntletProgress = { /** Name of the boss being fought (None once all are defeated) */ current_boss?: string | null; /** Bosses defeated so far in this gauntlet */ defeated: number; /** Gauntlet id, or None for a single Reflection of Strength */ gauntlet_id?: string | null; name: string; total: number };
+
+/** A memorable moment of an attempt */
+export type Highlight = { igt_ms?: number | null; kind: HighlightKind; /** Split name, or "Death 12" for deaths */ label: string; /** Milliseconds from the recording start (`recording_started_unix_ms`), or from the start of the attempt when it is not known */ offset_ms: number; /** Wall-clock time in milliseconds since the Unix epoch */ unix_ms: number };
+
+/** What made a moment memorable */
+export type HighlightKind = "split" | "death" | "rule_violation" | "gold";
+
+/** What is known about a game whose memory cannot be read */
+export type LimitedAccess = { /** The game window has the keyboard focus (None: unknown) */ foreground?: boolean | null; /** Process name of the game */ process_name: string; /** A visible top-level window of the game exists (None: unknown on this platform) */ window_found?: boolean | null; /** Title of the game window */ window_title?: string | null };
+
+/** Timing of the current (or last) mission attempt */
+export type MissionTiming = { /** Clear time reported by the game */ clear_time_ms?: number | null; /** IGT when the mission was cleared */ ended_igt_ms?: number | null; /** An attempt is in progress */ in_mission: boolean; /** Clear rank reported by the game ("S" to "D") */ rank?: string | null; /** Restarts and checkpoint reloads during the attempt */ restarts?: number; /** IGT when the attempt started */ started_igt_ms?: number | null };
+
+/** Rate limiting and coalescing policy */
+export type OutputConfig = { /** Waiting splits that are coalesced into one bulk sync message (0 = never) */ bulk_sync_threshold?: number; /** Messages released per second, also the burst size (0 = unlimited) */ max_messages_per_second?: number };
+
+/** A message for an integration */
+export type OutputMessage = { entry: RunLogEntry; type: "event" } | { entries: RunLogEntry[]; type: "bulk_sync" };
+
+/** An event flag the game sets when the boss enters a new phase */
+export type PhaseFlag = { flag_id: number; /** Name reported in the event (e.g., "phase_2") */ name: string };
+
+/** Read caps of a session; the default imposes none */
+export type ReadLimits = { /** Largest single read in bytes (0 = unlimited) */ max_bytes_per_read?: number; /** Reads allowed per tick (0 = unlimited); further reads in the tick fail */ max_reads_per_tick?: number; /** Only allow reads inside the main module and the writable regions of the process */ restrict_to_game_memory?: boolean };
+
+/** Expected memory traffic of a compiled plan */
+export type ReadPlanStats = { /** Distinct blocks (one address resolution each per tick) */ blocks: number; /** Block resolutions plus word reads per tick */ expected_reads_per_tick: number; /** Watched flags */ flags: number; /** Distinct flag words (one read each per tick) */ word_reads: number };
+
+/** Memory traffic of the attached game */
+export type ReadStats = { /** Average bytes per tick over the rolling window */ avg_bytes_per_tick: number; /** Average reads per tick over the rolling window */ avg_reads_per_tick: number; /** Bytes transferred during the last tick */ bytes_last_tick: number; /** The average exceeds `read_budget_per_tick` */ over_budget: boolean; /** Reads during the last tick */ reads_last_tick: number };
+
+/** Why the loop dropped a handle to re-attach */
+export type ReattachReason = "zombie" | "reads_failing";
+
+/** A boss fought as a reflection */
+export type ReflectionBoss = { /** Event flag set when the reflection is won */ flag_id: number; id: string; name: string };
+
+/** Where the player came back after dying */
+export type RespawnPoint = "stake" | "grace";
+
+/** Something that happened during a run */
+export type RunEvent = { game: string; type: "process_attached" } | { type: "process_detached" } | { reason: ReattachReason; type: "reattaching" } | { type: "reset" } | { boss_id: string; boss_name: string; /** Name rendered from `split_name_template`, when one is set */ display_name?: string | null; flag_id: number; /** Host metadata of the boss (see `BossFlag::metadata`) */ metadata?: Record<string, unknown>; /** Set when kill validation flagged the split */ suspicion?: Suspicion | null; type: "boss_defeated" } | { /** Name rendered from `split_name_template`, when one is set */ display_name?: string | null; index: number; kind?: TriggerKind; /** Host metadata of the trigger (see `CustomTrigger::metadata`) */ metadata?: Record<string, unknown>; name: string; trigger_id: string; type: "trigger_matched" } | { duration_ms: number; igt_duration_ms?: number | null; name: string; rule_id: string; start_elapsed_ms: number; type: "segment_timed" } | { stats: Record<string, number>; type: "stat_snapshot" } | { attribute: string; from: number; to: number; type: "level_increased" } | { death_count: number; position?: WorldPosition | null; type: "player_died" } | { position: WorldPosition; respawn: RespawnPoint; type: "player_respawned" } | { destination: number; type: "warp_initiated" } | { highlights: Highlight[]; type: "run_highlights" } | { achievement_id: string; name: string; type: "achievement_unlocked" } | { from: number; to: number; type: "value_changed"; watch_id: string } | { boss_id: string; fight_ms: number; hp: number; max_hp: number; percent: number; type: "boss_hp_threshold" } | { boss_id: string; fight_ms: number; flag_id: number; phase: string; type: "boss_phase_changed" } | { type: "mission_started" } | { attempt_igt_ms: number; restarts: number; type: "mission_restarted" } | { attempt_igt_ms: number; clear_time_ms: number; rank?: string | null; restarts: number; type: "mission_completed" };
+
+/** A run event with its timestamps */
+export type RunLogEntry = { /** Milliseconds since the log was started */ elapsed_ms: number; event: RunEvent; /** Host timer reading for the event, once the host clock is calibrated */ host_ms?: number | null; /** In-game time when the event was recorded, if the game exposes it */ igt_ms?: number | null; /** Time spent in menus or paused since the attempt started (see `menu_time`), once a game has been attached */ menu_time_ms?: number | null };
+
+/** A Sculptor's Idol a host can offer as a split choice */
+export type SculptorsIdol = { /** Area the idol is in, for grouping in pickers */ area: string; /** Event flag set when the player first rests at the idol */ flag_id: number; id: string; name: string };
+
+/** Why a boss kill was marked suspicious */
+export type Suspicion = "igt_not_advanced" | "player_not_loaded" | "manual";
+
+/** A segment timed from its start anchor to its end anchor */
+export type TimingRule = { end: Anchor; /** Split key of the segment (comparisons, attempt history) */ id: string; name: string; start: Anchor };
+
+/** Trail together with the run events that happened along it */
+export type TrailExport = { events: RunLogEntry[]; points: TrailPoint[] };
+
+/** A sampled position */
+export type TrailPoint = { /** Milliseconds since the run log was started */ elapsed_ms: number; igt_ms?: number | null; /** Packed map id, for games where coordinates are local to a map (Elden Ring) */ map_id?: number | null; x: number; y: number; z: number };
+
+/** Condition that fires a custom trigger */
+export type TriggerCondition = { flag_id: number; type: "event_flag" } | { name: string; op: CompareOp; type: "attribute"; value: number } | { flags?: number[]; mode: FlagSetMode; range?: FlagRange | null; type: "flag_set" } | { after_flag?: number | null; destination: number; type: "warp" } | { after_flag?: number | null; map_id?: number | null; radius: number; type: "position"; x: number; y: number; z: number };
+
+/** What a matched custom trigger means for the run */
+export type TriggerKind = "split" | "rule_violation";
+
+/** A value at the end of a pointer chain from the main module (see `Watch`) */
+export type ValuePointer = { module_offset: number; offsets?: number[]; value_type: WatchType };
+
+/** A value watched for changes */
+export type Watch = { /** Only report changes to values passing `compare` against `value` */ compare?: CompareOp | null; id: string; /** Offset of the static pointer from the main module base */ module_offset: number; /** Each offset but the last is added and dereferenced; the last is added to get the value address (empty reads the value at the module offset) */ offsets?: number[]; value?: number; value_type: WatchType };
+
+/** How the bytes at the end of a chain are read */
+export type WatchType = "u8" | "u16" | "i32" | "u32" | "i64" | "f32";
+
+/** Player position in game world coordinates */
+export type WorldPosition = { /** Packed map id, for games where coordinates are local to a map (Elden Ring) */ map_id?: number | null; x: number; y: number; z: number };
@@ -0,0 +1,54 @@
+//! Autosplitter as a background service
+//!
+//! ```bash
+//! cargo run --example daemon -- schemas/ds3.toml [preset] [127.0.0.1:16835]
+//! ```
+//!
+//! Serves the state on a local socket (see `nyacore_autosplitter::daemon`)
+//! until SIGTERM/Ctrl+C. Under systemd, use `Type=notify` so the unit is
+//! reported ready once the socket is listening.
+
+use nyacore_autosplitter::daemon::{self, Daemon};
+use nyacore_autosplitter::{Autosplitter, BossFlag, GameData};
+use std::path::Path;
+use std::sync::Arc;
+
+fn main() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+    let path = args
+        .next()
+        .ok_or("Usage: daemon <game.toml> [preset] [listen address]")?;
+    let preset = args.next().filter(|p| !p.is_empty() && p != "-");
+    let listen = args.next().unwrap_or_else(|| daemon::DEFAULT_LISTEN.to_string());
+
+    let game_data = GameData::from_file(Path::new(&path)).map_err(|e| e.to_string())?;
+    let bosses = match preset.as_deref() {
+        Some(preset) => game_data.get_preset_bosses(preset),
+        None => game_data.bosses.iter().collect(),
+    };
+    let boss_flags: Vec<BossFlag> = bosses
+        .into_iter()
+        .map(|boss| BossFlag {
+            boss_id: boss.id.clone(),
+            boss_name: boss.name.clone(),
+            flag_id: boss.flag_id,
+            is_dlc: boss.is_dlc,
+            metadata: boss.metadata.clone(),
+            group: None,
+            priority: 0,
+        })
+        .collect();
+
+    let autosplitter = Arc::new(Autosplitter::new());
+    autosplitter.start_with_game_data(game_data, boss_flags)?;
+
+    daemon::install_shutdown_handler();
+    let daemon = Daemon::bind(autosplitter, &listen)?;
+    println!("Listening on {}", daemon.local_addr()?);
+
+    #[cfg(target_os = "linux")]
+    let mut hooks = daemon::SystemdNotify;
+    #[cfg(not(target_os = "linux"))]
+    let mut hooks = daemon::NoHooks;
+    daemon.serve(&mut hooks)
+}
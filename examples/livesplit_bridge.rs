@@ -0,0 +1,114 @@
+//! Minimal LiveSplit Server bridge
+//!
+//! ```bash
+//! cargo run --example livesplit_bridge -- schemas/ds3.toml [preset] [host:port]
+//! ```
+//!
+//! Forwards boss kills and split triggers to LiveSplit Server (default
+//! `localhost:16834`) as `split` commands, resets as `reset`, and keeps
+//! LiveSplit's game time in sync with the game's IGT. Start the timer in
+//! LiveSplit as usual; the bridge only splits, resets and sets game time.
+//! Splits that arrive all at once (e.g., loading a late-game save) come in as
+//! a bulk sync and are not replayed.
+
+use nyacore_autosplitter::{Autosplitter, BossFlag, GameData, OutputMessage, RunEvent, TriggerKind};
+use std::io::Write;
+use std::net::TcpStream;
+use std::path::Path;
+use std::time::Duration;
+
+fn main() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+    let path = args
+        .next()
+        .ok_or("Usage: livesplit_bridge <game.toml> [preset] [host:port]")?;
+    let preset = args.next().filter(|p| !p.is_empty() && p != "-");
+    let address = args.next().unwrap_or_else(|| "localhost:16834".to_string());
+
+    let game_data = GameData::from_file(Path::new(&path)).map_err(|e| e.to_string())?;
+    let bosses = match preset.as_deref() {
+        Some(preset) => game_data.get_preset_bosses(preset),
+        None => game_data.bosses.iter().collect(),
+    };
+    let boss_flags: Vec<BossFlag> = bosses
+        .into_iter()
+        .map(|boss| BossFlag {
+            boss_id: boss.id.clone(),
+            boss_name: boss.name.clone(),
+            flag_id: boss.flag_id,
+            is_dlc: boss.is_dlc,
+            metadata: boss.metadata.clone(),
+            group: None,
+            priority: 0,
+        })
+        .collect();
+
+    let mut livesplit = TcpStream::connect(&address)
+        .map_err(|e| format!("Failed to connect to LiveSplit Server at {}: {}", address, e))?;
+    println!("Connected to LiveSplit Server at {}", address);
+    send(&mut livesplit, "initgametime")?;
+
+    let autosplitter = Autosplitter::new();
+    autosplitter.start_with_game_data(game_data, boss_flags)?;
+
+    let mut last_igt = None;
+    loop {
+        for message in autosplitter.poll_output() {
+            let entry = match message {
+                OutputMessage::Event { entry } => entry,
+                OutputMessage::BulkSync { entries } => {
+                    println!("Bulk sync: {} splits already done, not forwarded", entries.len());
+                    continue;
+                }
+            };
+            match &entry.event {
+                RunEvent::BossDefeated { boss_name, .. } => {
+                    println!("Split: {}", boss_name);
+                    send(&mut livesplit, "split")?;
+                }
+                RunEvent::TriggerMatched {
+                    name,
+                    kind: TriggerKind::Split,
+                    ..
+                } => {
+                    println!("Split: {}", name);
+                    send(&mut livesplit, "split")?;
+                }
+                RunEvent::SegmentTimed { name, .. } => {
+                    println!("Split: {}", name);
+                    send(&mut livesplit, "split")?;
+                }
+                RunEvent::Reset => send(&mut livesplit, "reset")?,
+                RunEvent::ProcessAttached { game } => println!("Attached to {}", game),
+                RunEvent::ProcessDetached => println!("Game closed"),
+                _ => {}
+            }
+        }
+
+        let igt = autosplitter.get_state().attributes.get("igt").copied();
+        if let Some(ms) = igt.filter(|_| igt != last_igt) {
+            send(&mut livesplit, &format!("setgametime {}", format_game_time(ms)))?;
+        }
+        last_igt = igt;
+
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+fn send(stream: &mut TcpStream, command: &str) -> Result<(), String> {
+    stream
+        .write_all(format!("{}\r\n", command).as_bytes())
+        .map_err(|e| format!("LiveSplit Server connection lost: {}", e))
+}
+
+/// LiveSplit time format: hh:mm:ss.fff
+fn format_game_time(ms: i64) -> String {
+    let total_seconds = ms / 1000;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60,
+        ms % 1000
+    )
+}
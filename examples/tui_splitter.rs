@@ -0,0 +1,160 @@
+//! Terminal splitter showing live splits and IGT
+//!
+//! ```bash
+//! cargo run --example tui_splitter -- schemas/ds3.toml [preset]
+//! cargo run --example tui_splitter -- --demo
+//! ```
+//!
+//! Loads a game configuration, watches its bosses (or a preset's) and shows
+//! each split with the IGT it was reached at; `--demo` plays the built-in
+//! synthetic game instead. `r` resets, `q` quits.
+
+use nyacore_autosplitter::{demo, Autosplitter, BossFlag, GameData, RunEvent};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Style, Stylize};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Table};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+fn main() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+    let path = args
+        .next()
+        .ok_or("Usage: tui_splitter <game.toml> [preset] | --demo")?;
+    let preset = args.next();
+
+    if path == "--demo" {
+        let boss_flags = demo::default_bosses();
+        let autosplitter = Autosplitter::new();
+        autosplitter.start_demo(boss_flags.clone(), None)?;
+        return show(&autosplitter, demo::DEMO_GAME_NAME, &boss_flags);
+    }
+
+    let game_data = GameData::from_file(Path::new(&path)).map_err(|e| e.to_string())?;
+    let boss_flags = boss_flags(&game_data, preset.as_deref());
+    if boss_flags.is_empty() {
+        return Err("No bosses to watch".to_string());
+    }
+    let title = game_data.game.name.clone();
+
+    let autosplitter = Autosplitter::new();
+    autosplitter.start_with_game_data(game_data, boss_flags.clone())?;
+    show(&autosplitter, &title, &boss_flags)
+}
+
+fn show(autosplitter: &Autosplitter, title: &str, boss_flags: &[BossFlag]) -> Result<(), String> {
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, autosplitter, title, boss_flags);
+    ratatui::restore();
+    autosplitter.stop();
+    result
+}
+
+fn boss_flags(game_data: &GameData, preset: Option<&str>) -> Vec<BossFlag> {
+    let bosses = match preset {
+        Some(preset) => game_data.get_preset_bosses(preset),
+        None => game_data.bosses.iter().collect(),
+    };
+    bosses
+        .into_iter()
+        .map(|boss| BossFlag {
+            boss_id: boss.id.clone(),
+            boss_name: boss.name.clone(),
+            flag_id: boss.flag_id,
+            is_dlc: boss.is_dlc,
+            metadata: boss.metadata.clone(),
+            group: None,
+            priority: 0,
+        })
+        .collect()
+}
+
+fn run(
+    terminal: &mut ratatui::DefaultTerminal,
+    autosplitter: &Autosplitter,
+    title: &str,
+    boss_flags: &[BossFlag],
+) -> Result<(), String> {
+    // IGT each boss was defeated at (None if the game does not expose IGT)
+    let mut split_times: HashMap<String, Option<i64>> = HashMap::new();
+
+    loop {
+        for entry in autosplitter.poll_events() {
+            match entry.event {
+                RunEvent::BossDefeated { boss_id, .. } => {
+                    split_times.insert(boss_id, entry.igt_ms);
+                }
+                RunEvent::Reset => split_times.clear(),
+                _ => {}
+            }
+        }
+
+        let state = autosplitter.get_state();
+        let status = if !state.process_attached {
+            "Waiting for game...".to_string()
+        } else if state.in_main_menu {
+            "In main menu".to_string()
+        } else {
+            format!("Attached (PID {})", state.process_id.unwrap_or(0))
+        };
+        let igt = state.attributes.get("igt").map_or("--".to_string(), |&ms| format_igt(ms));
+
+        let rows = boss_flags.iter().map(|boss| {
+            let (time, style) = match split_times.get(&boss.boss_id) {
+                Some(&Some(igt)) => (format_igt(igt), Style::default().fg(Color::Green)),
+                Some(None) => ("done".to_string(), Style::default().fg(Color::Green)),
+                None => ("-".to_string(), Style::default()),
+            };
+            Row::new(vec![boss.boss_name.clone(), time]).style(style)
+        });
+
+        terminal
+            .draw(|frame| {
+                let [header, splits, footer] = Layout::vertical([
+                    Constraint::Length(3),
+                    Constraint::Min(0),
+                    Constraint::Length(1),
+                ])
+                .areas(frame.area());
+
+                frame.render_widget(
+                    Paragraph::new(Line::from(vec![status.clone().into(), "   IGT ".into(), igt.clone().bold()]))
+                        .block(Block::bordered().title(title)),
+                    header,
+                );
+                frame.render_widget(
+                    Table::new(rows, [Constraint::Fill(1), Constraint::Length(12)])
+                        .block(Block::bordered().title("Splits")),
+                    splits,
+                );
+                frame.render_widget(Line::from("r: reset   q: quit").dim(), footer);
+            })
+            .map_err(|e| e.to_string())?;
+
+        if event::poll(Duration::from_millis(100)).map_err(|e| e.to_string())? {
+            if let Event::Key(key) = event::read().map_err(|e| e.to_string())? {
+                if key.kind == KeyEventKind::Press {
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                        KeyCode::Char('r') => autosplitter.reset(),
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn format_igt(ms: i64) -> String {
+    let total_seconds = ms / 1000;
+    format!(
+        "{}:{:02}:{:02}.{:01}",
+        total_seconds / 3600,
+        (total_seconds / 60) % 60,
+        total_seconds % 60,
+        (ms % 1000) / 100
+    )
+}
@@ -0,0 +1,123 @@
+//! Armored Core 6 Arena (Analysis) completion triggers
+//!
+//! Every Arena opponent has a completion flag that is set the first time the
+//! fight is won. [`opponents`] lists them for host pickers, and
+//! [`arena_triggers`] turns a selection into custom split triggers, so
+//! All-Arena runs and completionist overlays work without hand-written flag
+//! lists. Flag ids follow the numbering used by `schemas/ac6.toml`.
+
+use crate::config::{CustomTrigger, TriggerCondition, TriggerKind};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of trigger ids built by [`arena_triggers`]
+pub const TRIGGER_ID_PREFIX: &str = "arena.";
+
+/// (id, name, completion flag) in Arena order, lowest ranked first
+const OPPONENTS: &[(&str, &str, u32)] = &[
+    ("ring_freddy", "Ring Freddy", 60000),
+    ("chatty_stick", "Chatty Stick", 60010),
+    ("middle_flatwell", "Middle Flatwell", 60020),
+    ("g5_iguazu", "G5 Iguazu", 60030),
+    ("g4_volta", "G4 Volta", 60040),
+    ("g6_red", "G6 Red", 60050),
+    ("v8_pater", "V.VIII Pater", 60060),
+    ("v7_swinburne", "V.VII Swinburne", 60070),
+    ("g3_five_flowers", "G3 Five Flowers", 60080),
+    ("v5_hornet", "V.V Hornet", 60090),
+    ("g2_nail", "G2 Nail", 60100),
+    ("v4_rusty", "V.IV Rusty", 60110),
+    ("v3_okeeffe", "V.III O'Keeffe", 60120),
+    ("v2_snail", "V.II Snail", 60130),
+    ("g1_michigan", "G1 Michigan", 60140),
+    ("v1_freud", "V.I Freud", 60150),
+];
+
+/// An Arena opponent a host can offer as a split choice
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ArenaOpponent {
+    pub id: String,
+    pub name: String,
+    /// Event flag set once the fight has been won
+    pub flag_id: u32,
+}
+
+/// All Arena opponents in Arena order
+pub fn opponents() -> Vec<ArenaOpponent> {
+    OPPONENTS
+        .iter()
+        .map(|&(id, name, flag_id)| ArenaOpponent {
+            id: id.to_string(),
+            name: name.to_string(),
+            flag_id,
+        })
+        .collect()
+}
+
+/// Build split triggers for the selected opponents, in selection order
+///
+/// An empty selection means every opponent (All-Arena).
+pub fn arena_triggers(selected: &[String]) -> Result<Vec<CustomTrigger>, String> {
+    let all = opponents();
+    let chosen: Vec<&ArenaOpponent> = if selected.is_empty() {
+        all.iter().collect()
+    } else {
+        selected
+            .iter()
+            .map(|id| {
+                all.iter()
+                    .find(|opponent| &opponent.id == id)
+                    .ok_or_else(|| format!("Unknown arena opponent: {}", id))
+            })
+            .collect::<Result<_, _>>()?
+    };
+
+    Ok(chosen
+        .into_iter()
+        .map(|opponent| CustomTrigger {
+            id: format!("{}{}", TRIGGER_ID_PREFIX, opponent.id),
+            name: format!("Arena: {}", opponent.name),
+            condition: TriggerCondition::EventFlag {
+                flag_id: opponent.flag_id,
+            },
+            kind: TriggerKind::Split,
+            metadata: Default::default(),
+            group: None,
+            priority: 0,
+            suppression_ms: None,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_opponent_ids_and_flags_are_unique() {
+        let opponents = opponents();
+        let ids: HashSet<_> = opponents.iter().map(|o| o.id.as_str()).collect();
+        let flags: HashSet<_> = opponents.iter().map(|o| o.flag_id).collect();
+        assert_eq!(ids.len(), opponents.len());
+        assert_eq!(flags.len(), opponents.len());
+    }
+
+    #[test]
+    fn test_empty_selection_is_all_arena() {
+        let triggers = arena_triggers(&[]).unwrap();
+        assert_eq!(triggers.len(), OPPONENTS.len());
+        assert_eq!(triggers[0].id, "arena.ring_freddy");
+        assert_eq!(triggers.last().unwrap().name, "Arena: V.I Freud");
+    }
+
+    #[test]
+    fn test_selection_order_and_unknown_ids() {
+        let triggers = arena_triggers(&["v1_freud".to_string(), "g6_red".to_string()]).unwrap();
+        assert_eq!(triggers.len(), 2);
+        assert_eq!(triggers[0].condition, TriggerCondition::EventFlag { flag_id: 60150 });
+        assert_eq!(triggers[1].condition, TriggerCondition::EventFlag { flag_id: 60050 });
+
+        assert!(arena_triggers(&["raven".to_string()]).is_err());
+    }
+}
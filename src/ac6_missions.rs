@@ -0,0 +1,233 @@
+//! Armored Core 6 individual level timing
+//!
+//! IL leaderboards time single missions, so they need to know when an
+//! attempt started, when it ended and how often it was restarted.
+//! [`MissionTracker`] derives this from values the AC6 reader already has:
+//! the loading screen, the IGT and the last mission result the game writes
+//! when a mission is cleared.
+//!
+//! A mission attempt starts when a loading screen ends (the sortie). Further
+//! loading screens during the attempt are restarts; the game shows the same
+//! loading screen for a restart from the beginning and for a checkpoint
+//! reload, so both are counted in `restarts`. The attempt ends when the last
+//! mission result changes, and the loading screen back to the garage that
+//! follows does not start a new attempt. Abandoning a mission is only seen as
+//! a loading screen, so it counts as a restart of the attempt.
+//!
+//! Timestamps are IGT milliseconds, which exclude loading screens like the
+//! game's own clear time. The timing of the current attempt is published in
+//! `AutosplitterState::ac6_mission`, and `mission_started`,
+//! `mission_restarted` and `mission_completed` events are logged; they never
+//! split.
+
+use crate::run_log::RunEvent;
+use serde::{Deserialize, Serialize};
+
+/// Values of one tick read from the game
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MissionSample {
+    pub loading: bool,
+    pub igt_ms: i64,
+    /// Rank of the last cleared mission as stored by the game (0 = none)
+    pub last_rank: i32,
+    /// Clear time of the last cleared mission
+    pub last_time_ms: i64,
+}
+
+/// Timing of the current (or last) mission attempt
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct MissionTiming {
+    /// An attempt is in progress
+    pub in_mission: bool,
+    /// IGT when the attempt started
+    pub started_igt_ms: Option<i64>,
+    /// IGT when the mission was cleared
+    #[serde(default)]
+    pub ended_igt_ms: Option<i64>,
+    /// Restarts and checkpoint reloads during the attempt
+    #[serde(default)]
+    pub restarts: u32,
+    /// Clear time reported by the game
+    #[serde(default)]
+    pub clear_time_ms: Option<i64>,
+    /// Clear rank reported by the game ("S" to "D")
+    #[serde(default)]
+    pub rank: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    /// Garage, briefing or not known yet
+    #[default]
+    Idle,
+    InMission,
+    /// Cleared; the next loading screen goes back to the garage
+    Returning,
+}
+
+/// Per-attach mission attempt detection
+#[derive(Debug, Default)]
+pub struct MissionTracker {
+    phase: Phase,
+    last: Option<MissionSample>,
+    timing: MissionTiming,
+}
+
+impl MissionTracker {
+    /// Timing of the current (or last) attempt; None before the first one
+    pub fn timing(&self) -> Option<&MissionTiming> {
+        self.timing.started_igt_ms.map(|_| &self.timing)
+    }
+
+    /// Feed one tick; returns the mission events of this tick
+    pub fn observe(&mut self, sample: MissionSample) -> Vec<RunEvent> {
+        // The first tick only sets the baseline: a result already stored
+        // when attaching is not a clear
+        let Some(last) = self.last.replace(sample) else {
+            return Vec::new();
+        };
+
+        let mut events = Vec::new();
+        let result_changed = sample.last_time_ms > 0
+            && (sample.last_time_ms, sample.last_rank) != (last.last_time_ms, last.last_rank);
+        if result_changed && self.phase == Phase::InMission {
+            let started = self.timing.started_igt_ms.unwrap_or(sample.igt_ms);
+            let rank = rank_name(sample.last_rank).map(str::to_string);
+            self.timing.in_mission = false;
+            self.timing.ended_igt_ms = Some(sample.igt_ms);
+            self.timing.clear_time_ms = Some(sample.last_time_ms);
+            self.timing.rank = rank.clone();
+            self.phase = Phase::Returning;
+            events.push(RunEvent::MissionCompleted {
+                rank,
+                clear_time_ms: sample.last_time_ms,
+                attempt_igt_ms: sample.igt_ms.saturating_sub(started),
+                restarts: self.timing.restarts,
+            });
+        } else if result_changed {
+            // Attached during the attempt: only skip the garage return
+            self.phase = Phase::Returning;
+        }
+
+        if last.loading && !sample.loading {
+            match self.phase {
+                Phase::Idle => {
+                    self.timing = MissionTiming {
+                        in_mission: true,
+                        started_igt_ms: Some(sample.igt_ms),
+                        ..Default::default()
+                    };
+                    self.phase = Phase::InMission;
+                    events.push(RunEvent::MissionStarted);
+                }
+                Phase::InMission => {
+                    self.timing.restarts += 1;
+                    events.push(RunEvent::MissionRestarted {
+                        restarts: self.timing.restarts,
+                        attempt_igt_ms: sample
+                            .igt_ms
+                            .saturating_sub(self.timing.started_igt_ms.unwrap_or(sample.igt_ms)),
+                    });
+                }
+                Phase::Returning => self.phase = Phase::Idle,
+            }
+        }
+        events
+    }
+}
+
+/// Name of a mission rank as stored by the game
+fn rank_name(rank: i32) -> Option<&'static str> {
+    match rank {
+        1 => Some("D"),
+        2 => Some("C"),
+        3 => Some("B"),
+        4 => Some("A"),
+        5 => Some("S"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(loading: bool, igt_ms: i64, last_time_ms: i64) -> MissionSample {
+        MissionSample {
+            loading,
+            igt_ms,
+            last_rank: if last_time_ms > 0 { 5 } else { 0 },
+            last_time_ms,
+        }
+    }
+
+    #[test]
+    fn test_attempt_with_restarts() {
+        let mut tracker = MissionTracker::default();
+        // Attached in the garage with an older result stored
+        assert!(tracker.observe(sample(false, 10_000, 95_000)).is_empty());
+        assert!(tracker.timing().is_none());
+
+        // Sortie
+        tracker.observe(sample(true, 10_000, 95_000));
+        assert_eq!(tracker.observe(sample(false, 10_000, 95_000)), [RunEvent::MissionStarted]);
+        assert!(tracker.timing().unwrap().in_mission);
+
+        // Checkpoint reload, then a restart
+        tracker.observe(sample(true, 40_000, 95_000));
+        assert_eq!(
+            tracker.observe(sample(false, 40_000, 95_000)),
+            [RunEvent::MissionRestarted {
+                restarts: 1,
+                attempt_igt_ms: 30_000
+            }]
+        );
+        tracker.observe(sample(true, 50_000, 95_000));
+        tracker.observe(sample(false, 50_000, 95_000));
+
+        let events = tracker.observe(sample(false, 130_000, 78_000));
+        assert_eq!(
+            events,
+            [RunEvent::MissionCompleted {
+                rank: Some("S".to_string()),
+                clear_time_ms: 78_000,
+                attempt_igt_ms: 120_000,
+                restarts: 2,
+            }]
+        );
+        let timing = tracker.timing().unwrap();
+        assert!(!timing.in_mission);
+        assert_eq!(timing.started_igt_ms, Some(10_000));
+        assert_eq!(timing.ended_igt_ms, Some(130_000));
+        assert_eq!(timing.clear_time_ms, Some(78_000));
+    }
+
+    #[test]
+    fn test_garage_return_is_not_a_start() {
+        let mut tracker = MissionTracker::default();
+        tracker.observe(sample(true, 0, 0));
+        tracker.observe(sample(false, 0, 0));
+        tracker.observe(sample(false, 60_000, 58_000));
+
+        // Back to the garage
+        tracker.observe(sample(true, 61_000, 58_000));
+        assert!(tracker.observe(sample(false, 61_000, 58_000)).is_empty());
+        // Next sortie
+        tracker.observe(sample(true, 90_000, 58_000));
+        assert_eq!(tracker.observe(sample(false, 90_000, 58_000)), [RunEvent::MissionStarted]);
+        assert_eq!(tracker.timing().unwrap().started_igt_ms, Some(90_000));
+        assert_eq!(tracker.timing().unwrap().restarts, 0);
+    }
+
+    #[test]
+    fn test_result_outside_attempt_is_ignored() {
+        let mut tracker = MissionTracker::default();
+        // Attached mid-mission: no attempt was seen starting
+        tracker.observe(sample(false, 0, 0));
+        assert!(tracker.observe(sample(false, 5_000, 4_000)).is_empty());
+        tracker.observe(sample(true, 6_000, 4_000));
+        assert!(tracker.observe(sample(false, 6_000, 4_000)).is_empty());
+        assert!(tracker.timing().is_none());
+    }
+}
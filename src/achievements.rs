@@ -0,0 +1,209 @@
+//! Achievement progress from event flags
+//!
+//! Steam achievements for defeating bosses are unlocked by the same event
+//! flags the splits watch, so completion can be tracked without the host
+//! scraping Steam. [`builtin`] maps flags to achievements for Dark Souls 3
+//! and Elden Ring; `AutosplitterConfig::achievements` replaces the table,
+//! e.g. with a goal like "All Remembrances" listing the 15 remembrance boss
+//! flags, which the state then reports as "12/15".
+//!
+//! Flags are re-read every [`ACHIEVEMENT_POLL_INTERVAL_MS`], not every tick.
+//! Flag ids follow the boss flags in `schemas/`.
+
+use crate::run_log::RunEvent;
+use crate::GameType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Interval between achievement flag reads
+pub const ACHIEVEMENT_POLL_INTERVAL_MS: u64 = 2000;
+
+/// (id, name, flags) per game; an achievement is unlocked once all its
+/// flags are set
+const DARK_SOULS_3: &[(&str, &str, &[u32])] = &[
+    ("lord_of_cinder_abyss_watchers", "Lord of Cinder: Abyss Watchers", &[13800800]),
+    ("lord_of_cinder_yhorm", "Lord of Cinder: Yhorm the Giant", &[13700800]),
+    ("lord_of_cinder_aldrich", "Lord of Cinder: Aldrich", &[13700850]),
+    ("lord_of_cinder_lothric", "Lord of Cinder: Lothric, Younger Prince", &[14500800]),
+];
+
+const ELDEN_RING: &[(&str, &str, &[u32])] = &[
+    ("shardbearer_godrick", "Shardbearer Godrick", &[10000850]),
+    ("rennala", "Rennala, Queen of the Full Moon", &[14000850]),
+    ("shardbearer_radahn", "Shardbearer Radahn", &[1052380800]),
+    ("shardbearer_rykard", "Shardbearer Rykard", &[16000800]),
+    ("shardbearer_morgott", "Shardbearer Morgott", &[11000850]),
+    ("fire_giant", "Fire Giant", &[1052520800]),
+    ("shardbearer_mohg", "Shardbearer Mohg", &[12050800]),
+    ("shardbearer_malenia", "Shardbearer Malenia", &[15000800]),
+    ("maliketh", "Maliketh the Black Blade", &[13000850]),
+    ("hoarah_loux", "Hoarah Loux the Warrior", &[11050800]),
+    ("elden_beast", "Elden Beast", &[19000800]),
+    ("placidusax", "Dragonlord Placidusax", &[13000830]),
+];
+
+/// An achievement (or completion goal) unlocked once all its flags are set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub flags: Vec<u32>,
+}
+
+/// Completion of one achievement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct AchievementStatus {
+    pub id: String,
+    pub name: String,
+    /// Flags of the achievement that are set
+    pub progress: usize,
+    pub total: usize,
+    pub unlocked: bool,
+}
+
+/// Completion of every tracked achievement
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct AchievementProgress {
+    /// Unlocked achievements
+    pub unlocked: usize,
+    pub total: usize,
+    pub achievements: Vec<AchievementStatus>,
+}
+
+/// Built-in achievement table of a game (empty when there is none)
+pub fn builtin(game: GameType) -> Vec<Achievement> {
+    let table = match game {
+        GameType::DarkSouls3 => DARK_SOULS_3,
+        GameType::EldenRing => ELDEN_RING,
+        _ => &[],
+    };
+    table
+        .iter()
+        .map(|&(id, name, flags)| Achievement {
+            id: id.to_string(),
+            name: name.to_string(),
+            flags: flags.to_vec(),
+        })
+        .collect()
+}
+
+/// Completion of `achievements` given a flag reader
+pub fn progress(achievements: &[Achievement], mut read_flag: impl FnMut(u32) -> bool) -> AchievementProgress {
+    let statuses: Vec<AchievementStatus> = achievements
+        .iter()
+        .map(|achievement| {
+            let progress = achievement.flags.iter().filter(|&&flag_id| read_flag(flag_id)).count();
+            AchievementStatus {
+                id: achievement.id.clone(),
+                name: achievement.name.clone(),
+                progress,
+                total: achievement.flags.len(),
+                unlocked: progress == achievement.flags.len(),
+            }
+        })
+        .collect();
+
+    AchievementProgress {
+        unlocked: statuses.iter().filter(|status| status.unlocked).count(),
+        total: statuses.len(),
+        achievements: statuses,
+    }
+}
+
+/// Per-attach achievement polling
+#[derive(Debug, Default)]
+pub struct AchievementTracker {
+    last_poll_ms: Option<u64>,
+    /// Unlocked after the first poll; None before it
+    unlocked: Option<HashSet<String>>,
+}
+
+impl AchievementTracker {
+    /// Re-read the achievement flags when due; returns the progress and an
+    /// `achievement_unlocked` event per achievement unlocked since the last
+    /// poll (none on the first poll, which sees the save as loaded)
+    pub fn observe(
+        &mut self,
+        achievements: &[Achievement],
+        elapsed_ms: u64,
+        read_flag: impl FnMut(u32) -> bool,
+    ) -> Option<(AchievementProgress, Vec<RunEvent>)> {
+        if self
+            .last_poll_ms
+            .is_some_and(|last| elapsed_ms.saturating_sub(last) < ACHIEVEMENT_POLL_INTERVAL_MS)
+        {
+            return None;
+        }
+        self.last_poll_ms = Some(elapsed_ms);
+
+        let progress = progress(achievements, read_flag);
+        let unlocked: HashSet<String> = progress
+            .achievements
+            .iter()
+            .filter(|status| status.unlocked)
+            .map(|status| status.id.clone())
+            .collect();
+        let events = match &self.unlocked {
+            Some(previous) => progress
+                .achievements
+                .iter()
+                .filter(|status| status.unlocked && !previous.contains(&status.id))
+                .map(|status| RunEvent::AchievementUnlocked {
+                    achievement_id: status.id.clone(),
+                    name: status.name.clone(),
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        self.unlocked = Some(unlocked);
+        Some((progress, events))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_goal_progress() {
+        let goal = Achievement {
+            id: "all_bosses".to_string(),
+            name: "All Bosses".to_string(),
+            flags: builtin(GameType::EldenRing).iter().flat_map(|a| a.flags.clone()).collect(),
+        };
+        assert_eq!(goal.flags.len(), 12);
+
+        let defeated: HashSet<u32> = goal.flags[..9].iter().copied().collect();
+        let progress = progress(&[goal], |flag_id| defeated.contains(&flag_id));
+        assert_eq!(progress.unlocked, 0);
+        assert_eq!(progress.achievements[0].progress, 9);
+        assert_eq!(progress.achievements[0].total, 12);
+    }
+
+    #[test]
+    fn test_unlock_events() {
+        let achievements = builtin(GameType::DarkSouls3);
+        let mut defeated: HashSet<u32> = [13800800].into_iter().collect();
+        let mut tracker = AchievementTracker::default();
+
+        // Already unlocked in the loaded save: no event
+        let (progress, events) = tracker.observe(&achievements, 0, |flag| defeated.contains(&flag)).unwrap();
+        assert_eq!((progress.unlocked, progress.total), (1, 4));
+        assert!(events.is_empty());
+
+        defeated.insert(13700800);
+        assert!(tracker.observe(&achievements, 1000, |flag| defeated.contains(&flag)).is_none());
+        let (progress, events) = tracker.observe(&achievements, 2000, |flag| defeated.contains(&flag)).unwrap();
+        assert_eq!(progress.unlocked, 2);
+        assert_eq!(
+            events,
+            [RunEvent::AchievementUnlocked {
+                achievement_id: "lord_of_cinder_yhorm".to_string(),
+                name: "Lord of Cinder: Yhorm the Giant".to_string(),
+            }]
+        );
+    }
+}
@@ -5,11 +5,11 @@
 
 use std::collections::HashMap;
 
-use super::error::AslResult;
-use super::parser::{AslScript, AslVariable};
+use super::error::{AslError, AslResult};
+use super::parser::{AslBlock, AslCondition, AslExpression, AslScript, AslStatement, AslType, AslVariable, CompareOp};
 use crate::game_data::{
     AutosplitterConfig, BossDefinition, GameData, GameInfo, PatternDefinition, PointerDefinition,
-    PresetDefinition,
+    PresetDefinition, ValueDefinition,
 };
 
 /// Engine type for known games
@@ -101,10 +101,26 @@ pub fn asl_to_game_data(script: &AslScript, engine_hint: Option<&str>) -> AslRes
     // Create display name from process name
     let display_name = humanize_process_name(&script.process_name);
 
+    // Map the gameTime and isLoading blocks to the variables they read
+    let game_time = match script.game_time.as_ref().and_then(game_time_source) {
+        Some((name, scale_ms)) => Some((find_variable(script, &name)?, scale_ms, None)),
+        None => None,
+    };
+    let loading = match script.is_loading.as_ref().and_then(loading_source) {
+        Some((name, equals)) => Some((find_variable(script, &name)?, 1.0, equals)),
+        None => None,
+    };
+    let value_names: Vec<&str> = game_time
+        .iter()
+        .chain(loading.iter())
+        .map(|(var, _, _)| var.name.as_str())
+        .collect();
+
     // Convert variables to boss definitions
     let bosses: Vec<BossDefinition> = script
         .variables
         .iter()
+        .filter(|v| !value_names.contains(&v.name.as_str()))
         .map(|v| variable_to_boss(v, &engine))
         .collect();
 
@@ -112,7 +128,13 @@ pub fn asl_to_game_data(script: &AslScript, engine_hint: Option<&str>) -> AslRes
     let patterns = extract_patterns(&script.variables, &engine);
 
     // Extract pointers from variables
-    let pointers = extract_pointers(&script.variables, &engine);
+    let mut pointers = extract_pointers(&script.variables, &engine);
+    let game_time = game_time
+        .map(|(var, scale, equals)| variable_to_value(var, scale, equals, &mut pointers))
+        .transpose()?;
+    let loading = loading
+        .map(|(var, scale, equals)| variable_to_value(var, scale, equals, &mut pointers))
+        .transpose()?;
 
     // Create default preset with all bosses
     let preset = PresetDefinition {
@@ -135,11 +157,14 @@ pub fn asl_to_game_data(script: &AslScript, engine_hint: Option<&str>) -> AslRes
             engine: engine.as_str().to_string(),
             patterns,
             pointers,
+            game_time,
+            loading,
         },
         bosses,
         presets: vec![preset],
         custom_fields: HashMap::new(),
         attributes: Vec::new(),
+        triggers: Vec::new(),
     })
 }
 
@@ -176,7 +201,113 @@ fn variable_to_boss(var: &AslVariable, engine: &EngineType) -> BossDefinition {
         flag_id,
         is_dlc,
         custom: HashMap::new(),
+        metadata: Default::default(),
+    }
+}
+
+/// Variable read by `return TimeSpan.FromX(current.var);` in the gameTime
+/// block, with its unit in milliseconds
+fn game_time_source(block: &AslBlock) -> Option<(String, f64)> {
+    block.statements.iter().find_map(|statement| match statement {
+        AslStatement::ReturnValue(AslCondition {
+            left: AslExpression::TimeSpan { value, scale_ms },
+            op: None,
+            ..
+        }) => match value.as_ref() {
+            AslExpression::CurrentVar(name) => Some((name.clone(), *scale_ms)),
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+/// Variable the isLoading block tests, with the value that means loading
+/// (None: any non-zero value). Understands `return current.var;`,
+/// `return !current.var;`, `return current.var == N;` and the same
+/// conditions in an `if` that returns true.
+fn loading_source(block: &AslBlock) -> Option<(String, Option<i64>)> {
+    block.statements.iter().find_map(|statement| match statement {
+        AslStatement::ReturnValue(condition) => loading_condition(condition),
+        AslStatement::If { condition, body } if matches!(body.first(), Some(AslStatement::Return(true))) => {
+            loading_condition(condition)
+        }
+        _ => None,
+    })
+}
+
+fn loading_condition(condition: &AslCondition) -> Option<(String, Option<i64>)> {
+    if condition.next.is_some() {
+        return None;
     }
+    match (&condition.left, condition.op, &condition.right) {
+        (AslExpression::CurrentVar(name), None, _) => Some((name.clone(), None)),
+        (AslExpression::Not(inner), None, _) => match inner.as_ref() {
+            AslExpression::CurrentVar(name) => Some((name.clone(), Some(0))),
+            _ => None,
+        },
+        (AslExpression::CurrentVar(name), Some(CompareOp::Equals), Some(right)) => match right {
+            AslExpression::IntLiteral(value) => Some((name.clone(), Some(*value))),
+            AslExpression::HexLiteral(value) => Some((name.clone(), Some(*value as i64))),
+            AslExpression::True => Some((name.clone(), Some(1))),
+            AslExpression::False => Some((name.clone(), Some(0))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn find_variable<'a>(script: &'a AslScript, name: &str) -> AslResult<&'a AslVariable> {
+    script
+        .variables
+        .iter()
+        .find(|v| v.name == name)
+        .ok_or_else(|| AslError::conversion(format!("Unknown variable: current.{}", name)))
+}
+
+/// Add a pointer for the variable's chain (all offsets but the last) and
+/// read the value at the last offset
+fn variable_to_value(
+    var: &AslVariable,
+    scale: f64,
+    equals: Option<i64>,
+    pointers: &mut HashMap<String, PointerDefinition>,
+) -> AslResult<ValueDefinition> {
+    let value_type = match var.var_type {
+        AslType::Bool | AslType::Byte => "u8",
+        AslType::Short => "i16",
+        AslType::UShort => "u16",
+        AslType::Int => "i32",
+        AslType::UInt => "u32",
+        AslType::Long => "i64",
+        AslType::ULong => "u64",
+        AslType::Float => "f32",
+        AslType::String => {
+            return Err(AslError::unsupported(format!(
+                "String variable '{}' cannot be read as a number",
+                var.name
+            )))
+        }
+    };
+
+    let (offset, chain) = match var.offsets.split_last() {
+        Some((offset, chain)) => (*offset, chain.to_vec()),
+        None => (0, Vec::new()),
+    };
+    pointers.insert(
+        var.name.clone(),
+        PointerDefinition {
+            pattern: var.pointer_name.clone(),
+            offsets: chain,
+        },
+    );
+
+    Ok(ValueDefinition {
+        pointer: var.name.clone(),
+        offset,
+        value_type: value_type.to_string(),
+        scale,
+        equals,
+    })
 }
 
 /// Check if a boss is DLC based on name or flag range
@@ -580,6 +711,58 @@ state("game.exe") {
         assert_eq!(game_data.presets[0].bosses.len(), 2);
     }
 
+    #[test]
+    fn test_convert_game_time_and_loading() {
+        let input = r#"
+state("DarkSoulsIII.exe") {
+    bool vordt : "sprj_event_flag_man", 13000800;
+    uint igt : "game_data_man", 0xA4;
+    int loadState : "sprj_fade_man", 0x0, 0x2E8;
+}
+
+gameTime {
+    return TimeSpan.FromMilliseconds(current.igt);
+}
+
+isLoading {
+    if (current.loadState == 1) { return true; }
+    return false;
+}
+"#;
+        let game_data = parse_and_convert(input, Some("ds3")).unwrap();
+
+        // Time variables are not bosses
+        assert_eq!(game_data.bosses.len(), 1);
+        assert_eq!(game_data.presets[0].bosses, vec!["vordt"]);
+
+        let game_time = game_data.autosplitter.game_time.unwrap();
+        assert_eq!(game_time.pointer, "igt");
+        assert_eq!(game_time.offset, 0xA4);
+        assert_eq!(game_time.value_type, "u32");
+        assert_eq!(game_time.scale, 1.0);
+        assert!(game_data.autosplitter.pointers["igt"].offsets.is_empty());
+
+        let loading = game_data.autosplitter.loading.unwrap();
+        assert_eq!(loading.pointer, "loadState");
+        assert_eq!(loading.offset, 0x2E8);
+        assert_eq!(loading.equals, Some(1));
+        assert_eq!(game_data.autosplitter.pointers["loadState"].offsets, vec![0x0]);
+    }
+
+    #[test]
+    fn test_convert_game_time_unknown_variable() {
+        let input = r#"
+state("DarkSoulsIII.exe") {
+    bool vordt : "sprj_event_flag_man", 13000800;
+}
+
+gameTime {
+    return TimeSpan.FromSeconds(current.igt);
+}
+"#;
+        assert!(parse_and_convert(input, Some("ds3")).is_err());
+    }
+
     #[test]
     fn test_engine_type_as_str() {
         assert_eq!(EngineType::Ds3.as_str(), "ds3");
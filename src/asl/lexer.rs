@@ -15,6 +15,7 @@ pub enum TokenKind {
     Split,
     Reset,
     IsLoading,
+    GameTime,
     If,
     Return,
     True,
@@ -470,6 +471,7 @@ impl<'a> Lexer<'a> {
             "split" => TokenKind::Split,
             "reset" => TokenKind::Reset,
             "isLoading" => TokenKind::IsLoading,
+            "gameTime" => TokenKind::GameTime,
 
             // Control flow
             "if" => TokenKind::If,
@@ -540,7 +542,7 @@ mod tests {
 
     #[test]
     fn test_keywords() {
-        let mut lexer = Lexer::new("state startup init split reset isLoading if return true false");
+        let mut lexer = Lexer::new("state startup init split reset isLoading if return true false gameTime");
         let tokens = lexer.tokenize().unwrap();
 
         assert_eq!(tokens[0].kind, TokenKind::State);
@@ -553,6 +555,7 @@ mod tests {
         assert_eq!(tokens[7].kind, TokenKind::Return);
         assert_eq!(tokens[8].kind, TokenKind::True);
         assert_eq!(tokens[9].kind, TokenKind::False);
+        assert_eq!(tokens[10].kind, TokenKind::GameTime);
     }
 
     #[test]
@@ -9,6 +9,8 @@
 //! - Variable types: `bool`, `int`, `byte`, `float`
 //! - Pointer references with flag IDs or offset chains
 //! - `split`, `reset`, `isLoading` blocks with simple conditions
+//! - `gameTime` returning a `TimeSpan` of a variable, mapped with `isLoading`
+//!   to the game time and loading values of the definition
 //! - `startup` and `init` blocks (parsed but not executed)
 //!
 //! # Example ASL
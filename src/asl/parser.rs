@@ -23,6 +23,8 @@ pub struct AslScript {
     pub reset: Option<AslBlock>,
     /// isLoading block contents
     pub is_loading: Option<AslBlock>,
+    /// gameTime block contents
+    pub game_time: Option<AslBlock>,
 }
 
 /// Variable definition from state() block
@@ -82,6 +84,8 @@ pub enum AslStatement {
     },
     /// return true; or return false;
     Return(bool),
+    /// return <expression or comparison>; (isLoading, gameTime)
+    ReturnValue(AslCondition),
     /// Unrecognized statement (stored as raw text for future use)
     Unknown(String),
 }
@@ -140,8 +144,16 @@ pub enum AslExpression {
     FloatLiteral(f64),
     /// Plain identifier
     Identifier(String),
+    /// TimeSpan.FromSeconds(value) etc.: `value` in units of `scale_ms` milliseconds
+    TimeSpan {
+        value: Box<AslExpression>,
+        scale_ms: f64,
+    },
 }
 
+/// Length of a .NET tick (100 ns) in milliseconds
+const TICK_MS: f64 = 0.0001;
+
 /// ASL Parser
 pub struct Parser {
     tokens: Vec<Token>,
@@ -164,6 +176,7 @@ impl Parser {
             split: None,
             reset: None,
             is_loading: None,
+            game_time: None,
         };
 
         while !self.is_at_end() {
@@ -188,6 +201,9 @@ impl Parser {
                 TokenKind::IsLoading => {
                     script.is_loading = Some(self.parse_action_block("isLoading")?);
                 }
+                TokenKind::GameTime => {
+                    script.game_time = Some(self.parse_action_block("gameTime")?);
+                }
                 TokenKind::Eof => break,
                 _ => {
                     // Skip unknown top-level tokens
@@ -295,7 +311,7 @@ impl Parser {
         }))
     }
 
-    /// Parse an action block (split, reset, isLoading, startup, init)
+    /// Parse an action block (split, reset, isLoading, gameTime, startup, init)
     fn parse_action_block(&mut self, block_name: &str) -> AslResult<AslBlock> {
         self.advance(); // consume block keyword
         self.expect(TokenKind::LeftBrace)?;
@@ -368,23 +384,23 @@ impl Parser {
     fn parse_return_statement(&mut self) -> AslResult<AslStatement> {
         self.expect(TokenKind::Return)?;
 
-        let value = match self.current_kind() {
-            TokenKind::True => {
-                self.advance();
-                true
-            }
-            TokenKind::False => {
-                self.advance();
-                false
-            }
-            _ => {
+        let value = match (self.current_kind(), self.peek_kind()) {
+            (TokenKind::True, TokenKind::Semicolon) => true,
+            (TokenKind::False, TokenKind::Semicolon) => false,
+            (TokenKind::Semicolon, _) => {
                 return Err(AslError::parser_at(
-                    "Expected true or false after return",
+                    "Expected a value after return",
                     self.current_line(),
                     self.current_column(),
                 ))
             }
+            _ => {
+                let condition = self.parse_condition()?;
+                self.expect(TokenKind::Semicolon)?;
+                return Ok(AslStatement::ReturnValue(condition));
+            }
         };
+        self.advance();
 
         self.expect(TokenKind::Semicolon)?;
 
@@ -517,6 +533,14 @@ impl Parser {
                 self.advance();
                 Ok(AslExpression::FloatLiteral(val))
             }
+            TokenKind::Identifier(ref name) if name == "TimeSpan" && self.peek_kind() == TokenKind::Dot => {
+                self.parse_time_span_factory()
+            }
+            TokenKind::Identifier(ref name)
+                if name == "new" && self.peek_kind() == TokenKind::Identifier("TimeSpan".to_string()) =>
+            {
+                self.parse_time_span_constructor()
+            }
             TokenKind::Identifier(ref name) => {
                 let name = name.clone();
                 self.advance();
@@ -530,6 +554,91 @@ impl Parser {
         }
     }
 
+    /// Parse TimeSpan.FromMilliseconds(value), FromSeconds, FromMinutes,
+    /// FromHours or FromTicks
+    fn parse_time_span_factory(&mut self) -> AslResult<AslExpression> {
+        let (line, column) = (self.current_line(), self.current_column());
+        self.advance(); // consume TimeSpan
+        self.expect(TokenKind::Dot)?;
+
+        let factory = self.expect_identifier()?;
+        let scale_ms = match factory.as_str() {
+            "FromMilliseconds" => 1.0,
+            "FromSeconds" => 1_000.0,
+            "FromMinutes" => 60_000.0,
+            "FromHours" => 3_600_000.0,
+            "FromTicks" => TICK_MS,
+            _ => {
+                return Err(AslError::parser_at(
+                    format!("Unsupported TimeSpan factory: {}", factory),
+                    line,
+                    column,
+                ))
+            }
+        };
+
+        self.expect(TokenKind::LeftParen)?;
+        let value = self.parse_expression()?;
+        self.expect(TokenKind::RightParen)?;
+
+        Ok(AslExpression::TimeSpan {
+            value: Box::new(value),
+            scale_ms,
+        })
+    }
+
+    /// Parse new TimeSpan(ticks), new TimeSpan(h, m, s), new TimeSpan(d, h, m, s)
+    /// or new TimeSpan(d, h, m, s, ms) where one argument is the value and the
+    /// others are zero
+    fn parse_time_span_constructor(&mut self) -> AslResult<AslExpression> {
+        let (line, column) = (self.current_line(), self.current_column());
+        self.advance(); // consume new
+        self.advance(); // consume TimeSpan
+        self.expect(TokenKind::LeftParen)?;
+
+        let mut args = vec![self.parse_expression()?];
+        while self.check(TokenKind::Comma) {
+            self.advance();
+            args.push(self.parse_expression()?);
+        }
+        self.expect(TokenKind::RightParen)?;
+
+        let scales: &[f64] = match args.len() {
+            1 => &[TICK_MS],
+            3 => &[3_600_000.0, 60_000.0, 1_000.0],
+            4 => &[86_400_000.0, 3_600_000.0, 60_000.0, 1_000.0],
+            5 => &[86_400_000.0, 3_600_000.0, 60_000.0, 1_000.0, 1.0],
+            n => {
+                return Err(AslError::parser_at(
+                    format!("Unsupported TimeSpan constructor with {} arguments", n),
+                    line,
+                    column,
+                ))
+            }
+        };
+
+        let mut value = None;
+        for (arg, scale_ms) in args.into_iter().zip(scales) {
+            match arg {
+                AslExpression::IntLiteral(0) | AslExpression::HexLiteral(0) => {}
+                arg if value.is_none() => value = Some((arg, *scale_ms)),
+                _ => {
+                    return Err(AslError::parser_at(
+                        "TimeSpan constructor must have a single non-zero argument",
+                        line,
+                        column,
+                    ))
+                }
+            }
+        }
+
+        let (value, scale_ms) = value.unwrap_or((AslExpression::IntLiteral(0), 1.0));
+        Ok(AslExpression::TimeSpan {
+            value: Box::new(value),
+            scale_ms,
+        })
+    }
+
     // Helper methods
 
     fn is_at_end(&self) -> bool {
@@ -544,6 +653,10 @@ impl Parser {
         self.current().kind.clone()
     }
 
+    fn peek_kind(&self) -> TokenKind {
+        self.tokens[(self.pos + 1).min(self.tokens.len() - 1)].kind.clone()
+    }
+
     fn current_line(&self) -> usize {
         self.current().line
     }
@@ -749,6 +862,56 @@ isLoading {
         assert!(script.is_loading.is_some());
     }
 
+    #[test]
+    fn test_parse_game_time_block() {
+        let time_span = |body: &str| {
+            let input = format!("state(\"game.exe\") {{ int igt : \"ptr\", 0x10; }}\ngameTime {{ {} }}", body);
+            let script = parse(&input).unwrap();
+            match &script.game_time.unwrap().statements[0] {
+                AslStatement::ReturnValue(AslCondition {
+                    left: AslExpression::TimeSpan { value, scale_ms },
+                    ..
+                }) => match value.as_ref() {
+                    AslExpression::CurrentVar(name) => (name.clone(), *scale_ms),
+                    other => panic!("Expected current variable, got {:?}", other),
+                },
+                other => panic!("Expected TimeSpan return, got {:?}", other),
+            }
+        };
+
+        assert_eq!(time_span("return TimeSpan.FromMilliseconds(current.igt);"), ("igt".to_string(), 1.0));
+        assert_eq!(time_span("return TimeSpan.FromSeconds(current.igt);"), ("igt".to_string(), 1000.0));
+        assert_eq!(time_span("return new TimeSpan(0, 0, 0, 0, current.igt);"), ("igt".to_string(), 1.0));
+        assert_eq!(time_span("return new TimeSpan(0, 0, current.igt);"), ("igt".to_string(), 1000.0));
+        assert_eq!(time_span("return new TimeSpan(current.igt);"), ("igt".to_string(), TICK_MS));
+
+        assert!(parse("state(\"game.exe\") {}\ngameTime { return TimeSpan.FromDays(current.igt); }").is_err());
+        assert!(parse("state(\"game.exe\") {}\ngameTime { return new TimeSpan(0, current.a, current.b); }").is_err());
+    }
+
+    #[test]
+    fn test_parse_return_value() {
+        let input = r#"
+state("game.exe") {
+    int loadState : "ptr", 100;
+}
+
+isLoading {
+    return current.loadState == 2;
+}
+"#;
+        let script = parse(input).unwrap();
+
+        let is_loading = script.is_loading.unwrap();
+        if let AslStatement::ReturnValue(condition) = &is_loading.statements[0] {
+            assert!(matches!(&condition.left, AslExpression::CurrentVar(name) if name == "loadState"));
+            assert_eq!(condition.op, Some(CompareOp::Equals));
+            assert!(matches!(condition.right, Some(AslExpression::IntLiteral(2))));
+        } else {
+            panic!("Expected return value");
+        }
+    }
+
     #[test]
     fn test_parse_comparison_operators() {
         let input = r#"
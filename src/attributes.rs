@@ -0,0 +1,237 @@
+//! Game-agnostic attribute names
+//!
+//! Each game reads attributes under its own names: the health stat is
+//! "vigor" in Dark Souls 3 and Elden Ring but "vitality" in Sekiro, where
+//! "vitality" in Dark Souls 3 is equip load. [`CanonicalAttribute`] gives the
+//! common concepts one name, and per-game tables map them to the game's own
+//! attribute, so trigger configs and overlays can be shared across games.
+//! Canonical names are accepted anywhere an attribute name is (custom
+//! triggers, `get_attribute`); game-specific names keep working.
+
+use crate::GameType;
+use serde::{Deserialize, Serialize};
+
+/// Attribute concept shared between games
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum CanonicalAttribute {
+    /// In-game time in milliseconds
+    Igt,
+    /// Character level (soul level, rune level)
+    Level,
+    /// Stat that raises max HP (Vigor, Sekiro's Vitality)
+    Health,
+    /// Stat that raises FP (Attunement, Mind)
+    Focus,
+    /// Stat that raises stamina (Endurance)
+    Stamina,
+    /// Stat that raises equip load (Dark Souls 3 Vitality)
+    EquipLoad,
+    Strength,
+    Dexterity,
+    Intelligence,
+    Faith,
+    /// Luck, or Arcane in Elden Ring
+    Luck,
+    /// Sekiro's Attack Power
+    AttackPower,
+    DeathCount,
+    /// New Game+ cycle (0 = first playthrough)
+    NgLevel,
+    PlayerLoaded,
+    Loading,
+    Blackscreen,
+    /// Entity id of the bonfire or grace last rested at or warped to
+    LastWarpPoint,
+    /// Frames presented since the game started
+    FrameCount,
+}
+
+impl CanonicalAttribute {
+    pub const ALL: [CanonicalAttribute; 19] = [
+        CanonicalAttribute::Igt,
+        CanonicalAttribute::Level,
+        CanonicalAttribute::Health,
+        CanonicalAttribute::Focus,
+        CanonicalAttribute::Stamina,
+        CanonicalAttribute::EquipLoad,
+        CanonicalAttribute::Strength,
+        CanonicalAttribute::Dexterity,
+        CanonicalAttribute::Intelligence,
+        CanonicalAttribute::Faith,
+        CanonicalAttribute::Luck,
+        CanonicalAttribute::AttackPower,
+        CanonicalAttribute::DeathCount,
+        CanonicalAttribute::NgLevel,
+        CanonicalAttribute::PlayerLoaded,
+        CanonicalAttribute::Loading,
+        CanonicalAttribute::Blackscreen,
+        CanonicalAttribute::LastWarpPoint,
+        CanonicalAttribute::FrameCount,
+    ];
+
+    /// Name used in configs (e.g., "health")
+    pub fn name(&self) -> &'static str {
+        match self {
+            CanonicalAttribute::Igt => "igt",
+            CanonicalAttribute::Level => "level",
+            CanonicalAttribute::Health => "health",
+            CanonicalAttribute::Focus => "focus",
+            CanonicalAttribute::Stamina => "stamina",
+            CanonicalAttribute::EquipLoad => "equip_load",
+            CanonicalAttribute::Strength => "strength",
+            CanonicalAttribute::Dexterity => "dexterity",
+            CanonicalAttribute::Intelligence => "intelligence",
+            CanonicalAttribute::Faith => "faith",
+            CanonicalAttribute::Luck => "luck",
+            CanonicalAttribute::AttackPower => "attack_power",
+            CanonicalAttribute::DeathCount => "death_count",
+            CanonicalAttribute::NgLevel => "ng_level",
+            CanonicalAttribute::PlayerLoaded => "player_loaded",
+            CanonicalAttribute::Loading => "loading",
+            CanonicalAttribute::Blackscreen => "blackscreen",
+            CanonicalAttribute::LastWarpPoint => "last_warp_point",
+            CanonicalAttribute::FrameCount => "frame_count",
+        }
+    }
+
+    /// Parse a canonical name
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|attribute| attribute.name() == name)
+    }
+}
+
+use CanonicalAttribute::*;
+
+const DARK_SOULS_3: &[(CanonicalAttribute, &str)] = &[
+    (Igt, "igt"),
+    (Level, "soul_level"),
+    (Health, "vigor"),
+    (Focus, "attunement"),
+    (Stamina, "endurance"),
+    (EquipLoad, "vitality"),
+    (Strength, "strength"),
+    (Dexterity, "dexterity"),
+    (Intelligence, "intelligence"),
+    (Faith, "faith"),
+    (Luck, "luck"),
+    (PlayerLoaded, "player_loaded"),
+    (Loading, "loading"),
+    (Blackscreen, "blackscreen"),
+    (LastWarpPoint, "last_bonfire"),
+    (FrameCount, "frame_count"),
+];
+
+const ELDEN_RING: &[(CanonicalAttribute, &str)] = &[
+    (Igt, "igt"),
+    (Level, "rune_level"),
+    (Health, "vigor"),
+    (Focus, "mind"),
+    (Stamina, "endurance"),
+    (Strength, "strength"),
+    (Dexterity, "dexterity"),
+    (Intelligence, "intelligence"),
+    (Faith, "faith"),
+    (Luck, "arcane"),
+    (DeathCount, "death_count"),
+    (NgLevel, "ng_level"),
+    (PlayerLoaded, "player_loaded"),
+    (Blackscreen, "blackscreen"),
+    (LastWarpPoint, "last_grace"),
+    (FrameCount, "frame_count"),
+];
+
+const SEKIRO: &[(CanonicalAttribute, &str)] = &[
+    (Igt, "igt"),
+    (Health, "vitality"),
+    (AttackPower, "attack_power"),
+    (PlayerLoaded, "player_loaded"),
+    (Blackscreen, "blackscreen"),
+];
+
+const ARMORED_CORE_6: &[(CanonicalAttribute, &str)] = &[(Igt, "igt"), (Loading, "loading")];
+
+fn table(game: GameType) -> &'static [(CanonicalAttribute, &'static str)] {
+    match game {
+        GameType::DarkSouls3 => DARK_SOULS_3,
+        GameType::EldenRing => ELDEN_RING,
+        GameType::Sekiro => SEKIRO,
+        GameType::ArmoredCore6 => ARMORED_CORE_6,
+        GameType::DarkSouls1 | GameType::DarkSouls2 => &[],
+    }
+}
+
+/// A canonical attribute and the game's own name for it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct AttributeMapping {
+    pub canonical: CanonicalAttribute,
+    pub name: String,
+}
+
+/// Canonical attributes a game supports, with its names for them
+pub fn mappings(game: GameType) -> Vec<AttributeMapping> {
+    table(game)
+        .iter()
+        .map(|&(canonical, name)| AttributeMapping {
+            canonical,
+            name: name.to_string(),
+        })
+        .collect()
+}
+
+/// The game's name for a canonical attribute
+pub fn game_name(game: GameType, attribute: CanonicalAttribute) -> Option<&'static str> {
+    table(game)
+        .iter()
+        .find(|(canonical, _)| *canonical == attribute)
+        .map(|(_, name)| *name)
+}
+
+/// Resolve an attribute name for a game: canonical names map to the game's
+/// own name, anything else is returned unchanged
+pub fn resolve(game: GameType, name: &str) -> &str {
+    CanonicalAttribute::parse(name)
+        .and_then(|attribute| game_name(game, attribute))
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_round_trip() {
+        for attribute in CanonicalAttribute::ALL {
+            assert_eq!(CanonicalAttribute::parse(attribute.name()), Some(attribute));
+            let json = serde_json::to_string(&attribute).unwrap();
+            assert_eq!(json, format!("\"{}\"", attribute.name()));
+        }
+        assert_eq!(CanonicalAttribute::parse("vigor"), None);
+    }
+
+    #[test]
+    fn test_resolve_per_game() {
+        assert_eq!(resolve(GameType::DarkSouls3, "health"), "vigor");
+        assert_eq!(resolve(GameType::Sekiro, "health"), "vitality");
+        assert_eq!(resolve(GameType::EldenRing, "level"), "rune_level");
+        assert_eq!(resolve(GameType::EldenRing, "luck"), "arcane");
+        // Game-specific names pass through
+        assert_eq!(resolve(GameType::DarkSouls3, "vitality"), "vitality");
+        // Unsupported canonical names are left as is (and read as missing)
+        assert_eq!(resolve(GameType::ArmoredCore6, "health"), "health");
+    }
+
+    #[test]
+    fn test_canonical_names_never_shadow_other_attributes() {
+        // A canonical name that is also a game attribute must mean the same thing
+        for game in GameType::ALL {
+            for &(canonical, name) in table(game) {
+                if let Some(other) = CanonicalAttribute::parse(name) {
+                    assert_eq!(other, canonical, "{:?}: {}", game, name);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Split audio cues
+//!
+//! Plays the sound files configured in [`AudioCueConfig`] when splits, gold
+//! splits, boss kills, rule violations or boss fight phases happen, so minimal hosts and
+//! accessibility setups get audible feedback without extra software.
+//!
+//! Playback uses rodio and is only compiled with the `audio` feature; without
+//! it cues are resolved as usual but not played.
+
+use crate::config::{AudioCueConfig, TriggerKind};
+use crate::run_log::RunEvent;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "audio")]
+use std::sync::mpsc::{self, Sender};
+
+/// Kind of audio cue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Cue {
+    Split,
+    /// Best segment ever; decided by the host, which plays it through `play_cue`
+    Gold,
+    BossDefeated,
+    RuleViolation,
+    /// Practice event of a watched boss fight (see `boss_phases`)
+    BossPhase,
+}
+
+impl Cue {
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "split" => Some(Cue::Split),
+            "gold" => Some(Cue::Gold),
+            "boss_defeated" => Some(Cue::BossDefeated),
+            "rule_violation" => Some(Cue::RuleViolation),
+            "boss_phase" => Some(Cue::BossPhase),
+            _ => None,
+        }
+    }
+
+    /// Cue played for a run log event, if any
+    pub fn for_event(event: &RunEvent) -> Option<Self> {
+        match event {
+            RunEvent::BossDefeated { .. } => Some(Cue::BossDefeated),
+            RunEvent::TriggerMatched { kind, .. } => match kind {
+                TriggerKind::Split => Some(Cue::Split),
+                TriggerKind::RuleViolation => Some(Cue::RuleViolation),
+            },
+            RunEvent::SegmentTimed { .. } => Some(Cue::Split),
+            RunEvent::BossHpThreshold { .. } | RunEvent::BossPhaseChanged { .. } => Some(Cue::BossPhase),
+            _ => None,
+        }
+    }
+}
+
+/// Sound file configured for a cue
+pub fn sound_for(config: &AudioCueConfig, cue: Cue) -> Option<&str> {
+    let sound = match cue {
+        Cue::Split => config.split.as_ref(),
+        Cue::Gold => config.gold.as_ref(),
+        Cue::BossDefeated => config.boss_defeated.as_ref().or(config.split.as_ref()),
+        Cue::RuleViolation => config.rule_violation.as_ref(),
+        Cue::BossPhase => config.boss_phase.as_ref(),
+    };
+    sound.map(|s| s.as_str()).filter(|s| !s.is_empty())
+}
+
+/// Plays cues on a background audio thread (started on first use)
+#[derive(Default)]
+pub struct CuePlayer {
+    #[cfg(feature = "audio")]
+    sender: Option<Sender<(String, f32)>>,
+}
+
+impl CuePlayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Play the sound configured for `cue`, if audio cues are enabled
+    pub fn play(&mut self, config: &AudioCueConfig, cue: Cue) {
+        if !config.enabled {
+            return;
+        }
+
+        if let Some(path) = sound_for(config, cue) {
+            log::debug!("Audio cue {:?}: {}", cue, path);
+            self.play_file(path, config.volume);
+        }
+    }
+
+    #[cfg(feature = "audio")]
+    fn play_file(&mut self, path: &str, volume: f32) {
+        let sender = self.sender.get_or_insert_with(spawn_audio_thread);
+        if sender.send((path.to_string(), volume)).is_err() {
+            // Audio thread exited (no output device) - retry with a new one next time
+            self.sender = None;
+        }
+    }
+
+    #[cfg(not(feature = "audio"))]
+    fn play_file(&mut self, path: &str, _volume: f32) {
+        log::debug!("Audio cue not played (built without the `audio` feature): {}", path);
+    }
+}
+
+/// Own the output stream on a dedicated thread (rodio streams are not Send)
+#[cfg(feature = "audio")]
+fn spawn_audio_thread() -> Sender<(String, f32)> {
+    let (sender, receiver) = mpsc::channel::<(String, f32)>();
+
+    std::thread::spawn(move || {
+        let (_stream, handle) = match rodio::OutputStream::try_default() {
+            Ok(output) => output,
+            Err(e) => {
+                log::warn!("No audio output device for cues: {}", e);
+                return;
+            }
+        };
+
+        for (path, volume) in receiver {
+            let source = std::fs::File::open(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|file| {
+                    rodio::Decoder::new(std::io::BufReader::new(file)).map_err(|e| e.to_string())
+                });
+            let source = match source {
+                Ok(source) => source,
+                Err(e) => {
+                    log::warn!("Failed to load audio cue {}: {}", path, e);
+                    continue;
+                }
+            };
+
+            match rodio::Sink::try_new(&handle) {
+                Ok(sink) => {
+                    sink.set_volume(volume);
+                    sink.append(source);
+                    sink.detach();
+                }
+                Err(e) => log::warn!("Failed to play audio cue {}: {}", path, e),
+            }
+        }
+    });
+
+    sender
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cue_for_event() {
+        let boss = RunEvent::BossDefeated {
+            boss_id: "iudex".to_string(),
+            boss_name: "Iudex Gundyr".to_string(),
+            flag_id: 14000800,
+            suspicion: None,
+            display_name: None,
+            metadata: Default::default(),
+        };
+        assert_eq!(Cue::for_event(&boss), Some(Cue::BossDefeated));
+
+        let violation = RunEvent::TriggerMatched {
+            index: 0,
+            trigger_id: "sl_cap".to_string(),
+            name: "Soul level above 1".to_string(),
+            kind: TriggerKind::RuleViolation,
+            display_name: None,
+            metadata: Default::default(),
+        };
+        assert_eq!(Cue::for_event(&violation), Some(Cue::RuleViolation));
+        assert_eq!(Cue::for_event(&RunEvent::Reset), None);
+
+        let phase = RunEvent::BossHpThreshold {
+            boss_id: "nameless_king".to_string(),
+            percent: 50,
+            hp: 4000,
+            max_hp: 8000,
+            fight_ms: 30_000,
+        };
+        assert_eq!(Cue::for_event(&phase), Some(Cue::BossPhase));
+    }
+
+    #[test]
+    fn test_boss_defeated_falls_back_to_split() {
+        let mut config = AudioCueConfig {
+            split: Some("split.wav".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(sound_for(&config, Cue::BossDefeated), Some("split.wav"));
+        assert_eq!(sound_for(&config, Cue::Gold), None);
+
+        config.boss_defeated = Some("boss.ogg".to_string());
+        assert_eq!(sound_for(&config, Cue::BossDefeated), Some("boss.ogg"));
+    }
+
+    #[test]
+    fn test_cue_from_name() {
+        assert_eq!(Cue::from_name("gold"), Some(Cue::Gold));
+        assert_eq!(Cue::from_name("Rule_Violation"), Some(Cue::RuleViolation));
+        assert_eq!(Cue::from_name("pb"), None);
+    }
+}
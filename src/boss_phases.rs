@@ -0,0 +1,320 @@
+//! Boss damage phases for fight practice
+//!
+//! Practice hosts can watch a boss's HP and phase flags to play cues or time
+//! the phases of a fight. A [`BossPhaseWatch`] names the boss, the pointer
+//! chain of its HP (resolved like a value watch), optionally the chain of its
+//! max HP, the HP percentages to report and the event flags the game sets on
+//! phase transitions (e.g., a second-phase animation).
+//!
+//! The fight starts when the HP becomes readable and starts over when the HP
+//! is back to full (the player died or reloaded). During a fight, HP dropping
+//! to or below a threshold logs a `boss_hp_threshold` event and a phase flag
+//! turning on logs a `boss_phase_changed` event, both with the time since the
+//! fight started. These practice events never split; they play the
+//! `boss_phase` audio cue.
+
+use crate::run_log::RunEvent;
+use crate::watches::{self, WatchType, MAX_CHAIN_DEPTH};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// HP percentages reported when no thresholds are configured
+pub const DEFAULT_HP_THRESHOLDS: &[u8] = &[75, 50, 25];
+
+/// A value at the end of a pointer chain from the main module (see `Watch`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ValuePointer {
+    pub module_offset: usize,
+    #[serde(default)]
+    pub offsets: Vec<usize>,
+    pub value_type: WatchType,
+}
+
+impl ValuePointer {
+    fn read(
+        &self,
+        module_base: usize,
+        pointer_size: usize,
+        read: &impl Fn(usize, usize) -> Option<Vec<u8>>,
+    ) -> Option<i64> {
+        watches::read_chain(module_base, pointer_size, self.module_offset, &self.offsets, self.value_type, read)
+    }
+}
+
+/// An event flag the game sets when the boss enters a new phase
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct PhaseFlag {
+    pub flag_id: u32,
+    /// Name reported in the event (e.g., "phase_2")
+    pub name: String,
+}
+
+/// A boss whose fight is reported in practice events
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct BossPhaseWatch {
+    pub boss_id: String,
+    pub hp: ValuePointer,
+    /// None takes the HP read when the fight starts as the max HP
+    #[serde(default)]
+    pub max_hp: Option<ValuePointer>,
+    /// HP percentages reported when the HP drops to or below them
+    #[serde(default = "default_hp_thresholds")]
+    pub hp_thresholds: Vec<u8>,
+    #[serde(default)]
+    pub phase_flags: Vec<PhaseFlag>,
+}
+
+fn default_hp_thresholds() -> Vec<u8> {
+    DEFAULT_HP_THRESHOLDS.to_vec()
+}
+
+impl BossPhaseWatch {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.boss_id.is_empty() {
+            return Err("Boss phase watch: boss_id must not be empty".to_string());
+        }
+        for pointer in std::iter::once(&self.hp).chain(&self.max_hp) {
+            if pointer.offsets.len() > MAX_CHAIN_DEPTH {
+                return Err(format!(
+                    "Boss phase watch '{}': pointer chain has {} offsets (max {})",
+                    self.boss_id,
+                    pointer.offsets.len(),
+                    MAX_CHAIN_DEPTH
+                ));
+            }
+        }
+        if let Some(percent) = self.hp_thresholds.iter().find(|percent| !(1..=99).contains(*percent)) {
+            return Err(format!(
+                "Boss phase watch '{}': HP threshold {}% is not between 1 and 99",
+                self.boss_id, percent
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// State of a fight in progress
+#[derive(Debug)]
+struct Fight {
+    started: Instant,
+    max_hp: i64,
+    last_hp: i64,
+    /// Thresholds already reported (or already passed at the start)
+    crossed: Vec<u8>,
+    /// Phase flags already set
+    set_flags: Vec<u32>,
+}
+
+/// Per-attach fight bookkeeping of the boss phase watches
+#[derive(Debug, Default)]
+pub struct BossPhaseTracker {
+    module_base: usize,
+    /// Pointer size of the game (see `memory::arch::module_pointer_size`)
+    pointer_size: usize,
+    fights: HashMap<String, Fight>,
+}
+
+impl BossPhaseTracker {
+    pub fn new(module_base: usize, pointer_size: usize) -> Self {
+        Self {
+            module_base,
+            pointer_size,
+            fights: HashMap::new(),
+        }
+    }
+
+    /// Read the HP and phase flags of every watched boss; returns the
+    /// practice events of this tick
+    pub fn observe(
+        &mut self,
+        watches: &[BossPhaseWatch],
+        now: Instant,
+        read: impl Fn(usize, usize) -> Option<Vec<u8>>,
+        read_flag: impl Fn(u32) -> bool,
+    ) -> Vec<RunEvent> {
+        self.fights.retain(|boss_id, _| watches.iter().any(|watch| &watch.boss_id == boss_id));
+
+        let mut events = Vec::new();
+        for watch in watches {
+            let Some(hp) = watch.hp.read(self.module_base, self.pointer_size, &read) else {
+                // Boss not loaded: the fight is over
+                self.fights.remove(&watch.boss_id);
+                continue;
+            };
+
+            let restarted = self
+                .fights
+                .get(&watch.boss_id)
+                .is_some_and(|fight| hp > fight.last_hp && hp >= fight.max_hp);
+            if restarted || !self.fights.contains_key(&watch.boss_id) {
+                let max_hp = match &watch.max_hp {
+                    Some(pointer) => pointer.read(self.module_base, self.pointer_size, &read),
+                    None => Some(hp),
+                };
+                match max_hp.filter(|max_hp| *max_hp > 0) {
+                    Some(max_hp) => {
+                        let fight = Fight {
+                            started: now,
+                            max_hp,
+                            last_hp: hp,
+                            crossed: watch
+                                .hp_thresholds
+                                .iter()
+                                .copied()
+                                .filter(|percent| below(hp, max_hp, *percent))
+                                .collect(),
+                            set_flags: watch
+                                .phase_flags
+                                .iter()
+                                .map(|flag| flag.flag_id)
+                                .filter(|flag_id| read_flag(*flag_id))
+                                .collect(),
+                        };
+                        self.fights.insert(watch.boss_id.clone(), fight);
+                    }
+                    None => {
+                        self.fights.remove(&watch.boss_id);
+                    }
+                }
+                continue;
+            }
+
+            let Some(fight) = self.fights.get_mut(&watch.boss_id) else {
+                continue;
+            };
+            fight.last_hp = hp;
+            let fight_ms = now.saturating_duration_since(fight.started).as_millis() as u64;
+
+            // Report the highest threshold first when a hit passes several
+            let mut thresholds = watch.hp_thresholds.clone();
+            thresholds.sort_unstable_by(|a, b| b.cmp(a));
+            for percent in thresholds {
+                if fight.crossed.contains(&percent) || !below(hp, fight.max_hp, percent) {
+                    continue;
+                }
+                fight.crossed.push(percent);
+                events.push(RunEvent::BossHpThreshold {
+                    boss_id: watch.boss_id.clone(),
+                    percent,
+                    hp,
+                    max_hp: fight.max_hp,
+                    fight_ms,
+                });
+            }
+
+            for flag in &watch.phase_flags {
+                if fight.set_flags.contains(&flag.flag_id) || !read_flag(flag.flag_id) {
+                    continue;
+                }
+                fight.set_flags.push(flag.flag_id);
+                events.push(RunEvent::BossPhaseChanged {
+                    boss_id: watch.boss_id.clone(),
+                    phase: flag.name.clone(),
+                    flag_id: flag.flag_id,
+                    fight_ms,
+                });
+            }
+        }
+        events
+    }
+}
+
+/// HP at or below `percent` of the max HP
+fn below(hp: i64, max_hp: i64, percent: u8) -> bool {
+    hp.saturating_mul(100) <= max_hp.saturating_mul(percent as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    const BASE: usize = 0x140000000;
+
+    fn watch() -> BossPhaseWatch {
+        serde_json::from_str(
+            r#"{
+                "boss_id": "nameless_king",
+                "hp": {"module_offset": 4096, "value_type": "i32"},
+                "phase_flags": [{"flag_id": 14100801, "name": "king_phase"}]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_thresholds_and_phases() {
+        let watches = [watch()];
+        assert!(watches[0].validate().is_ok());
+        assert_eq!(watches[0].hp_thresholds, DEFAULT_HP_THRESHOLDS);
+
+        let mut tracker = BossPhaseTracker::new(BASE, 8);
+        let start = Instant::now();
+        let mut observe = |hp: Option<i32>, flag: bool, at_ms: u64| {
+            tracker.observe(
+                &watches,
+                start + Duration::from_millis(at_ms),
+                |address, size| hp.filter(|_| address == BASE + 4096).map(|hp| hp.to_le_bytes()[..size].to_vec()),
+                |flag_id| flag && flag_id == 14100801,
+            )
+        };
+
+        // Not loaded, then the fight starts at full HP
+        assert!(observe(None, false, 0).is_empty());
+        assert!(observe(Some(8000), false, 1_000).is_empty());
+        assert!(observe(Some(6100), false, 5_000).is_empty());
+
+        // One hit past 75% and 50%: highest first
+        let events = observe(Some(3900), false, 11_000);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], RunEvent::BossHpThreshold { percent: 75, fight_ms: 10_000, .. }));
+        assert!(matches!(events[1], RunEvent::BossHpThreshold { percent: 50, .. }));
+        assert!(observe(Some(3800), false, 12_000).is_empty());
+
+        assert_eq!(
+            observe(Some(3700), true, 20_000),
+            [RunEvent::BossPhaseChanged {
+                boss_id: "nameless_king".to_string(),
+                phase: "king_phase".to_string(),
+                flag_id: 14100801,
+                fight_ms: 19_000,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_fight_restarts_at_full_hp() {
+        let watches = [watch()];
+        let mut tracker = BossPhaseTracker::new(BASE, 8);
+        let start = Instant::now();
+        let mut observe = |hp: i32, at_ms: u64| {
+            tracker.observe(
+                &watches,
+                start + Duration::from_millis(at_ms),
+                |_, size| Some(hp.to_le_bytes()[..size].to_vec()),
+                |_| false,
+            )
+        };
+
+        observe(1000, 0);
+        assert_eq!(observe(700, 1_000).len(), 1);
+        // The player died: the boss is back to full and the fight starts over
+        assert!(observe(1000, 30_000).is_empty());
+        let events = observe(740, 32_000);
+        assert!(matches!(events[..], [RunEvent::BossHpThreshold { percent: 75, fight_ms: 2_000, .. }]));
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut watch = watch();
+        watch.hp_thresholds = vec![50, 100];
+        assert!(watch.validate().is_err());
+        watch.hp_thresholds = vec![50];
+        watch.boss_id.clear();
+        assert!(watch.validate().is_err());
+    }
+}
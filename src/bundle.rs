@@ -0,0 +1,221 @@
+//! Run verification bundles
+//!
+//! A bundle is a single zip a runner can attach to a leaderboard submission.
+//! It holds everything needed to check how an attempt was timed:
+//!
+//! - `manifest.json`: bundle format, library version, platform and a size and
+//!   CRC-32 for every other file
+//! - `run_log.json`: the run log of the attempt
+//! - `splits.json`: the boss flags and custom triggers the run was started with
+//! - `config.json`: the `AutosplitterConfig` in effect
+//! - `game_data.json`: the game definition, when started from one
+//! - `diagnostics.json`: the state at the end of the attempt (read stats,
+//!   read plan, suspicious bosses)
+//! - `flag_history.json`: the watched flags' values over the last ticks
+//!
+//! Files are stored uncompressed so the archive needs no compression library
+//! and opens with any zip tool.
+
+use crate::config::{BossFlag, CustomTrigger};
+use crate::game_data::GameData;
+use serde::{Deserialize, Serialize};
+
+/// Version of the bundle layout, bumped when files are renamed or removed
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// Name of the manifest, always the first file of the archive
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+/// What a run was started with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSetup {
+    pub boss_flags: Vec<BossFlag>,
+    /// Game definition, when started with `start_with_game_data`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub game_data: Option<GameData>,
+}
+
+/// Split configuration stored in `splits.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SplitsFile {
+    pub boss_flags: Vec<BossFlag>,
+    pub triggers: Vec<CustomTrigger>,
+}
+
+/// A file listed in the manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct BundleFile {
+    pub name: String,
+    pub size: u64,
+    pub crc32: u32,
+}
+
+/// Contents of `manifest.json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub library_version: String,
+    pub os: String,
+    pub arch: String,
+    pub game_id: String,
+    pub created_unix_ms: u64,
+    /// Other files of the bundle, in archive order
+    pub files: Vec<BundleFile>,
+}
+
+/// CRC-32 (IEEE) as used by zip
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Build a bundle from its files; a manifest describing them is added first
+pub fn build(game_id: &str, created_unix_ms: u64, files: Vec<(String, Vec<u8>)>) -> Vec<u8> {
+    let manifest = BundleManifest {
+        format_version: BUNDLE_FORMAT_VERSION,
+        library_version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        game_id: game_id.to_string(),
+        created_unix_ms,
+        files: files
+            .iter()
+            .map(|(name, data)| BundleFile {
+                name: name.clone(),
+                size: data.len() as u64,
+                crc32: crc32(data),
+            })
+            .collect(),
+    };
+
+    let manifest = serde_json::to_vec_pretty(&manifest).unwrap_or_default();
+    let mut entries = vec![(MANIFEST_NAME.to_string(), manifest)];
+    entries.extend(files);
+    write_zip(&entries)
+}
+
+/// Read the manifest back from a bundle
+pub fn read_manifest(zip: &[u8]) -> Option<BundleManifest> {
+    if zip.get(0..4)? != b"PK\x03\x04" {
+        return None;
+    }
+    let size = u32::from_le_bytes(zip.get(18..22)?.try_into().ok()?) as usize;
+    let name_len = u16::from_le_bytes(zip.get(26..28)?.try_into().ok()?) as usize;
+    let extra_len = u16::from_le_bytes(zip.get(28..30)?.try_into().ok()?) as usize;
+    if zip.get(30..30 + name_len)? != MANIFEST_NAME.as_bytes() {
+        return None;
+    }
+    let start = 30 + name_len + extra_len;
+    serde_json::from_slice(zip.get(start..start + size)?).ok()
+}
+
+/// Write a zip archive with stored (uncompressed) entries
+///
+/// Timestamps are fixed at 1980-01-01 so equal contents give equal archives.
+fn write_zip(entries: &[(String, Vec<u8>)]) -> Vec<u8> {
+    const DOS_DATE: u16 = (1 << 5) | 1;
+
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for (name, data) in entries {
+        let offset = out.len() as u32;
+        let crc = crc32(data);
+        let size = data.len() as u32;
+
+        // Local file header
+        out.extend_from_slice(b"PK\x03\x04");
+        out.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        out.extend_from_slice(&0u16.to_le_bytes()); // flags
+        out.extend_from_slice(&0u16.to_le_bytes()); // method: stored
+        out.extend_from_slice(&0u16.to_le_bytes()); // time
+        out.extend_from_slice(&DOS_DATE.to_le_bytes());
+        out.extend_from_slice(&crc.to_le_bytes());
+        out.extend_from_slice(&size.to_le_bytes());
+        out.extend_from_slice(&size.to_le_bytes());
+        out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes()); // extra length
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(data);
+
+        // Central directory header
+        central.extend_from_slice(b"PK\x01\x02");
+        central.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        central.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        central.extend_from_slice(&0u16.to_le_bytes());
+        central.extend_from_slice(&0u16.to_le_bytes());
+        central.extend_from_slice(&0u16.to_le_bytes());
+        central.extend_from_slice(&DOS_DATE.to_le_bytes());
+        central.extend_from_slice(&crc.to_le_bytes());
+        central.extend_from_slice(&size.to_le_bytes());
+        central.extend_from_slice(&size.to_le_bytes());
+        central.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        central.extend_from_slice(&[0; 8]); // extra, comment, disk, internal attributes
+        central.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+
+    let central_offset = out.len() as u32;
+    let count = entries.len() as u16;
+    out.extend_from_slice(&central);
+
+    // End of central directory
+    out.extend_from_slice(b"PK\x05\x06");
+    out.extend_from_slice(&[0; 4]); // disk numbers
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&count.to_le_bytes());
+    out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    out.extend_from_slice(&central_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes()); // comment length
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let files = vec![
+            ("run_log.json".to_string(), b"[]".to_vec()),
+            ("config.json".to_string(), b"{}".to_vec()),
+        ];
+        let zip = build("ds3", 1_700_000_000_000, files);
+
+        let manifest = read_manifest(&zip).unwrap();
+        assert_eq!(manifest.format_version, BUNDLE_FORMAT_VERSION);
+        assert_eq!(manifest.library_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(manifest.game_id, "ds3");
+        let names: Vec<_> = manifest.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["run_log.json", "config.json"]);
+        assert_eq!(manifest.files[0].crc32, crc32(b"[]"));
+    }
+
+    #[test]
+    fn test_zip_layout() {
+        let zip = write_zip(&[("a.txt".to_string(), b"hello".to_vec())]);
+        // Local header + name + data, central header + name, end record
+        assert_eq!(zip.len(), (30 + 5 + 5) + (46 + 5) + 22);
+        let end = &zip[zip.len() - 22..];
+        assert_eq!(&end[0..4], b"PK\x05\x06");
+        assert_eq!(u16::from_le_bytes([end[10], end[11]]), 1);
+        assert_eq!(u32::from_le_bytes([end[16], end[17], end[18], end[19]]), 40);
+
+        // Deterministic
+        assert_eq!(zip, write_zip(&[("a.txt".to_string(), b"hello".to_vec())]));
+    }
+}
@@ -0,0 +1,298 @@
+//! Compiled configuration cache
+//!
+//! Parsing ASL scripts and validating GameData is repeated every time a host
+//! starts a session. This module keeps the compiled result (validated GameData,
+//! resolved engine type and pre-parsed byte patterns) keyed by a hash of the
+//! source text, so restarting a session with the same configuration is cheap.
+//! The hash is FNV-1a, which is stable across Rust releases and platforms, and
+//! each entry keeps its source so a hash collision is a miss rather than
+//! another configuration's result.
+
+use crate::asl;
+use crate::engine::{parse_all_patterns, EngineType};
+use crate::game_data::GameData;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+/// Maximum number of compiled configurations kept in memory
+pub const MAX_CACHED_CONFIGS: usize = 16;
+
+/// GameData that has been validated and prepared for the generic engine
+#[derive(Debug, Clone)]
+pub struct CompiledGameData {
+    pub game_data: GameData,
+    pub engine_type: EngineType,
+    /// Byte patterns keyed by pattern name
+    pub patterns: HashMap<String, Vec<Option<u8>>>,
+}
+
+impl CompiledGameData {
+    /// Validate and compile a GameData definition
+    pub fn compile(game_data: GameData) -> Result<Self, String> {
+        game_data.validate()?;
+        let engine_type = EngineType::resolve(&game_data.autosplitter.engine)
+            .ok_or_else(|| format!("Unknown engine type: {}", game_data.autosplitter.engine))?;
+        let patterns = parse_all_patterns(&game_data);
+
+        Ok(Self {
+            game_data,
+            engine_type,
+            patterns,
+        })
+    }
+}
+
+/// What a configuration was compiled from
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Source {
+    kind: &'static str,
+    content: String,
+    hint: Option<String>,
+}
+
+impl Source {
+    fn new(kind: &'static str, content: &str, hint: Option<&str>) -> Self {
+        Self {
+            kind,
+            content: content.to_string(),
+            hint: hint.map(str::to_string),
+        }
+    }
+
+    /// Hash of the kind, hint and content
+    fn key(&self) -> u64 {
+        let hint = self.hint.as_deref().map_or(&[0xff][..], str::as_bytes);
+        fnv1a(&[self.kind.as_bytes(), &[0], hint, &[0], self.content.as_bytes()])
+    }
+}
+
+/// 64-bit FNV-1a hash of the concatenated parts
+fn fnv1a(parts: &[&[u8]]) -> u64 {
+    parts
+        .iter()
+        .flat_map(|part| part.iter())
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+#[derive(Default)]
+struct Cache {
+    entries: HashMap<u64, (Source, Arc<CompiledGameData>)>,
+    order: VecDeque<u64>,
+}
+
+impl Cache {
+    /// Cached result of `source`; an entry with the same key but another
+    /// source is a miss
+    fn get(&self, key: u64, source: &Source) -> Option<Arc<CompiledGameData>> {
+        self.entries
+            .get(&key)
+            .filter(|(cached, _)| cached == source)
+            .map(|(_, compiled)| compiled.clone())
+    }
+
+    fn insert(&mut self, key: u64, source: Source, compiled: Arc<CompiledGameData>) {
+        if self.entries.insert(key, (source, compiled)).is_none() {
+            self.order.push_back(key);
+        }
+        while self.order.len() > MAX_CACHED_CONFIGS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+static CACHE: Lazy<Mutex<Cache>> = Lazy::new(|| Mutex::new(Cache::default()));
+
+fn get_or_compile(
+    source: Source,
+    compile: impl FnOnce() -> Result<GameData, String>,
+) -> Result<Arc<CompiledGameData>, String> {
+    let key = source.key();
+    if let Some(compiled) = CACHE.lock().unwrap().get(key, &source) {
+        log::debug!("Compiled config cache hit ({:016x})", key);
+        return Ok(compiled);
+    }
+
+    let compiled = Arc::new(CompiledGameData::compile(compile()?)?);
+    CACHE.lock().unwrap().insert(key, source, compiled.clone());
+    Ok(compiled)
+}
+
+/// Compile a GameData TOML definition, reusing a cached result if the same
+/// content was compiled before
+pub fn precompile_game_data(toml_str: &str) -> Result<Arc<CompiledGameData>, String> {
+    get_or_compile(Source::new("toml", toml_str, None), || {
+        GameData::from_toml(toml_str).map_err(|e| format!("Failed to parse game data TOML: {}", e))
+    })
+}
+
+/// Compile an ASL script, reusing a cached result if the same script and
+/// engine hint were compiled before
+pub fn precompile_asl(
+    asl_content: &str,
+    engine_hint: Option<&str>,
+) -> Result<Arc<CompiledGameData>, String> {
+    get_or_compile(Source::new("asl", asl_content, engine_hint), || {
+        asl::parse_asl(asl_content, engine_hint).map_err(|e| format!("Failed to parse ASL: {}", e))
+    })
+}
+
+/// Number of compiled configurations currently cached
+pub fn cached_count() -> usize {
+    CACHE.lock().unwrap().entries.len()
+}
+
+/// Drop all cached configurations
+pub fn clear() {
+    let mut cache = CACHE.lock().unwrap();
+    cache.entries.clear();
+    cache.order.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::GenericGame;
+
+    const TEST_TOML: &str = r#"
+[game]
+id = "cache_test"
+name = "Cache Test"
+process_names = ["cache_test.exe"]
+
+[autosplitter]
+engine = "ds3"
+
+[[autosplitter.patterns]]
+name = "event_flags"
+pattern = "48 8b 35 ? ? ? ?"
+resolve = "rip_relative"
+rip_offset = 3
+
+[autosplitter.pointers.event_flags]
+pattern = "event_flags"
+offsets = [0]
+"#;
+
+    #[test]
+    fn test_compile_game_data() {
+        let data = GameData::from_toml(TEST_TOML).unwrap();
+        let compiled = CompiledGameData::compile(data).unwrap();
+
+        assert_eq!(compiled.engine_type, EngineType::Ds3);
+        assert_eq!(compiled.patterns.get("event_flags").unwrap().len(), 7);
+    }
+
+    #[test]
+    fn test_compile_rejects_unknown_engine() {
+        let mut data = GameData::from_toml(TEST_TOML).unwrap();
+        data.autosplitter.engine = "unknown".to_string();
+
+        assert!(CompiledGameData::compile(data).is_err());
+    }
+
+    #[test]
+    fn test_compile_registered_engine() {
+        struct OddFlags;
+        impl crate::engine::Engine for OddFlags {
+            fn read_event_flag(&self, _game: &GenericGame, flag_id: u32) -> bool {
+                flag_id % 2 == 1
+            }
+        }
+
+        let mut data = GameData::from_toml(TEST_TOML).unwrap();
+        data.autosplitter.engine = "Odd_Flags".to_string();
+        assert!(CompiledGameData::compile(data.clone()).is_err());
+
+        crate::engine::register_engine("odd_flags", Arc::new(OddFlags)).unwrap();
+        assert!(crate::engine::register_engine("ds3", Arc::new(OddFlags)).is_err());
+        let compiled = CompiledGameData::compile(data).unwrap();
+        assert_eq!(compiled.engine_type, EngineType::Custom);
+
+        let game = GenericGame::from_compiled(&compiled);
+        assert!(game.read_event_flag(13000801));
+        assert!(!game.read_event_flag(13000800));
+        assert_eq!(game.get_kill_count(13000801), 1);
+        assert!(crate::capabilities::describe().engines.iter().any(|e| e.id == "odd_flags"));
+
+        assert!(crate::engine::unregister_engine("odd_flags"));
+        assert!(!GenericGame::from_compiled(&compiled).read_event_flag(13000801));
+    }
+
+    #[test]
+    fn test_precompile_game_data_reuses_entry() {
+        let first = precompile_game_data(TEST_TOML).unwrap();
+        let second = precompile_game_data(TEST_TOML).unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_precompile_asl_keyed_by_hint() {
+        let asl = r#"
+state("DarkSoulsIII.exe") {
+    bool cacheBoss : "sprj_event_flag_man", 13000050;
+}
+"#;
+        let ds3 = precompile_asl(asl, Some("ds3")).unwrap();
+        let again = precompile_asl(asl, Some("ds3")).unwrap();
+        let sekiro = precompile_asl(asl, Some("sekiro")).unwrap();
+
+        assert!(Arc::ptr_eq(&ds3, &again));
+        assert!(!Arc::ptr_eq(&ds3, &sekiro));
+        assert_eq!(sekiro.engine_type, EngineType::Sekiro);
+    }
+
+    #[test]
+    fn test_precompile_invalid_toml() {
+        let err = precompile_game_data("invalid toml {{{").unwrap_err();
+        assert!(err.starts_with("Failed to parse game data TOML"));
+    }
+
+    #[test]
+    fn test_cache_eviction() {
+        let mut cache = Cache::default();
+        let compiled = Arc::new(
+            CompiledGameData::compile(GameData::from_toml(TEST_TOML).unwrap()).unwrap(),
+        );
+
+        let source = |key: u64| Source::new("toml", &key.to_string(), None);
+
+        for key in 0..(MAX_CACHED_CONFIGS as u64 + 4) {
+            cache.insert(key, source(key), compiled.clone());
+        }
+
+        assert_eq!(cache.entries.len(), MAX_CACHED_CONFIGS);
+        assert!(cache.get(0, &source(0)).is_none());
+        let last = MAX_CACHED_CONFIGS as u64 + 3;
+        assert!(cache.get(last, &source(last)).is_some());
+    }
+
+    #[test]
+    fn test_key_collision_is_a_miss() {
+        let mut cache = Cache::default();
+        let compiled = Arc::new(
+            CompiledGameData::compile(GameData::from_toml(TEST_TOML).unwrap()).unwrap(),
+        );
+        let source = Source::new("toml", TEST_TOML, None);
+        cache.insert(source.key(), source.clone(), compiled);
+
+        // Another configuration under the same key is not served the cached one
+        let other = Source::new("toml", "[game]", None);
+        assert!(cache.get(source.key(), &other).is_none());
+        assert!(cache.get(source.key(), &source).is_some());
+    }
+
+    #[test]
+    fn test_key_is_stable() {
+        // Reference values of 64-bit FNV-1a
+        assert_eq!(fnv1a(&[]), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(&[b"a"]), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(&[b"foo", b"bar"]), fnv1a(&[b"foobar"]));
+
+        assert_ne!(Source::new("asl", "x", None).key(), Source::new("asl", "x", Some("ds3")).key());
+        assert_ne!(Source::new("asl", "x", Some("")).key(), Source::new("asl", "x", None).key());
+    }
+}
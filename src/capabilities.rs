@@ -0,0 +1,225 @@
+//! Capability listing for host applications
+//!
+//! Describes the built-in games, generic engines and flag algorithms so host
+//! UIs can build game pickers and feature toggles without hard-coding them.
+
+use crate::attributes::{self, AttributeMapping};
+use crate::engine::{self, EngineType};
+use crate::GameType;
+use serde::Serialize;
+
+/// A built-in game implementation
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct GameCapability {
+    /// Identifier accepted by `autosplitter_start` (e.g., "DarkSouls3")
+    pub id: String,
+    pub name: String,
+    pub process_names: Vec<String>,
+    /// Readable values and detections (e.g., "event_flags", "igt", "position")
+    pub features: Vec<String>,
+    /// Canonical attributes the game supports and its own names for them
+    pub attributes: Vec<AttributeMapping>,
+}
+
+/// A generic engine usable from GameData or ASL configurations
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct EngineCapability {
+    /// Identifier used in `[autosplitter] engine = "..."`
+    pub id: String,
+    /// Flag algorithm used by this engine
+    pub algorithm: String,
+    pub uses_kill_counters: bool,
+}
+
+/// An event flag reading algorithm
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct AlgorithmCapability {
+    pub id: String,
+    pub description: String,
+}
+
+/// Everything the library supports
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Capabilities {
+    pub version: String,
+    pub games: Vec<GameCapability>,
+    pub engines: Vec<EngineCapability>,
+    pub algorithms: Vec<AlgorithmCapability>,
+}
+
+pub(crate) const ALGORITHMS: &[(&str, &str)] = &[
+    (
+        "category_decomposition",
+        "Flags grouped into categories by flag_id / divisor (DS3, Sekiro, AC6)",
+    ),
+    (
+        "binary_tree",
+        "Flag groups stored in a binary tree keyed by flag_id / divisor (Elden Ring)",
+    ),
+    (
+        "offset_table",
+        "Flags stored in a flat bit array indexed by flag_id (DS1)",
+    ),
+    (
+        "kill_counter",
+        "Boss kills tracked as counters instead of flags (DS2)",
+    ),
+];
+
+/// Features provided by a built-in game implementation
+pub fn game_features(game_type: GameType) -> &'static [&'static str] {
+    match game_type {
+        GameType::DarkSouls1 => &[
+            "event_flags",
+            "igt",
+            "position",
+            "attributes",
+            "player_loaded",
+            "credits",
+            "ng_level",
+            "save_slot",
+            "player_health",
+            "warp",
+        ],
+        GameType::DarkSouls2 => &[
+            "event_flags",
+            "kill_counters",
+            "igt",
+            "position",
+            "attributes",
+            "loading",
+            "trigger_catalog",
+        ],
+        GameType::DarkSouls3 => &[
+            "event_flags",
+            "igt",
+            "position",
+            "attributes",
+            "loading",
+            "player_loaded",
+            "blackscreen",
+            "stat_snapshots",
+            "weapon_upgrade",
+            "warp_destination",
+            "frame_rate",
+            "achievements",
+        ],
+        GameType::EldenRing => &[
+            "event_flags",
+            "igt",
+            "position",
+            "player_loaded",
+            "blackscreen",
+            "screen_state",
+            "ng_level",
+            "attributes",
+            "stat_snapshots",
+            "deaths",
+            "pvp_detection",
+            "warp_destination",
+            "frame_rate",
+            "achievements",
+        ],
+        GameType::Sekiro => &[
+            "event_flags",
+            "igt",
+            "position",
+            "attributes",
+            "player_loaded",
+            "blackscreen",
+            "demon_bell",
+            "kuros_charm",
+            "gauntlets",
+            "idols",
+        ],
+        GameType::ArmoredCore6 => &["event_flags", "igt", "loading", "mission_ranks", "arena", "mission_timing"],
+    }
+}
+
+/// Describe the games, engines and algorithms supported by this build
+pub fn describe() -> Capabilities {
+    let games = GameType::ALL
+        .iter()
+        .map(|game| GameCapability {
+            id: format!("{:?}", game),
+            name: game.display_name().to_string(),
+            process_names: game.process_names().iter().map(|n| n.to_string()).collect(),
+            features: game_features(*game).iter().map(|f| f.to_string()).collect(),
+            attributes: attributes::mappings(*game),
+        })
+        .collect();
+
+    let engines = EngineType::ALL
+        .iter()
+        .map(|engine| EngineCapability {
+            id: engine.id().to_string(),
+            algorithm: engine.algorithm().to_string(),
+            uses_kill_counters: engine.uses_kill_counters(),
+        })
+        .chain(engine::registered_engines().into_iter().map(|id| EngineCapability {
+            id,
+            algorithm: EngineType::Custom.algorithm().to_string(),
+            uses_kill_counters: false,
+        }))
+        .collect();
+
+    let algorithms = ALGORITHMS
+        .iter()
+        .map(|(id, description)| AlgorithmCapability {
+            id: id.to_string(),
+            description: description.to_string(),
+        })
+        .collect();
+
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        games,
+        engines,
+        algorithms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_lists_all_games() {
+        let caps = describe();
+        assert_eq!(caps.games.len(), GameType::ALL.len());
+
+        let ds3 = caps.games.iter().find(|g| g.id == "DarkSouls3").unwrap();
+        assert_eq!(ds3.process_names, vec!["DarkSoulsIII.exe"]);
+        assert!(ds3.features.contains(&"event_flags".to_string()));
+        assert!(ds3
+            .attributes
+            .iter()
+            .any(|a| a.canonical == attributes::CanonicalAttribute::Health && a.name == "vigor"));
+    }
+
+    #[test]
+    fn test_engine_ids_round_trip() {
+        for engine in EngineType::ALL {
+            assert_eq!(EngineType::from_str(engine.id()), Some(engine));
+        }
+    }
+
+    #[test]
+    fn test_engine_algorithms_are_listed() {
+        let caps = describe();
+        for engine in &caps.engines {
+            assert!(caps.algorithms.iter().any(|a| a.id == engine.algorithm));
+        }
+    }
+
+    #[test]
+    fn test_describe_serializes() {
+        let json = serde_json::to_string(&describe()).unwrap();
+        assert!(json.contains("\"engines\""));
+        assert!(json.contains("\"kill_counter\""));
+    }
+}
@@ -0,0 +1,229 @@
+//! VOD chapter export
+//!
+//! Turns the splits of an attempt into chapters for video tools: FFmpeg
+//! metadata files (`ffmpeg -i run.mp4 -i run.ffmetadata -map_metadata 1 ...`)
+//! and YouTube description timestamps. Each chapter is the segment ending at a
+//! split and is named after it. Times are aligned to wall-clock: to the moment
+//! the recording started when the host reports it, otherwise to the start of
+//! the attempt.
+
+use crate::run_log::{RunEvent, RunLogEntry};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// A chapter, in milliseconds from the start of the recording
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Chapter {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub title: String,
+}
+
+/// Output format for chapter exports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum ChapterFormat {
+    /// FFmpeg metadata file (`;FFMETADATA1`)
+    Ffmpeg,
+    /// YouTube description timestamps (`0:00 Title`)
+    Youtube,
+}
+
+impl ChapterFormat {
+    /// Parse a format name ("ffmpeg" or "youtube")
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "ffmpeg" | "ffmetadata" => Some(ChapterFormat::Ffmpeg),
+            "youtube" => Some(ChapterFormat::Youtube),
+            _ => None,
+        }
+    }
+
+    /// File extension used when writing chapter files
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ChapterFormat::Ffmpeg => "ffmetadata",
+            ChapterFormat::Youtube => "youtube.txt",
+        }
+    }
+
+    /// Render chapters in this format
+    pub fn render(&self, chapters: &[Chapter]) -> String {
+        match self {
+            ChapterFormat::Ffmpeg => to_ffmpeg_metadata(chapters),
+            ChapterFormat::Youtube => to_youtube(chapters),
+        }
+    }
+}
+
+/// Display name of a split event
+pub(crate) fn split_name(event: &RunEvent) -> Option<&str> {
+    match event {
+        RunEvent::BossDefeated { boss_name, display_name, .. } => Some(display_name.as_deref().unwrap_or(boss_name)),
+        RunEvent::TriggerMatched { name, display_name, .. } => Some(display_name.as_deref().unwrap_or(name)),
+        RunEvent::SegmentTimed { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+/// Build chapters for the current attempt (everything after the last reset)
+///
+/// `log_started_unix_ms` is the wall-clock start of the run log;
+/// `recording_started_unix_ms` is when the VOD recording started, if known.
+/// Segments that ended before the recording started are dropped.
+pub fn build(
+    entries: &[RunLogEntry],
+    log_started_unix_ms: u64,
+    recording_started_unix_ms: Option<u64>,
+) -> Vec<Chapter> {
+    let reset = entries.iter().rposition(|e| matches!(e.event, RunEvent::Reset));
+    let attempt_start_ms = reset.map_or(0, |i| entries[i].elapsed_ms);
+    let attempt = &entries[reset.map_or(0, |i| i + 1)..];
+
+    // Log-relative time of the recording start
+    let origin = recording_started_unix_ms.map_or(attempt_start_ms as i64, |recording| {
+        recording as i64 - log_started_unix_ms as i64
+    });
+    let relative = |elapsed_ms: u64| (elapsed_ms as i64 - origin).max(0) as u64;
+
+    let mut chapters = Vec::new();
+    let mut segment_start = relative(attempt_start_ms);
+    for entry in attempt {
+        if crate::speech::split_key(&entry.event).is_none() {
+            continue;
+        }
+        let Some(title) = split_name(&entry.event) else {
+            continue;
+        };
+
+        let end = relative(entry.elapsed_ms);
+        if end > segment_start {
+            chapters.push(Chapter {
+                start_ms: segment_start,
+                end_ms: end,
+                title: title.to_string(),
+            });
+        }
+        segment_start = end;
+    }
+
+    chapters
+}
+
+fn escape_ffmpeg(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// FFmpeg metadata file with one `[CHAPTER]` per segment
+pub fn to_ffmpeg_metadata(chapters: &[Chapter]) -> String {
+    let mut out = String::from(";FFMETADATA1\n");
+    for chapter in chapters {
+        out.push_str("\n[CHAPTER]\nTIMEBASE=1/1000\n");
+        let _ = writeln!(out, "START={}", chapter.start_ms);
+        let _ = writeln!(out, "END={}", chapter.end_ms);
+        let _ = writeln!(out, "title={}", escape_ffmpeg(&chapter.title));
+    }
+    out
+}
+
+fn format_timestamp(ms: u64) -> String {
+    let total_seconds = ms / 1000;
+    let (hours, minutes, seconds) = (total_seconds / 3600, (total_seconds / 60) % 60, total_seconds % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
+
+/// YouTube chapter list; YouTube requires the first chapter at 0:00, so a
+/// "Start" chapter is added when the first segment begins later
+pub fn to_youtube(chapters: &[Chapter]) -> String {
+    let mut out = String::new();
+    if chapters.first().is_some_and(|c| c.start_ms >= 1000) {
+        out.push_str("0:00 Start\n");
+    }
+    for (index, chapter) in chapters.iter().enumerate() {
+        let start = if index == 0 && chapter.start_ms < 1000 { 0 } else { chapter.start_ms };
+        let _ = writeln!(out, "{} {}", format_timestamp(start), chapter.title);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(elapsed_ms: u64, event: RunEvent) -> RunLogEntry {
+        RunLogEntry {
+            elapsed_ms,
+            igt_ms: None,
+            host_ms: None,
+            menu_time_ms: None,
+            event,
+        }
+    }
+
+    fn boss(name: &str) -> RunEvent {
+        RunEvent::BossDefeated {
+            boss_id: name.to_lowercase(),
+            boss_name: name.to_string(),
+            flag_id: 0,
+            suspicion: None,
+            display_name: None,
+            metadata: Default::default(),
+        }
+    }
+
+    fn log() -> Vec<RunLogEntry> {
+        vec![
+            entry(1_000, boss("Old Attempt")),
+            entry(10_000, RunEvent::Reset),
+            entry(70_000, boss("Iudex Gundyr")),
+            entry(75_000, RunEvent::StatSnapshot { stats: Default::default() }),
+            entry(3_730_000, boss("Vordt")),
+        ]
+    }
+
+    #[test]
+    fn test_chapters_aligned_to_attempt_start() {
+        let chapters = build(&log(), 1_700_000_000_000, None);
+        assert_eq!(
+            chapters,
+            vec![
+                Chapter { start_ms: 0, end_ms: 60_000, title: "Iudex Gundyr".to_string() },
+                Chapter { start_ms: 60_000, end_ms: 3_720_000, title: "Vordt".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chapters_aligned_to_recording_start() {
+        // Recording started 5 s after the log (so 5 s before the reset)
+        let chapters = build(&log(), 1_700_000_000_000, Some(1_700_000_005_000));
+        assert_eq!(chapters[0].start_ms, 5_000);
+        assert_eq!(chapters[0].end_ms, 65_000);
+
+        let youtube = to_youtube(&chapters);
+        assert_eq!(youtube, "0:00 Start\n0:05 Iudex Gundyr\n1:05 Vordt\n");
+    }
+
+    #[test]
+    fn test_ffmpeg_metadata() {
+        let chapters = vec![Chapter { start_ms: 0, end_ms: 60_000, title: "A=B; #1".to_string() }];
+        assert_eq!(
+            to_ffmpeg_metadata(&chapters),
+            ";FFMETADATA1\n\n[CHAPTER]\nTIMEBASE=1/1000\nSTART=0\nEND=60000\ntitle=A\\=B\\; \\#1\n"
+        );
+        assert_eq!(format_timestamp(3_720_000), "1:02:00");
+    }
+}
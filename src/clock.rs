@@ -0,0 +1,144 @@
+//! Time sources for the run loops
+//!
+//! The loops never call `Instant::now` or `thread::sleep` directly: they read
+//! the time from a [`Clock`] and wait through a [`Sleeper`]. Hosts use the
+//! real ones ([`SystemClock`]); tests hand `Autosplitter::with_clock` a
+//! [`SimulatedClock`], which implements both and advances instantly on every
+//! sleep, so reconnect backoff, save-data stabilization and snapshot intervals
+//! run deterministically in microseconds.
+
+use crate::idle::Waker;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Source of the current time
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// Blocking waits of the run loops
+pub trait Sleeper: Send + Sync {
+    fn sleep(&self, duration: Duration);
+
+    /// Sleep up to `timeout` unless `waker` is woken first; returns true if
+    /// woken early
+    fn sleep_until_woken(&self, waker: &Waker, timeout: Duration) -> bool;
+}
+
+/// Real time and real sleeps
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+impl Sleeper for SystemClock {
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+
+    fn sleep_until_woken(&self, waker: &Waker, timeout: Duration) -> bool {
+        waker.wait_timeout(timeout)
+    }
+}
+
+/// Simulated time: sleeping returns immediately and moves the clock forward
+#[derive(Debug)]
+pub struct SimulatedClock {
+    start: Instant,
+    inner: Mutex<SimulatedTime>,
+}
+
+#[derive(Debug, Default)]
+struct SimulatedTime {
+    elapsed: Duration,
+    sleeps: Vec<Duration>,
+}
+
+impl Default for SimulatedClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatedClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            inner: Mutex::new(SimulatedTime::default()),
+        }
+    }
+
+    /// Move the clock forward without recording a sleep
+    pub fn advance(&self, duration: Duration) {
+        self.inner.lock().unwrap().elapsed += duration;
+    }
+
+    /// Simulated time since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        self.inner.lock().unwrap().elapsed
+    }
+
+    /// Every sleep so far, in order (woken idle waits record what they slept)
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.inner.lock().unwrap().sleeps.clone()
+    }
+}
+
+impl Clock for SimulatedClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed()
+    }
+}
+
+impl Sleeper for SimulatedClock {
+    fn sleep(&self, duration: Duration) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.elapsed += duration;
+        inner.sleeps.push(duration);
+        drop(inner);
+        // Let other threads (e.g. a test driving the loop) make progress
+        std::thread::yield_now();
+    }
+
+    fn sleep_until_woken(&self, waker: &Waker, timeout: Duration) -> bool {
+        if waker.wait_timeout(Duration::ZERO) {
+            self.inner.lock().unwrap().sleeps.push(Duration::ZERO);
+            return true;
+        }
+        self.sleep(timeout);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulated_sleep_advances_clock() {
+        let clock = SimulatedClock::new();
+        let start = clock.now();
+        clock.sleep(Duration::from_millis(1500));
+        clock.advance(Duration::from_millis(500));
+
+        assert_eq!(clock.now() - start, Duration::from_secs(2));
+        assert_eq!(clock.sleeps(), vec![Duration::from_millis(1500)]);
+    }
+
+    #[test]
+    fn test_simulated_wait_is_woken() {
+        let clock = SimulatedClock::new();
+        let waker = Waker::new();
+
+        assert!(!clock.sleep_until_woken(&waker, Duration::from_secs(30)));
+        assert_eq!(clock.elapsed(), Duration::from_secs(30));
+
+        waker.wake();
+        assert!(clock.sleep_until_woken(&waker, Duration::from_secs(30)));
+        assert_eq!(clock.elapsed(), Duration::from_secs(30));
+    }
+}
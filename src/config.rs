@@ -2,8 +2,21 @@
 //!
 //! These types define the structure of autosplitter configurations loaded from TOML files.
 
+use crate::ac6_missions::MissionTiming;
+use crate::achievements::{Achievement, AchievementProgress};
+use crate::boss_phases::BossPhaseWatch;
+use crate::cross_check::FlagDisagreement;
+use crate::frame_rate::FrameRate;
+use crate::limited_access::LimitedAccess;
+use crate::memory::ReadLimits;
+use crate::output::OutputConfig;
+use crate::position_trail::WorldPosition;
+use crate::read_plan::ReadPlanStats;
+use crate::read_stats::ReadStats;
+use crate::sekiro_gauntlet::GauntletProgress;
+use crate::timing_rules::TimingRule;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Memory pattern configuration for scanning
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -176,6 +189,11 @@ pub struct AutosplitterMemoryConfig {
     pub pointer_chain: Vec<i64>,
 }
 
+/// Free-form host data attached to a boss or trigger (e.g., icon path,
+/// color, wiki URL); the autosplitter never interprets it and passes it
+/// through to events and state unchanged
+pub type SplitMetadata = BTreeMap<String, serde_json::Value>;
+
 /// Boss flag information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BossFlag {
@@ -184,19 +202,588 @@ pub struct BossFlag {
     pub flag_id: u32,
     #[serde(default)]
     pub is_dlc: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: SplitMetadata,
+    /// Trigger group the boss split competes in with custom triggers of the
+    /// same group (see `CustomTrigger::group`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Rank within the group (higher wins; ties go to the boss)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub priority: i32,
+}
+
+/// Comparison operator for attribute triggers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum CompareOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl CompareOp {
+    /// Compare a read value against the configured value
+    pub fn compare(&self, actual: i64, expected: i64) -> bool {
+        match self {
+            CompareOp::Eq => actual == expected,
+            CompareOp::Ne => actual != expected,
+            CompareOp::Gt => actual > expected,
+            CompareOp::Ge => actual >= expected,
+            CompareOp::Lt => actual < expected,
+            CompareOp::Le => actual <= expected,
+        }
+    }
+}
+
+/// How many flags of a flag set must be set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum FlagSetMode {
+    /// At least one flag
+    Any,
+    /// Every flag
+    All,
+    /// At least N flags (e.g., `{ "at_least": 20 }`)
+    AtLeast(usize),
+}
+
+impl FlagSetMode {
+    /// Check a set count against the total number of flags
+    pub fn matches(&self, set: usize, total: usize) -> bool {
+        match self {
+            FlagSetMode::Any => set > 0,
+            FlagSetMode::All => total > 0 && set == total,
+            FlagSetMode::AtLeast(count) => set >= *count,
+        }
+    }
+}
+
+/// Inclusive range of flag ids, optionally with a step (e.g., every 10th flag)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct FlagRange {
+    pub first: u32,
+    pub last: u32,
+    #[serde(default = "default_flag_step")]
+    pub step: u32,
+}
+
+fn default_flag_step() -> u32 {
+    1
+}
+
+/// Largest flag set a single trigger may watch
+pub const MAX_FLAG_SET_SIZE: usize = 10_000;
+
+/// Condition that fires a custom trigger
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum TriggerCondition {
+    /// Event flag is set
+    EventFlag { flag_id: u32 },
+    /// Named game attribute compared against a value (e.g., "last_mission_rank")
+    Attribute {
+        name: String,
+        op: CompareOp,
+        value: i64,
+    },
+    /// Flags from a list and/or range, matched with any/all/at-least semantics
+    /// (e.g., "any of the four Lords of Cinder")
+    FlagSet {
+        #[serde(default)]
+        flags: Vec<u32>,
+        #[serde(default)]
+        range: Option<FlagRange>,
+        mode: FlagSetMode,
+    },
+    /// Player warped to a bonfire or grace (entity id of the destination),
+    /// optionally only once `after_flag` is set (e.g., "first warp to
+    /// Firelink after Vordt")
+    Warp {
+        destination: u32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        after_flag: Option<u32>,
+    },
+    /// Player is within `radius` of a position, optionally only once
+    /// `after_flag` is set (e.g., "arriving in Majula"); `map_id` narrows it
+    /// to one map in games that report it (Elden Ring)
+    Position {
+        x: f32,
+        y: f32,
+        z: f32,
+        radius: f32,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        map_id: Option<u32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        after_flag: Option<u32>,
+    },
+}
+
+impl TriggerCondition {
+    /// Evaluate the condition using the given flag and attribute readers
+    pub fn evaluate(
+        &self,
+        read_flag: impl Fn(u32) -> bool,
+        read_attribute: impl Fn(&str) -> Option<i64>,
+    ) -> bool {
+        match self {
+            TriggerCondition::EventFlag { flag_id } => read_flag(*flag_id),
+            TriggerCondition::Attribute { name, op, value } => read_attribute(name)
+                .map(|actual| op.compare(actual, *value))
+                .unwrap_or(false),
+            TriggerCondition::FlagSet { mode, .. } => {
+                let flag_ids = self.flag_ids();
+                let set = flag_ids.iter().filter(|&&flag_id| read_flag(flag_id)).count();
+                mode.matches(set, flag_ids.len())
+            }
+            // Matched on warp events, not polled (see `warp`)
+            TriggerCondition::Warp { .. } => false,
+            // Matched against the player position (see `matches_position`)
+            TriggerCondition::Position { .. } => false,
+        }
+    }
+
+    /// Whether the player position is inside a position condition's area
+    /// (flags are not checked)
+    pub fn matches_position(&self, position: &WorldPosition) -> bool {
+        let TriggerCondition::Position {
+            x,
+            y,
+            z,
+            radius,
+            map_id,
+            ..
+        } = self
+        else {
+            return false;
+        };
+        if position.is_origin() || map_id.is_some_and(|map_id| position.map_id != Some(map_id)) {
+            return false;
+        }
+        let distance = ((position.x - x).powi(2) + (position.y - y).powi(2) + (position.z - z).powi(2)).sqrt();
+        distance <= *radius
+    }
+
+    /// Whether a position condition fires at `position`: it matches the
+    /// position and its `after_flag`, if any, is set
+    pub fn position_fires(&self, position: &WorldPosition, read_flag: impl Fn(u32) -> bool) -> bool {
+        match self {
+            TriggerCondition::Position { after_flag, .. } => {
+                self.matches_position(position) && after_flag.is_none_or(read_flag)
+            }
+            _ => false,
+        }
+    }
+
+    /// Event flags read by this condition (sorted and deduplicated for flag sets)
+    pub fn flag_ids(&self) -> Vec<u32> {
+        match self {
+            TriggerCondition::EventFlag { flag_id } => vec![*flag_id],
+            TriggerCondition::Attribute { .. } => Vec::new(),
+            TriggerCondition::Warp { after_flag, .. } | TriggerCondition::Position { after_flag, .. } => {
+                after_flag.iter().copied().collect()
+            }
+            TriggerCondition::FlagSet { flags, range, .. } => {
+                let mut flag_ids = flags.clone();
+                if let Some(range) = range {
+                    let step = range.step.max(1) as usize;
+                    flag_ids.extend((range.first..=range.last).step_by(step).take(MAX_FLAG_SET_SIZE + 1));
+                }
+                flag_ids.sort_unstable();
+                flag_ids.dedup();
+                flag_ids
+            }
+        }
+    }
+
+    /// Check a condition loaded from a config before it is evaluated
+    pub fn validate(&self) -> Result<(), String> {
+        if let TriggerCondition::Position { radius, .. } = self {
+            if !radius.is_finite() || *radius <= 0.0 {
+                return Err("Position radius must be positive".to_string());
+            }
+            return Ok(());
+        }
+        let TriggerCondition::FlagSet { range, mode, .. } = self else {
+            return Ok(());
+        };
+
+        if let Some(range) = range {
+            if range.first > range.last || range.step == 0 {
+                return Err(format!(
+                    "Invalid flag range {}..={} (step {})",
+                    range.first, range.last, range.step
+                ));
+            }
+        }
+
+        let total = self.flag_ids().len();
+        if total == 0 {
+            return Err("Flag set has no flags".to_string());
+        }
+        if total > MAX_FLAG_SET_SIZE {
+            return Err(format!("Flag set watches more than {} flags", MAX_FLAG_SET_SIZE));
+        }
+        if let FlagSetMode::AtLeast(count) = mode {
+            if *count == 0 || *count > total {
+                return Err(format!("at_least = {} is outside 1..={}", count, total));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What a matched custom trigger means for the run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum TriggerKind {
+    /// Regular split
+    #[default]
+    Split,
+    /// Category rule was broken (e.g., soul level above the cap in an SL1 run)
+    RuleViolation,
+}
+
+/// Custom split trigger checked alongside boss flags
+/// Matched trigger indices are reported in `AutosplitterState::triggers_matched`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct CustomTrigger {
+    pub id: String,
+    pub name: String,
+    pub condition: TriggerCondition,
+    #[serde(default)]
+    pub kind: TriggerKind,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: SplitMetadata,
+    /// Mutual-exclusion group: when several triggers of a group fire on the
+    /// same tick, only the one with the highest `priority` splits
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Rank within the group (higher wins; ties go to the earlier trigger)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub priority: i32,
+    /// Overrides `AutosplitterConfig::split_suppression_ms` for this trigger
+    /// (0 = never suppressed)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppression_ms: Option<u64>,
+}
+
+fn is_zero(value: &i32) -> bool {
+    *value == 0
+}
+
+/// Split the splits that fired on one tick, given as (group, priority) in
+/// order, into the positions of those that split and those suppressed by a
+/// higher-priority split of the same group
+pub fn resolve_groups(fired: &[(Option<&str>, i32)]) -> (Vec<usize>, Vec<usize>) {
+    let mut winners: Vec<usize> = Vec::new();
+    let mut suppressed = Vec::new();
+    for (index, &(group, priority)) in fired.iter().enumerate() {
+        let Some(group) = group else {
+            winners.push(index);
+            continue;
+        };
+
+        let rival = winners.iter().position(|&w| fired[w].0 == Some(group));
+        match rival {
+            Some(position) if priority > fired[winners[position]].1 => {
+                suppressed.push(winners[position]);
+                winners[position] = index;
+            }
+            Some(_) => suppressed.push(index),
+            None => winners.push(index),
+        }
+    }
+    winners.sort_unstable();
+    suppressed.sort_unstable();
+    (winners, suppressed)
+}
+
+/// Split the triggers that fired on one tick (indices into `triggers`, in
+/// order) into those that split and those suppressed by a higher-priority
+/// trigger of the same group
+pub fn resolve_trigger_groups(triggers: &[CustomTrigger], fired: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let ranks: Vec<(Option<&str>, i32)> = fired
+        .iter()
+        .map(|&index| (triggers[index].group.as_deref(), triggers[index].priority))
+        .collect();
+    let (winners, suppressed) = resolve_groups(&ranks);
+    (
+        winners.into_iter().map(|position| fired[position]).collect(),
+        suppressed.into_iter().map(|position| fired[position]).collect(),
+    )
+}
+
+impl CustomTrigger {
+    /// Validate the trigger condition, naming the trigger in the error
+    pub fn validate(&self) -> Result<(), String> {
+        self.condition
+            .validate()
+            .map_err(|e| format!("Trigger '{}': {}", self.id, e))
+    }
+
+    /// Whether the trigger is swallowed when it fires `since_split_ms` after
+    /// the last split (None = no split yet); rule violations never are
+    pub fn suppressed_after_split(&self, default_window_ms: u64, since_split_ms: Option<u64>) -> bool {
+        let window_ms = self.suppression_ms.unwrap_or(default_window_ms);
+        self.kind == TriggerKind::Split && since_split_ms.is_some_and(|since| since < window_ms)
+    }
+}
+
+/// Sound files played on run events (requires the `audio` feature)
+///
+/// Paths may point to WAV, OGG Vorbis or MP3 files. Events without a sound
+/// are silent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct AudioCueConfig {
+    pub enabled: bool,
+    /// Playback volume (1.0 = original)
+    pub volume: f32,
+    pub split: Option<String>,
+    pub gold: Option<String>,
+    /// Falls back to `split` when not set
+    pub boss_defeated: Option<String>,
+    pub rule_violation: Option<String>,
+    /// Practice events of watched boss fights (see `boss_phases`)
+    pub boss_phase: Option<String>,
+}
+
+impl Default for AudioCueConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            volume: 1.0,
+            split: None,
+            gold: None,
+            boss_defeated: None,
+            rule_violation: None,
+            boss_phase: None,
+        }
+    }
+}
+
+/// Host-adjustable autosplitter settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct AutosplitterConfig {
+    /// Interval between stat snapshots written to the run log (0 disables)
+    pub stat_snapshot_interval_ms: u64,
+    /// Mark boss flags set without IGT advancing or while the player was not
+    /// loaded as suspicious (see `kill_validation`)
+    pub validate_boss_kills: bool,
+    /// Interval between player position samples for the position trail (0 disables)
+    pub position_trail_interval_ms: u64,
+    pub audio_cues: AudioCueConfig,
+    /// Announce splits with text-to-speech (requires the `tts` feature)
+    pub announce_splits: bool,
+    /// Comparison (e.g., personal best) IGT per split, keyed by boss or trigger id;
+    /// used for the deltas in split announcements
+    pub comparison_igt_ms: HashMap<String, i64>,
+    /// Delay between process searches while the game is not running
+    pub idle_backoff_initial_ms: u64,
+    /// Cap for the exponential idle backoff
+    pub idle_backoff_max_ms: u64,
+    /// Directory where VOD chapter files (FFmpeg metadata and YouTube
+    /// timestamps) are written at the end of each attempt
+    pub chapters_dir: Option<String>,
+    /// Wall-clock time the VOD recording started (ms since the Unix epoch);
+    /// chapters are aligned to the start of the attempt when not set
+    pub recording_started_unix_ms: Option<u64>,
+    /// Directory where a verification bundle (zip of the run log, splits,
+    /// config and diagnostics) is written at the end of each attempt
+    pub bundle_dir: Option<String>,
+    /// Batching and rate limiting of `poll_output` messages
+    pub output: OutputConfig,
+    /// Average memory reads per tick above which a warning is logged and
+    /// `read_stats.over_budget` is set (0 disables)
+    pub read_budget_per_tick: u64,
+    /// Elden Ring: pause split evaluation in the Colosseum and while other
+    /// players are in the world (warm-up matches can toggle boss flags)
+    pub suspend_during_pvp: bool,
+    /// Boss flags read through both the game's reader and the cross-check
+    /// reader (`set_cross_check`); they split only when both agree. Empty
+    /// checks every boss flag while a cross-check reader is set.
+    pub cross_check_flags: Vec<u32>,
+    /// Segments timed between a start and an end anchor (flag edges, loads,
+    /// blackscreens, area entry), logged as `segment_timed` splits
+    pub timing_rules: Vec<TimingRule>,
+    /// Seconds of per-tick watched flag values kept for diagnostics
+    /// (`get_flag_history_json`, verification bundles); 0 disables
+    pub flag_history_seconds: u64,
+    /// After a split, custom split triggers firing within this window are
+    /// consumed without splitting, so one game event setting several watched
+    /// flags cannot cascade into several splits (0 disables; see
+    /// `CustomTrigger::suppression_ms`)
+    pub split_suppression_ms: u64,
+    /// Achievements tracked in `AutosplitterState::achievements`; empty uses
+    /// the attached game's built-in table (see `achievements::builtin`)
+    pub achievements: Vec<Achievement>,
+    /// Caps on the memory reads of the session (see `memory::sandbox`)
+    pub read_limits: ReadLimits,
+    /// Template for the display name of boss splits and split triggers, e.g.
+    /// `"{area} - {name} ({kill_count})"` (see `split_names`); None keeps the
+    /// configured names
+    pub split_name_template: Option<String>,
+    /// Directory of game definitions and achievement tables used instead of
+    /// the data compiled into the library (see `data_dir`)
+    pub data_dir: Option<String>,
+    /// Bosses whose HP thresholds and phase flags are logged as practice
+    /// events (see `boss_phases`)
+    pub boss_phases: Vec<BossPhaseWatch>,
+    /// Attach in a limited mode when the game's memory cannot be read,
+    /// tracking only the process and its window (see `limited_access`)
+    pub low_privilege_fallback: bool,
+    /// Entries kept in the run log; when it is full the oldest are dropped
+    /// (0 keeps every entry)
+    pub run_log_max_entries: usize,
+}
+
+impl Default for AutosplitterConfig {
+    fn default() -> Self {
+        Self {
+            stat_snapshot_interval_ms: 60_000,
+            validate_boss_kills: false,
+            position_trail_interval_ms: 0,
+            audio_cues: AudioCueConfig::default(),
+            announce_splits: false,
+            comparison_igt_ms: HashMap::new(),
+            idle_backoff_initial_ms: 2000,
+            idle_backoff_max_ms: 30_000,
+            chapters_dir: None,
+            recording_started_unix_ms: None,
+            bundle_dir: None,
+            output: OutputConfig::default(),
+            read_budget_per_tick: 500,
+            suspend_during_pvp: false,
+            cross_check_flags: Vec::new(),
+            timing_rules: Vec::new(),
+            flag_history_seconds: 60,
+            split_suppression_ms: 0,
+            achievements: Vec::new(),
+            read_limits: ReadLimits::default(),
+            split_name_template: None,
+            data_dir: None,
+            boss_phases: Vec::new(),
+            low_privilege_fallback: true,
+            run_log_max_entries: crate::run_log::DEFAULT_MAX_ENTRIES,
+        }
+    }
+}
+
+/// A defeated boss in `AutosplitterState::bosses_defeated`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct DefeatedBoss {
+    /// Position of the boss in the configured boss list
+    pub split_index: usize,
+    pub boss_id: String,
+    /// Run log time of the defeat (ms since the log started)
+    pub elapsed_ms: u64,
+    #[serde(default)]
+    pub igt_ms: Option<i64>,
 }
 
 /// Autosplitter state (serializable for FFI)
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct AutosplitterState {
     pub running: bool,
     pub game_id: String,
     pub process_attached: bool,
     pub process_id: Option<u32>,
-    pub bosses_defeated: Vec<String>,
+    /// Defeated bosses ordered by `split_index`, each boss at most once;
+    /// kept across re-attaching and cleared on reset
+    pub bosses_defeated: Vec<DefeatedBoss>,
     pub triggers_matched: Vec<usize>,
+    /// Grouped bosses whose split lost to a higher-priority trigger of their
+    /// group; they do not split again until reset
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bosses_suppressed: Vec<String>,
     #[serde(default)]
     pub boss_kill_counts: HashMap<String, u32>,
+    /// Game-specific values read each tick (e.g., "igt", "last_mission_rank")
+    #[serde(default)]
+    pub attributes: HashMap<String, i64>,
+    /// Defeated bosses whose flags failed kill validation (subset of `bosses_defeated`)
+    #[serde(default)]
+    pub suspicious_bosses: Vec<String>,
+    /// Attached, but no save/character is loaded; flags are not evaluated
+    #[serde(default)]
+    pub in_main_menu: bool,
+    /// Boss flag read plan of the attached game (diagnostics; None when flags are read one by one)
+    #[serde(default)]
+    pub read_plan: Option<ReadPlanStats>,
+    /// The Steam overlay is open and capturing input; hosts should ignore
+    /// split/reset hotkeys while it is set
+    #[serde(default)]
+    pub overlay_open: bool,
+    /// Memory reads of the attached game (None until the second tick)
+    #[serde(default)]
+    pub read_stats: Option<ReadStats>,
+    /// Metadata of the configured bosses that have any, by boss id
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub boss_metadata: HashMap<String, SplitMetadata>,
+    /// Sekiro: the Reflection of Strength or gauntlet being fought
+    #[serde(default)]
+    pub gauntlet: Option<GauntletProgress>,
+    /// Elden Ring: "colosseum" or "online" while a multiplayer session is active
+    #[serde(default)]
+    pub pvp_session: Option<String>,
+    /// Cross-checked flags the two readers currently disagree on
+    #[serde(default)]
+    pub flag_disagreements: Vec<FlagDisagreement>,
+    /// Frame rate of the attached game (games with a frame counter; None
+    /// until a full window was sampled)
+    #[serde(default)]
+    pub frame_rate: Option<FrameRate>,
+    /// Completion of the tracked achievements (None without an achievement table)
+    #[serde(default)]
+    pub achievements: Option<AchievementProgress>,
+    /// Memory reads denied by `read_limits` since attaching
+    #[serde(default)]
+    pub reads_denied: u64,
+    /// Set while attached without memory access (see `limited_access`)
+    #[serde(default)]
+    pub limited_access: Option<LimitedAccess>,
+    /// AC6: timing of the current or last mission attempt (see `ac6_missions`)
+    #[serde(default)]
+    pub ac6_mission: Option<MissionTiming>,
+}
+
+impl AutosplitterState {
+    /// Whether the boss is in `bosses_defeated`
+    pub fn is_defeated(&self, boss_id: &str) -> bool {
+        self.bosses_defeated.iter().any(|defeat| defeat.boss_id == boss_id)
+    }
+
+    /// Add a defeat in split order; false when the boss was already defeated
+    pub fn record_defeat(&mut self, defeat: DefeatedBoss) -> bool {
+        if self.is_defeated(&defeat.boss_id) {
+            return false;
+        }
+        let position = self
+            .bosses_defeated
+            .partition_point(|other| other.split_index <= defeat.split_index);
+        self.bosses_defeated.insert(position, defeat);
+        true
+    }
+
+    /// Ids of the defeated bosses in split order
+    pub fn defeated_boss_ids(&self) -> Vec<String> {
+        self.bosses_defeated.iter().map(|defeat| defeat.boss_id.clone()).collect()
+    }
 }
 
 #[cfg(test)]
@@ -362,6 +949,9 @@ mod tests {
             boss_name: "Asylum Demon".to_string(),
             flag_id: 13000050,
             is_dlc: false,
+            metadata: Default::default(),
+            group: None,
+            priority: 0,
         };
 
         let json = serde_json::to_string(&flag).unwrap();
@@ -397,6 +987,208 @@ mod tests {
         assert!(state.bosses_defeated.is_empty());
         assert!(state.triggers_matched.is_empty());
         assert!(state.boss_kill_counts.is_empty());
+        assert!(state.attributes.is_empty());
+    }
+
+    #[test]
+    fn test_bosses_defeated_in_split_order() {
+        let defeat = |split_index: usize, boss_id: &str, elapsed_ms: u64| DefeatedBoss {
+            split_index,
+            boss_id: boss_id.to_string(),
+            elapsed_ms,
+            igt_ms: None,
+        };
+        let mut state = AutosplitterState::default();
+        // Out of route order, e.g. pre-populated from a late-game save
+        assert!(state.record_defeat(defeat(2, "dancer", 1_000)));
+        assert!(state.record_defeat(defeat(0, "gundyr", 1_000)));
+        assert!(state.record_defeat(defeat(1, "vordt", 2_000)));
+        // Seen again after re-attaching: the first defeat is kept
+        assert!(!state.record_defeat(defeat(0, "gundyr", 9_000)));
+
+        assert_eq!(state.defeated_boss_ids(), ["gundyr", "vordt", "dancer"]);
+        assert_eq!(state.bosses_defeated[0].elapsed_ms, 1_000);
+        assert!(state.is_defeated("vordt"));
+    }
+
+    #[test]
+    fn test_autosplitter_config_defaults() {
+        let config: AutosplitterConfig = serde_json::from_str("{}").unwrap();
+        assert_eq!(config.stat_snapshot_interval_ms, 60_000);
+
+        let config: AutosplitterConfig =
+            serde_json::from_str(r#"{ "stat_snapshot_interval_ms": 0 }"#).unwrap();
+        assert_eq!(config.stat_snapshot_interval_ms, 0);
+    }
+
+    #[test]
+    fn test_compare_op() {
+        assert!(CompareOp::Eq.compare(5, 5));
+        assert!(CompareOp::Ne.compare(4, 5));
+        assert!(CompareOp::Gt.compare(6, 5));
+        assert!(CompareOp::Ge.compare(5, 5));
+        assert!(CompareOp::Lt.compare(4, 5));
+        assert!(!CompareOp::Le.compare(6, 5));
+    }
+
+    #[test]
+    fn test_custom_trigger_json() {
+        let trigger: CustomTrigger = serde_json::from_str(r#"{
+            "id": "s_rank",
+            "name": "Mission S-Rank",
+            "condition": { "type": "attribute", "name": "last_mission_rank", "op": "ge", "value": 5 }
+        }"#).unwrap();
+
+        assert_eq!(trigger.id, "s_rank");
+        assert_eq!(
+            trigger.condition,
+            TriggerCondition::Attribute {
+                name: "last_mission_rank".to_string(),
+                op: CompareOp::Ge,
+                value: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_rule_violation_triggers() {
+        // SL1 run: fire as soon as soul level goes above the cap
+        let triggers: Vec<CustomTrigger> = serde_json::from_str(r#"[
+            { "id": "sl_cap", "name": "Soul level above 1",
+              "condition": { "type": "attribute", "name": "soul_level", "op": "gt", "value": 1 } },
+            { "id": "no_upgrade", "name": "Weapon upgraded",
+              "condition": { "type": "attribute", "name": "right_weapon_upgrade", "op": "gt", "value": 0 } }
+        ]"#).unwrap();
+
+        let read = |soul_level: i64, upgrade: i64| {
+            move |name: &str| match name {
+                "soul_level" => Some(soul_level),
+                "right_weapon_upgrade" => Some(upgrade),
+                _ => None,
+            }
+        };
+
+        assert!(!triggers[0].condition.evaluate(|_| false, read(1, 0)));
+        assert!(triggers[0].condition.evaluate(|_| false, read(2, 0)));
+        assert!(!triggers[1].condition.evaluate(|_| false, read(1, 0)));
+        assert!(triggers[1].condition.evaluate(|_| false, read(1, 3)));
+    }
+
+    #[test]
+    fn test_trigger_condition_evaluate() {
+        let flag = TriggerCondition::EventFlag { flag_id: 100 };
+        assert!(flag.evaluate(|id| id == 100, |_| None));
+        assert!(!flag.evaluate(|_| false, |_| None));
+
+        let attr = TriggerCondition::Attribute {
+            name: "rank".to_string(),
+            op: CompareOp::Eq,
+            value: 5,
+        };
+        assert!(attr.evaluate(|_| false, |name| (name == "rank").then_some(5)));
+        assert!(!attr.evaluate(|_| false, |_| Some(4)));
+        // Unreadable attributes never match
+        assert!(!attr.evaluate(|_| false, |_| None));
+    }
+
+    #[test]
+    fn test_warp_condition() {
+        let warp: TriggerCondition =
+            serde_json::from_str(r#"{ "type": "warp", "destination": 4001950, "after_flag": 13000800 }"#).unwrap();
+        assert_eq!(
+            warp,
+            TriggerCondition::Warp {
+                destination: 4001950,
+                after_flag: Some(13000800)
+            }
+        );
+        assert_eq!(warp.flag_ids(), vec![13000800]);
+        // Matched on warp events only
+        assert!(!warp.evaluate(|_| true, |_| Some(4001950)));
+    }
+
+    #[test]
+    fn test_position_condition() {
+        let area: TriggerCondition =
+            serde_json::from_str(r#"{ "type": "position", "x": 10.0, "y": 0.0, "z": -5.0, "radius": 4.0 }"#).unwrap();
+        assert!(area.validate().is_ok());
+        assert!(area.flag_ids().is_empty());
+        let at = |x: f32, z: f32| WorldPosition { x, y: 0.0, z, map_id: None };
+        assert!(area.matches_position(&at(12.0, -3.0)));
+        assert!(!area.matches_position(&at(14.0, -2.0)));
+        // No character loaded
+        assert!(!TriggerCondition::Position {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            radius: 1.0,
+            map_id: None,
+            after_flag: None
+        }
+        .matches_position(&at(0.0, 0.0)));
+
+        let invalid: TriggerCondition =
+            serde_json::from_str(r#"{ "type": "position", "x": 0.0, "y": 0.0, "z": 0.0, "radius": 0.0 }"#).unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_flag_set_modes() {
+        // Lords of Cinder
+        let lords: TriggerCondition = serde_json::from_str(
+            r#"{ "type": "flag_set", "flags": [14100800, 13800800, 13700800, 15000800], "mode": "any" }"#,
+        )
+        .unwrap();
+        assert!(!lords.evaluate(|_| false, |_| None));
+        assert!(lords.evaluate(|id| id == 13700800, |_| None));
+
+        let all = TriggerCondition::FlagSet {
+            flags: vec![1, 2],
+            range: None,
+            mode: FlagSetMode::All,
+        };
+        assert!(!all.evaluate(|id| id == 1, |_| None));
+        assert!(all.evaluate(|_| true, |_| None));
+
+        let seeds: TriggerCondition = serde_json::from_str(
+            r#"{ "type": "flag_set", "range": { "first": 100, "last": 129 }, "mode": { "at_least": 20 } }"#,
+        )
+        .unwrap();
+        assert_eq!(seeds.flag_ids().len(), 30);
+        assert!(!seeds.evaluate(|id| id < 119, |_| None));
+        assert!(seeds.evaluate(|id| id < 120, |_| None));
+    }
+
+    #[test]
+    fn test_flag_set_range_step_and_validation() {
+        let stepped = TriggerCondition::FlagSet {
+            flags: vec![5, 10],
+            range: Some(FlagRange { first: 0, last: 30, step: 10 }),
+            mode: FlagSetMode::All,
+        };
+        assert_eq!(stepped.flag_ids(), vec![0, 5, 10, 20, 30]);
+        assert!(stepped.validate().is_ok());
+
+        let too_many = TriggerCondition::FlagSet {
+            flags: vec![1, 2],
+            range: None,
+            mode: FlagSetMode::AtLeast(3),
+        };
+        assert!(too_many.validate().is_err());
+
+        let reversed = TriggerCondition::FlagSet {
+            flags: Vec::new(),
+            range: Some(FlagRange { first: 10, last: 1, step: 1 }),
+            mode: FlagSetMode::Any,
+        };
+        assert!(reversed.validate().is_err());
+
+        let huge = TriggerCondition::FlagSet {
+            flags: Vec::new(),
+            range: Some(FlagRange { first: 0, last: u32::MAX, step: 1 }),
+            mode: FlagSetMode::Any,
+        };
+        assert!(huge.validate().is_err());
     }
 
     #[test]
@@ -406,9 +1198,30 @@ mod tests {
             game_id: "ds3".to_string(),
             process_attached: true,
             process_id: Some(12345),
-            bosses_defeated: vec!["iudex_gundyr".to_string()],
+            bosses_defeated: vec![DefeatedBoss {
+                split_index: 0,
+                boss_id: "iudex_gundyr".to_string(),
+                elapsed_ms: 65_000,
+                igt_ms: Some(60_000),
+            }],
             triggers_matched: vec![0, 1],
+            bosses_suppressed: Vec::new(),
             boss_kill_counts: HashMap::new(),
+            attributes: HashMap::new(),
+            suspicious_bosses: Vec::new(),
+            in_main_menu: false,
+            read_plan: None,
+            overlay_open: false,
+            read_stats: None,
+            boss_metadata: HashMap::new(),
+            gauntlet: None,
+            pvp_session: None,
+            flag_disagreements: Vec::new(),
+            frame_rate: None,
+            achievements: None,
+            reads_denied: 0,
+            limited_access: None,
+            ac6_mission: None,
         };
         state.boss_kill_counts.insert("iudex_gundyr".to_string(), 1);
 
@@ -419,7 +1232,7 @@ mod tests {
         assert_eq!(parsed.game_id, "ds3");
         assert!(parsed.process_attached);
         assert_eq!(parsed.process_id, Some(12345));
-        assert_eq!(parsed.bosses_defeated, vec!["iudex_gundyr"]);
+        assert_eq!(parsed.bosses_defeated, state.bosses_defeated);
         assert_eq!(parsed.triggers_matched, vec![0, 1]);
         assert_eq!(parsed.boss_kill_counts.get("iudex_gundyr"), Some(&1));
     }
@@ -437,4 +1250,46 @@ mod tests {
         assert!(config.offset_table_config.is_none());
         assert!(config.kill_counter_config.is_none());
     }
+
+    #[test]
+    fn test_split_suppression_window() {
+        let mut trigger: CustomTrigger = serde_json::from_str(
+            r#"{ "id": "gate", "name": "Gate", "condition": { "type": "event_flag", "flag_id": 13000801 } }"#,
+        )
+        .unwrap();
+        assert!(!trigger.suppressed_after_split(2000, None));
+        assert!(trigger.suppressed_after_split(2000, Some(0)));
+        assert!(trigger.suppressed_after_split(2000, Some(1999)));
+        assert!(!trigger.suppressed_after_split(2000, Some(2000)));
+        assert!(!trigger.suppressed_after_split(0, Some(0)));
+
+        // Per-trigger override
+        trigger.suppression_ms = Some(0);
+        assert!(!trigger.suppressed_after_split(2000, Some(100)));
+        trigger.suppression_ms = Some(5000);
+        assert!(trigger.suppressed_after_split(0, Some(4000)));
+
+        // Rule violations are never swallowed
+        trigger.kind = TriggerKind::RuleViolation;
+        assert!(!trigger.suppressed_after_split(5000, Some(0)));
+    }
+
+    #[test]
+    fn test_trigger_groups() {
+        let triggers: Vec<CustomTrigger> = serde_json::from_str(r#"[
+            { "id": "area", "name": "Area entry", "condition": { "type": "attribute", "name": "area", "op": "eq", "value": 3 }, "group": "dancer", "priority": 1 },
+            { "id": "flag", "name": "Dancer flag", "condition": { "type": "event_flag", "flag_id": 13000890 }, "group": "dancer", "priority": 10 },
+            { "id": "other", "name": "Other", "condition": { "type": "event_flag", "flag_id": 13000800 } },
+            { "id": "tie", "name": "Tie", "condition": { "type": "event_flag", "flag_id": 13000801 }, "group": "dancer", "priority": 10 }
+        ]"#).unwrap();
+        assert_eq!(triggers[0].priority, 1);
+        assert!(triggers[2].group.is_none());
+
+        // Boss flag beats area entry; ungrouped triggers are unaffected
+        assert_eq!(resolve_trigger_groups(&triggers, &[0, 1, 2]), (vec![1, 2], vec![0]));
+        // Ties go to the earlier trigger
+        assert_eq!(resolve_trigger_groups(&triggers, &[1, 3]), (vec![1], vec![3]));
+        // Alone in its group on this tick: splits
+        assert_eq!(resolve_trigger_groups(&triggers, &[0]), (vec![0], vec![]));
+    }
 }
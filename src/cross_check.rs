@@ -0,0 +1,110 @@
+//! Redundant flag reads
+//!
+//! A reader bug (a wrong offset after a patch, a misresolved pattern) makes a
+//! boss flag read as set too early or never, and nothing in the run log shows
+//! it. For high-stakes runs the host can supply a second game definition
+//! (e.g. `schemas/ds3.toml` for the generic category-decomposition engine)
+//! that is attached alongside the primary reader. Cross-checked flags are read
+//! through both, and a boss only splits once both readers see its flag set.
+//! Every disagreement is logged when it starts and when it resolves, and the
+//! open ones are listed in the state's `flag_disagreements`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A flag the two readers currently disagree on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct FlagDisagreement {
+    pub flag_id: u32,
+    /// Value read by the game's own reader
+    pub primary: bool,
+    /// Value read by the cross-check reader
+    pub secondary: bool,
+}
+
+/// Per-attach agreement tracking between the primary and secondary reader
+#[derive(Debug, Default)]
+pub struct CrossChecker {
+    open: BTreeMap<u32, FlagDisagreement>,
+}
+
+impl CrossChecker {
+    /// Combine both reads of a flag: set only when both readers agree it is
+    /// set. Logs when a disagreement starts, changes or resolves.
+    pub fn reconcile(&mut self, flag_id: u32, primary: bool, secondary: bool) -> bool {
+        if primary == secondary {
+            if let Some(previous) = self.open.remove(&flag_id) {
+                log::info!(
+                    "Flag {} readers agree again (both {}) after primary={}, secondary={}",
+                    flag_id,
+                    primary,
+                    previous.primary,
+                    previous.secondary
+                );
+            }
+            return primary;
+        }
+
+        let disagreement = FlagDisagreement { flag_id, primary, secondary };
+        if self.open.insert(flag_id, disagreement) != Some(disagreement) {
+            log::warn!(
+                "Flag {} readers disagree: primary={}, secondary={}; holding split until they agree",
+                flag_id,
+                primary,
+                secondary
+            );
+        }
+        false
+    }
+
+    /// Disagreements that are still open, by flag id
+    pub fn disagreements(&self) -> Vec<FlagDisagreement> {
+        self.open.values().copied().collect()
+    }
+}
+
+/// Whether a flag is read through both readers; an empty list covers every
+/// boss flag
+pub fn is_cross_checked(cross_check_flags: &[u32], flag_id: u32) -> bool {
+    cross_check_flags.is_empty() || cross_check_flags.contains(&flag_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_agreement_required() {
+        let mut checker = CrossChecker::default();
+        assert!(!checker.reconcile(13000800, false, false));
+        assert!(checker.disagreements().is_empty());
+
+        // Primary sees the flag first: held back and reported
+        assert!(!checker.reconcile(13000800, true, false));
+        assert_eq!(
+            checker.disagreements(),
+            vec![FlagDisagreement { flag_id: 13000800, primary: true, secondary: false }]
+        );
+
+        // Both set: fires and the disagreement is resolved
+        assert!(checker.reconcile(13000800, true, true));
+        assert!(checker.disagreements().is_empty());
+    }
+
+    #[test]
+    fn test_secondary_only_does_not_fire() {
+        let mut checker = CrossChecker::default();
+        assert!(!checker.reconcile(14000800, false, true));
+        assert!(!checker.reconcile(14000800, false, true));
+        assert_eq!(checker.disagreements().len(), 1);
+        assert!(!checker.disagreements()[0].primary);
+    }
+
+    #[test]
+    fn test_cross_checked_flags() {
+        assert!(is_cross_checked(&[], 13000800));
+        assert!(is_cross_checked(&[13000800], 13000800));
+        assert!(!is_cross_checked(&[13000800], 14000800));
+    }
+}
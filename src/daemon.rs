@@ -0,0 +1,247 @@
+//! Background daemon mode
+//!
+//! Runs the autosplitter as a long-lived service (a systemd unit on Linux, a
+//! service wrapper such as `sc`/NSSM on Windows) so splitting survives host
+//! application restarts on always-on streaming PCs. The daemon serves the
+//! state over a local TCP socket; a host reconnects and picks up where it
+//! left off.
+//!
+//! The protocol is line based: each request line is a command and each
+//! response is one line of JSON.
+//!
+//! | Command   | Response                                   |
+//! |-----------|--------------------------------------------|
+//! | `ping`    | `{"ok":true}`                              |
+//! | `state`   | `AutosplitterState`                        |
+//! | `poll`    | `OutputMessage` array (see `poll_output`)  |
+//! | `run_log` | `RunLogEntry` array                        |
+//! | `reset`   | `{"ok":true}`                              |
+//! | `wake`    | `{"ok":true}`                              |
+
+use crate::Autosplitter;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+/// Default listen address (loopback only)
+pub const DEFAULT_LISTEN: &str = "127.0.0.1:16835";
+
+/// Set by the OS shutdown handler (SIGTERM/SIGINT, console close/shutdown)
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Service lifecycle hooks, called by [`Daemon::serve`]
+pub trait ServiceHooks {
+    /// The socket is listening and the autosplitter is running
+    fn on_ready(&mut self, _listen: SocketAddr) {}
+    /// Shutdown was requested; called before the autosplitter is stopped
+    fn on_stopping(&mut self) {}
+}
+
+/// Hooks that do nothing (plain background process)
+pub struct NoHooks;
+
+impl ServiceHooks for NoHooks {}
+
+/// systemd `Type=notify` support: reports readiness and shutdown through
+/// `$NOTIFY_SOCKET`; does nothing when not started by systemd
+#[cfg(target_os = "linux")]
+pub struct SystemdNotify;
+
+#[cfg(target_os = "linux")]
+impl SystemdNotify {
+    fn notify(state: &str) {
+        let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+            return;
+        };
+        let Ok(socket) = std::os::unix::net::UnixDatagram::unbound() else {
+            return;
+        };
+
+        // A leading '@' denotes an abstract socket address
+        let result = match path.to_str().and_then(|p| p.strip_prefix('@')) {
+            Some(name) => {
+                use std::os::linux::net::SocketAddrExt;
+                std::os::unix::net::SocketAddr::from_abstract_name(name)
+                    .and_then(|addr| socket.send_to_addr(state.as_bytes(), &addr))
+            }
+            None => socket.send_to(state.as_bytes(), &path),
+        };
+        if let Err(e) = result {
+            log::warn!("Failed to notify systemd: {}", e);
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl ServiceHooks for SystemdNotify {
+    fn on_ready(&mut self, listen: SocketAddr) {
+        Self::notify(&format!("READY=1\nSTATUS=Listening on {}", listen));
+    }
+
+    fn on_stopping(&mut self) {
+        Self::notify("STOPPING=1");
+    }
+}
+
+#[cfg(target_os = "linux")]
+extern "C" fn handle_signal(_signal: libc::c_int) {
+    SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Request shutdown on SIGTERM (systemd stop) and SIGINT
+#[cfg(target_os = "linux")]
+pub fn install_shutdown_handler() {
+    let handler = handle_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    unsafe {
+        libc::signal(libc::SIGTERM, handler);
+        libc::signal(libc::SIGINT, handler);
+    }
+}
+
+#[cfg(target_os = "windows")]
+unsafe extern "system" fn handle_console_event(_event: u32) -> windows::Win32::Foundation::BOOL {
+    SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst);
+    // Give `serve` time to stop the autosplitter before the process is ended
+    thread::sleep(Duration::from_millis(500));
+    true.into()
+}
+
+/// Request shutdown on Ctrl+C, console close, logoff and system shutdown
+/// (the events service wrappers deliver when stopping a service)
+#[cfg(target_os = "windows")]
+pub fn install_shutdown_handler() {
+    use windows::Win32::Foundation::TRUE;
+    use windows::Win32::System::Console::SetConsoleCtrlHandler;
+    if let Err(e) = unsafe { SetConsoleCtrlHandler(Some(handle_console_event), TRUE) } {
+        log::warn!("Failed to install console control handler: {}", e);
+    }
+}
+
+/// Autosplitter served over a local socket
+pub struct Daemon {
+    autosplitter: Arc<Autosplitter>,
+    listener: TcpListener,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl Daemon {
+    /// Bind the control socket (e.g., [`DEFAULT_LISTEN`])
+    pub fn bind(autosplitter: Arc<Autosplitter>, listen: &str) -> Result<Self, String> {
+        let listener =
+            TcpListener::bind(listen).map_err(|e| format!("Failed to listen on {}: {}", listen, e))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to configure socket: {}", e))?;
+
+        Ok(Self {
+            autosplitter,
+            listener,
+            shutdown: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, String> {
+        self.listener.local_addr().map_err(|e| e.to_string())
+    }
+
+    /// Flag that stops [`Daemon::serve`] when set
+    pub fn shutdown_handle(&self) -> Arc<AtomicBool> {
+        self.shutdown.clone()
+    }
+
+    /// Accept clients until shutdown is requested (through the handle or the
+    /// OS shutdown handler), then stop the autosplitter
+    pub fn serve(&self, hooks: &mut dyn ServiceHooks) -> Result<(), String> {
+        let listen = self.local_addr()?;
+        log::info!("Daemon listening on {}", listen);
+        hooks.on_ready(listen);
+
+        while !self.shutdown.load(Ordering::SeqCst) && !SHUTDOWN_REQUESTED.load(Ordering::SeqCst) {
+            match self.listener.accept() {
+                Ok((stream, peer)) => {
+                    log::info!("Daemon client connected: {}", peer);
+                    let autosplitter = self.autosplitter.clone();
+                    thread::spawn(move || {
+                        if let Err(e) = serve_client(&autosplitter, stream) {
+                            log::debug!("Daemon client {} disconnected: {}", peer, e);
+                        }
+                    });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    thread::sleep(Duration::from_millis(100));
+                }
+                Err(e) => log::warn!("Daemon accept failed: {}", e),
+            }
+        }
+
+        log::info!("Daemon shutting down");
+        hooks.on_stopping();
+        self.autosplitter.stop();
+        Ok(())
+    }
+}
+
+fn serve_client(autosplitter: &Autosplitter, stream: TcpStream) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let response = handle_command(autosplitter, line?.trim());
+        writer.write_all(response.as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Execute one protocol command and return its JSON response
+pub fn handle_command(autosplitter: &Autosplitter, command: &str) -> String {
+    const OK: &str = r#"{"ok":true}"#;
+    match command {
+        "ping" => OK.to_string(),
+        "state" => serde_json::to_string(&autosplitter.get_state()).unwrap_or_else(|_| "{}".to_string()),
+        "poll" => serde_json::to_string(&autosplitter.poll_output()).unwrap_or_else(|_| "[]".to_string()),
+        "run_log" => autosplitter.get_run_log_json(),
+        "reset" => {
+            autosplitter.reset();
+            OK.to_string()
+        }
+        "wake" => {
+            autosplitter.wake();
+            OK.to_string()
+        }
+        _ => serde_json::json!({ "error": format!("Unknown command: {}", command) }).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_command() {
+        let autosplitter = Autosplitter::new();
+        assert_eq!(handle_command(&autosplitter, "ping"), r#"{"ok":true}"#);
+        assert!(handle_command(&autosplitter, "state").contains("\"running\":false"));
+        assert_eq!(handle_command(&autosplitter, "poll"), "[]");
+        assert!(handle_command(&autosplitter, "split").contains("Unknown command: split"));
+    }
+
+    #[test]
+    fn test_serve_over_socket() {
+        let daemon = Daemon::bind(Arc::new(Autosplitter::new()), "127.0.0.1:0").unwrap();
+        let addr = daemon.local_addr().unwrap();
+        let shutdown = daemon.shutdown_handle();
+        let server = thread::spawn(move || daemon.serve(&mut NoHooks));
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(b"ping\nstate\n").unwrap();
+        let mut lines = BufReader::new(stream).lines();
+        assert_eq!(lines.next().unwrap().unwrap(), r#"{"ok":true}"#);
+        assert!(lines.next().unwrap().unwrap().starts_with('{'));
+
+        shutdown.store(true, Ordering::SeqCst);
+        assert!(server.join().unwrap().is_ok());
+    }
+}
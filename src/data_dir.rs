@@ -0,0 +1,147 @@
+//! External data directory
+//!
+//! Game data is compiled into the library by default: the built-in readers
+//! and the achievement tables. Packagers (Flatpak, portable zips) can ship it
+//! in a data directory instead, set with `AutosplitterConfig::data_dir`, and
+//! update it independently of the binary. Files are named after the engine id
+//! of the game (e.g., `ds3`):
+//!
+//! - `<engine id>.toml`: a game definition (patterns, pointers, bosses,
+//!   presets and triggers, see `game_data`)
+//! - `<engine id>.achievements.json`: an achievement table (array of
+//!   `Achievement`)
+//!
+//! Precedence, per game:
+//! - `Autosplitter::start` uses the definition from the data directory
+//!   instead of the built-in reader; `start_with_game_data`,
+//!   `start_with_data_pack` and the other explicit starts are unaffected
+//! - achievements come from `AutosplitterConfig::achievements`, then the
+//!   data directory, then the built-in table
+//!
+//! A missing file falls back to the built-in data; a file that fails to load
+//! is logged and falls back as well. Achievement tables are read once per
+//! path and process.
+
+use crate::achievements::Achievement;
+use crate::cache::{precompile_game_data, CompiledGameData};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+static ACHIEVEMENTS: Lazy<Mutex<HashMap<PathBuf, Option<Vec<Achievement>>>>> = Lazy::new(Default::default);
+
+/// Path of the game definition for an engine id
+pub fn definition_path(dir: &Path, engine_id: &str) -> PathBuf {
+    dir.join(format!("{}.toml", file_stem(engine_id)))
+}
+
+/// Path of the achievement table for an engine id
+pub fn achievements_path(dir: &Path, engine_id: &str) -> PathBuf {
+    dir.join(format!("{}.achievements.json", file_stem(engine_id)))
+}
+
+/// Compiled game definition for an engine id, if the directory has one
+pub fn load_definition(dir: &Path, engine_id: &str) -> Option<Arc<CompiledGameData>> {
+    let path = definition_path(dir, engine_id);
+    let toml = read(&path)?;
+    match precompile_game_data(&toml) {
+        Ok(compiled) => {
+            log::info!("Using game definition {}", path.display());
+            Some(compiled)
+        }
+        Err(e) => {
+            log::warn!("Ignoring {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+/// Achievement table for an engine id, if the directory has one
+pub fn achievements(dir: &Path, engine_id: &str) -> Option<Vec<Achievement>> {
+    let path = achievements_path(dir, engine_id);
+    let mut cache = ACHIEVEMENTS.lock().unwrap();
+    cache
+        .entry(path)
+        .or_insert_with_key(|path| {
+            let json = read(path)?;
+            serde_json::from_str(&json)
+                .map_err(|e| log::warn!("Ignoring {}: {}", path.display(), e))
+                .ok()
+        })
+        .clone()
+}
+
+fn read(path: &Path) -> Option<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            log::warn!("Failed to read {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+fn file_stem(engine_id: &str) -> String {
+    engine_id
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFINITION: &str = r#"
+[game]
+id = "ds3"
+name = "Dark Souls III"
+process_names = ["DarkSoulsIII.exe"]
+
+[autosplitter]
+engine = "ds3"
+
+[[autosplitter.patterns]]
+name = "event_flags"
+pattern = "48 8b 35 ? ? ? ?"
+
+[[bosses]]
+id = "vordt"
+name = "Vordt of the Boreal Valley"
+flag_id = 13000800
+"#;
+
+    #[test]
+    fn test_load_from_directory() {
+        let dir = std::env::temp_dir().join(format!("nyacore-data-dir-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Nothing in the directory: built-in data
+        assert!(load_definition(&dir, "ds3").is_none());
+        assert!(achievements(&dir, "elden_ring").is_none());
+
+        std::fs::write(definition_path(&dir, "ds3"), DEFINITION).unwrap();
+        std::fs::write(
+            achievements_path(&dir, "ds3"),
+            r#"[{"id": "vordt", "name": "Vordt", "flags": [13000800]}]"#,
+        )
+        .unwrap();
+        std::fs::write(definition_path(&dir, "sekiro"), "not toml").unwrap();
+
+        let compiled = load_definition(&dir, "ds3").unwrap();
+        assert_eq!(compiled.game_data.bosses[0].id, "vordt");
+        assert_eq!(achievements(&dir, "ds3").unwrap()[0].flags, vec![13000800]);
+        assert!(load_definition(&dir, "sekiro").is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_file_names() {
+        let dir = Path::new("data");
+        assert_eq!(definition_path(dir, "elden_ring"), dir.join("elden_ring.toml"));
+        assert_eq!(achievements_path(dir, "../ds3"), dir.join("___ds3.achievements.json"));
+    }
+}
@@ -0,0 +1,377 @@
+//! Signed game-definition data packs
+//!
+//! A game patch that moves a pointer breaks pattern scanning until a new
+//! library is shipped. Data packs decouple the two: a pack is a GameData TOML
+//! (patterns, flag layout, bosses) published next to a signed manifest, and
+//! the autosplitter downloads and installs newer packs at runtime. Installed
+//! packs are used by `Autosplitter::start_with_data_pack`; a pack installed
+//! while a session runs applies from the next start.
+//!
+//! The manifest lists every pack with its version and SHA-256 and is signed
+//! with Ed25519 (`<manifest_url>.sig`, hex). Nothing is installed unless the
+//! signature verifies against the host's public key, every hash matches and
+//! every pack compiles. Manifests carry an increasing `sequence`; an older
+//! manifest than the one installed is rejected, so a replayed manifest cannot
+//! roll packs back.
+//!
+//! When `cache_dir` is set, verified manifests and packs are kept on disk and
+//! `load_cached` restores them on the next launch without network access.
+//! Only built with the `updates` feature.
+
+use crate::cache::{precompile_game_data, CompiledGameData};
+use crate::hex::{decode_hex, encode_hex};
+use ed25519_zebra::{Signature, VerificationKey};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Version of the manifest layout this library understands
+pub const MANIFEST_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const SIGNATURE_FILE: &str = "manifest.json.sig";
+
+/// Where to look for updates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct UpdateConfig {
+    /// URL of the signed manifest; pack URLs are relative to it
+    pub manifest_url: String,
+    /// Ed25519 public key of the publisher (hex, 32 bytes)
+    pub public_key: String,
+    /// Directory where verified packs are kept between launches
+    #[serde(default)]
+    pub cache_dir: Option<String>,
+}
+
+/// A pack listed in the manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct PackEntry {
+    /// Engine id of the game (e.g., "ds3", "elden_ring")
+    pub game: String,
+    pub version: String,
+    /// GameData TOML, absolute or relative to the manifest URL
+    pub url: String,
+    /// SHA-256 of the TOML (hex)
+    pub sha256: String,
+}
+
+/// Signed list of available packs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct PackManifest {
+    pub format_version: u32,
+    /// Increases with every published manifest
+    pub sequence: u64,
+    pub packs: Vec<PackEntry>,
+}
+
+/// An installed pack (without the compiled data)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct InstalledPack {
+    pub game: String,
+    pub version: String,
+    /// Sequence of the manifest it was installed from
+    pub sequence: u64,
+}
+
+#[derive(Default)]
+struct Registry {
+    sequence: u64,
+    packs: HashMap<String, (InstalledPack, Arc<CompiledGameData>)>,
+}
+
+static REGISTRY: Lazy<Mutex<Registry>> = Lazy::new(|| Mutex::new(Registry::default()));
+
+/// Compiled game data of the installed pack for a game
+pub fn installed(game: &str) -> Option<Arc<CompiledGameData>> {
+    REGISTRY.lock().unwrap().packs.get(game).map(|(_, compiled)| compiled.clone())
+}
+
+/// All installed packs, sorted by game
+pub fn list() -> Vec<InstalledPack> {
+    let mut packs: Vec<InstalledPack> = REGISTRY.lock().unwrap().packs.values().map(|(p, _)| p.clone()).collect();
+    packs.sort_by(|a, b| a.game.cmp(&b.game));
+    packs
+}
+
+/// Verify a manifest's signature and parse it
+pub fn verify_manifest(manifest: &[u8], signature_hex: &str, public_key_hex: &str) -> Result<PackManifest, String> {
+    let key: [u8; 32] = decode_hex(public_key_hex)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Public key must be 32 bytes of hex")?;
+    let signature: [u8; 64] = decode_hex(signature_hex.trim())
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Manifest signature must be 64 bytes of hex")?;
+
+    let key = VerificationKey::try_from(key).map_err(|e| format!("Invalid public key: {}", e))?;
+    key.verify(&Signature::from(signature), manifest)
+        .map_err(|_| "Manifest signature does not verify".to_string())?;
+
+    let manifest: PackManifest =
+        serde_json::from_slice(manifest).map_err(|e| format!("Invalid manifest: {}", e))?;
+    if manifest.format_version > MANIFEST_FORMAT_VERSION {
+        return Err(format!(
+            "Manifest format version {} is not supported; update the autosplitter",
+            manifest.format_version
+        ));
+    }
+    Ok(manifest)
+}
+
+/// Verify a manifest, fetch the packs that are newer than the installed ones
+/// and install them; returns the packs that were installed
+///
+/// All packs are fetched, hash-checked and compiled before any is installed.
+pub fn install(
+    manifest_bytes: &[u8],
+    signature_hex: &str,
+    public_key_hex: &str,
+    mut fetch: impl FnMut(&str) -> Result<Vec<u8>, String>,
+) -> Result<Vec<(InstalledPack, String)>, String> {
+    let manifest = verify_manifest(manifest_bytes, signature_hex, public_key_hex)?;
+    let (current_sequence, current_versions) = {
+        let registry = REGISTRY.lock().unwrap();
+        let versions: HashMap<String, String> =
+            registry.packs.iter().map(|(game, (p, _))| (game.clone(), p.version.clone())).collect();
+        (registry.sequence, versions)
+    };
+    if manifest.sequence < current_sequence {
+        return Err(format!(
+            "Manifest sequence {} is older than the installed {}",
+            manifest.sequence, current_sequence
+        ));
+    }
+
+    let mut staged = Vec::new();
+    for entry in &manifest.packs {
+        if current_versions.get(&entry.game) == Some(&entry.version) {
+            continue;
+        }
+
+        let data = fetch(&entry.url)?;
+        if encode_hex(&Sha256::digest(&data)) != entry.sha256.to_lowercase() {
+            return Err(format!("Pack '{}' {} does not match its hash", entry.game, entry.version));
+        }
+        let toml = String::from_utf8(data).map_err(|_| format!("Pack '{}' is not UTF-8", entry.game))?;
+        let compiled =
+            precompile_game_data(&toml).map_err(|e| format!("Pack '{}' {}: {}", entry.game, entry.version, e))?;
+        let pack = InstalledPack {
+            game: entry.game.clone(),
+            version: entry.version.clone(),
+            sequence: manifest.sequence,
+        };
+        staged.push((pack, compiled, toml));
+    }
+
+    let mut registry = REGISTRY.lock().unwrap();
+    registry.sequence = manifest.sequence;
+    let mut installed = Vec::new();
+    for (pack, compiled, toml) in staged {
+        log::info!("Installed data pack {} {}", pack.game, pack.version);
+        registry.packs.insert(pack.game.clone(), (pack.clone(), compiled));
+        installed.push((pack, toml));
+    }
+    Ok(installed)
+}
+
+/// Check the configured URL for a newer manifest and install its packs;
+/// returns the packs that were installed
+pub fn check_for_updates(config: &UpdateConfig) -> Result<Vec<InstalledPack>, String> {
+    let manifest = download(&config.manifest_url)?;
+    let signature = download(&format!("{}.sig", config.manifest_url))?;
+    let signature = String::from_utf8_lossy(&signature).into_owned();
+
+    let installed = install(&manifest, &signature, &config.public_key, |url| {
+        download(&resolve_url(&config.manifest_url, url))
+    })?;
+
+    if let Some(dir) = &config.cache_dir {
+        save_cache(Path::new(dir), &manifest, &signature, &installed)?;
+    }
+    Ok(installed.into_iter().map(|(pack, _)| pack).collect())
+}
+
+/// Install the packs kept in `dir` by an earlier `check_for_updates`
+pub fn load_cached(dir: &Path, public_key_hex: &str) -> Result<Vec<InstalledPack>, String> {
+    let read = |name: &str| std::fs::read(dir.join(name)).map_err(|e| format!("Failed to read {}: {}", name, e));
+    let manifest = read(MANIFEST_FILE)?;
+    let signature = String::from_utf8_lossy(&read(SIGNATURE_FILE)?).into_owned();
+
+    let packs = verify_manifest(&manifest, &signature, public_key_hex)?.packs;
+    let installed = install(&manifest, &signature, public_key_hex, |url| {
+        let entry = packs.iter().find(|p| p.url == url).ok_or("Pack missing from manifest")?;
+        read(&cache_file_name(&entry.game))
+    })?;
+    Ok(installed.into_iter().map(|(pack, _)| pack).collect())
+}
+
+fn save_cache(dir: &Path, manifest: &[u8], signature: &str, installed: &[(InstalledPack, String)]) -> Result<(), String> {
+    let write = |name: &str, data: &[u8]| {
+        std::fs::write(dir.join(name), data).map_err(|e| format!("Failed to write {}: {}", name, e))
+    };
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    for (pack, toml) in installed {
+        write(&cache_file_name(&pack.game), toml.as_bytes())?;
+    }
+    write(SIGNATURE_FILE, signature.as_bytes())?;
+    write(MANIFEST_FILE, manifest)
+}
+
+fn cache_file_name(game: &str) -> String {
+    let safe: String = game
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+    format!("{}.toml", safe)
+}
+
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    let response = ureq::get(url).call().map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    let mut data = Vec::new();
+    std::io::Read::read_to_end(&mut response.into_reader(), &mut data)
+        .map_err(|e| format!("Failed to download {}: {}", url, e))?;
+    Ok(data)
+}
+
+/// Resolve a pack URL against the manifest URL
+fn resolve_url(manifest_url: &str, url: &str) -> String {
+    if url.contains("://") {
+        return url.to_string();
+    }
+    match manifest_url.rsplit_once('/') {
+        Some((base, _)) => format!("{}/{}", base, url),
+        None => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_zebra::SigningKey;
+
+    const PACK: &str = r#"
+[game]
+id = "ds3_pack_test"
+name = "Dark Souls III"
+process_names = ["DarkSoulsIII.exe"]
+
+[autosplitter]
+engine = "ds3"
+
+[[autosplitter.patterns]]
+name = "sprj_event_flag_man"
+pattern = "48 c7 05 ? ? ? ? 00 00 00 00"
+rip_offset = 3
+"#;
+
+    fn signed(manifest: &PackManifest) -> (Vec<u8>, String, String) {
+        let key = SigningKey::from([7u8; 32]);
+        let bytes = serde_json::to_vec(manifest).unwrap();
+        let signature = encode_hex(&<[u8; 64]>::from(key.sign(&bytes)));
+        let public_key = encode_hex(VerificationKey::from(&key).as_ref());
+        (bytes, signature, public_key)
+    }
+
+    fn manifest(sequence: u64, version: &str, data: &str) -> PackManifest {
+        PackManifest {
+            format_version: MANIFEST_FORMAT_VERSION,
+            sequence,
+            packs: vec![PackEntry {
+                game: "ds3_pack_test".to_string(),
+                version: version.to_string(),
+                url: "ds3.toml".to_string(),
+                sha256: encode_hex(&Sha256::digest(data.as_bytes())),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_signature_required() {
+        let (bytes, signature, public_key) = signed(&manifest(1, "1", PACK));
+        assert!(verify_manifest(&bytes, &signature, &public_key).is_ok());
+
+        let mut tampered = bytes.clone();
+        tampered[10] ^= 1;
+        assert!(verify_manifest(&tampered, &signature, &public_key).is_err());
+        assert!(verify_manifest(&bytes, &signature, &"00".repeat(32)).is_err());
+        assert!(verify_manifest(&bytes, "zz", &public_key).is_err());
+    }
+
+    #[test]
+    fn test_install_and_rollback() {
+        // Hash mismatch installs nothing
+        let (bytes, signature, public_key) = signed(&manifest(10, "1", PACK));
+        let tampered = PACK.replace("00 00 00 00", "00 00 00 01 ");
+        assert!(install(&bytes, &signature, &public_key, |_| Ok(tampered.clone().into_bytes())).is_err());
+        assert!(installed("ds3_pack_test").is_none());
+
+        let installed_packs = install(&bytes, &signature, &public_key, |url| {
+            assert_eq!(url, "ds3.toml");
+            Ok(PACK.as_bytes().to_vec())
+        })
+        .unwrap();
+        assert_eq!(installed_packs.len(), 1);
+        assert_eq!(installed("ds3_pack_test").unwrap().game_data.game.id, "ds3_pack_test");
+        assert!(list().iter().any(|p| p.game == "ds3_pack_test" && p.version == "1"));
+
+        // Same version again is not fetched
+        let again = install(&bytes, &signature, &public_key, |_| Err("fetched".to_string())).unwrap();
+        assert!(again.is_empty());
+
+        // Older manifest is rejected
+        let (old, old_signature, _) = signed(&manifest(9, "0", PACK));
+        assert!(install(&old, &old_signature, &public_key, |_| Ok(PACK.as_bytes().to_vec()))
+            .unwrap_err()
+            .contains("older"));
+    }
+
+    #[test]
+    fn test_pack_for_built_in_game_runs_generic_engine() {
+        let ds3 = include_str!("../schemas/ds3.toml");
+        let manifest = PackManifest {
+            format_version: MANIFEST_FORMAT_VERSION,
+            sequence: 10,
+            packs: vec![PackEntry {
+                game: "ds3".to_string(),
+                version: "1".to_string(),
+                url: "ds3.toml".to_string(),
+                sha256: encode_hex(&Sha256::digest(ds3.as_bytes())),
+            }],
+        };
+        let (bytes, signature, public_key) = signed(&manifest);
+        install(&bytes, &signature, &public_key, |_| Ok(ds3.as_bytes().to_vec())).unwrap();
+
+        let autosplitter = crate::Autosplitter::new();
+        let boss_flags = vec![crate::BossFlag {
+            boss_id: "iudex_gundyr".to_string(),
+            boss_name: "Iudex Gundyr".to_string(),
+            flag_id: 13000800,
+            is_dlc: false,
+            metadata: Default::default(),
+            group: None,
+            priority: 0,
+        }];
+        autosplitter.start_with_data_pack("ds3", boss_flags).unwrap();
+        let state = autosplitter.get_state();
+        autosplitter.stop();
+
+        // The pack lists DarkSoulsIII.exe, but runs on the generic engine
+        // (the built-in reader reports "DarkSouls3")
+        assert_eq!(state.game_id, "ds3");
+    }
+
+    #[test]
+    fn test_resolve_url() {
+        assert_eq!(
+            resolve_url("https://example.com/packs/manifest.json", "ds3.toml"),
+            "https://example.com/packs/ds3.toml"
+        );
+        assert_eq!(resolve_url("https://example.com/m.json", "https://cdn/x.toml"), "https://cdn/x.toml");
+    }
+}
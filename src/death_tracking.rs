@@ -0,0 +1,175 @@
+//! Death and respawn tracking
+//!
+//! Watches the death counter to record where the player died and where they
+//! came back. Elden Ring respawns at a Stake of Marika next to the death
+//! location or at the last Site of Grace; there is no pointer telling them
+//! apart, so a respawn on the same map within [`STAKE_RESPAWN_RADIUS`] of the
+//! death is classified as a stake respawn. Deaths right next to a grace can
+//! therefore be reported as stake respawns.
+
+use crate::position_trail::WorldPosition;
+use crate::run_log::RunEvent;
+use serde::{Deserialize, Serialize};
+
+/// Max distance between death and respawn positions for a stake respawn
+pub const STAKE_RESPAWN_RADIUS: f32 = 150.0;
+
+/// Where the player came back after dying
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum RespawnPoint {
+    /// Stake of Marika near the death location
+    Stake,
+    /// Last rested Site of Grace (or bonfire)
+    Grace,
+}
+
+impl RespawnPoint {
+    /// Classify a respawn by its distance to the death location
+    pub fn classify(death: Option<&WorldPosition>, respawn: &WorldPosition) -> Self {
+        match death {
+            Some(death) if death.map_id == respawn.map_id && distance(death, respawn) <= STAKE_RESPAWN_RADIUS => {
+                RespawnPoint::Stake
+            }
+            _ => RespawnPoint::Grace,
+        }
+    }
+}
+
+fn distance(a: &WorldPosition, b: &WorldPosition) -> f32 {
+    ((a.x - b.x).powi(2) + (a.y - b.y).powi(2) + (a.z - b.z).powi(2)).sqrt()
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+enum Phase {
+    #[default]
+    Alive,
+    /// Died; waiting for the reload that follows "YOU DIED"
+    Dying,
+    /// Reloading; the next loaded position is the respawn point
+    Reloading,
+}
+
+/// Per-attach death/respawn bookkeeping
+#[derive(Debug, Default)]
+pub struct DeathTracker {
+    last_count: Option<i64>,
+    last_alive: Option<WorldPosition>,
+    death_position: Option<WorldPosition>,
+    phase: Phase,
+}
+
+impl DeathTracker {
+    /// Feed the current death count and player position (None or the origin
+    /// while not loaded); returns a death or respawn event when one happens
+    pub fn observe(&mut self, death_count: i64, position: Option<WorldPosition>) -> Option<RunEvent> {
+        let position = position.filter(|p| !p.is_origin());
+
+        let previous = self.last_count.replace(death_count);
+        match previous {
+            Some(previous) if death_count > previous => {
+                // The player is still at the death location until the reload
+                self.death_position = position.or(self.last_alive);
+                self.phase = Phase::Dying;
+                return Some(RunEvent::PlayerDied {
+                    death_count,
+                    position: self.death_position,
+                });
+            }
+            // First sample, or a different save was loaded
+            None => return None,
+            Some(previous) if death_count < previous => {
+                self.phase = Phase::Alive;
+                self.last_alive = position;
+                return None;
+            }
+            _ => {}
+        }
+
+        match (self.phase, position) {
+            (Phase::Alive, position) => {
+                if position.is_some() {
+                    self.last_alive = position;
+                }
+                None
+            }
+            (Phase::Dying, None) => {
+                self.phase = Phase::Reloading;
+                None
+            }
+            (Phase::Dying, Some(_)) | (Phase::Reloading, None) => None,
+            (Phase::Reloading, Some(respawn)) => {
+                self.phase = Phase::Alive;
+                self.last_alive = Some(respawn);
+                Some(RunEvent::PlayerRespawned {
+                    position: respawn,
+                    respawn: RespawnPoint::classify(self.death_position.as_ref(), &respawn),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(x: f32, map_id: u32) -> Option<WorldPosition> {
+        Some(WorldPosition {
+            x,
+            y: 0.0,
+            z: 10.0,
+            map_id: Some(map_id),
+        })
+    }
+
+    fn respawn_after_death(respawn: Option<WorldPosition>) -> Option<RunEvent> {
+        let mut tracker = DeathTracker::default();
+        assert_eq!(tracker.observe(3, pos(0.0, 0x3C2C2400)), None);
+        assert_eq!(tracker.observe(3, pos(500.0, 0x3C2C2400)), None);
+
+        assert_eq!(
+            tracker.observe(4, pos(510.0, 0x3C2C2400)),
+            Some(RunEvent::PlayerDied {
+                death_count: 4,
+                position: pos(510.0, 0x3C2C2400),
+            })
+        );
+        assert_eq!(tracker.observe(4, pos(510.0, 0x3C2C2400)), None);
+        assert_eq!(tracker.observe(4, None), None);
+        tracker.observe(4, respawn)
+    }
+
+    #[test]
+    fn test_stake_respawn() {
+        let event = respawn_after_death(pos(450.0, 0x3C2C2400));
+        assert!(matches!(
+            event,
+            Some(RunEvent::PlayerRespawned {
+                respawn: RespawnPoint::Stake,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_grace_respawn() {
+        let far = respawn_after_death(pos(-2000.0, 0x3C2C2400));
+        assert!(matches!(far, Some(RunEvent::PlayerRespawned { respawn: RespawnPoint::Grace, .. })));
+
+        let other_map = respawn_after_death(pos(510.0, 0x0A000000));
+        assert!(matches!(
+            other_map,
+            Some(RunEvent::PlayerRespawned { respawn: RespawnPoint::Grace, .. })
+        ));
+    }
+
+    #[test]
+    fn test_save_switch_is_not_a_death() {
+        let mut tracker = DeathTracker::default();
+        tracker.observe(10, pos(0.0, 1));
+        assert_eq!(tracker.observe(2, pos(5.0, 1)), None);
+        assert!(matches!(tracker.observe(3, pos(5.0, 1)), Some(RunEvent::PlayerDied { .. })));
+    }
+}
@@ -0,0 +1,306 @@
+//! Demo mode
+//!
+//! `Autosplitter::start_demo` runs the regular run loop against a built-in
+//! synthetic game instead of a FromSoftware process, so new users and host
+//! developers can exercise the API, FFI, run events and integrations on any
+//! machine. The game plays a scripted timeline: steps set and clear event
+//! flags, kill the player and raise the soul level, while IGT advances and the
+//! player walks along the x axis. Boss splits, flag triggers, deaths and
+//! respawns, level-ups, stat snapshots, the position trail and the exports on
+//! reset all run through the same code as with a real game.
+//!
+//! Without a script, [`DemoScript::for_bosses`] kills the watched bosses one
+//! after another and restarts the attempt after the last one.
+
+use crate::config::BossFlag;
+use crate::position_trail::WorldPosition;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Name the synthetic game attaches as
+pub const DEMO_GAME_NAME: &str = "Demo";
+
+/// Attributes polled into the state
+pub const ATTRIBUTE_NAMES: &[&str] = &["igt", "soul_level", "death_count"];
+
+/// Attributes recorded in run log stat snapshots
+pub const STAT_NAMES: &[&str] = &["soul_level"];
+
+/// How long the player stays unloaded after dying
+pub const RESPAWN_DELAY_MS: u64 = 3000;
+
+/// Walking speed along the x axis, in units per second
+const WALK_SPEED: f32 = 5.0;
+
+/// Where the player spawns (not the origin, which reads as "not loaded")
+const SPAWN_X: f32 = 10.0;
+
+/// Something that happens in the synthetic game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum DemoAction {
+    /// Set an event flag; setting a boss flag again counts another kill
+    SetFlag { flag_id: u32 },
+    ClearFlag { flag_id: u32 },
+    /// The player dies and respawns at the last checkpoint
+    Die,
+    LevelUp { levels: i64 },
+}
+
+/// A scripted action, `at_ms` after the start of the attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct DemoStep {
+    pub at_ms: u64,
+    #[serde(flatten)]
+    pub action: DemoAction,
+}
+
+/// Timeline of the synthetic game
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct DemoScript {
+    pub steps: Vec<DemoStep>,
+    /// Start a new attempt this long after the last step (None plays once)
+    #[serde(default)]
+    pub restart_after_ms: Option<u64>,
+}
+
+impl DemoScript {
+    /// A boss kill every 20 seconds, a death before every other boss and a
+    /// level-up after each kill; restarts 15 seconds after the last boss
+    pub fn for_bosses(boss_flags: &[BossFlag]) -> Self {
+        let mut steps = Vec::new();
+        for (index, boss) in boss_flags.iter().enumerate() {
+            let at_ms = (index as u64 + 1) * 20_000;
+            if index % 2 == 1 {
+                steps.push(DemoStep { at_ms: at_ms - 12_000, action: DemoAction::Die });
+            }
+            steps.push(DemoStep {
+                at_ms,
+                action: DemoAction::SetFlag { flag_id: boss.flag_id },
+            });
+            steps.push(DemoStep {
+                at_ms: at_ms + 2_000,
+                action: DemoAction::LevelUp { levels: 3 },
+            });
+        }
+        Self {
+            steps,
+            restart_after_ms: Some(15_000),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.steps.is_empty() {
+            return Err("Demo script has no steps".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Bosses watched when the demo is started without any
+pub fn default_bosses() -> Vec<BossFlag> {
+    [
+        ("iudex_gundyr", "Iudex Gundyr", 13000800),
+        ("vordt", "Vordt of the Boreal Valley", 13100800),
+        ("curse_rotted_greatwood", "Curse-Rotted Greatwood", 13010800),
+        ("crystal_sage", "Crystal Sage", 13200800),
+        ("deacons", "Deacons of the Deep", 13300800),
+        ("abyss_watchers", "Abyss Watchers", 13800800),
+    ]
+    .into_iter()
+    .map(|(boss_id, boss_name, flag_id)| BossFlag {
+        boss_id: boss_id.to_string(),
+        boss_name: boss_name.to_string(),
+        flag_id,
+        is_dlc: false,
+        metadata: Default::default(),
+        group: None,
+        priority: 0,
+    })
+    .collect()
+}
+
+/// The synthetic game, played forward by `advance`
+#[derive(Debug)]
+pub struct DemoGame {
+    script: DemoScript,
+    /// Loop time the current attempt started at
+    attempt_start_ms: u64,
+    now_ms: u64,
+    next_step: usize,
+    kill_counts: HashMap<u32, u32>,
+    death_count: i64,
+    soul_level: i64,
+    /// Where the player (re)spawns and since when they walk from there
+    checkpoint_x: f32,
+    walking_since_ms: u64,
+}
+
+impl DemoGame {
+    pub fn new(mut script: DemoScript) -> Self {
+        script.steps.sort_by_key(|step| step.at_ms);
+        let mut game = Self {
+            script,
+            attempt_start_ms: 0,
+            now_ms: 0,
+            next_step: 0,
+            kill_counts: HashMap::new(),
+            death_count: 0,
+            soul_level: 1,
+            checkpoint_x: SPAWN_X,
+            walking_since_ms: 0,
+        };
+        game.restart(0);
+        game
+    }
+
+    /// Start a new attempt at loop time `now_ms`: a fresh save
+    pub fn restart(&mut self, now_ms: u64) {
+        self.attempt_start_ms = now_ms;
+        self.now_ms = now_ms;
+        self.next_step = 0;
+        self.kill_counts.clear();
+        self.death_count = 0;
+        self.soul_level = 1;
+        self.checkpoint_x = SPAWN_X;
+        self.walking_since_ms = now_ms;
+    }
+
+    /// Play the script up to loop time `now_ms`; true when the script
+    /// restarted, i.e. a new attempt began
+    pub fn advance(&mut self, now_ms: u64) -> bool {
+        self.now_ms = now_ms;
+
+        if let (Some(last), Some(restart_after)) = (self.script.steps.last(), self.script.restart_after_ms) {
+            if self.next_step == self.script.steps.len() && self.igt_milliseconds() as u64 >= last.at_ms + restart_after {
+                self.restart(now_ms);
+                return true;
+            }
+        }
+
+        while let Some(step) = self.script.steps.get(self.next_step) {
+            let at_ms = self.attempt_start_ms + step.at_ms;
+            if at_ms > now_ms {
+                break;
+            }
+            self.apply(step.action, at_ms);
+            self.next_step += 1;
+        }
+        false
+    }
+
+    fn apply(&mut self, action: DemoAction, at_ms: u64) {
+        match action {
+            DemoAction::SetFlag { flag_id } => {
+                *self.kill_counts.entry(flag_id).or_default() += 1;
+                // Bosses are checkpoints: deaths after this respawn here
+                self.checkpoint_x = self.x_at(at_ms);
+                self.walking_since_ms = at_ms;
+            }
+            DemoAction::ClearFlag { flag_id } => {
+                self.kill_counts.remove(&flag_id);
+            }
+            DemoAction::Die => {
+                self.death_count += 1;
+                self.walking_since_ms = at_ms + RESPAWN_DELAY_MS;
+            }
+            DemoAction::LevelUp { levels } => self.soul_level += levels,
+        }
+    }
+
+    fn x_at(&self, now_ms: u64) -> f32 {
+        let walked_ms = now_ms.saturating_sub(self.walking_since_ms);
+        self.checkpoint_x + walked_ms as f32 / 1000.0 * WALK_SPEED
+    }
+
+    pub fn read_event_flag(&self, flag_id: u32) -> bool {
+        self.kill_counts.contains_key(&flag_id)
+    }
+
+    pub fn get_kill_count(&self, flag_id: u32) -> u32 {
+        self.kill_counts.get(&flag_id).copied().unwrap_or(0)
+    }
+
+    pub fn igt_milliseconds(&self) -> i64 {
+        (self.now_ms - self.attempt_start_ms) as i64
+    }
+
+    pub fn death_count(&self) -> i64 {
+        self.death_count
+    }
+
+    /// False while respawning after a death
+    pub fn is_player_loaded(&self) -> bool {
+        self.now_ms >= self.walking_since_ms
+    }
+
+    /// Player position; the origin while not loaded
+    pub fn position(&self) -> WorldPosition {
+        let x = if self.is_player_loaded() { self.x_at(self.now_ms) } else { 0.0 };
+        WorldPosition { x, y: 0.0, z: 0.0, map_id: None }
+    }
+
+    pub fn read_attribute_by_name(&self, name: &str) -> Option<i64> {
+        match name {
+            "igt" => Some(self.igt_milliseconds()),
+            "soul_level" => Some(self.soul_level),
+            "death_count" => Some(self.death_count),
+            "loading" => Some(!self.is_player_loaded() as i64),
+            "player_loaded" => Some(self.is_player_loaded() as i64),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_script() {
+        let bosses = default_bosses();
+        let mut game = DemoGame::new(DemoScript::for_bosses(&bosses));
+
+        assert!(!game.advance(19_999));
+        assert!(!game.read_event_flag(bosses[0].flag_id));
+        assert!(!game.advance(20_000));
+        assert_eq!(game.get_kill_count(bosses[0].flag_id), 1);
+
+        // Death before the second boss: unloaded until the respawn
+        game.advance(28_000);
+        assert_eq!(game.death_count(), 1);
+        assert!(!game.is_player_loaded());
+        assert!(game.position().is_origin());
+        game.advance(31_000);
+        assert!(game.is_player_loaded());
+        assert_eq!(game.position().x, SPAWN_X + 100.0);
+
+        // Restart 15 s after the last step
+        let last = bosses.len() as u64 * 20_000 + 2_000;
+        assert!(!game.advance(last));
+        assert!(game.read_event_flag(bosses[5].flag_id));
+        assert!(game.advance(last + 15_000));
+        assert!(!game.read_event_flag(bosses[0].flag_id));
+        assert_eq!(game.igt_milliseconds(), 0);
+        assert_eq!(game.death_count(), 0);
+    }
+
+    #[test]
+    fn test_script_json() {
+        let script: DemoScript = serde_json::from_str(
+            r#"{"steps": [{"at_ms": 5000, "action": "set_flag", "flag_id": 13000800}, {"at_ms": 1000, "action": "die"}]}"#,
+        )
+        .unwrap();
+        assert!(script.validate().is_ok());
+        assert_eq!(script.restart_after_ms, None);
+
+        let mut game = DemoGame::new(script);
+        game.advance(5_000);
+        assert!(game.read_event_flag(13000800));
+        assert_eq!(game.death_count(), 1);
+        assert!(!game.advance(60_000));
+    }
+}
@@ -0,0 +1,269 @@
+//! Game definition reference
+//!
+//! Generates Markdown documentation of the game definition TOML schema
+//! (`GameData` and everything it references, including custom triggers) from
+//! the serde structs via schemars, so the reference cannot drift from the
+//! code and hosts can render it as in-app help. The per-engine requirements
+//! (flag algorithm, pointers the engine needs) come from `EngineType`.
+//! Only built with the `typescript` feature, which brings in schemars.
+//!
+//! Available as `game_data::docgen`; the reference is written to
+//! `schemas/REFERENCE.md` by `cargo test --features typescript`.
+
+use crate::capabilities::ALGORITHMS;
+use crate::engine::EngineType;
+use crate::game_data::{GameData, VALUE_TYPES};
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+
+/// Path of the generated reference, relative to the crate root
+pub const OUTPUT_PATH: &str = "schemas/REFERENCE.md";
+
+/// Name of the root definition, listed first
+const ROOT: &str = "GameData";
+
+/// Generate the Markdown reference of the game definition schema
+pub fn generate() -> String {
+    let settings = SchemaSettings::draft07().with(|s| {
+        s.option_nullable = false;
+        s.option_add_null_type = true;
+    });
+    let mut gen = SchemaGenerator::new(settings);
+    gen.subschema_for::<GameData>();
+
+    let mut definitions: Vec<_> = gen.definitions().iter().collect();
+    definitions.sort_by_key(|(name, _)| (name.as_str() != ROOT, name.as_str()));
+
+    let mut out = String::new();
+    out.push_str("# Game Definition Reference\n\n");
+    out.push_str("<!-- Generated by nyacore-autosplitter. Do not edit by hand. -->\n");
+    out.push_str("<!-- Regenerate with `cargo test --features typescript`. -->\n\n");
+    out.push_str(
+        "Game definitions are TOML files deserialized into `GameData` (see `[game]`, \
+         `[autosplitter]`, `[[bosses]]`, `[[presets]]`, `[custom_fields]`, `[[attributes]]` \
+         and `[[triggers]]`). Fields without a default are required.\n",
+    );
+
+    out.push_str("\n## Engines\n\n");
+    out.push_str("`[autosplitter] engine` selects the flag algorithm and the pointers the engine needs.\n\n");
+    out.push_str("| Engine | Algorithm | Required pointers | Boss `flag_id` |\n");
+    out.push_str("|---|---|---|---|\n");
+    for engine in EngineType::ALL {
+        let pointers: Vec<String> = engine.required_pointers().iter().map(|p| format!("`{}`", p)).collect();
+        let flag_id = if engine.uses_kill_counters() { "kill counter offset" } else { "event flag id" };
+        out.push_str(&format!(
+            "| `{}` | `{}` | {} | {} |\n",
+            engine.id(),
+            engine.algorithm(),
+            pointers.join(", "),
+            flag_id
+        ));
+    }
+    out.push_str("\nEngines registered by the host (`register_engine`) check their own requirements.\n\n");
+    for (id, description) in ALGORITHMS {
+        out.push_str(&format!("- `{}`: {}\n", id, description));
+    }
+    let value_types: Vec<String> = VALUE_TYPES.iter().map(|t| format!("`{}`", t)).collect();
+    out.push_str(&format!("\nValue types of `game_time` and `loading`: {}.\n", value_types.join(", ")));
+
+    for (name, schema) in definitions {
+        out.push_str(&format!("\n## {}\n\n", name));
+        if let Some(description) = description(schema) {
+            out.push_str(&format!("{}\n\n", description));
+        }
+        if let Schema::Object(obj) = schema {
+            definition_body(obj, &mut out);
+        }
+    }
+
+    out
+}
+
+fn definition_body(obj: &SchemaObject, out: &mut String) {
+    if obj.object.as_ref().is_some_and(|object| !object.properties.is_empty()) {
+        field_table(obj, out);
+        return;
+    }
+
+    let variants = obj
+        .subschemas
+        .as_ref()
+        .and_then(|subschemas| subschemas.one_of.as_ref().or(subschemas.any_of.as_ref()));
+    if let Some(variants) = variants {
+        out.push_str("One of:\n");
+        for variant in variants {
+            let Schema::Object(variant) = variant else {
+                continue;
+            };
+            let properties: Vec<&str> = variant
+                .object
+                .as_ref()
+                .map(|object| object.properties.keys().map(|name| name.as_str()).collect())
+                .unwrap_or_default();
+            let heading = match tag(variant) {
+                Some((field, value)) => format!("`{} = {}`", field, value),
+                // Externally tagged variants are tables with the variant name as their only key
+                None if !properties.is_empty() => format!("`{}`", properties.join("`, `")),
+                None => type_name(&Schema::Object(variant.clone())),
+            };
+            out.push_str(&format!("\n### {}\n", heading));
+            if let Some(description) = description(&Schema::Object(variant.clone())) {
+                out.push_str(&format!("\n{}\n", description));
+            }
+            if !properties.is_empty() {
+                out.push('\n');
+                field_table(variant, out);
+            }
+        }
+        return;
+    }
+
+    out.push_str(&format!("Type: {}\n", type_name(&Schema::Object(obj.clone()))));
+}
+
+fn field_table(obj: &SchemaObject, out: &mut String) {
+    let Some(object) = &obj.object else {
+        return;
+    };
+    out.push_str("| Field | Type | Default | Description |\n");
+    out.push_str("|---|---|---|---|\n");
+    for (name, schema) in &object.properties {
+        let default = if object.required.contains(name) {
+            "required".to_string()
+        } else {
+            default_value(schema).unwrap_or_else(|| "none".to_string())
+        };
+        out.push_str(&format!(
+            "| `{}` | {} | {} | {} |\n",
+            name,
+            type_name(schema),
+            default,
+            description(schema).map(|d| d.replace('|', "\\|")).unwrap_or_default()
+        ));
+    }
+}
+
+/// Tag field and value of an internally tagged enum variant
+fn tag(variant: &SchemaObject) -> Option<(&str, String)> {
+    let object = variant.object.as_ref()?;
+    object.properties.iter().find_map(|(name, schema)| match schema {
+        Schema::Object(SchemaObject {
+            enum_values: Some(values),
+            ..
+        }) if values.len() == 1 => Some((name.as_str(), values[0].to_string())),
+        _ => None,
+    })
+}
+
+fn description(schema: &Schema) -> Option<String> {
+    match schema {
+        Schema::Object(obj) => obj
+            .metadata
+            .as_ref()
+            .and_then(|m| m.description.as_ref())
+            .map(|d| d.replace('\n', " ")),
+        Schema::Bool(_) => None,
+    }
+}
+
+fn default_value(schema: &Schema) -> Option<String> {
+    match schema {
+        Schema::Object(obj) => obj
+            .metadata
+            .as_ref()
+            .and_then(|m| m.default.as_ref())
+            .filter(|value| !value.is_null())
+            .map(|value| format!("`{}`", value)),
+        Schema::Bool(_) => None,
+    }
+}
+
+fn type_name(schema: &Schema) -> String {
+    let obj = match schema {
+        Schema::Bool(_) => return "any".to_string(),
+        Schema::Object(obj) => obj,
+    };
+
+    if let Some(reference) = &obj.reference {
+        let name = reference.rsplit('/').next().unwrap_or(reference);
+        return format!("[{}](#{})", name, name.to_lowercase());
+    }
+    if let Some(values) = &obj.enum_values {
+        let values: Vec<String> = values.iter().map(|v| format!("`{}`", v)).collect();
+        return values.join(" \\| ");
+    }
+    if let Some(subschemas) = &obj.subschemas {
+        let schemas = subschemas
+            .any_of
+            .as_ref()
+            .or(subschemas.one_of.as_ref())
+            .or(subschemas.all_of.as_ref());
+        if let Some(schemas) = schemas {
+            // Option<T> is T or null; optional-ness shows in the default
+            let types: Vec<String> = schemas.iter().map(type_name).filter(|t| t != "null").collect();
+            return types.join(" or ");
+        }
+    }
+
+    match &obj.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => instance_type_name(obj, instance_type),
+        Some(SingleOrVec::Vec(instance_types)) => {
+            let types: Vec<String> = instance_types
+                .iter()
+                .filter(|t| **t != InstanceType::Null)
+                .map(|t| instance_type_name(obj, t))
+                .collect();
+            types.join(" or ")
+        }
+        None => "any".to_string(),
+    }
+}
+
+fn instance_type_name(obj: &SchemaObject, instance_type: &InstanceType) -> String {
+    match instance_type {
+        InstanceType::Null => "null".to_string(),
+        InstanceType::Boolean => "boolean".to_string(),
+        InstanceType::Integer => "integer".to_string(),
+        InstanceType::Number => "float".to_string(),
+        InstanceType::String => "string".to_string(),
+        InstanceType::Array => match obj.array.as_ref().and_then(|a| a.items.as_ref()) {
+            Some(SingleOrVec::Single(item)) => format!("array of {}", type_name(item)),
+            _ => "array".to_string(),
+        },
+        InstanceType::Object => match obj.object.as_ref().and_then(|o| o.additional_properties.as_deref()) {
+            Some(value) => format!("table of {}", type_name(value)),
+            None => "table".to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_reference() {
+        let docs = generate();
+        assert!(docs.contains("\n## GameData\n"));
+        assert!(docs.contains("| `engine` | string | required |"));
+        assert!(docs.contains("| `resolve` | string | `\"none\"` |"));
+        assert!(docs.contains("| `ds3` | `category_decomposition` | `event_flags`, `field_area` |"));
+        assert!(docs.contains("### `type = \"flag_set\"`"));
+        // Every linked definition is documented
+        for link in docs.split("](#").skip(1) {
+            let anchor = &link[..link.find(')').unwrap()];
+            assert!(
+                docs.to_lowercase().contains(&format!("\n## {}\n", anchor)),
+                "missing definition {}",
+                anchor
+            );
+        }
+    }
+
+    #[test]
+    fn test_export_reference() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(OUTPUT_PATH);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, generate()).unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+//! Dark Souls 2 trigger catalog
+//!
+//! DS2 splits are kill counters, which cannot express lighting a Primal
+//! Bonfire. [`landmarks`] lists the Primal Bonfires a host can offer, and
+//! [`landmark_triggers`] turns them into custom position triggers: the player
+//! reaching the bonfire once the Great Soul boss guarding it is dead (its kill
+//! counter, see `BossType` of the DS2 reader).
+//!
+//! The library ships no bonfire coordinates, since no public reference for
+//! them was found; the host passes the positions the runner recorded at each
+//! bonfire (from the position trail, see `position_trail_interval_ms`). DS2
+//! reports no map id, and its positions are local to the map, so the kill
+//! counter is what keeps a trigger from firing at the same coordinates in
+//! another map before the boss is dead. Places without a kill counter to gate
+//! on (Majula, the Black Gulch bonfire after The Rotten) are not listed.
+
+use crate::config::{CustomTrigger, TriggerCondition, TriggerKind};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Prefix of trigger ids built by [`landmark_triggers`]
+pub const TRIGGER_ID_PREFIX: &str = "ds2.";
+
+/// Radius around a recorded position within which a trigger fires
+pub const LANDMARK_RADIUS: f32 = 8.0;
+
+/// (id, name, kill counter offset of the boss guarding it)
+const LANDMARKS: &[(&str, &str, u32)] = &[
+    ("primal_iron_keep", "Primal Bonfire: Iron Keep", 0x1c),
+    ("primal_sinners_rise", "Primal Bonfire: Sinner's Rise", 0x18),
+    ("primal_brightstone_cove", "Primal Bonfire: Brightstone Cove Tseldora", 0x14),
+];
+
+/// A place a host can offer as a DS2 position split
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Ds2Landmark {
+    pub id: String,
+    pub name: String,
+    /// Kill counter offset of the boss that must be dead
+    pub after_kill: u32,
+}
+
+/// The DS2 landmarks, in progression order
+pub fn landmarks() -> Vec<Ds2Landmark> {
+    LANDMARKS
+        .iter()
+        .map(|&(id, name, after_kill)| Ds2Landmark {
+            id: id.to_string(),
+            name: name.to_string(),
+            after_kill,
+        })
+        .collect()
+}
+
+/// Build split triggers for the landmarks whose position the runner
+/// recorded (x, y, z keyed by landmark id), in progression order
+pub fn landmark_triggers(positions: &HashMap<String, [f32; 3]>) -> Result<Vec<CustomTrigger>, String> {
+    if positions.is_empty() {
+        return Err("No landmark positions given".to_string());
+    }
+    let all = landmarks();
+    if let Some(unknown) = positions.keys().find(|id| !all.iter().any(|landmark| &landmark.id == *id)) {
+        return Err(format!("Unknown landmark: {}", unknown));
+    }
+
+    Ok(all
+        .into_iter()
+        .filter_map(|landmark| {
+            let [x, y, z] = *positions.get(&landmark.id)?;
+            Some(CustomTrigger {
+                id: format!("{}{}", TRIGGER_ID_PREFIX, landmark.id),
+                name: landmark.name,
+                condition: TriggerCondition::Position {
+                    x,
+                    y,
+                    z,
+                    radius: LANDMARK_RADIUS,
+                    map_id: None,
+                    after_flag: Some(landmark.after_kill),
+                },
+                kind: TriggerKind::Split,
+                metadata: Default::default(),
+                group: None,
+                priority: 0,
+                suppression_ms: None,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::position_trail::WorldPosition;
+
+    fn iron_keep() -> CustomTrigger {
+        let positions = HashMap::from([("primal_iron_keep".to_string(), [10.0, 20.0, 30.0])]);
+        landmark_triggers(&positions).unwrap().remove(0)
+    }
+
+    #[test]
+    fn test_landmark_triggers() {
+        let trigger = iron_keep();
+        assert!(trigger.validate().is_ok());
+        assert_eq!(trigger.id, "ds2.primal_iron_keep");
+        assert_eq!(trigger.condition.flag_ids(), vec![0x1c]);
+
+        assert!(landmark_triggers(&HashMap::new()).is_err());
+        let unknown = HashMap::from([("majula".to_string(), [0.0, 0.0, 0.0])]);
+        assert!(landmark_triggers(&unknown).is_err());
+    }
+
+    #[test]
+    fn test_same_coordinates_in_another_map_do_not_fire() {
+        let trigger = iron_keep();
+        let at_bonfire = WorldPosition {
+            x: 11.0,
+            y: 20.0,
+            z: 28.0,
+            map_id: None,
+        };
+
+        // The coordinates match, but the Old Iron King is not dead: the
+        // player is in another map (or has not reached Iron Keep's boss yet)
+        assert!(!trigger.condition.position_fires(&at_bonfire, |_| false));
+        assert!(trigger.condition.position_fires(&at_bonfire, |offset| offset == 0x1c));
+
+        // A host that knows the map can pin the trigger to it
+        let pinned = TriggerCondition::Position {
+            x: 10.0,
+            y: 20.0,
+            z: 30.0,
+            radius: LANDMARK_RADIUS,
+            map_id: Some(1),
+            after_flag: Some(0x1c),
+        };
+        let elsewhere = WorldPosition {
+            map_id: Some(2),
+            ..at_bonfire
+        };
+        assert!(!pinned.position_fires(&elsewhere, |_| true));
+    }
+}
@@ -4,15 +4,21 @@
 //! using configurations loaded from TOML files.
 //!
 //! The algorithms are implemented in Rust (too complex for config),
-//! but the memory patterns and pointers come from TOML config.
+//! but the memory patterns and pointers come from TOML config. Host crates
+//! can add their own algorithms by implementing [`Engine`] and registering
+//! it with [`register_engine`].
 
-use crate::game_data::{GameData, PatternDefinition, PointerDefinition};
+use crate::cache::CompiledGameData;
+use crate::game_data::{GameData, PatternDefinition, PointerDefinition, ValueDefinition};
 use crate::memory::pointer::Pointer;
 use crate::memory::{parse_pattern, resolve_rip_relative, scan_pattern};
+use crate::read_plan::{byte_bit_location, FlagBlockReader, FlagLocation};
+use once_cell::sync::Lazy;
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 #[cfg(target_os = "windows")]
-use crate::memory::{read_i32, read_i64};
+use crate::memory::{read_i32, read_i64, read_u32};
 #[cfg(target_os = "windows")]
 use windows::Win32::Foundation::HANDLE;
 
@@ -33,9 +39,47 @@ pub enum EngineType {
     Sekiro,
     /// Armored Core 6 - event flags
     Ac6,
+    /// Engine registered by the host (see `register_engine`)
+    Custom,
 }
 
 impl EngineType {
+    /// All supported engine types
+    pub const ALL: [EngineType; 7] = [
+        Self::Ds1Ptde,
+        Self::Ds1Remaster,
+        Self::Ds2Sotfs,
+        Self::Ds3,
+        Self::EldenRing,
+        Self::Sekiro,
+        Self::Ac6,
+    ];
+
+    /// Canonical identifier accepted by `from_str`
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Ds1Ptde => "ds1_ptde",
+            Self::Ds1Remaster => "ds1_remaster",
+            Self::Ds2Sotfs => "ds2_sotfs",
+            Self::Ds3 => "ds3",
+            Self::EldenRing => "elden_ring",
+            Self::Sekiro => "sekiro",
+            Self::Ac6 => "ac6",
+            Self::Custom => "custom",
+        }
+    }
+
+    /// Name of the flag reading algorithm used by this engine
+    pub fn algorithm(&self) -> &'static str {
+        match self {
+            Self::Ds1Ptde | Self::Ds1Remaster => "offset_table",
+            Self::Ds2Sotfs => "kill_counter",
+            Self::Ds3 | Self::Sekiro | Self::Ac6 => "category_decomposition",
+            Self::EldenRing => "binary_tree",
+            Self::Custom => "custom",
+        }
+    }
+
     /// Parse engine type from string
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
@@ -50,10 +94,93 @@ impl EngineType {
         }
     }
 
+    /// Parse a built-in engine type, or `Custom` for an engine registered
+    /// under that name
+    pub fn resolve(s: &str) -> Option<Self> {
+        Self::from_str(s).or_else(|| custom_engine(s).map(|_| Self::Custom))
+    }
+
     /// Check if this engine uses kill counters (vs event flags)
     pub fn uses_kill_counters(&self) -> bool {
         matches!(self, Self::Ds2Sotfs)
     }
+
+    /// Pointers a game definition must define for this engine (custom
+    /// engines check their own)
+    pub fn required_pointers(&self) -> &'static [&'static str] {
+        match self {
+            Self::Ds2Sotfs => &["boss_counters"],
+            Self::Ds3 => &["event_flags", "field_area"],
+            Self::Custom => &[],
+            _ => &["event_flags"],
+        }
+    }
+}
+
+/// Flag reading algorithm provided by a host crate
+///
+/// Register it with [`register_engine`]; game definitions then select it by
+/// name (`[autosplitter] engine = "my_engine"`). Patterns and pointers are
+/// scanned and resolved from the definition as for the built-in engines and
+/// are available in `GenericGame::pointers`.
+pub trait Engine: Send + Sync {
+    /// Check the resolved pointers after attaching; false fails the attach
+    fn validate(&self, game: &GenericGame) -> bool {
+        game.pointers.contains_key("event_flags")
+    }
+
+    /// Read an event flag
+    fn read_event_flag(&self, game: &GenericGame, flag_id: u32) -> bool;
+
+    /// Kill count of a boss (1 or 0 from its event flag unless overridden)
+    fn get_kill_count(&self, game: &GenericGame, flag_id: u32) -> u32 {
+        self.read_event_flag(game, flag_id) as u32
+    }
+}
+
+static CUSTOM_ENGINES: Lazy<RwLock<HashMap<String, Arc<dyn Engine>>>> = Lazy::new(Default::default);
+
+/// Register a host-provided engine under `name` (case-insensitive);
+/// registering a name again replaces the engine for games created afterwards
+pub fn register_engine(name: &str, engine: Arc<dyn Engine>) -> Result<(), String> {
+    let name = name.trim().to_lowercase();
+    if name.is_empty() {
+        return Err("Engine name is empty".to_string());
+    }
+    if EngineType::from_str(&name).is_some() || name == EngineType::Custom.id() {
+        return Err(format!("Engine name '{}' is reserved by a built-in engine", name));
+    }
+
+    log::info!("Registered custom engine '{}'", name);
+    CUSTOM_ENGINES.write().unwrap().insert(name, engine);
+    Ok(())
+}
+
+/// Remove a registered engine; returns whether it was registered
+pub fn unregister_engine(name: &str) -> bool {
+    CUSTOM_ENGINES.write().unwrap().remove(&name.trim().to_lowercase()).is_some()
+}
+
+/// Names of the registered engines, sorted
+pub fn registered_engines() -> Vec<String> {
+    let mut names: Vec<String> = CUSTOM_ENGINES.read().unwrap().keys().cloned().collect();
+    names.sort_unstable();
+    names
+}
+
+/// The engine registered under `name`
+pub fn custom_engine(name: &str) -> Option<Arc<dyn Engine>> {
+    CUSTOM_ENGINES.read().unwrap().get(&name.trim().to_lowercase()).cloned()
+}
+
+/// Parse every pattern in a game definition, keyed by pattern name
+pub(crate) fn parse_all_patterns(game_data: &GameData) -> HashMap<String, Vec<Option<u8>>> {
+    game_data
+        .autosplitter
+        .patterns
+        .iter()
+        .map(|p| (p.name.clone(), parse_pattern(&p.pattern)))
+        .collect()
 }
 
 /// Generic game instance that uses data-driven configuration
@@ -66,14 +193,20 @@ pub struct GenericGame {
     pub patterns: HashMap<String, usize>,
     /// Resolved pointers
     pub pointers: HashMap<String, Pointer>,
+    /// Byte patterns parsed ahead of scanning, keyed by pattern name
+    parsed_patterns: HashMap<String, Vec<Option<u8>>>,
+    /// Host-provided algorithm when `engine_type` is `Custom`
+    custom_engine: Option<Arc<dyn Engine>>,
 }
 
 #[cfg(target_os = "windows")]
 impl GenericGame {
     /// Create a new generic game instance
     pub fn new(game_data: GameData) -> Result<Self, String> {
-        let engine_type = EngineType::from_str(&game_data.autosplitter.engine)
+        let engine_type = EngineType::resolve(&game_data.autosplitter.engine)
             .ok_or_else(|| format!("Unknown engine type: {}", game_data.autosplitter.engine))?;
+        let parsed_patterns = parse_all_patterns(&game_data);
+        let custom_engine = custom_engine(&game_data.autosplitter.engine);
 
         Ok(Self {
             handle: HANDLE::default(),
@@ -81,9 +214,24 @@ impl GenericGame {
             engine_type,
             patterns: HashMap::new(),
             pointers: HashMap::new(),
+            parsed_patterns,
+            custom_engine,
         })
     }
 
+    /// Create a generic game instance from a precompiled configuration
+    pub fn from_compiled(compiled: &CompiledGameData) -> Self {
+        Self {
+            handle: HANDLE::default(),
+            game_data: compiled.game_data.clone(),
+            engine_type: compiled.engine_type,
+            patterns: HashMap::new(),
+            pointers: HashMap::new(),
+            parsed_patterns: compiled.patterns.clone(),
+            custom_engine: custom_engine(&compiled.game_data.autosplitter.engine),
+        }
+    }
+
     /// Initialize by scanning for patterns in memory
     pub fn init(&mut self, handle: HANDLE, base: usize, size: usize) -> bool {
         self.handle = handle;
@@ -126,7 +274,10 @@ impl GenericGame {
         size: usize,
         pattern_def: &PatternDefinition,
     ) -> Option<usize> {
-        let pattern = parse_pattern(&pattern_def.pattern);
+        let pattern = match self.parsed_patterns.get(&pattern_def.name) {
+            Some(pattern) => pattern.clone(),
+            None => parse_pattern(&pattern_def.pattern),
+        };
         let found = scan_pattern(handle, base, size, &pattern)?;
 
         // Apply resolution
@@ -163,19 +314,13 @@ impl GenericGame {
     /// Validate that required patterns were found
     fn validate_patterns(&self) -> bool {
         match self.engine_type {
-            EngineType::Ds2Sotfs => {
-                self.pointers.contains_key("boss_counters")
-            }
-            EngineType::Ds3 => {
-                self.pointers.contains_key("event_flags")
-                    && self.pointers.contains_key("field_area")
-            }
-            EngineType::EldenRing => {
-                self.pointers.contains_key("event_flags")
-            }
-            _ => {
-                self.pointers.contains_key("event_flags")
+            EngineType::Custom => {
+                self.custom_engine.as_ref().is_some_and(|engine| engine.validate(self))
             }
+            engine_type => engine_type
+                .required_pointers()
+                .iter()
+                .all(|name| self.pointers.contains_key(*name)),
         }
     }
 
@@ -183,12 +328,11 @@ impl GenericGame {
     pub fn read_event_flag(&self, flag_id: u32) -> bool {
         match self.engine_type {
             EngineType::Ds2Sotfs => self.read_kill_counter(flag_id) > 0,
-            EngineType::Ds3 => self.read_ds3_event_flag(flag_id),
-            EngineType::EldenRing => self.read_elden_ring_event_flag(flag_id),
-            EngineType::Sekiro => self.read_sekiro_event_flag(flag_id),
-            EngineType::Ds1Remaster => self.read_ds1r_event_flag(flag_id),
-            EngineType::Ds1Ptde => self.read_ds1_ptde_event_flag(flag_id),
-            EngineType::Ac6 => self.read_ac6_event_flag(flag_id),
+            EngineType::Custom => self
+                .custom_engine
+                .as_ref()
+                .is_some_and(|engine| engine.read_event_flag(self, flag_id)),
+            _ => self.read_flag(flag_id),
         }
     }
 
@@ -196,6 +340,8 @@ impl GenericGame {
     pub fn get_kill_count(&self, flag_id: u32) -> u32 {
         if self.engine_type == EngineType::Ds2Sotfs {
             self.read_kill_counter(flag_id).max(0) as u32
+        } else if let Some(engine) = &self.custom_engine {
+            engine.get_kill_count(self, flag_id)
         } else {
             if self.read_event_flag(flag_id) { 1 } else { 0 }
         }
@@ -218,16 +364,20 @@ impl GenericGame {
     // DS3 - Area-based Event Flags (port from SoulSplitter)
     // =========================================================================
 
-    fn read_ds3_event_flag(&self, event_flag_id: u32) -> bool {
-        let event_flags = match self.pointers.get("event_flags") {
-            Some(p) => p,
-            None => return false,
-        };
+    fn ds3_flag_location(event_flag_id: u32) -> FlagLocation {
+        let mod_1000 = event_flag_id % 1000;
+        FlagLocation {
+            block: event_flag_id / 1000,
+            offset: ((mod_1000 >> 5) * 4) as i64,
+            mask: 1u32 << (0x1f - (mod_1000 & 0x1f)),
+        }
+    }
 
-        let field_area = match self.pointers.get("field_area") {
-            Some(p) => p,
-            None => return false,
-        };
+    /// Resolve the block holding flags `block * 1000 ..= block * 1000 + 999`
+    fn ds3_flag_block(&self, block: u32) -> Option<i64> {
+        let event_flag_id = block * 1000;
+        let event_flags = self.pointers.get("event_flags")?;
+        let field_area = self.pointers.get("field_area")?;
 
         // Decompose event flag ID
         let event_flag_id_div_10000000 = ((event_flag_id / 10_000_000) % 10) as i64;
@@ -241,7 +391,7 @@ impl GenericGame {
             flag_world_block_info_category = 0;
         } else {
             if field_area.is_null_ptr() {
-                return false;
+                return None;
             }
 
             let world_info_owner = field_area.append(&[0x0, 0x10]).create_pointer_from_address(None);
@@ -294,48 +444,42 @@ impl GenericGame {
         let ptr = event_flags.append(&[0x218, event_flag_id_div_10000000 * 0x18, 0x0]);
 
         if ptr.is_null_ptr() || flag_world_block_info_category < 0 {
-            return false;
+            return None;
         }
 
         let result_base = (event_flag_id_div_1000 << 4)
             + ptr.get_address()
             + (flag_world_block_info_category as i64 * 0xa8);
 
-        let mut result_pointer = Pointer::new();
-        result_pointer.initialize(self.handle, true, result_base, &[0x0]);
-
-        if !result_pointer.is_null_ptr() {
-            let mod_1000 = (event_flag_id % 1000) as u32;
-            let read_offset = ((mod_1000 >> 5) * 4) as i64;
-            let value = result_pointer.read_u32(Some(read_offset));
-
-            let bit_shift = 0x1f - ((mod_1000 as u8) & 0x1f);
-            let mask = 1u32 << (bit_shift & 0x1f);
-
-            return (value & mask) != 0;
-        }
-
-        false
+        // Flag words live behind the block pointer
+        read_i64(self.handle, result_base as usize)
     }
 
     // =========================================================================
     // Elden Ring - Virtual Memory Flags (port from SoulSplitter)
     // =========================================================================
 
-    fn read_elden_ring_event_flag(&self, event_flag_id: u32) -> bool {
-        let event_flags = match self.pointers.get("event_flags") {
-            Some(p) => p,
-            None => return false,
-        };
+    /// Flags per category, read from virtual_memory_flag + 0x1c (0 until a save is loaded)
+    fn elden_ring_flag_divisor(&self) -> Option<u32> {
+        let divisor = self.pointers.get("event_flags")?.read_i32(Some(0x1c));
+        (divisor > 0).then_some(divisor as u32)
+    }
 
-        // Read divisor from virtual_memory_flag + 0x1c
-        let divisor = event_flags.read_i32(Some(0x1c));
-        if divisor == 0 {
-            return false;
-        }
+    fn elden_ring_flag_location(&self, event_flag_id: u32) -> Option<FlagLocation> {
+        let divisor = self.elden_ring_flag_divisor()?;
+        let category = event_flag_id / divisor;
+        let least_significant_digits = event_flag_id - (category * divisor);
+
+        Some(byte_bit_location(
+            category,
+            least_significant_digits >> 3,
+            7 - (least_significant_digits & 7),
+        ))
+    }
 
-        let category = event_flag_id / divisor as u32;
-        let least_significant_digits = event_flag_id - (category * divisor as u32);
+    /// Resolve a category's flag block by walking the category tree
+    fn elden_ring_flag_block(&self, category: u32) -> Option<i64> {
+        let event_flags = self.pointers.get("event_flags")?;
 
         // Binary tree traversal
         let current_element_root = event_flags.create_pointer_from_address(Some(0x38));
@@ -362,7 +506,7 @@ impl GenericGame {
         let sub_elem_addr = current_sub_element.get_address();
 
         if current_elem_addr == sub_elem_addr {
-            return false;
+            return None;
         }
 
         let mystery_value = read_i32(self.handle, (current_elem_addr + 0x28) as usize).unwrap_or(0) - 1;
@@ -374,43 +518,31 @@ impl GenericGame {
             let base_addr = event_flags.read_i64(Some(0x28));
             calculated_pointer = (mult as i64 * elem_val as i64) + base_addr;
         } else if mystery_value == 1 {
-            return false;
+            return None;
         } else {
             calculated_pointer = read_i64(self.handle, (current_elem_addr + 0x30) as usize).unwrap_or(0);
         }
 
-        if calculated_pointer == 0 {
-            return false;
-        }
-
-        let thing = 7 - (least_significant_digits & 7);
-        let mask = 1i32 << thing;
-        let shifted = least_significant_digits >> 3;
-
-        let final_addr = (calculated_pointer + shifted as i64) as usize;
-        if let Some(read_value) = read_i32(self.handle, final_addr) {
-            return (read_value & mask) != 0;
-        }
-
-        false
+        Some(calculated_pointer)
     }
 
     // =========================================================================
     // Sekiro - Event Flags (similar to DS3 but simpler)
     // =========================================================================
 
-    fn read_sekiro_event_flag(&self, event_flag_id: u32) -> bool {
-        let event_flags = match self.pointers.get("event_flags") {
-            Some(p) => p,
-            None => return false,
-        };
+    fn sekiro_flag_location(event_flag_id: u32) -> FlagLocation {
+        let mod_1000 = event_flag_id % 1000;
+        byte_bit_location(event_flag_id / 1000, mod_1000 / 8, mod_1000 % 8)
+    }
+
+    /// Resolve the block holding flags `block * 1000 ..= block * 1000 + 999`
+    fn sekiro_flag_block(&self, block: u32) -> Option<i64> {
+        let event_flags = self.pointers.get("event_flags")?;
 
         // Sekiro uses a simpler system similar to DS3 category 0
-        let divisor = 1000u32;
-        let id_div_by_divisor = event_flag_id / divisor;
-        let category = id_div_by_divisor / 100000;
-        let sub_category = (id_div_by_divisor % 100000) / 10000;
-        let byte_index = id_div_by_divisor % 10000;
+        let category = block / 100000;
+        let sub_category = (block % 100000) / 10000;
+        let byte_index = block % 10000;
 
         // Navigate to the flag location
         let ptr = event_flags.append(&[
@@ -423,33 +555,20 @@ impl GenericGame {
         ]);
 
         if ptr.is_null_ptr() {
-            return false;
+            return None;
         }
 
-        let mod_1000 = event_flag_id % 1000;
-        let byte_offset = (mod_1000 / 8) as i64;
-        let bit_index = mod_1000 % 8;
-
-        let byte_val = ptr.read_byte(Some(byte_offset));
-        let mask = 1u8 << bit_index;
-
-        (byte_val & mask) != 0
+        Some(ptr.create_pointer_from_address(None).get_address())
     }
 
     // =========================================================================
-    // DS1 Remastered - Event Flags
+    // DS1 Remastered / PTDE - Event Flags (one block behind event_flags)
     // =========================================================================
 
-    fn read_ds1r_event_flag(&self, event_flag_id: u32) -> bool {
-        let event_flags = match self.pointers.get("event_flags") {
-            Some(p) => p,
-            None => return false,
-        };
-
+    fn ds1_flag_location(event_flag_id: u32) -> FlagLocation {
         // DS1R event flag calculation
         let id_div_100000 = (event_flag_id / 100000) as i64;
         let id_mod_100000 = event_flag_id % 100000;
-        let _id_div_100000_mod_10 = id_div_100000 % 10;
 
         let offset_base = match id_div_100000 {
             0 => 0x0,
@@ -459,48 +578,56 @@ impl GenericGame {
             7 => 0x7300,
             _ => {
                 // Calculate based on area
-                let area_offset = if id_div_100000 < 50 {
+                if id_div_100000 < 50 {
                     (id_div_100000 - 10) * 0x500 + 0xA00
                 } else {
                     (id_div_100000 - 50) * 0x100 + 0x7D00
-                };
-                area_offset
+                }
             }
         };
 
         let id_div_10000_mod_10 = (id_mod_100000 / 10000) % 10;
         let sub_offset = (id_div_10000_mod_10 as i64) * 0x80;
 
-        let final_offset = offset_base + sub_offset + ((id_mod_100000 % 10000) / 32) as i64 * 4;
-
-        let ptr = event_flags.append(&[final_offset]);
-        if ptr.is_null_ptr() {
-            return false;
+        FlagLocation {
+            block: 0,
+            offset: offset_base + sub_offset + ((id_mod_100000 % 10000) / 32) as i64 * 4,
+            mask: 1u32 << (id_mod_100000 % 32),
         }
-
-        let value = ptr.read_u32(None);
-        let bit = (id_mod_100000 % 32) as u32;
-        let mask = 1u32 << bit;
-
-        (value & mask) != 0
     }
 
-    // =========================================================================
-    // DS1 PTDE - Event Flags (32-bit)
-    // =========================================================================
+    fn ds1_flag_block(&self) -> Option<i64> {
+        // PTDE uses same layout as Remastered but 32-bit pointers
+        let event_flags = self.pointers.get("event_flags")?;
+        Some(event_flags.create_pointer_from_address(None).get_address())
+    }
+}
 
-    fn read_ds1_ptde_event_flag(&self, event_flag_id: u32) -> bool {
-        // PTDE uses same algorithm as Remastered but 32-bit pointers
-        self.read_ds1r_event_flag(event_flag_id)
+#[cfg(target_os = "windows")]
+impl FlagBlockReader for GenericGame {
+    fn flag_location(&self, flag_id: u32) -> Option<FlagLocation> {
+        match self.engine_type {
+            EngineType::Ds2Sotfs | EngineType::Custom => None,
+            EngineType::Ds3 => Some(Self::ds3_flag_location(flag_id)),
+            EngineType::EldenRing | EngineType::Ac6 => self.elden_ring_flag_location(flag_id),
+            EngineType::Sekiro => Some(Self::sekiro_flag_location(flag_id)),
+            EngineType::Ds1Remaster | EngineType::Ds1Ptde => Some(Self::ds1_flag_location(flag_id)),
+        }
     }
 
-    // =========================================================================
-    // AC6 - Event Flags (similar to Elden Ring)
-    // =========================================================================
+    fn block_address(&self, block: u32) -> Option<i64> {
+        match self.engine_type {
+            EngineType::Ds2Sotfs | EngineType::Custom => None,
+            EngineType::Ds3 => self.ds3_flag_block(block),
+            // AC6 uses the same virtual memory flag system as Elden Ring
+            EngineType::EldenRing | EngineType::Ac6 => self.elden_ring_flag_block(block),
+            EngineType::Sekiro => self.sekiro_flag_block(block),
+            EngineType::Ds1Remaster | EngineType::Ds1Ptde => self.ds1_flag_block(),
+        }
+    }
 
-    fn read_ac6_event_flag(&self, event_flag_id: u32) -> bool {
-        // AC6 uses the same virtual memory flag system as Elden Ring
-        self.read_elden_ring_event_flag(event_flag_id)
+    fn read_flag_word(&self, address: i64) -> Option<u32> {
+        read_u32(self.handle, address as usize)
     }
 }
 
@@ -509,7 +636,7 @@ impl GenericGame {
 // =========================================================================
 
 #[cfg(target_os = "linux")]
-use crate::memory::{read_i32, read_i64};
+use crate::memory::{read_i32, read_i64, read_u32};
 
 /// Generic game instance that uses data-driven configuration (Linux/Proton)
 #[cfg(target_os = "linux")]
@@ -521,14 +648,20 @@ pub struct GenericGame {
     pub patterns: HashMap<String, usize>,
     /// Resolved pointers
     pub pointers: HashMap<String, Pointer>,
+    /// Byte patterns parsed ahead of scanning, keyed by pattern name
+    parsed_patterns: HashMap<String, Vec<Option<u8>>>,
+    /// Host-provided algorithm when `engine_type` is `Custom`
+    custom_engine: Option<Arc<dyn Engine>>,
 }
 
 #[cfg(target_os = "linux")]
 impl GenericGame {
     /// Create a new generic game instance
     pub fn new(game_data: GameData) -> Result<Self, String> {
-        let engine_type = EngineType::from_str(&game_data.autosplitter.engine)
+        let engine_type = EngineType::resolve(&game_data.autosplitter.engine)
             .ok_or_else(|| format!("Unknown engine type: {}", game_data.autosplitter.engine))?;
+        let parsed_patterns = parse_all_patterns(&game_data);
+        let custom_engine = custom_engine(&game_data.autosplitter.engine);
 
         Ok(Self {
             pid: 0,
@@ -536,9 +669,24 @@ impl GenericGame {
             engine_type,
             patterns: HashMap::new(),
             pointers: HashMap::new(),
+            parsed_patterns,
+            custom_engine,
         })
     }
 
+    /// Create a generic game instance from a precompiled configuration
+    pub fn from_compiled(compiled: &CompiledGameData) -> Self {
+        Self {
+            pid: 0,
+            game_data: compiled.game_data.clone(),
+            engine_type: compiled.engine_type,
+            patterns: HashMap::new(),
+            pointers: HashMap::new(),
+            parsed_patterns: compiled.patterns.clone(),
+            custom_engine: custom_engine(&compiled.game_data.autosplitter.engine),
+        }
+    }
+
     /// Initialize by scanning for patterns in memory (Linux/Proton)
     pub fn init(&mut self, pid: i32, base: usize, size: usize) -> bool {
         self.pid = pid;
@@ -581,7 +729,10 @@ impl GenericGame {
         size: usize,
         pattern_def: &PatternDefinition,
     ) -> Option<usize> {
-        let pattern = parse_pattern(&pattern_def.pattern);
+        let pattern = match self.parsed_patterns.get(&pattern_def.name) {
+            Some(pattern) => pattern.clone(),
+            None => parse_pattern(&pattern_def.pattern),
+        };
         let found = scan_pattern(pid, base, size, &pattern)?;
 
         // Apply resolution
@@ -618,19 +769,13 @@ impl GenericGame {
     /// Validate that required patterns were found
     fn validate_patterns(&self) -> bool {
         match self.engine_type {
-            EngineType::Ds2Sotfs => {
-                self.pointers.contains_key("boss_counters")
-            }
-            EngineType::Ds3 => {
-                self.pointers.contains_key("event_flags")
-                    && self.pointers.contains_key("field_area")
-            }
-            EngineType::EldenRing => {
-                self.pointers.contains_key("event_flags")
-            }
-            _ => {
-                self.pointers.contains_key("event_flags")
+            EngineType::Custom => {
+                self.custom_engine.as_ref().is_some_and(|engine| engine.validate(self))
             }
+            engine_type => engine_type
+                .required_pointers()
+                .iter()
+                .all(|name| self.pointers.contains_key(*name)),
         }
     }
 
@@ -638,12 +783,11 @@ impl GenericGame {
     pub fn read_event_flag(&self, flag_id: u32) -> bool {
         match self.engine_type {
             EngineType::Ds2Sotfs => self.read_kill_counter(flag_id) > 0,
-            EngineType::Ds3 => self.read_ds3_event_flag(flag_id),
-            EngineType::EldenRing => self.read_elden_ring_event_flag(flag_id),
-            EngineType::Sekiro => self.read_sekiro_event_flag(flag_id),
-            EngineType::Ds1Remaster => self.read_ds1r_event_flag(flag_id),
-            EngineType::Ds1Ptde => self.read_ds1_ptde_event_flag(flag_id),
-            EngineType::Ac6 => self.read_ac6_event_flag(flag_id),
+            EngineType::Custom => self
+                .custom_engine
+                .as_ref()
+                .is_some_and(|engine| engine.read_event_flag(self, flag_id)),
+            _ => self.read_flag(flag_id),
         }
     }
 
@@ -651,6 +795,8 @@ impl GenericGame {
     pub fn get_kill_count(&self, flag_id: u32) -> u32 {
         if self.engine_type == EngineType::Ds2Sotfs {
             self.read_kill_counter(flag_id).max(0) as u32
+        } else if let Some(engine) = &self.custom_engine {
+            engine.get_kill_count(self, flag_id)
         } else {
             if self.read_event_flag(flag_id) { 1 } else { 0 }
         }
@@ -673,16 +819,20 @@ impl GenericGame {
     // DS3 - Area-based Event Flags (port from SoulSplitter)
     // =========================================================================
 
-    fn read_ds3_event_flag(&self, event_flag_id: u32) -> bool {
-        let event_flags = match self.pointers.get("event_flags") {
-            Some(p) => p,
-            None => return false,
-        };
+    fn ds3_flag_location(event_flag_id: u32) -> FlagLocation {
+        let mod_1000 = event_flag_id % 1000;
+        FlagLocation {
+            block: event_flag_id / 1000,
+            offset: ((mod_1000 >> 5) * 4) as i64,
+            mask: 1u32 << (0x1f - (mod_1000 & 0x1f)),
+        }
+    }
 
-        let field_area = match self.pointers.get("field_area") {
-            Some(p) => p,
-            None => return false,
-        };
+    /// Resolve the block holding flags `block * 1000 ..= block * 1000 + 999`
+    fn ds3_flag_block(&self, block: u32) -> Option<i64> {
+        let event_flag_id = block * 1000;
+        let event_flags = self.pointers.get("event_flags")?;
+        let field_area = self.pointers.get("field_area")?;
 
         // Decompose event flag ID
         let event_flag_id_div_10000000 = ((event_flag_id / 10_000_000) % 10) as i64;
@@ -696,7 +846,7 @@ impl GenericGame {
             flag_world_block_info_category = 0;
         } else {
             if field_area.is_null_ptr() {
-                return false;
+                return None;
             }
 
             let world_info_owner = field_area.append(&[0x0, 0x10]).create_pointer_from_address(None);
@@ -749,48 +899,42 @@ impl GenericGame {
         let ptr = event_flags.append(&[0x218, event_flag_id_div_10000000 * 0x18, 0x0]);
 
         if ptr.is_null_ptr() || flag_world_block_info_category < 0 {
-            return false;
+            return None;
         }
 
         let result_base = (event_flag_id_div_1000 << 4)
             + ptr.get_address()
             + (flag_world_block_info_category as i64 * 0xa8);
 
-        let mut result_pointer = Pointer::new();
-        result_pointer.initialize(self.pid, true, result_base, &[0x0]);
-
-        if !result_pointer.is_null_ptr() {
-            let mod_1000 = (event_flag_id % 1000) as u32;
-            let read_offset = ((mod_1000 >> 5) * 4) as i64;
-            let value = result_pointer.read_u32(Some(read_offset));
-
-            let bit_shift = 0x1f - ((mod_1000 as u8) & 0x1f);
-            let mask = 1u32 << (bit_shift & 0x1f);
-
-            return (value & mask) != 0;
-        }
-
-        false
+        // Flag words live behind the block pointer
+        read_i64(self.pid, result_base as usize)
     }
 
     // =========================================================================
     // Elden Ring - Virtual Memory Flags (port from SoulSplitter)
     // =========================================================================
 
-    fn read_elden_ring_event_flag(&self, event_flag_id: u32) -> bool {
-        let event_flags = match self.pointers.get("event_flags") {
-            Some(p) => p,
-            None => return false,
-        };
+    /// Flags per category, read from virtual_memory_flag + 0x1c (0 until a save is loaded)
+    fn elden_ring_flag_divisor(&self) -> Option<u32> {
+        let divisor = self.pointers.get("event_flags")?.read_i32(Some(0x1c));
+        (divisor > 0).then_some(divisor as u32)
+    }
 
-        // Read divisor from virtual_memory_flag + 0x1c
-        let divisor = event_flags.read_i32(Some(0x1c));
-        if divisor == 0 {
-            return false;
-        }
+    fn elden_ring_flag_location(&self, event_flag_id: u32) -> Option<FlagLocation> {
+        let divisor = self.elden_ring_flag_divisor()?;
+        let category = event_flag_id / divisor;
+        let least_significant_digits = event_flag_id - (category * divisor);
+
+        Some(byte_bit_location(
+            category,
+            least_significant_digits >> 3,
+            7 - (least_significant_digits & 7),
+        ))
+    }
 
-        let category = event_flag_id / divisor as u32;
-        let least_significant_digits = event_flag_id - (category * divisor as u32);
+    /// Resolve a category's flag block by walking the category tree
+    fn elden_ring_flag_block(&self, category: u32) -> Option<i64> {
+        let event_flags = self.pointers.get("event_flags")?;
 
         // Binary tree traversal
         let current_element_root = event_flags.create_pointer_from_address(Some(0x38));
@@ -817,7 +961,7 @@ impl GenericGame {
         let sub_elem_addr = current_sub_element.get_address();
 
         if current_elem_addr == sub_elem_addr {
-            return false;
+            return None;
         }
 
         let mystery_value = read_i32(self.pid, (current_elem_addr + 0x28) as usize).unwrap_or(0) - 1;
@@ -829,43 +973,31 @@ impl GenericGame {
             let base_addr = event_flags.read_i64(Some(0x28));
             calculated_pointer = (mult as i64 * elem_val as i64) + base_addr;
         } else if mystery_value == 1 {
-            return false;
+            return None;
         } else {
             calculated_pointer = read_i64(self.pid, (current_elem_addr + 0x30) as usize).unwrap_or(0);
         }
 
-        if calculated_pointer == 0 {
-            return false;
-        }
-
-        let thing = 7 - (least_significant_digits & 7);
-        let mask = 1i32 << thing;
-        let shifted = least_significant_digits >> 3;
-
-        let final_addr = (calculated_pointer + shifted as i64) as usize;
-        if let Some(read_value) = read_i32(self.pid, final_addr) {
-            return (read_value & mask) != 0;
-        }
-
-        false
+        Some(calculated_pointer)
     }
 
     // =========================================================================
     // Sekiro - Event Flags (similar to DS3 but simpler)
     // =========================================================================
 
-    fn read_sekiro_event_flag(&self, event_flag_id: u32) -> bool {
-        let event_flags = match self.pointers.get("event_flags") {
-            Some(p) => p,
-            None => return false,
-        };
+    fn sekiro_flag_location(event_flag_id: u32) -> FlagLocation {
+        let mod_1000 = event_flag_id % 1000;
+        byte_bit_location(event_flag_id / 1000, mod_1000 / 8, mod_1000 % 8)
+    }
+
+    /// Resolve the block holding flags `block * 1000 ..= block * 1000 + 999`
+    fn sekiro_flag_block(&self, block: u32) -> Option<i64> {
+        let event_flags = self.pointers.get("event_flags")?;
 
         // Sekiro uses a simpler system similar to DS3 category 0
-        let divisor = 1000u32;
-        let id_div_by_divisor = event_flag_id / divisor;
-        let category = id_div_by_divisor / 100000;
-        let sub_category = (id_div_by_divisor % 100000) / 10000;
-        let byte_index = id_div_by_divisor % 10000;
+        let category = block / 100000;
+        let sub_category = (block % 100000) / 10000;
+        let byte_index = block % 10000;
 
         // Navigate to the flag location
         let ptr = event_flags.append(&[
@@ -878,29 +1010,17 @@ impl GenericGame {
         ]);
 
         if ptr.is_null_ptr() {
-            return false;
+            return None;
         }
 
-        let mod_1000 = event_flag_id % 1000;
-        let byte_offset = (mod_1000 / 8) as i64;
-        let bit_index = mod_1000 % 8;
-
-        let byte_val = ptr.read_byte(Some(byte_offset));
-        let mask = 1u8 << bit_index;
-
-        (byte_val & mask) != 0
+        Some(ptr.create_pointer_from_address(None).get_address())
     }
 
     // =========================================================================
-    // DS1 Remastered - Event Flags
+    // DS1 Remastered / PTDE - Event Flags (one block behind event_flags)
     // =========================================================================
 
-    fn read_ds1r_event_flag(&self, event_flag_id: u32) -> bool {
-        let event_flags = match self.pointers.get("event_flags") {
-            Some(p) => p,
-            None => return false,
-        };
-
+    fn ds1_flag_location(event_flag_id: u32) -> FlagLocation {
         // DS1R event flag calculation
         let id_div_100000 = (event_flag_id / 100000) as i64;
         let id_mod_100000 = event_flag_id % 100000;
@@ -924,35 +1044,102 @@ impl GenericGame {
         let id_div_10000_mod_10 = (id_mod_100000 / 10000) % 10;
         let sub_offset = (id_div_10000_mod_10 as i64) * 0x80;
 
-        let final_offset = offset_base + sub_offset + ((id_mod_100000 % 10000) / 32) as i64 * 4;
+        FlagLocation {
+            block: 0,
+            offset: offset_base + sub_offset + ((id_mod_100000 % 10000) / 32) as i64 * 4,
+            mask: 1u32 << (id_mod_100000 % 32),
+        }
+    }
 
-        let ptr = event_flags.append(&[final_offset]);
-        if ptr.is_null_ptr() {
-            return false;
+    fn ds1_flag_block(&self) -> Option<i64> {
+        // PTDE uses same layout as Remastered but 32-bit pointers
+        let event_flags = self.pointers.get("event_flags")?;
+        Some(event_flags.create_pointer_from_address(None).get_address())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl FlagBlockReader for GenericGame {
+    fn flag_location(&self, flag_id: u32) -> Option<FlagLocation> {
+        match self.engine_type {
+            EngineType::Ds2Sotfs | EngineType::Custom => None,
+            EngineType::Ds3 => Some(Self::ds3_flag_location(flag_id)),
+            EngineType::EldenRing | EngineType::Ac6 => self.elden_ring_flag_location(flag_id),
+            EngineType::Sekiro => Some(Self::sekiro_flag_location(flag_id)),
+            EngineType::Ds1Remaster | EngineType::Ds1Ptde => Some(Self::ds1_flag_location(flag_id)),
         }
+    }
 
-        let value = ptr.read_u32(None);
-        let bit = (id_mod_100000 % 32) as u32;
-        let mask = 1u32 << bit;
+    fn block_address(&self, block: u32) -> Option<i64> {
+        match self.engine_type {
+            EngineType::Ds2Sotfs | EngineType::Custom => None,
+            EngineType::Ds3 => self.ds3_flag_block(block),
+            // AC6 uses the same virtual memory flag system as Elden Ring
+            EngineType::EldenRing | EngineType::Ac6 => self.elden_ring_flag_block(block),
+            EngineType::Sekiro => self.sekiro_flag_block(block),
+            EngineType::Ds1Remaster | EngineType::Ds1Ptde => self.ds1_flag_block(),
+        }
+    }
 
-        (value & mask) != 0
+    fn read_flag_word(&self, address: i64) -> Option<u32> {
+        read_u32(self.pid, address as usize)
     }
+}
 
-    // =========================================================================
-    // DS1 PTDE - Event Flags (32-bit)
-    // =========================================================================
 
-    fn read_ds1_ptde_event_flag(&self, event_flag_id: u32) -> bool {
-        // PTDE uses same algorithm as Remastered but 32-bit pointers
-        self.read_ds1r_event_flag(event_flag_id)
+// =========================================================================
+// Game time and loading values (e.g., from ASL gameTime/isLoading blocks)
+// =========================================================================
+
+/// Attributes the generic engine provides when the definition has values for them
+pub const TIME_ATTRIBUTE_NAMES: &[&str] = &["igt", "loading"];
+
+impl GenericGame {
+    /// Read a value through its pointer; None while the chain is null
+    fn read_value(&self, value: &ValueDefinition) -> Option<f64> {
+        let pointer = self.pointers.get(&value.pointer)?;
+        if pointer.is_null_ptr() {
+            return None;
+        }
+
+        let offset = Some(value.offset);
+        let raw = match value.value_type.as_str() {
+            "u8" => pointer.read_byte(offset) as f64,
+            "i16" => pointer.read_u32(offset) as u16 as i16 as f64,
+            "u16" => pointer.read_u32(offset) as u16 as f64,
+            "i32" => pointer.read_i32(offset) as f64,
+            "u32" => pointer.read_u32(offset) as f64,
+            "i64" => pointer.read_i64(offset) as f64,
+            "u64" => pointer.read_u64(offset) as f64,
+            "f32" => f32::from_bits(pointer.read_u32(offset)) as f64,
+            "f64" => f64::from_bits(pointer.read_u64(offset)),
+            _ => return None,
+        };
+        Some(raw)
     }
 
-    // =========================================================================
-    // AC6 - Event Flags (similar to Elden Ring)
-    // =========================================================================
+    /// In-game time in milliseconds, if the definition has a game time value
+    pub fn game_time_milliseconds(&self) -> Option<i64> {
+        let game_time = self.game_data.autosplitter.game_time.as_ref()?;
+        let igt = self.read_value(game_time)? * game_time.scale;
+        igt.is_finite().then_some(igt as i64)
+    }
 
-    fn read_ac6_event_flag(&self, event_flag_id: u32) -> bool {
-        // AC6 uses the same virtual memory flag system as Elden Ring
-        self.read_elden_ring_event_flag(event_flag_id)
+    /// Whether the game is loading, if the definition has a loading value
+    pub fn is_loading(&self) -> Option<bool> {
+        let loading = self.game_data.autosplitter.loading.as_ref()?;
+        let value = self.read_value(loading)?;
+        Some(match loading.equals {
+            Some(equals) => value == equals as f64,
+            None => value != 0.0,
+        })
+    }
+
+    pub fn read_attribute_by_name(&self, name: &str) -> Option<i64> {
+        match name {
+            "igt" => self.game_time_milliseconds(),
+            "loading" => self.is_loading().map(|loading| loading as i64),
+            _ => None,
+        }
     }
 }
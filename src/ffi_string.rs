@@ -0,0 +1,121 @@
+//! FFI string helpers
+//!
+//! Converts Rust strings for C consumers without panicking on interior NULs,
+//! remembers the last error message for `autosplitter_last_error`, and builds
+//! NUL-terminated UTF-16 copies for Windows hosts that use wide strings.
+
+use once_cell::sync::Lazy;
+use std::ffi::CString;
+use std::os::raw::c_char;
+use std::sync::Mutex;
+
+static LAST_ERROR: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// Replace interior NULs so the string survives C string conversion
+pub(crate) fn sanitize(s: &str) -> String {
+    s.replace('\0', "\u{FFFD}")
+}
+
+/// Convert to an owned C string (free with `autosplitter_free_string`)
+pub(crate) fn to_c_string(s: impl AsRef<str>) -> *mut c_char {
+    match CString::new(sanitize(s.as_ref())) {
+        Ok(c_string) => c_string.into_raw(),
+        // Unreachable after sanitizing, but never panic across the FFI boundary
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/// Record an error as the last error and return it as an owned C string
+pub(crate) fn error(message: impl AsRef<str>) -> *mut c_char {
+    set_last_error(message.as_ref());
+    to_c_string(message)
+}
+
+pub(crate) fn set_last_error(message: &str) {
+    log::debug!("FFI error: {}", message);
+    *LAST_ERROR.lock().unwrap() = Some(sanitize(message));
+}
+
+/// Most recent error reported through the FFI, if any
+pub(crate) fn last_error() -> Option<String> {
+    LAST_ERROR.lock().unwrap().clone()
+}
+
+pub(crate) fn clear_last_error() {
+    *LAST_ERROR.lock().unwrap() = None;
+}
+
+fn encode_utf16(s: &str) -> Vec<u16> {
+    sanitize(s).encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Convert to an owned NUL-terminated UTF-16 string (free with `free_utf16`)
+pub(crate) fn to_utf16(s: impl AsRef<str>) -> *mut u16 {
+    let boxed: Box<[u16]> = encode_utf16(s.as_ref()).into_boxed_slice();
+    Box::into_raw(boxed) as *mut u16
+}
+
+/// Free a string created by `to_utf16`
+///
+/// # Safety
+/// `ptr` must be null or a pointer returned by `to_utf16` that was not freed yet
+pub(crate) unsafe fn free_utf16(ptr: *mut u16) {
+    if ptr.is_null() {
+        return;
+    }
+
+    // Sanitized strings contain no interior NULs, so the first 0 is the terminator
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+
+    let slice = std::ptr::slice_from_raw_parts_mut(ptr, len + 1);
+    drop(Box::from_raw(slice));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn test_to_c_string_with_nul() {
+        let ptr = to_c_string("bad\0name");
+        assert!(!ptr.is_null());
+
+        let c_string = unsafe { CString::from_raw(ptr) };
+        assert_eq!(c_string.to_str().unwrap(), "bad\u{FFFD}name");
+    }
+
+    #[test]
+    fn test_error_sets_last_error() {
+        let ptr = error("Autosplitter not initialized");
+        let message = unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() };
+        unsafe { drop(CString::from_raw(ptr)) };
+
+        assert_eq!(message, "Autosplitter not initialized");
+        assert!(last_error().is_some());
+    }
+
+    #[test]
+    fn test_encode_utf16() {
+        let encoded = encode_utf16("Ωa\0");
+        assert_eq!(encoded, vec![0x03A9, 0x61, 0xFFFD, 0]);
+    }
+
+    #[test]
+    fn test_utf16_round_trip() {
+        let ptr = to_utf16("Elden Ring – ボス");
+        let decoded = unsafe {
+            let mut len = 0;
+            while *ptr.add(len) != 0 {
+                len += 1;
+            }
+            String::from_utf16(std::slice::from_raw_parts(ptr, len)).unwrap()
+        };
+        unsafe { free_utf16(ptr) };
+
+        assert_eq!(decoded, "Elden Ring – ボス");
+    }
+}
@@ -0,0 +1,179 @@
+//! Event flag history
+//!
+//! Keeps the last N seconds of every watched flag (boss flags, custom trigger
+//! and timing rule flags) as one bitset per tick. When a runner reports a
+//! missed or early split, the history shows tick by tick what the reader saw
+//! around the incident: a flag that flickered, one that was set before the
+//! boss was reached, or one that never changed at all.
+//!
+//! The watched set only changes when splits are reconfigured; each change
+//! starts a new segment with its own flag list so older ticks stay readable.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// Flag values on one tick
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct FlagHistoryTick {
+    /// Milliseconds since the run log was started
+    pub elapsed_ms: u64,
+    #[serde(default)]
+    pub igt_ms: Option<i64>,
+    /// One character per flag of the segment, in order: '1' set, '0' unset
+    pub values: String,
+}
+
+/// Consecutive ticks with the same watched flags
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct FlagHistorySegment {
+    pub flags: Vec<u32>,
+    pub ticks: Vec<FlagHistoryTick>,
+}
+
+#[derive(Debug)]
+struct Frame {
+    elapsed_ms: u64,
+    igt_ms: Option<i64>,
+    flags: Arc<Vec<u32>>,
+    bits: Vec<u64>,
+}
+
+/// Rolling per-tick flag values
+#[derive(Debug, Default)]
+pub struct FlagHistory {
+    frames: VecDeque<Frame>,
+}
+
+impl FlagHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record one tick; `flags` is the sorted watched list. Ticks older than
+    /// `window_ms` are dropped (0 disables the history).
+    pub fn record(
+        &mut self,
+        elapsed_ms: u64,
+        igt_ms: Option<i64>,
+        flags: &[u32],
+        values: &HashMap<u32, bool>,
+        window_ms: u64,
+    ) {
+        if window_ms == 0 {
+            self.frames.clear();
+            return;
+        }
+
+        // Share the flag list with the previous frame while it is unchanged
+        let flags = match self.frames.back() {
+            Some(last) if last.flags.as_slice() == flags => last.flags.clone(),
+            _ => Arc::new(flags.to_vec()),
+        };
+        let mut bits = vec![0u64; flags.len().div_ceil(64)];
+        for (i, flag_id) in flags.iter().enumerate() {
+            if values.get(flag_id).copied().unwrap_or(false) {
+                bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+        self.frames.push_back(Frame { elapsed_ms, igt_ms, flags, bits });
+
+        let cutoff = elapsed_ms.saturating_sub(window_ms);
+        while self.frames.front().is_some_and(|f| f.elapsed_ms < cutoff) {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Recorded ticks, oldest first, grouped by watched flags
+    pub fn segments(&self) -> Vec<FlagHistorySegment> {
+        let mut segments: Vec<FlagHistorySegment> = Vec::new();
+        let mut current: Option<&Arc<Vec<u32>>> = None;
+        for frame in &self.frames {
+            if current.is_none_or(|flags| !Arc::ptr_eq(flags, &frame.flags)) {
+                segments.push(FlagHistorySegment {
+                    flags: frame.flags.to_vec(),
+                    ticks: Vec::new(),
+                });
+                current = Some(&frame.flags);
+            }
+            let values = (0..frame.flags.len())
+                .map(|i| if frame.bits[i / 64] & (1 << (i % 64)) != 0 { '1' } else { '0' })
+                .collect();
+            segments.last_mut().unwrap().ticks.push(FlagHistoryTick {
+                elapsed_ms: frame.elapsed_ms,
+                igt_ms: frame.igt_ms,
+                values,
+            });
+        }
+        segments
+    }
+
+    /// Export the history as JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.segments()).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(set: &[u32]) -> HashMap<u32, bool> {
+        set.iter().map(|&f| (f, true)).collect()
+    }
+
+    #[test]
+    fn test_record_and_window() {
+        let mut history = FlagHistory::new();
+        let flags = [13000800, 13000890];
+        for tick in 0..50u64 {
+            let set: &[u32] = if tick >= 45 { &[13000800] } else { &[] };
+            history.record(tick * 100, Some(tick as i64 * 100), &flags, &values(set), 1000);
+        }
+
+        let segments = history.segments();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].flags, flags);
+        // 3900..=4900 are within one second of the last tick
+        assert_eq!(segments[0].ticks.len(), 11);
+        assert_eq!(segments[0].ticks[0].elapsed_ms, 3900);
+        assert_eq!(segments[0].ticks[5].values, "00");
+        assert_eq!(segments[0].ticks[6].values, "10");
+    }
+
+    #[test]
+    fn test_segments_follow_flag_changes() {
+        let mut history = FlagHistory::new();
+        history.record(0, None, &[1, 2], &values(&[2]), 60_000);
+        history.record(100, None, &[1, 2], &values(&[2]), 60_000);
+        history.record(200, None, &[1, 2, 3], &values(&[3]), 60_000);
+
+        let segments = history.segments();
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].ticks.len(), 2);
+        assert_eq!(segments[0].ticks[1].values, "01");
+        assert_eq!(segments[1].flags, [1, 2, 3]);
+        assert_eq!(segments[1].ticks[0].values, "001");
+
+        history.record(300, None, &[1], &values(&[]), 0);
+        assert!(history.segments().is_empty());
+    }
+
+    #[test]
+    fn test_many_flags() {
+        let mut history = FlagHistory::new();
+        let flags: Vec<u32> = (0..130).collect();
+        history.record(0, None, &flags, &values(&[0, 64, 129]), 1000);
+
+        let tick = &history.segments()[0].ticks[0];
+        assert_eq!(tick.values.len(), 130);
+        let set: Vec<usize> = tick.values.match_indices('1').map(|(i, _)| i).collect();
+        assert_eq!(set, [0, 64, 129]);
+    }
+}
@@ -0,0 +1,215 @@
+//! Event flag id calculators
+//!
+//! Flag ids are not opaque: each engine derives the memory location from the
+//! decimal digits. A mistyped id (130000800 for 13000800) reads a location
+//! that never changes, so the split silently never fires. These helpers split
+//! ids into their parts, build ids from parts and reject ids that cannot exist
+//! in a game; `GameData::validate` runs them over boss and trigger flags.
+//!
+//! Dark Souls 3 ids are `G AA B S III`: group, map area, block, section and
+//! index. Areas 90 and above (and area 0 with block 0) are global flags;
+//! other areas are looked up in the loaded world, so they must be real maps.
+//!
+//! Elden Ring ids are read as `category * 1000 + index`, and their digits
+//! follow the map layout: global flags below 10,000,000, legacy dungeons as
+//! `AA BB NNNN` (map `mAA_BB`) and the open world as `M L XX YY NNNN`
+//! (map `m6M` at tile level L, grid `XX`/`YY`).
+
+use crate::engine::EngineType;
+use serde::{Deserialize, Serialize};
+
+/// Dark Souls 3 map areas that own world-block flags (m30 to m51)
+const DS3_AREAS: &[u8] = &[30, 31, 32, 33, 34, 35, 37, 38, 39, 40, 41, 45, 46, 47, 50, 51, 52];
+
+/// Dark Souls 3 flag id parts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Ds3FlagId {
+    pub group: u8,
+    pub area: u8,
+    pub block: u8,
+    pub section: u8,
+    pub index: u16,
+}
+
+impl Ds3FlagId {
+    /// Split and validate a flag id
+    pub fn decompose(flag_id: u32) -> Result<Self, String> {
+        if flag_id >= 100_000_000 {
+            return Err(format!(
+                "DS3 flag ids have at most 8 digits, {} has {}",
+                flag_id,
+                flag_id.to_string().len()
+            ));
+        }
+
+        let id = Self {
+            group: (flag_id / 10_000_000) as u8,
+            area: ((flag_id / 100_000) % 100) as u8,
+            block: ((flag_id / 10_000) % 10) as u8,
+            section: ((flag_id / 1_000) % 10) as u8,
+            index: (flag_id % 1_000) as u16,
+        };
+        if !id.is_global() && !DS3_AREAS.contains(&id.area) {
+            return Err(format!("Area {} of flag {} is not a DS3 map", id.area, flag_id));
+        }
+        Ok(id)
+    }
+
+    /// Build a flag id from its parts
+    pub fn compose(&self) -> Result<u32, String> {
+        if self.group > 9 || self.area > 99 || self.block > 9 || self.section > 9 || self.index > 999 {
+            return Err(format!("DS3 flag part out of range: {:?}", self));
+        }
+        let flag_id = self.group as u32 * 10_000_000
+            + self.area as u32 * 100_000
+            + self.block as u32 * 10_000
+            + self.section as u32 * 1_000
+            + self.index as u32;
+        Self::decompose(flag_id).map(|_| flag_id)
+    }
+
+    /// Global flags are stored outside the world blocks
+    pub fn is_global(&self) -> bool {
+        self.area >= 90 || (self.area == 0 && self.block == 0)
+    }
+}
+
+/// Elden Ring flag id parts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum ErFlagId {
+    /// Global flag (below 10,000,000)
+    Global { id: u32 },
+    /// Legacy dungeon flag of map `mAA_BB`
+    Dungeon { area: u8, block: u8, local: u16 },
+    /// Open world flag of map `m60` (base game) or `m61` (Shadow of the
+    /// Erdtree) at tile level `level`, grid `x`/`y`
+    Overworld { map: u8, level: u8, x: u8, y: u8, local: u16 },
+}
+
+impl ErFlagId {
+    /// Split and validate a flag id
+    pub fn decompose(flag_id: u32) -> Result<Self, String> {
+        let local = (flag_id % 10_000) as u16;
+        match flag_id {
+            0..=9_999_999 => Ok(ErFlagId::Global { id: flag_id }),
+            10_000_000..=99_999_999 => Ok(ErFlagId::Dungeon {
+                area: (flag_id / 1_000_000) as u8,
+                block: ((flag_id / 10_000) % 100) as u8,
+                local,
+            }),
+            100_000_000..=999_999_999 => Err(format!(
+                "ER flag ids have 8 digits (legacy dungeons) or 10 (open world), {} has 9",
+                flag_id
+            )),
+            _ => {
+                let prefix = flag_id / 100_000_000;
+                let (map, level) = (60 + prefix / 10 - 1, prefix % 10);
+                if !(1..=2).contains(&(prefix / 10)) || level > 2 {
+                    return Err(format!(
+                        "Open world flag {} must start with 10-12 (m60) or 20-22 (m61)",
+                        flag_id
+                    ));
+                }
+                Ok(ErFlagId::Overworld {
+                    map: map as u8,
+                    level: level as u8,
+                    x: ((flag_id / 1_000_000) % 100) as u8,
+                    y: ((flag_id / 10_000) % 100) as u8,
+                    local,
+                })
+            }
+        }
+    }
+
+    /// Build a flag id from its parts
+    pub fn compose(&self) -> Result<u32, String> {
+        let flag_id = match *self {
+            ErFlagId::Global { id } => id as u64,
+            ErFlagId::Dungeon { area, block, local } => {
+                if !(10..=99).contains(&area) || block > 99 || local > 9_999 {
+                    return Err(format!("ER flag part out of range: {:?}", self));
+                }
+                area as u64 * 1_000_000 + block as u64 * 10_000 + local as u64
+            }
+            ErFlagId::Overworld { map, level, x, y, local } => {
+                if !(60..=61).contains(&map) || level > 2 || x > 99 || y > 99 || local > 9_999 {
+                    return Err(format!("ER flag part out of range: {:?}", self));
+                }
+                ((map as u64 - 59) * 10 + level as u64) * 100_000_000
+                    + x as u64 * 1_000_000
+                    + y as u64 * 10_000
+                    + local as u64
+            }
+        };
+        let flag_id = u32::try_from(flag_id).map_err(|_| format!("ER flag id out of range: {:?}", self))?;
+        Self::decompose(flag_id).map(|_| flag_id)
+    }
+
+    /// Flag tree category and index (the game's divisor is 1000)
+    pub fn category_and_index(flag_id: u32) -> (u32, u32) {
+        (flag_id / 1_000, flag_id % 1_000)
+    }
+}
+
+/// Check that a flag id can exist for an engine; engines without a known
+/// id scheme accept every id
+pub fn validate(engine: EngineType, flag_id: u32) -> Result<(), String> {
+    match engine {
+        EngineType::Ds3 => Ds3FlagId::decompose(flag_id).map(|_| ()),
+        EngineType::EldenRing => ErFlagId::decompose(flag_id).map(|_| ()),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ds3_decompose_and_compose() {
+        let vordt = Ds3FlagId::decompose(13000800).unwrap();
+        assert_eq!(
+            vordt,
+            Ds3FlagId { group: 1, area: 30, block: 0, section: 0, index: 800 }
+        );
+        assert_eq!(vordt.compose(), Ok(13000800));
+        assert!(Ds3FlagId::decompose(50000000).unwrap().is_global());
+        assert!(Ds3FlagId::decompose(6950).unwrap().is_global());
+    }
+
+    #[test]
+    fn test_ds3_rejects_impossible_ids() {
+        // Extra zero typo
+        assert!(Ds3FlagId::decompose(130000800).unwrap_err().contains("8 digits"));
+        // Area 36 is not a map
+        assert!(Ds3FlagId::decompose(13600800).unwrap_err().contains("not a DS3 map"));
+        assert!(Ds3FlagId { group: 1, area: 30, block: 0, section: 0, index: 1000 }.compose().is_err());
+    }
+
+    #[test]
+    fn test_er_decompose_and_compose() {
+        let godrick = ErFlagId::decompose(10000800).unwrap();
+        assert_eq!(godrick, ErFlagId::Dungeon { area: 10, block: 0, local: 800 });
+        assert_eq!(godrick.compose(), Ok(10000800));
+
+        let overworld = ErFlagId::decompose(1042380800).unwrap();
+        assert_eq!(overworld, ErFlagId::Overworld { map: 60, level: 0, x: 42, y: 38, local: 800 });
+        assert_eq!(overworld.compose(), Ok(1042380800));
+
+        let dlc = ErFlagId::decompose(2044450800).unwrap();
+        assert!(matches!(dlc, ErFlagId::Overworld { map: 61, x: 44, y: 45, .. }));
+        assert_eq!(ErFlagId::category_and_index(1042380800), (1042380, 800));
+    }
+
+    #[test]
+    fn test_er_rejects_impossible_ids() {
+        assert!(ErFlagId::decompose(100000800).unwrap_err().contains("has 9"));
+        assert!(ErFlagId::decompose(3042380800).is_err());
+        assert!(validate(EngineType::EldenRing, 1042380800).is_ok());
+        // Other engines accept anything
+        assert!(validate(EngineType::Ds2Sotfs, 130000800).is_ok());
+    }
+}
@@ -0,0 +1,103 @@
+//! Frame rate measurement
+//!
+//! Dark Souls 3 and Elden Ring count the frames they present (the
+//! `frame_count` attribute). Sampling the counter once per tick and dividing
+//! the frames by the time between samples gives the game's own frame rate,
+//! independent of the autosplitter's tick rate. The state reports it averaged
+//! over [`FRAME_RATE_WINDOW_MS`] together with the lowest and highest average
+//! seen since attaching: a `max_fps` well above 60 in a category that runs at
+//! the 60 fps cap shows that a frame rate unlocker was active.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Time covered by the rolling average
+pub const FRAME_RATE_WINDOW_MS: u64 = 1000;
+
+/// Frame rate of the attached game
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct FrameRate {
+    /// Frames per second over the last `FRAME_RATE_WINDOW_MS`
+    pub fps: f64,
+    /// Lowest rolling average since attaching
+    pub min_fps: f64,
+    /// Highest rolling average since attaching
+    pub max_fps: f64,
+}
+
+/// Per-attach sampler of the frame counter
+#[derive(Debug, Default)]
+pub struct FrameRateMeter {
+    /// (elapsed_ms, frame count) samples, oldest first
+    samples: VecDeque<(u64, u32)>,
+    extremes: Option<(f64, f64)>,
+}
+
+impl FrameRateMeter {
+    /// Sample the frame counter; returns the frame rate once the samples span
+    /// a full window
+    pub fn observe(&mut self, frame_count: u32, elapsed_ms: u64) -> Option<FrameRate> {
+        // The counter restarts with the game (or a different game was attached)
+        if self.samples.back().is_some_and(|&(_, last)| frame_count < last) {
+            self.samples.clear();
+        }
+        self.samples.push_back((elapsed_ms, frame_count));
+        while self
+            .samples
+            .get(1)
+            .is_some_and(|&(ms, _)| elapsed_ms.saturating_sub(ms) >= FRAME_RATE_WINDOW_MS)
+        {
+            self.samples.pop_front();
+        }
+
+        let &(first_ms, first_count) = self.samples.front()?;
+        let span_ms = elapsed_ms.saturating_sub(first_ms);
+        if span_ms < FRAME_RATE_WINDOW_MS {
+            return None;
+        }
+        let fps = (frame_count - first_count) as f64 * 1000.0 / span_ms as f64;
+
+        let (min_fps, max_fps) = match self.extremes {
+            Some((min, max)) => (min.min(fps), max.max(fps)),
+            None => (fps, fps),
+        };
+        self.extremes = Some((min_fps, max_fps));
+        Some(FrameRate { fps, min_fps, max_fps })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_average() {
+        let mut meter = FrameRateMeter::default();
+        // 60 fps sampled every 100 ms
+        for tick in 0..10u64 {
+            assert_eq!(meter.observe(tick as u32 * 6, tick * 100), None);
+        }
+        let rate = meter.observe(60, 1000).unwrap();
+        assert_eq!(rate.fps, 60.0);
+
+        // Unlocked to 120 fps for a full window
+        let mut rate = rate;
+        for tick in 11..=20u32 {
+            rate = meter.observe(60 + (tick - 10) * 12, tick as u64 * 100).unwrap();
+        }
+        assert_eq!(rate.fps, 120.0);
+        assert_eq!(rate.min_fps, 60.0);
+        assert_eq!(rate.max_fps, 120.0);
+    }
+
+    #[test]
+    fn test_counter_restart() {
+        let mut meter = FrameRateMeter::default();
+        meter.observe(1000, 0);
+        assert!(meter.observe(1030, 1000).is_some());
+        // Game restarted: wait for a new full window
+        assert_eq!(meter.observe(5, 1100), None);
+        assert_eq!(meter.observe(35, 2100).unwrap().fps, 30.0);
+    }
+}
@@ -6,11 +6,18 @@
 //! - Community-contributed game definitions
 //! - Custom presets with special fields (like DS2 kill counts)
 
+use crate::config::{BossFlag, CustomTrigger, SplitMetadata};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Markdown reference of this schema, generated from the structs (see
+/// `docgen`; requires the `typescript` feature)
+#[cfg(feature = "typescript")]
+pub use crate::docgen::generate as docgen;
+
 /// Root game data structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct GameData {
     pub game: GameInfo,
     pub autosplitter: AutosplitterConfig,
@@ -22,10 +29,15 @@ pub struct GameData {
     pub custom_fields: HashMap<String, CustomFieldDefinition>,
     #[serde(default)]
     pub attributes: Vec<AttributeDefinition>,
+    /// Custom split triggers (event flags, flag sets, attributes); replace the
+    /// host's triggers when the autosplitter is started with this game data
+    #[serde(default)]
+    pub triggers: Vec<CustomTrigger>,
 }
 
 /// Basic game information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct GameInfo {
     pub id: String,
     pub name: String,
@@ -36,6 +48,7 @@ pub struct GameInfo {
 
 /// Autosplitter configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct AutosplitterConfig {
     /// Engine type determines the reading algorithm
     /// Supported: "ds1_ptde", "ds1_remaster", "ds2_sotfs", "ds3", "elden_ring", "sekiro", "ac6"
@@ -46,10 +59,19 @@ pub struct AutosplitterConfig {
     /// Pointer chains for accessing game data
     #[serde(default)]
     pub pointers: HashMap<String, PointerDefinition>,
+    /// In-game time of the generic engine, scaled to milliseconds (e.g., from
+    /// an ASL `gameTime` block)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub game_time: Option<ValueDefinition>,
+    /// Loading flag of the generic engine; time while it is set counts as
+    /// paused (e.g., from an ASL `isLoading` block)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loading: Option<ValueDefinition>,
 }
 
 /// Memory pattern definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct PatternDefinition {
     pub name: String,
     pub pattern: String,
@@ -70,6 +92,7 @@ fn default_resolve() -> String {
 
 /// Pointer chain definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct PointerDefinition {
     /// Pattern name to use as base
     pub pattern: String,
@@ -78,8 +101,41 @@ pub struct PointerDefinition {
     pub offsets: Vec<i64>,
 }
 
+/// Value read through a pointer chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ValueDefinition {
+    /// Pointer name (key in `pointers`)
+    pub pointer: String,
+    /// Offset of the value from the end of the chain
+    #[serde(default)]
+    pub offset: i64,
+    /// Value type: "u8", "i16", "u16", "i32", "u32", "i64", "u64", "f32", "f64"
+    #[serde(rename = "type", default = "default_value_type")]
+    pub value_type: String,
+    /// Game time: factor to milliseconds (e.g., 1000 for seconds)
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+    /// Loading flags: set when the value equals this (any non-zero value
+    /// when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals: Option<i64>,
+}
+
+/// Value types a `ValueDefinition` can read
+pub const VALUE_TYPES: &[&str] = &["u8", "i16", "u16", "i32", "u32", "i64", "u64", "f32", "f64"];
+
+fn default_value_type() -> String {
+    "i32".to_string()
+}
+
+fn default_scale() -> f64 {
+    1.0
+}
+
 /// Boss definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct BossDefinition {
     pub id: String,
     pub name: String,
@@ -91,10 +147,14 @@ pub struct BossDefinition {
     /// Custom field values for this boss
     #[serde(default)]
     pub custom: HashMap<String, serde_json::Value>,
+    /// Free-form host metadata passed through to `BossFlag::metadata`
+    #[serde(default, skip_serializing_if = "SplitMetadata::is_empty")]
+    pub metadata: SplitMetadata,
 }
 
 /// Preset definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct PresetDefinition {
     pub id: String,
     pub name: String,
@@ -112,6 +172,7 @@ pub struct PresetDefinition {
 
 /// Custom field definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct CustomFieldDefinition {
     /// Field type: "integer", "boolean", "string", "select"
     #[serde(rename = "type")]
@@ -137,6 +198,7 @@ fn default_applies_to() -> String {
 
 /// Option for select-type fields
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct SelectOption {
     pub value: String,
     pub label: String,
@@ -144,6 +206,7 @@ pub struct SelectOption {
 
 /// Character attribute definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
 pub struct AttributeDefinition {
     pub id: String,
     pub name: String,
@@ -163,6 +226,74 @@ impl GameData {
         Ok(Self::from_toml(&content)?)
     }
 
+    /// Validate the definition before it is handed to the generic engine
+    ///
+    /// Checks that the engine is known (built in or registered), that every pattern parses to a
+    /// non-empty byte sequence, that pointers only reference patterns
+    /// defined in the same file, that game time and loading values read
+    /// through known pointers, that trigger flag sets are well formed and
+    /// that boss and trigger flag ids fit the engine's id scheme.
+    pub fn validate(&self) -> Result<(), String> {
+        let Some(engine) = crate::engine::EngineType::resolve(&self.autosplitter.engine) else {
+            return Err(format!("Unknown engine type: {}", self.autosplitter.engine));
+        };
+
+        for pattern_def in &self.autosplitter.patterns {
+            let invalid = pattern_def
+                .pattern
+                .split_whitespace()
+                .filter(|s| *s != "?" && *s != "??")
+                .any(|s| u8::from_str_radix(s, 16).is_err());
+            if invalid {
+                return Err(format!("Pattern '{}' contains invalid bytes", pattern_def.name));
+            }
+            let parsed = crate::memory::parse_pattern(&pattern_def.pattern);
+            if parsed.iter().all(|b| b.is_none()) {
+                return Err(format!("Pattern '{}' has no fixed bytes", pattern_def.name));
+            }
+        }
+
+        for (name, pointer_def) in &self.autosplitter.pointers {
+            if self.get_pattern(&pointer_def.pattern).is_none() {
+                return Err(format!(
+                    "Pointer '{}' references unknown pattern '{}'",
+                    name, pointer_def.pattern
+                ));
+            }
+        }
+
+        let values = [("game_time", &self.autosplitter.game_time), ("loading", &self.autosplitter.loading)];
+        for (name, value) in values {
+            let Some(value) = value else {
+                continue;
+            };
+            if !self.autosplitter.pointers.contains_key(&value.pointer) {
+                return Err(format!("{} references unknown pointer '{}'", name, value.pointer));
+            }
+            if !VALUE_TYPES.contains(&value.value_type.as_str()) {
+                return Err(format!("{} has unknown value type '{}'", name, value.value_type));
+            }
+            if !value.scale.is_finite() || value.scale <= 0.0 {
+                return Err(format!("{} scale must be positive", name));
+            }
+        }
+
+        for boss in &self.bosses {
+            crate::flag_ids::validate(engine, boss.flag_id)
+                .map_err(|e| format!("Boss '{}': {}", boss.id, e))?;
+        }
+
+        for trigger in &self.triggers {
+            trigger.validate()?;
+            for flag_id in trigger.condition.flag_ids() {
+                crate::flag_ids::validate(engine, flag_id)
+                    .map_err(|e| format!("Trigger '{}': {}", trigger.id, e))?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get a boss by ID
     pub fn get_boss(&self, id: &str) -> Option<&BossDefinition> {
         self.bosses.iter().find(|b| b.id == id)
@@ -195,6 +326,16 @@ impl GameData {
             .unwrap_or_default()
     }
 
+    /// Copy boss metadata from the definitions into boss flags that carry
+    /// none of their own (matched by boss id)
+    pub fn fill_boss_metadata(&self, boss_flags: &mut [BossFlag]) {
+        for flag in boss_flags.iter_mut().filter(|f| f.metadata.is_empty()) {
+            if let Some(boss) = self.get_boss(&flag.boss_id) {
+                flag.metadata = boss.metadata.clone();
+            }
+        }
+    }
+
     /// Get custom field value for a boss in a preset
     pub fn get_boss_custom_value(
         &self,
@@ -230,6 +371,7 @@ impl GameData {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{FlagSetMode, TriggerCondition};
 
     fn create_test_game_data() -> GameData {
         let toml = r#"
@@ -624,6 +766,157 @@ pattern = "48 89"
         assert_eq!(pattern.extra_offset, 0);
     }
 
+    #[test]
+    fn test_validate_ok() {
+        let data = create_test_game_data();
+        assert!(data.validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_unknown_engine() {
+        let mut data = create_test_game_data();
+        data.autosplitter.engine = "generic".to_string();
+
+        let err = data.validate().unwrap_err();
+        assert!(err.contains("Unknown engine type"));
+    }
+
+    #[test]
+    fn test_validate_bad_pattern() {
+        let mut data = create_test_game_data();
+        data.autosplitter.patterns[0].pattern = "48 zz ?".to_string();
+        assert!(data.validate().unwrap_err().contains("invalid bytes"));
+
+        data.autosplitter.patterns[0].pattern = "? ? ?".to_string();
+        assert!(data.validate().unwrap_err().contains("no fixed bytes"));
+    }
+
+    #[test]
+    fn test_validate_dangling_pointer() {
+        let mut data = create_test_game_data();
+        data.autosplitter.pointers.get_mut("player").unwrap().pattern = "missing".to_string();
+
+        let err = data.validate().unwrap_err();
+        assert!(err.contains("unknown pattern 'missing'"));
+    }
+
+    #[test]
+    fn test_validate_game_time() {
+        let mut data = create_test_game_data();
+        let mut game_time: ValueDefinition =
+            toml::from_str("pointer = \"player\"\noffset = 0xA4\ntype = \"f32\"\nscale = 1000.0").unwrap();
+        data.autosplitter.game_time = Some(game_time.clone());
+        assert!(data.validate().is_ok());
+
+        game_time.value_type = "f16".to_string();
+        data.autosplitter.game_time = Some(game_time.clone());
+        assert!(data.validate().unwrap_err().contains("unknown value type 'f16'"));
+
+        game_time.pointer = "missing".to_string();
+        data.autosplitter.game_time = Some(game_time);
+        assert!(data.validate().unwrap_err().contains("unknown pointer 'missing'"));
+    }
+
+    #[test]
+    fn test_validate_impossible_flag_id() {
+        let mut data = create_test_game_data();
+        data.bosses[0].flag_id = 130000800;
+
+        let err = data.validate().unwrap_err();
+        assert!(err.starts_with(&format!("Boss '{}'", data.bosses[0].id)));
+        assert!(err.contains("8 digits"));
+
+        // Kill counter offsets are not flag ids
+        data.autosplitter.engine = "ds2_sotfs".to_string();
+        assert!(data.validate().is_ok());
+    }
+
+    #[test]
+    fn test_flag_set_triggers_from_toml() {
+        let toml = r#"
+[game]
+id = "test"
+name = "Test Game"
+process_names = ["test.exe"]
+
+[autosplitter]
+engine = "ds3"
+
+[[triggers]]
+id = "any_lord"
+name = "Any Lord of Cinder"
+condition = { type = "flag_set", flags = [14100800, 13800800, 13700800, 15000800], mode = "any" }
+
+[[triggers]]
+id = "seeds"
+name = "20 seeds"
+condition = { type = "flag_set", range = { first = 50000000, last = 50000029 }, mode = { at_least = 20 } }
+"#;
+
+        let mut data = GameData::from_toml(toml).unwrap();
+        assert_eq!(data.triggers.len(), 2);
+        assert_eq!(data.triggers[1].condition.flag_ids().len(), 30);
+        assert!(data.validate().is_ok());
+
+        data.triggers[1].condition = TriggerCondition::FlagSet {
+            flags: Vec::new(),
+            range: None,
+            mode: FlagSetMode::All,
+        };
+        assert!(data.validate().unwrap_err().contains("Trigger 'seeds'"));
+    }
+
+    #[test]
+    fn test_boss_metadata_passthrough() {
+        let toml = r##"
+[game]
+id = "test"
+name = "Test Game"
+process_names = ["test.exe"]
+
+[autosplitter]
+engine = "ds3"
+
+[[bosses]]
+id = "vordt"
+name = "Vordt of the Boreal Valley"
+flag_id = 13000800
+metadata = { icon = "icons/vordt.png", color = "#4fa3ff", order = 2 }
+"##;
+
+        let data = GameData::from_toml(toml).unwrap();
+        let mut flags = vec![
+            BossFlag {
+                boss_id: "vordt".to_string(),
+                boss_name: "Vordt".to_string(),
+                flag_id: 13000800,
+                is_dlc: false,
+                metadata: Default::default(),
+                group: None,
+                priority: 0,
+            },
+            BossFlag {
+                boss_id: "unknown".to_string(),
+                boss_name: "Unknown".to_string(),
+                flag_id: 1,
+                is_dlc: false,
+                metadata: Default::default(),
+                group: None,
+                priority: 0,
+            },
+        ];
+        data.fill_boss_metadata(&mut flags);
+
+        assert_eq!(flags[0].metadata["icon"], "icons/vordt.png");
+        assert_eq!(flags[0].metadata["order"], 2);
+        assert!(flags[1].metadata.is_empty());
+
+        // Preserved verbatim in JSON; omitted when empty
+        let json = serde_json::to_string(&flags).unwrap();
+        assert!(json.contains(r##""metadata":{"color":"#4fa3ff","icon":"icons/vordt.png","order":2}"##));
+        assert_eq!(json.matches("metadata").count(), 1);
+    }
+
     #[test]
     fn test_invalid_toml() {
         let toml = "invalid toml {{{";
@@ -3,6 +3,8 @@
 //!
 //! Uses CSEventFlagMan with a tree-based structure similar to Elden Ring
 
+#[cfg(target_os = "windows")]
+use std::cell::RefCell;
 #[cfg(target_os = "windows")]
 use windows::Win32::Foundation::HANDLE;
 
@@ -19,6 +21,73 @@ pub const FD4_TIME_PATTERN: &str = "48 8b 0d ? ? ? ? 0f 28 c8 f3 0f 59 0d";
 #[cfg(target_os = "windows")]
 pub const CS_MENU_MAN_PATTERN: &str = "48 8b 35 ? ? ? ? 33 db 89 5c 24 20";
 
+// GameDataMan and the mission result layout below (last result at 0x4c0/0x4c4,
+// 0x10-byte records from 0x500) are not part of SoulSplitter, and no public
+// reference documents them; treat them as unverified. Every reader returns
+// "no result" when the pattern is not found.
+#[cfg(target_os = "windows")]
+pub const GAME_DATA_MAN_PATTERN: &str = "48 8b 05 ? ? ? ? 48 8b 48 08 48 85 c9 74 ? 8b 81";
+
+/// Number of mission records stored in the save (unverified, see above)
+#[cfg(target_os = "windows")]
+pub const MISSION_RECORD_COUNT: u32 = 64;
+
+/// Attributes polled into the autosplitter state every tick. The mission
+/// table ("s_rank_count", "mission_rank.<id>") is left out; it stays
+/// readable by name for triggers.
+#[cfg(target_os = "windows")]
+pub const ATTRIBUTE_NAMES: &[&str] = &[
+    "igt",
+    "last_mission_rank",
+    "last_mission_time_ms",
+];
+
+/// Mission clear rank as stored in the save (0 = not cleared)
+#[cfg(target_os = "windows")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(i32)]
+pub enum MissionRank {
+    None = 0,
+    D = 1,
+    C = 2,
+    B = 3,
+    A = 4,
+    S = 5,
+}
+
+#[cfg(target_os = "windows")]
+impl MissionRank {
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            1 => MissionRank::D,
+            2 => MissionRank::C,
+            3 => MissionRank::B,
+            4 => MissionRank::A,
+            5 => MissionRank::S,
+            _ => MissionRank::None,
+        }
+    }
+}
+
+/// Best result recorded for a mission
+#[cfg(target_os = "windows")]
+#[derive(Debug, Clone, Copy)]
+pub struct MissionRecord {
+    pub mission_id: u32,
+    pub rank: MissionRank,
+    pub clear_time_milliseconds: i32,
+}
+
+/// Mission records read at the last mission result
+#[cfg(target_os = "windows")]
+struct MissionTable {
+    /// Last mission (rank, clear time) when the records were read
+    last_result: (i32, i32),
+    /// A loading screen was seen since; the save may have changed
+    stale: bool,
+    records: Vec<MissionRecord>,
+}
+
 /// Armored Core 6 autosplitter state
 #[cfg(target_os = "windows")]
 pub struct ArmoredCore6 {
@@ -27,8 +96,10 @@ pub struct ArmoredCore6 {
     pub cs_event_flag_man: Pointer,
     pub fd4_time: Pointer,
     pub cs_menu_man: Pointer,
+    pub game_data_man: Pointer,
     // Derived pointers
     pub igt: Pointer,
+    mission_table: RefCell<Option<MissionTable>>,
 }
 
 #[cfg(target_os = "windows")]
@@ -39,7 +110,9 @@ impl ArmoredCore6 {
             cs_event_flag_man: Pointer::new(),
             fd4_time: Pointer::new(),
             cs_menu_man: Pointer::new(),
+            game_data_man: Pointer::new(),
             igt: Pointer::new(),
+            mission_table: RefCell::new(None),
         }
     }
 
@@ -86,6 +159,15 @@ impl ArmoredCore6 {
             }
         }
 
+        // Scan for GameDataMan (mission results)
+        let pattern = parse_pattern(GAME_DATA_MAN_PATTERN);
+        if let Some(found) = scan_pattern(handle, base, size, &pattern) {
+            if let Some(addr) = resolve_rip_relative(handle, found, 3, 7) {
+                self.game_data_man.initialize(handle, true, addr as i64, &[0x0, 0x0]);
+                log::info!("AC6: GameDataMan at 0x{:X}", addr);
+            }
+        }
+
         true
     }
 
@@ -171,6 +253,108 @@ impl ArmoredCore6 {
         }
         read_i32(self.handle, (addr + 0x8e4) as usize).unwrap_or(0) != 0
     }
+
+    /// Get the rank of the most recently completed mission
+    pub fn get_last_mission_rank(&self) -> MissionRank {
+        if self.game_data_man.is_null_ptr() {
+            return MissionRank::None;
+        }
+        MissionRank::from_i32(self.game_data_man.read_i32(Some(0x4c0)))
+    }
+
+    /// Get the completion time of the most recently completed mission
+    pub fn get_last_mission_time_milliseconds(&self) -> i32 {
+        if self.game_data_man.is_null_ptr() {
+            return 0;
+        }
+        self.game_data_man.read_i32(Some(0x4c4))
+    }
+
+    /// Get the best recorded result for the mission record at `index`
+    pub fn get_mission_record(&self, index: u32) -> Option<MissionRecord> {
+        if index >= MISSION_RECORD_COUNT || self.game_data_man.is_null_ptr() {
+            return None;
+        }
+
+        // Records are 0x10 bytes: mission id, rank, clear time, padding
+        let entry = 0x500 + (index as i64 * 0x10);
+        let mission_id = self.game_data_man.read_u32(Some(entry));
+        if mission_id == 0 {
+            return None;
+        }
+
+        Some(MissionRecord {
+            mission_id,
+            rank: MissionRank::from_i32(self.game_data_man.read_i32(Some(entry + 0x4))),
+            clear_time_milliseconds: self.game_data_man.read_i32(Some(entry + 0x8)),
+        })
+    }
+
+    /// All recorded mission results. The table only changes when a mission
+    /// is cleared (the last result changes) or another save is loaded (after
+    /// a loading screen), so it is read again only on those edges; during a
+    /// loading screen the previous table is kept.
+    pub fn mission_records(&self) -> Vec<MissionRecord> {
+        let loading = self.is_loading_screen_visible();
+        let last_result = (
+            self.get_last_mission_rank() as i32,
+            self.get_last_mission_time_milliseconds(),
+        );
+
+        let mut table = self.mission_table.borrow_mut();
+        if let Some(table) = table.as_mut() {
+            table.stale |= loading;
+            if loading || (!table.stale && table.last_result == last_result) {
+                return table.records.clone();
+            }
+        }
+
+        let records: Vec<MissionRecord> = (0..MISSION_RECORD_COUNT)
+            .filter_map(|index| self.get_mission_record(index))
+            .collect();
+        *table = Some(MissionTable {
+            last_result,
+            stale: false,
+            records: records.clone(),
+        });
+        records
+    }
+
+    /// Find the best recorded result for a mission id
+    pub fn find_mission_record(&self, mission_id: u32) -> Option<MissionRecord> {
+        self.mission_records()
+            .into_iter()
+            .find(|record| record.mission_id == mission_id)
+    }
+
+    /// Count missions cleared with an S rank
+    pub fn count_s_ranks(&self) -> u32 {
+        self.mission_records()
+            .iter()
+            .filter(|record| record.rank == MissionRank::S)
+            .count() as u32
+    }
+
+    /// Read a named attribute for custom triggers
+    /// Per-mission values use "mission_rank.<id>" and "mission_time_ms.<id>"
+    pub fn read_attribute_by_name(&self, name: &str) -> Option<i64> {
+        match name {
+            "igt" => Some(self.get_in_game_time_milliseconds() as i64),
+            "loading" => Some(self.is_loading_screen_visible() as i64),
+            "last_mission_rank" => Some(self.get_last_mission_rank() as i64),
+            "last_mission_time_ms" => Some(self.get_last_mission_time_milliseconds() as i64),
+            "s_rank_count" => Some(self.count_s_ranks() as i64),
+            _ => {
+                let (key, mission_id) = name.split_once('.')?;
+                let record = self.find_mission_record(mission_id.parse().ok()?);
+                match key {
+                    "mission_rank" => Some(record.map(|r| r.rank as i64).unwrap_or(0)),
+                    "mission_time_ms" => Some(record.map(|r| r.clear_time_milliseconds as i64).unwrap_or(0)),
+                    _ => None,
+                }
+            }
+        }
+    }
 }
 
 #[cfg(target_os = "windows")]
@@ -184,6 +368,9 @@ impl Default for ArmoredCore6 {
 // Linux Implementation (for Proton/Wine)
 // =============================================================================
 
+#[cfg(target_os = "linux")]
+use std::cell::RefCell;
+
 #[cfg(target_os = "linux")]
 use crate::memory::{parse_pattern, resolve_rip_relative, scan_pattern, read_i32, read_i64};
 #[cfg(target_os = "linux")]
@@ -197,6 +384,73 @@ pub const FD4_TIME_PATTERN: &str = "48 8b 0d ? ? ? ? 0f 28 c8 f3 0f 59 0d";
 #[cfg(target_os = "linux")]
 pub const CS_MENU_MAN_PATTERN: &str = "48 8b 35 ? ? ? ? 33 db 89 5c 24 20";
 
+// GameDataMan and the mission result layout below (last result at 0x4c0/0x4c4,
+// 0x10-byte records from 0x500) are not part of SoulSplitter, and no public
+// reference documents them; treat them as unverified. Every reader returns
+// "no result" when the pattern is not found.
+#[cfg(target_os = "linux")]
+pub const GAME_DATA_MAN_PATTERN: &str = "48 8b 05 ? ? ? ? 48 8b 48 08 48 85 c9 74 ? 8b 81";
+
+/// Number of mission records stored in the save (unverified, see above)
+#[cfg(target_os = "linux")]
+pub const MISSION_RECORD_COUNT: u32 = 64;
+
+/// Attributes polled into the autosplitter state every tick. The mission
+/// table ("s_rank_count", "mission_rank.<id>") is left out; it stays
+/// readable by name for triggers.
+#[cfg(target_os = "linux")]
+pub const ATTRIBUTE_NAMES: &[&str] = &[
+    "igt",
+    "last_mission_rank",
+    "last_mission_time_ms",
+];
+
+/// Mission clear rank as stored in the save (0 = not cleared)
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(i32)]
+pub enum MissionRank {
+    None = 0,
+    D = 1,
+    C = 2,
+    B = 3,
+    A = 4,
+    S = 5,
+}
+
+#[cfg(target_os = "linux")]
+impl MissionRank {
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            1 => MissionRank::D,
+            2 => MissionRank::C,
+            3 => MissionRank::B,
+            4 => MissionRank::A,
+            5 => MissionRank::S,
+            _ => MissionRank::None,
+        }
+    }
+}
+
+/// Best result recorded for a mission
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy)]
+pub struct MissionRecord {
+    pub mission_id: u32,
+    pub rank: MissionRank,
+    pub clear_time_milliseconds: i32,
+}
+
+/// Mission records read at the last mission result
+#[cfg(target_os = "linux")]
+struct MissionTable {
+    /// Last mission (rank, clear time) when the records were read
+    last_result: (i32, i32),
+    /// A loading screen was seen since; the save may have changed
+    stale: bool,
+    records: Vec<MissionRecord>,
+}
+
 #[cfg(target_os = "linux")]
 pub struct ArmoredCore6 {
     pub pid: i32,
@@ -204,8 +458,10 @@ pub struct ArmoredCore6 {
     pub cs_event_flag_man: Pointer,
     pub fd4_time: Pointer,
     pub cs_menu_man: Pointer,
+    pub game_data_man: Pointer,
     // Derived pointers
     pub igt: Pointer,
+    mission_table: RefCell<Option<MissionTable>>,
 }
 
 #[cfg(target_os = "linux")]
@@ -216,7 +472,9 @@ impl ArmoredCore6 {
             cs_event_flag_man: Pointer::new(),
             fd4_time: Pointer::new(),
             cs_menu_man: Pointer::new(),
+            game_data_man: Pointer::new(),
             igt: Pointer::new(),
+            mission_table: RefCell::new(None),
         }
     }
 
@@ -263,6 +521,15 @@ impl ArmoredCore6 {
             }
         }
 
+        // Scan for GameDataMan (mission results)
+        let pattern = parse_pattern(GAME_DATA_MAN_PATTERN);
+        if let Some(found) = scan_pattern(pid, base, size, &pattern) {
+            if let Some(addr) = resolve_rip_relative(pid, found, 3, 7) {
+                self.game_data_man.initialize(pid, true, addr as i64, &[0x0, 0x0]);
+                log::info!("AC6: GameDataMan at 0x{:X}", addr);
+            }
+        }
+
         true
     }
 
@@ -344,6 +611,106 @@ impl ArmoredCore6 {
         }
         read_i32(self.pid, (addr + 0x8e4) as usize).unwrap_or(0) != 0
     }
+
+    pub fn get_last_mission_rank(&self) -> MissionRank {
+        if self.game_data_man.is_null_ptr() {
+            return MissionRank::None;
+        }
+        MissionRank::from_i32(self.game_data_man.read_i32(Some(0x4c0)))
+    }
+
+    pub fn get_last_mission_time_milliseconds(&self) -> i32 {
+        if self.game_data_man.is_null_ptr() {
+            return 0;
+        }
+        self.game_data_man.read_i32(Some(0x4c4))
+    }
+
+    /// Get the best recorded result for the mission record at `index`
+    pub fn get_mission_record(&self, index: u32) -> Option<MissionRecord> {
+        if index >= MISSION_RECORD_COUNT || self.game_data_man.is_null_ptr() {
+            return None;
+        }
+
+        // Records are 0x10 bytes: mission id, rank, clear time, padding
+        let entry = 0x500 + (index as i64 * 0x10);
+        let mission_id = self.game_data_man.read_u32(Some(entry));
+        if mission_id == 0 {
+            return None;
+        }
+
+        Some(MissionRecord {
+            mission_id,
+            rank: MissionRank::from_i32(self.game_data_man.read_i32(Some(entry + 0x4))),
+            clear_time_milliseconds: self.game_data_man.read_i32(Some(entry + 0x8)),
+        })
+    }
+
+    /// All recorded mission results. The table only changes when a mission
+    /// is cleared (the last result changes) or another save is loaded (after
+    /// a loading screen), so it is read again only on those edges; during a
+    /// loading screen the previous table is kept.
+    pub fn mission_records(&self) -> Vec<MissionRecord> {
+        let loading = self.is_loading_screen_visible();
+        let last_result = (
+            self.get_last_mission_rank() as i32,
+            self.get_last_mission_time_milliseconds(),
+        );
+
+        let mut table = self.mission_table.borrow_mut();
+        if let Some(table) = table.as_mut() {
+            table.stale |= loading;
+            if loading || (!table.stale && table.last_result == last_result) {
+                return table.records.clone();
+            }
+        }
+
+        let records: Vec<MissionRecord> = (0..MISSION_RECORD_COUNT)
+            .filter_map(|index| self.get_mission_record(index))
+            .collect();
+        *table = Some(MissionTable {
+            last_result,
+            stale: false,
+            records: records.clone(),
+        });
+        records
+    }
+
+    /// Find the best recorded result for a mission id
+    pub fn find_mission_record(&self, mission_id: u32) -> Option<MissionRecord> {
+        self.mission_records()
+            .into_iter()
+            .find(|record| record.mission_id == mission_id)
+    }
+
+    /// Count missions cleared with an S rank
+    pub fn count_s_ranks(&self) -> u32 {
+        self.mission_records()
+            .iter()
+            .filter(|record| record.rank == MissionRank::S)
+            .count() as u32
+    }
+
+    /// Read a named attribute for custom triggers
+    /// Per-mission values use "mission_rank.<id>" and "mission_time_ms.<id>"
+    pub fn read_attribute_by_name(&self, name: &str) -> Option<i64> {
+        match name {
+            "igt" => Some(self.get_in_game_time_milliseconds() as i64),
+            "loading" => Some(self.is_loading_screen_visible() as i64),
+            "last_mission_rank" => Some(self.get_last_mission_rank() as i64),
+            "last_mission_time_ms" => Some(self.get_last_mission_time_milliseconds() as i64),
+            "s_rank_count" => Some(self.count_s_ranks() as i64),
+            _ => {
+                let (key, mission_id) = name.split_once('.')?;
+                let record = self.find_mission_record(mission_id.parse().ok()?);
+                match key {
+                    "mission_rank" => Some(record.map(|r| r.rank as i64).unwrap_or(0)),
+                    "mission_time_ms" => Some(record.map(|r| r.clear_time_milliseconds as i64).unwrap_or(0)),
+                    _ => None,
+                }
+            }
+        }
+    }
 }
 
 #[cfg(target_os = "linux")]
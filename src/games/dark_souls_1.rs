@@ -1,6 +1,11 @@
 //! Dark Souls Remastered autosplitter - port of SoulSplitter's Remastered.cs
 //! https://github.com/FrankvdStam/SoulSplitter
 //!
+//! Also supports the 32-bit Prepare to Die Edition (Ptde.cs), detected when the
+//! Remastered patterns are not found and the image is 32-bit. Only event flags,
+//! IGT, NG+ and stats are read there; player, warp, credits and save slot reads
+//! use Remastered layouts and report nothing under PTDE.
+//!
 //! Credit to JKAnderson for the original event flag reading code (DSR-Gadget)
 
 #[cfg(target_os = "windows")]
@@ -9,7 +14,7 @@ use std::collections::HashMap;
 use windows::Win32::Foundation::HANDLE;
 
 #[cfg(target_os = "windows")]
-use crate::memory::{parse_pattern, resolve_rip_relative, scan_pattern, read_u32, read_i32, read_f32};
+use crate::memory::{arch, parse_pattern, read_bytes, resolve_rip_relative, scan_pattern, read_u32, read_i32, read_f32, ImageMachine};
 #[cfg(target_os = "windows")]
 use crate::memory::pointer::Pointer;
 
@@ -27,6 +32,23 @@ pub const MENU_MAN_PATTERN: &str = "48 8b 15 ? ? ? ? 89 82 7c 08 00 00";
 #[cfg(target_os = "windows")]
 pub const BONFIRE_DB_PATTERN: &str = "48 83 3d ? ? ? ? 00 48 8b f1";
 
+// Prepare to Die Edition (32-bit DARKSOULS.exe) patterns from SoulSplitter's Ptde.cs;
+// these use absolute addresses instead of RIP-relative ones
+#[cfg(target_os = "windows")]
+pub const PTDE_EVENT_FLAGS_PATTERN: &str = "56 8b f1 8b 46 1c 50 a1 ? ? ? ? 32 c9";
+#[cfg(target_os = "windows")]
+pub const PTDE_GAME_DATA_MAN_PATTERN: &str = "8b 0d ? ? ? ? 8b 7e 1c 8b 49 08 8b 46 20 81 c1 b8 01 00 00 57 51 32 db";
+
+/// Game edition, detected from which pattern set matches
+#[cfg(target_os = "windows")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ds1Version {
+    /// Dark Souls Remastered (64-bit)
+    Remastered,
+    /// Prepare to Die Edition (32-bit)
+    Ptde,
+}
+
 /// Player position as 3D vector
 #[cfg(target_os = "windows")]
 #[derive(Debug, Clone, Copy, Default)]
@@ -87,6 +109,11 @@ pub struct DarkSouls1 {
     // Version-specific offsets
     player_ctrl_offset: i64,
     current_save_slot_offset: i64,
+    igt_offset: i64,
+    ng_offset: i64,
+    player_health_offset: i64,
+    /// Edition detected by `init_pointers`
+    pub version: Ds1Version,
 }
 
 #[cfg(target_os = "windows")]
@@ -136,6 +163,10 @@ impl DarkSouls1 {
             event_flag_areas,
             player_ctrl_offset: 0x68,      // Default, 0x48 for v1.0.1
             current_save_slot_offset: 0xaa0, // Default, 0xa90 for v1.0.1
+            igt_offset: 0xa4,
+            ng_offset: 0x78,
+            player_health_offset: 0x3e8,
+            version: Ds1Version::Remastered,
         }
     }
 
@@ -163,8 +194,8 @@ impl DarkSouls1 {
                 }
             }
             None => {
-                log::warn!("DS1R: EventFlags pattern not found");
-                return false;
+                log::info!("DS1R: EventFlags pattern not found, trying Prepare to Die Edition");
+                return self.init_ptde_pointers(handle, base, size);
             }
         };
         // DSProcess does TWO dereferences: *(*eventFlagPtr + 0) + 0
@@ -233,6 +264,47 @@ impl DarkSouls1 {
         true
     }
 
+    /// Initialize pointers for Prepare to Die Edition (32-bit, absolute addresses)
+    ///
+    /// Requires a 32-bit image, so an unrelated process is not taken for PTDE.
+    /// The event flag layout is the same as Remastered; WorldChrMan, GameMan,
+    /// MenuMan and BonfireDb are not located, so player, warp, credits and save
+    /// slot reads report nothing.
+    fn init_ptde_pointers(&mut self, handle: HANDLE, base: usize, size: usize) -> bool {
+        let machine = arch::module_machine(base, |address, len| read_bytes(handle, address, len));
+        if machine != Some(ImageMachine::X86) {
+            log::warn!("DS1: Remastered patterns not found and the image is not a 32-bit PTDE executable");
+            return false;
+        }
+
+        let pattern = parse_pattern(PTDE_EVENT_FLAGS_PATTERN);
+        let event_flags_addr = match scan_pattern(handle, base, size, &pattern)
+            .and_then(|found| read_u32(handle, found + 8))
+        {
+            Some(addr) => addr,
+            None => {
+                log::warn!("DS1: EventFlags pattern not found (neither Remastered nor PTDE)");
+                return false;
+            }
+        };
+
+        self.version = Ds1Version::Ptde;
+        self.igt_offset = 0x68;
+        self.ng_offset = 0x3c;
+
+        self.event_flags.initialize(handle, false, event_flags_addr as i64, &[0x0, 0x0, 0x0]);
+        log::info!("DS1 PTDE: EventFlags at 0x{:X}", event_flags_addr);
+
+        let pattern = parse_pattern(PTDE_GAME_DATA_MAN_PATTERN);
+        if let Some(addr) = scan_pattern(handle, base, size, &pattern).and_then(|found| read_u32(handle, found + 2)) {
+            self.game_data_man.initialize(handle, false, addr as i64, &[0x0]);
+            self.player_game_data.initialize(handle, false, addr as i64, &[0x0, 0x8]);
+            log::info!("DS1 PTDE: GameDataMan at 0x{:X}", addr);
+        }
+
+        true
+    }
+
     /// Get the offset and mask for an event flag
     fn get_event_flag_offset(&self, event_flag_id: u32) -> Option<(i32, u32)> {
         let id_string = format!("{:08}", event_flag_id);
@@ -310,7 +382,7 @@ impl DarkSouls1 {
         if addr == 0 {
             return 0;
         }
-        read_i32(self.handle, (addr + 0xa4) as usize).unwrap_or(0)
+        read_i32(self.handle, (addr + self.igt_offset) as usize).unwrap_or(0)
     }
 
     /// Check if player is loaded
@@ -318,6 +390,12 @@ impl DarkSouls1 {
         !self.player_ins.is_null_ptr()
     }
 
+    /// Whether the Prepare to Die Edition is attached; player reads are not
+    /// available there
+    pub fn is_ptde(&self) -> bool {
+        self.version == Ds1Version::Ptde
+    }
+
     /// Get player position
     pub fn get_position(&self) -> Vector3f {
         let addr = self.player_pos.get_address();
@@ -346,11 +424,14 @@ impl DarkSouls1 {
         if addr == 0 {
             return 0;
         }
-        read_i32(self.handle, (addr + 0x78) as usize).unwrap_or(0)
+        read_i32(self.handle, (addr + self.ng_offset) as usize).unwrap_or(0)
     }
 
     /// Check if warp is requested (for quitout detection)
     pub fn is_warp_requested(&self) -> bool {
+        if self.version == Ds1Version::Ptde {
+            return false;
+        }
         let game_man_addr = self.game_man.get_address();
         if game_man_addr == 0 {
             return false;
@@ -363,6 +444,9 @@ impl DarkSouls1 {
 
     /// Check if credits are rolling
     pub fn are_credits_rolling(&self) -> bool {
+        if self.version == Ds1Version::Ptde {
+            return false;
+        }
         let addr = self.menu_man.get_address();
         if addr == 0 {
             return false;
@@ -378,6 +462,9 @@ impl DarkSouls1 {
 
     /// Get current save slot
     pub fn get_current_save_slot(&self) -> i32 {
+        if self.version == Ds1Version::Ptde {
+            return -1;
+        }
         let addr = self.game_data_man.get_address();
         if addr == 0 {
             return -1;
@@ -391,7 +478,7 @@ impl DarkSouls1 {
         if addr == 0 {
             return 0;
         }
-        read_i32(self.handle, (addr + 0x3e8) as usize).unwrap_or(0)
+        read_i32(self.handle, (addr + self.player_health_offset) as usize).unwrap_or(0)
     }
 }
 
@@ -410,7 +497,7 @@ impl Default for DarkSouls1 {
 use std::collections::HashMap;
 
 #[cfg(target_os = "linux")]
-use crate::memory::{parse_pattern, resolve_rip_relative, scan_pattern, read_u32, read_i32, read_f32};
+use crate::memory::{arch, parse_pattern, read_bytes, resolve_rip_relative, scan_pattern, read_u32, read_i32, read_f32, ImageMachine};
 #[cfg(target_os = "linux")]
 use crate::memory::pointer::Pointer;
 
@@ -428,6 +515,23 @@ pub const MENU_MAN_PATTERN: &str = "48 8b 15 ? ? ? ? 89 82 7c 08 00 00";
 #[cfg(target_os = "linux")]
 pub const BONFIRE_DB_PATTERN: &str = "48 83 3d ? ? ? ? 00 48 8b f1";
 
+// Prepare to Die Edition (32-bit DARKSOULS.exe) patterns from SoulSplitter's Ptde.cs;
+// these use absolute addresses instead of RIP-relative ones
+#[cfg(target_os = "linux")]
+pub const PTDE_EVENT_FLAGS_PATTERN: &str = "56 8b f1 8b 46 1c 50 a1 ? ? ? ? 32 c9";
+#[cfg(target_os = "linux")]
+pub const PTDE_GAME_DATA_MAN_PATTERN: &str = "8b 0d ? ? ? ? 8b 7e 1c 8b 49 08 8b 46 20 81 c1 b8 01 00 00 57 51 32 db";
+
+/// Game edition, detected from which pattern set matches
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ds1Version {
+    /// Dark Souls Remastered (64-bit)
+    Remastered,
+    /// Prepare to Die Edition (32-bit)
+    Ptde,
+}
+
 #[cfg(target_os = "linux")]
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Vector3f {
@@ -484,6 +588,11 @@ pub struct DarkSouls1 {
     // Version-specific offsets
     player_ctrl_offset: i64,
     current_save_slot_offset: i64,
+    igt_offset: i64,
+    ng_offset: i64,
+    player_health_offset: i64,
+    /// Edition detected by `init_pointers`
+    pub version: Ds1Version,
 }
 
 #[cfg(target_os = "linux")]
@@ -533,6 +642,10 @@ impl DarkSouls1 {
             event_flag_areas,
             player_ctrl_offset: 0x68,
             current_save_slot_offset: 0xaa0,
+            igt_offset: 0xa4,
+            ng_offset: 0x78,
+            player_health_offset: 0x3e8,
+            version: Ds1Version::Remastered,
         }
     }
 
@@ -553,8 +666,8 @@ impl DarkSouls1 {
                 }
             }
             None => {
-                log::warn!("DS1R: EventFlags pattern not found");
-                return false;
+                log::info!("DS1R: EventFlags pattern not found, trying Prepare to Die Edition");
+                return self.init_ptde_pointers(pid, base, size);
             }
         };
         self.event_flags.initialize(pid, true, event_flags_addr as i64, &[0x0, 0x0, 0x0]);
@@ -611,6 +724,47 @@ impl DarkSouls1 {
         true
     }
 
+    /// Initialize pointers for Prepare to Die Edition (32-bit, absolute addresses)
+    ///
+    /// Requires a 32-bit image, so an unrelated process is not taken for PTDE.
+    /// The event flag layout is the same as Remastered; WorldChrMan, GameMan,
+    /// MenuMan and BonfireDb are not located, so player, warp, credits and save
+    /// slot reads report nothing.
+    fn init_ptde_pointers(&mut self, pid: i32, base: usize, size: usize) -> bool {
+        let machine = arch::module_machine(base, |address, len| read_bytes(pid, address, len));
+        if machine != Some(ImageMachine::X86) {
+            log::warn!("DS1: Remastered patterns not found and the image is not a 32-bit PTDE executable");
+            return false;
+        }
+
+        let pattern = parse_pattern(PTDE_EVENT_FLAGS_PATTERN);
+        let event_flags_addr = match scan_pattern(pid, base, size, &pattern)
+            .and_then(|found| read_u32(pid, found + 8))
+        {
+            Some(addr) => addr,
+            None => {
+                log::warn!("DS1: EventFlags pattern not found (neither Remastered nor PTDE)");
+                return false;
+            }
+        };
+
+        self.version = Ds1Version::Ptde;
+        self.igt_offset = 0x68;
+        self.ng_offset = 0x3c;
+
+        self.event_flags.initialize(pid, false, event_flags_addr as i64, &[0x0, 0x0, 0x0]);
+        log::info!("DS1 PTDE: EventFlags at 0x{:X}", event_flags_addr);
+
+        let pattern = parse_pattern(PTDE_GAME_DATA_MAN_PATTERN);
+        if let Some(addr) = scan_pattern(pid, base, size, &pattern).and_then(|found| read_u32(pid, found + 2)) {
+            self.game_data_man.initialize(pid, false, addr as i64, &[0x0]);
+            self.player_game_data.initialize(pid, false, addr as i64, &[0x0, 0x8]);
+            log::info!("DS1 PTDE: GameDataMan at 0x{:X}", addr);
+        }
+
+        true
+    }
+
     fn get_event_flag_offset(&self, event_flag_id: u32) -> Option<(i32, u32)> {
         let id_string = format!("{:08}", event_flag_id);
         if id_string.len() != 8 {
@@ -658,13 +812,19 @@ impl DarkSouls1 {
         if addr == 0 {
             return 0;
         }
-        read_i32(self.pid, (addr + 0xa4) as usize).unwrap_or(0)
+        read_i32(self.pid, (addr + self.igt_offset) as usize).unwrap_or(0)
     }
 
     pub fn is_player_loaded(&self) -> bool {
         !self.player_ins.is_null_ptr()
     }
 
+    /// Whether the Prepare to Die Edition is attached; player reads are not
+    /// available there
+    pub fn is_ptde(&self) -> bool {
+        self.version == Ds1Version::Ptde
+    }
+
     pub fn get_position(&self) -> Vector3f {
         let addr = self.player_pos.get_address();
         if addr == 0 {
@@ -690,10 +850,13 @@ impl DarkSouls1 {
         if addr == 0 {
             return 0;
         }
-        read_i32(self.pid, (addr + 0x78) as usize).unwrap_or(0)
+        read_i32(self.pid, (addr + self.ng_offset) as usize).unwrap_or(0)
     }
 
     pub fn is_warp_requested(&self) -> bool {
+        if self.version == Ds1Version::Ptde {
+            return false;
+        }
         let game_man_addr = self.game_man.get_address();
         if game_man_addr == 0 {
             return false;
@@ -703,6 +866,9 @@ impl DarkSouls1 {
     }
 
     pub fn are_credits_rolling(&self) -> bool {
+        if self.version == Ds1Version::Ptde {
+            return false;
+        }
         let addr = self.menu_man.get_address();
         if addr == 0 {
             return false;
@@ -714,6 +880,9 @@ impl DarkSouls1 {
     }
 
     pub fn get_current_save_slot(&self) -> i32 {
+        if self.version == Ds1Version::Ptde {
+            return -1;
+        }
         let addr = self.game_data_man.get_address();
         if addr == 0 {
             return -1;
@@ -726,7 +895,7 @@ impl DarkSouls1 {
         if addr == 0 {
             return 0;
         }
-        read_i32(self.pid, (addr + 0x3e8) as usize).unwrap_or(0)
+        read_i32(self.pid, (addr + self.player_health_offset) as usize).unwrap_or(0)
     }
 }
 
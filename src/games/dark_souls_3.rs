@@ -19,6 +19,17 @@ pub const GAME_DATA_MAN_PATTERN: &str = "48 8b 0d ? ? ? ? 4c 8d 44 24 40 45 33 c
 pub const PLAYER_INS_PATTERN: &str = "48 8b 0d ? ? ? ? 45 33 c0 48 8d 55 e7 e8 ? ? ? ? 0f 2f";
 pub const LOADING_PATTERN: &str = "c6 05 ? ? ? ? ? e8 ? ? ? ? 84 c0 0f 94 c0 e9";
 pub const SPRJ_FADE_IMP_PATTERN: &str = "48 8b 0d ? ? ? ? 4c 8d 4c 24 38 4c 8d 44 24 48 33 d2";
+// GameMan pattern from community cheat tables (not read by SoulSplitter)
+pub const GAME_MAN_PATTERN: &str = "48 8b 1d ? ? ? 04 8b 53 3c 83 fa ff 74 ? 48 8b cb";
+
+/// GameMan offset of the bonfire entity id the player last rested at or warped to
+pub const LAST_BONFIRE_OFFSET: i64 = 0xacc;
+
+// SprjFlipper pattern from community cheat tables (frame timing)
+pub const SPRJ_FLIPPER_PATTERN: &str = "48 8b 0d ? ? ? ? 48 85 c9 74 ? 48 8b 01 ff 50 ? 48 8b 0d ? ? ? ? e8";
+
+/// SprjFlipper offset of the number of frames presented since the game started
+pub const FRAME_COUNT_OFFSET: i64 = 0x254;
 
 /// Player position as 3D vector
 #[cfg(target_os = "windows")]
@@ -46,6 +57,40 @@ pub enum Attribute {
     SoulLevel = 0x68,
 }
 
+/// Attributes polled into the autosplitter state every tick
+#[cfg(target_os = "windows")]
+pub const ATTRIBUTE_NAMES: &[&str] = &["igt", "soul_level", "right_weapon_upgrade"];
+
+/// Attributes recorded in run log stat snapshots
+#[cfg(target_os = "windows")]
+pub const STAT_NAMES: &[&str] = &[
+    "soul_level",
+    "vigor",
+    "attunement",
+    "endurance",
+    "vitality",
+    "strength",
+    "dexterity",
+    "intelligence",
+    "faith",
+    "luck",
+    "right_weapon_upgrade",
+];
+
+/// Right hand weapon slot offsets into PlayerGameData (ChrAsm equip ids)
+#[cfg(target_os = "windows")]
+const RIGHT_WEAPON_SLOT_OFFSETS: [i64; 3] = [0x32c, 0x334, 0x33c];
+
+/// Reinforcement level encoded in a weapon item id (last two digits)
+/// Infusions add multiples of 100, so they do not affect the result
+#[cfg(target_os = "windows")]
+pub fn weapon_reinforcement_level(weapon_id: i32) -> i32 {
+    if weapon_id <= 0 {
+        return 0;
+    }
+    weapon_id % 100
+}
+
 /// Dark Souls III autosplitter state
 #[cfg(target_os = "windows")]
 pub struct DarkSouls3 {
@@ -59,6 +104,8 @@ pub struct DarkSouls3 {
     pub player_ins: Pointer,
     pub loading: Pointer,
     pub sprj_fade_imp: Pointer,
+    pub game_man: Pointer,
+    pub sprj_flipper: Pointer,
     // Derived pointers
     pub player_game_data: Pointer,
     pub sprj_chr_physics_module: Pointer,
@@ -80,6 +127,8 @@ impl DarkSouls3 {
             player_ins: Pointer::new(),
             loading: Pointer::new(),
             sprj_fade_imp: Pointer::new(),
+            game_man: Pointer::new(),
+            sprj_flipper: Pointer::new(),
             player_game_data: Pointer::new(),
             sprj_chr_physics_module: Pointer::new(),
             blackscreen: Pointer::new(),
@@ -175,6 +224,24 @@ impl DarkSouls3 {
             }
         }
 
+        // Scan for GameMan (warp destination)
+        let pattern = parse_pattern(GAME_MAN_PATTERN);
+        if let Some(found) = scan_pattern(handle, base, size, &pattern) {
+            if let Some(addr) = resolve_rip_relative(handle, found, 3, 7) {
+                self.game_man.initialize(handle, true, addr as i64, &[0x0]);
+                log::info!("DS3: GameMan at 0x{:X}", addr);
+            }
+        }
+
+        // Scan for SprjFlipper (frame counter)
+        let pattern = parse_pattern(SPRJ_FLIPPER_PATTERN);
+        if let Some(found) = scan_pattern(handle, base, size, &pattern) {
+            if let Some(addr) = resolve_rip_relative(handle, found, 3, 7) {
+                self.sprj_flipper.initialize(handle, true, addr as i64, &[0x0]);
+                log::info!("DS3: SprjFlipper at 0x{:X}", addr);
+            }
+        }
+
         log::info!("DS3: All pointers initialized successfully");
         true
     }
@@ -343,6 +410,70 @@ impl DarkSouls3 {
         }
         read_i32(self.handle, (addr + attribute as i64) as usize).unwrap_or(-1)
     }
+
+    /// Highest reinforcement level among the right hand weapon slots
+    pub fn read_right_weapon_upgrade(&self) -> i32 {
+        if self.read_attribute(Attribute::SoulLevel) < 0 {
+            return -1;
+        }
+
+        let addr = self.player_game_data.get_address();
+        RIGHT_WEAPON_SLOT_OFFSETS
+            .iter()
+            .map(|offset| read_i32(self.handle, (addr + offset) as usize).unwrap_or(-1))
+            .map(weapon_reinforcement_level)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Bonfire entity id the player last rested at or selected as a warp
+    /// destination (None before a character is loaded)
+    pub fn read_last_bonfire(&self) -> Option<u32> {
+        if self.game_man.is_null_ptr() {
+            return None;
+        }
+        let id = self.game_man.read_i32(Some(LAST_BONFIRE_OFFSET));
+        (id > 0).then_some(id as u32)
+    }
+
+    /// Frames presented since the game started (None if SprjFlipper was not found)
+    pub fn read_frame_count(&self) -> Option<u32> {
+        if self.sprj_flipper.is_null_ptr() {
+            return None;
+        }
+        Some(self.sprj_flipper.read_i32(Some(FRAME_COUNT_OFFSET)) as u32)
+    }
+
+    /// Read a named attribute for custom triggers and stat snapshots
+    pub fn read_attribute_by_name(&self, name: &str) -> Option<i64> {
+        let attribute = match name {
+            "igt" => return Some(self.get_in_game_time_milliseconds() as i64),
+            "loading" => return Some(self.is_loading() as i64),
+            "player_loaded" => return Some(self.is_player_loaded() as i64),
+            "blackscreen" => return Some(self.blackscreen_active() as i64),
+            "last_bonfire" => return self.read_last_bonfire().map(|id| id as i64),
+            "frame_count" => return self.read_frame_count().map(|count| count as i64),
+            "right_weapon_upgrade" => {
+                let upgrade = self.read_right_weapon_upgrade();
+                return (upgrade >= 0).then_some(upgrade as i64);
+            }
+            "soul_level" => Attribute::SoulLevel,
+            "vigor" => Attribute::Vigor,
+            "attunement" => Attribute::Attunement,
+            "endurance" => Attribute::Endurance,
+            "vitality" => Attribute::Vitality,
+            "strength" => Attribute::Strength,
+            "dexterity" => Attribute::Dexterity,
+            "intelligence" => Attribute::Intelligence,
+            "faith" => Attribute::Faith,
+            "luck" => Attribute::Luck,
+            _ => return None,
+        };
+
+        // -1 means the player is not loaded or a menu is blocking reads
+        let value = self.read_attribute(attribute);
+        (value >= 0).then_some(value as i64)
+    }
 }
 
 #[cfg(target_os = "windows")]
@@ -387,6 +518,40 @@ pub enum Attribute {
     SoulLevel = 0x68,
 }
 
+/// Attributes polled into the autosplitter state every tick (Linux)
+#[cfg(target_os = "linux")]
+pub const ATTRIBUTE_NAMES: &[&str] = &["igt", "soul_level", "right_weapon_upgrade"];
+
+/// Attributes recorded in run log stat snapshots (Linux)
+#[cfg(target_os = "linux")]
+pub const STAT_NAMES: &[&str] = &[
+    "soul_level",
+    "vigor",
+    "attunement",
+    "endurance",
+    "vitality",
+    "strength",
+    "dexterity",
+    "intelligence",
+    "faith",
+    "luck",
+    "right_weapon_upgrade",
+];
+
+/// Right hand weapon slot offsets into PlayerGameData (ChrAsm equip ids) (Linux)
+#[cfg(target_os = "linux")]
+const RIGHT_WEAPON_SLOT_OFFSETS: [i64; 3] = [0x32c, 0x334, 0x33c];
+
+/// Reinforcement level encoded in a weapon item id (last two digits) (Linux)
+/// Infusions add multiples of 100, so they do not affect the result
+#[cfg(target_os = "linux")]
+pub fn weapon_reinforcement_level(weapon_id: i32) -> i32 {
+    if weapon_id <= 0 {
+        return 0;
+    }
+    weapon_id % 100
+}
+
 /// Dark Souls III autosplitter state (Linux)
 #[cfg(target_os = "linux")]
 pub struct DarkSouls3 {
@@ -400,6 +565,8 @@ pub struct DarkSouls3 {
     pub player_ins: Pointer,
     pub loading: Pointer,
     pub sprj_fade_imp: Pointer,
+    pub game_man: Pointer,
+    pub sprj_flipper: Pointer,
     // Derived pointers
     pub player_game_data: Pointer,
     pub sprj_chr_physics_module: Pointer,
@@ -421,6 +588,8 @@ impl DarkSouls3 {
             player_ins: Pointer::new(),
             loading: Pointer::new(),
             sprj_fade_imp: Pointer::new(),
+            game_man: Pointer::new(),
+            sprj_flipper: Pointer::new(),
             player_game_data: Pointer::new(),
             sprj_chr_physics_module: Pointer::new(),
             blackscreen: Pointer::new(),
@@ -513,6 +682,24 @@ impl DarkSouls3 {
             }
         }
 
+        // Scan for GameMan (warp destination)
+        let pattern = parse_pattern(GAME_MAN_PATTERN);
+        if let Some(found) = scan_pattern(pid, base, size, &pattern) {
+            if let Some(addr) = resolve_rip_relative(pid, found, 3, 7) {
+                self.game_man.initialize(pid, true, addr as i64, &[0x0]);
+                log::info!("DS3: GameMan at 0x{:X}", addr);
+            }
+        }
+
+        // Scan for SprjFlipper (frame counter)
+        let pattern = parse_pattern(SPRJ_FLIPPER_PATTERN);
+        if let Some(found) = scan_pattern(pid, base, size, &pattern) {
+            if let Some(addr) = resolve_rip_relative(pid, found, 3, 7) {
+                self.sprj_flipper.initialize(pid, true, addr as i64, &[0x0]);
+                log::info!("DS3: SprjFlipper at 0x{:X}", addr);
+            }
+        }
+
         log::info!("DS3 (Linux): All pointers initialized successfully");
         true
     }
@@ -678,6 +865,69 @@ impl DarkSouls3 {
         }
         read_i32(self.pid, (addr + attribute as i64) as usize).unwrap_or(-1)
     }
+
+    /// Highest reinforcement level among the right hand weapon slots
+    pub fn read_right_weapon_upgrade(&self) -> i32 {
+        if self.read_attribute(Attribute::SoulLevel) < 0 {
+            return -1;
+        }
+
+        let addr = self.player_game_data.get_address();
+        RIGHT_WEAPON_SLOT_OFFSETS
+            .iter()
+            .map(|offset| read_i32(self.pid, (addr + offset) as usize).unwrap_or(-1))
+            .map(weapon_reinforcement_level)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Bonfire entity id the player last rested at or selected as a warp
+    /// destination (None before a character is loaded)
+    pub fn read_last_bonfire(&self) -> Option<u32> {
+        if self.game_man.is_null_ptr() {
+            return None;
+        }
+        let id = self.game_man.read_i32(Some(LAST_BONFIRE_OFFSET));
+        (id > 0).then_some(id as u32)
+    }
+
+    /// Frames presented since the game started (None if SprjFlipper was not found)
+    pub fn read_frame_count(&self) -> Option<u32> {
+        if self.sprj_flipper.is_null_ptr() {
+            return None;
+        }
+        Some(self.sprj_flipper.read_i32(Some(FRAME_COUNT_OFFSET)) as u32)
+    }
+
+    pub fn read_attribute_by_name(&self, name: &str) -> Option<i64> {
+        let attribute = match name {
+            "igt" => return Some(self.get_in_game_time_milliseconds() as i64),
+            "loading" => return Some(self.is_loading() as i64),
+            "player_loaded" => return Some(self.is_player_loaded() as i64),
+            "blackscreen" => return Some(self.blackscreen_active() as i64),
+            "last_bonfire" => return self.read_last_bonfire().map(|id| id as i64),
+            "frame_count" => return self.read_frame_count().map(|count| count as i64),
+            "right_weapon_upgrade" => {
+                let upgrade = self.read_right_weapon_upgrade();
+                return (upgrade >= 0).then_some(upgrade as i64);
+            }
+            "soul_level" => Attribute::SoulLevel,
+            "vigor" => Attribute::Vigor,
+            "attunement" => Attribute::Attunement,
+            "endurance" => Attribute::Endurance,
+            "vitality" => Attribute::Vitality,
+            "strength" => Attribute::Strength,
+            "dexterity" => Attribute::Dexterity,
+            "intelligence" => Attribute::Intelligence,
+            "faith" => Attribute::Faith,
+            "luck" => Attribute::Luck,
+            _ => return None,
+        };
+
+        // -1 means the player is not loaded or a menu is blocking reads
+        let value = self.read_attribute(attribute);
+        (value >= 0).then_some(value as i64)
+    }
 }
 
 #[cfg(target_os = "linux")]
@@ -22,6 +22,64 @@ pub const WORLD_CHR_MAN_PATTERN: &str = "48 8b 35 ? ? ? ? 48 85 f6 ? ? bb 01 00
 pub const MENU_MAN_IMP_PATTERN: &str = "48 8b 0d ? ? ? ? 48 8b 53 08 48 8b 92 d8 00 00 00 48 83 c4 20 5b";
 #[cfg(target_os = "windows")]
 pub const GAME_DATA_MAN_PATTERN: &str = "48 8b 05 ? ? ? ? 48 8d 4d c0 41 b8 10 00 00 00 48 8b 10 48 83 c2 1c";
+#[cfg(target_os = "windows")]
+pub const GAME_MAN_PATTERN: &str = "48 8b 05 ? ? ? ? 80 b8 ? ? ? ? 0d 0f 94 c0 c3";
+#[cfg(target_os = "windows")]
+pub const CS_FLIPPER_PATTERN: &str = "48 8b 0d ? ? ? ? 80 bb d7 00 00 00 00 0f 84 ce 00 00 00 48 85 c9 75 2e";
+
+/// Attributes polled into the autosplitter state every tick
+#[cfg(target_os = "windows")]
+pub const ATTRIBUTE_NAMES: &[&str] = &["igt", "ng_level", "rune_level", "death_count"];
+
+/// Attributes recorded in run log stat snapshots
+#[cfg(target_os = "windows")]
+pub const STAT_NAMES: &[&str] = &[
+    "rune_level",
+    "vigor",
+    "mind",
+    "endurance",
+    "strength",
+    "dexterity",
+    "intelligence",
+    "faith",
+    "arcane",
+];
+
+/// Map area of the Colosseums (m45_00 Royal, m45_01 Caelid, m45_02 Limgrave)
+#[cfg(target_os = "windows")]
+pub const COLOSSEUM_MAP_AREA: u8 = 45;
+
+/// GameMan offset of the grace entity id the player last rested at or warped to
+#[cfg(target_os = "windows")]
+const LAST_GRACE_OFFSET: i64 = 0xb6c;
+
+/// CSFlipper offset of the number of frames presented since the game started
+#[cfg(target_os = "windows")]
+const FRAME_COUNT_OFFSET: i64 = 0x264;
+
+/// WorldChrMan offset of the ChrSet holding player characters
+#[cfg(target_os = "windows")]
+const PLAYER_CHR_SET_OFFSET: i64 = 0x10EF8;
+
+/// Player slots in the ChrSet (the host plus up to five summons or invaders)
+#[cfg(target_os = "windows")]
+const PLAYER_CHR_SET_CAPACITY: i64 = 6;
+
+/// Character attributes (offsets into PlayerGameData)
+#[cfg(target_os = "windows")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i64)]
+pub enum Attribute {
+    Vigor = 0x3c,
+    Mind = 0x40,
+    Endurance = 0x44,
+    Strength = 0x48,
+    Dexterity = 0x4c,
+    Intelligence = 0x50,
+    Faith = 0x54,
+    Arcane = 0x58,
+    RuneLevel = 0x68,
+}
 
 /// Player position with map info
 #[cfg(target_os = "windows")]
@@ -71,6 +129,8 @@ pub struct EldenRing {
     pub world_chr_man: Pointer,
     pub menu_man_imp: Pointer,
     pub game_data_man: Pointer,
+    pub game_man: Pointer,
+    pub cs_flipper: Pointer,
     // Derived pointers
     pub igt: Pointer,
     pub player_ins: Pointer,
@@ -93,6 +153,8 @@ impl EldenRing {
             world_chr_man: Pointer::new(),
             menu_man_imp: Pointer::new(),
             game_data_man: Pointer::new(),
+            game_man: Pointer::new(),
+            cs_flipper: Pointer::new(),
             igt: Pointer::new(),
             player_ins: Pointer::new(),
             ng_level: Pointer::new(),
@@ -169,6 +231,24 @@ impl EldenRing {
             }
         }
 
+        // Scan for GameMan (warp destination)
+        let pattern = parse_pattern(GAME_MAN_PATTERN);
+        if let Some(found) = scan_pattern(handle, base, size, &pattern) {
+            if let Some(addr) = resolve_rip_relative(handle, found, 3, 7) {
+                self.game_man.initialize(handle, true, addr as i64, &[0x0]);
+                log::info!("ER: GameMan at 0x{:X}", addr);
+            }
+        }
+
+        // Scan for CSFlipper (frame counter)
+        let pattern = parse_pattern(CS_FLIPPER_PATTERN);
+        if let Some(found) = scan_pattern(handle, base, size, &pattern) {
+            if let Some(addr) = resolve_rip_relative(handle, found, 3, 7) {
+                self.cs_flipper.initialize(handle, true, addr as i64, &[0x0]);
+                log::info!("ER: CSFlipper at 0x{:X}", addr);
+            }
+        }
+
         true
     }
 
@@ -249,6 +329,11 @@ impl EldenRing {
         self.ng_level.read_i32(None)
     }
 
+    /// Read the death counter (GameDataMan + 0x94)
+    pub fn read_death_count(&self) -> i32 {
+        self.game_data_man.read_i32(Some(0x94))
+    }
+
     /// Check if player is loaded
     pub fn is_player_loaded(&self) -> bool {
         let addr = self.player_ins.get_address();
@@ -309,6 +394,83 @@ impl EldenRing {
             z: read_f32(self.handle, (addr + self.position_offset + 8) as usize).unwrap_or(0.0),
         }
     }
+
+    /// Check if the player is in one of the Colosseums
+    pub fn is_in_colosseum(&self) -> bool {
+        self.get_position().area == COLOSSEUM_MAP_AREA
+    }
+
+    /// Count other players' characters (summons, invaders, Colosseum
+    /// opponents) loaded into the world
+    pub fn remote_player_count(&self) -> usize {
+        let entries = self.world_chr_man.read_i64(Some(PLAYER_CHR_SET_OFFSET + 0x18));
+        if entries == 0 {
+            return 0;
+        }
+        let local = self.world_chr_man.read_i64(Some(self.player_ins_offset));
+
+        (0..PLAYER_CHR_SET_CAPACITY)
+            .filter_map(|slot| read_i64(self.handle, (entries + slot * 0x10) as usize))
+            .filter(|&chr| chr != 0 && chr != local)
+            .count()
+    }
+
+    /// Get character attribute value
+    pub fn get_attribute(&self, attribute: Attribute) -> i32 {
+        if self.player_game_data.is_null_ptr() {
+            return -1;
+        }
+        self.player_game_data.read_i32(Some(attribute as i64))
+    }
+
+    /// Grace entity id the player last rested at or selected as a warp
+    /// destination (None before a character is loaded)
+    pub fn read_last_grace(&self) -> Option<u32> {
+        if self.game_man.is_null_ptr() {
+            return None;
+        }
+        let id = self.game_man.read_i32(Some(LAST_GRACE_OFFSET));
+        (id > 0).then_some(id as u32)
+    }
+
+    /// Frames presented since the game started (None if CSFlipper was not found)
+    pub fn read_frame_count(&self) -> Option<u32> {
+        if self.cs_flipper.is_null_ptr() {
+            return None;
+        }
+        Some(self.cs_flipper.read_i32(Some(FRAME_COUNT_OFFSET)) as u32)
+    }
+
+    /// Read a named attribute for custom triggers and stat snapshots
+    pub fn read_attribute_by_name(&self, name: &str) -> Option<i64> {
+        let attribute = match name {
+            "igt" => return Some(self.get_in_game_time_milliseconds() as i64),
+            "ng_level" => return Some(self.read_ng_level() as i64),
+            "death_count" => return Some(self.read_death_count() as i64),
+            "player_loaded" => return Some(self.is_player_loaded() as i64),
+            "blackscreen" => return Some(self.is_blackscreen_active() as i64),
+            "in_colosseum" => return Some(self.is_in_colosseum() as i64),
+            "remote_players" => return Some(self.remote_player_count() as i64),
+            "last_grace" => return self.read_last_grace().map(|id| id as i64),
+            "frame_count" => return self.read_frame_count().map(|count| count as i64),
+            "rune_level" => Attribute::RuneLevel,
+            "vigor" => Attribute::Vigor,
+            "mind" => Attribute::Mind,
+            "endurance" => Attribute::Endurance,
+            "strength" => Attribute::Strength,
+            "dexterity" => Attribute::Dexterity,
+            "intelligence" => Attribute::Intelligence,
+            "faith" => Attribute::Faith,
+            "arcane" => Attribute::Arcane,
+            _ => return None,
+        };
+
+        // Stats are meaningless before a character is loaded
+        if !self.is_player_loaded() {
+            return None;
+        }
+        Some(self.get_attribute(attribute) as i64)
+    }
 }
 
 #[cfg(target_os = "windows")]
@@ -338,6 +500,58 @@ pub const WORLD_CHR_MAN_PATTERN: &str = "48 8b 35 ? ? ? ? 48 85 f6 ? ? bb 01 00
 pub const MENU_MAN_IMP_PATTERN: &str = "48 8b 0d ? ? ? ? 48 8b 53 08 48 8b 92 d8 00 00 00 48 83 c4 20 5b";
 #[cfg(target_os = "linux")]
 pub const GAME_DATA_MAN_PATTERN: &str = "48 8b 05 ? ? ? ? 48 8d 4d c0 41 b8 10 00 00 00 48 8b 10 48 83 c2 1c";
+#[cfg(target_os = "linux")]
+pub const GAME_MAN_PATTERN: &str = "48 8b 05 ? ? ? ? 80 b8 ? ? ? ? 0d 0f 94 c0 c3";
+#[cfg(target_os = "linux")]
+pub const CS_FLIPPER_PATTERN: &str = "48 8b 0d ? ? ? ? 80 bb d7 00 00 00 00 0f 84 ce 00 00 00 48 85 c9 75 2e";
+
+#[cfg(target_os = "linux")]
+pub const ATTRIBUTE_NAMES: &[&str] = &["igt", "ng_level", "rune_level", "death_count"];
+
+#[cfg(target_os = "linux")]
+pub const STAT_NAMES: &[&str] = &[
+    "rune_level",
+    "vigor",
+    "mind",
+    "endurance",
+    "strength",
+    "dexterity",
+    "intelligence",
+    "faith",
+    "arcane",
+];
+
+#[cfg(target_os = "linux")]
+pub const COLOSSEUM_MAP_AREA: u8 = 45;
+
+/// GameMan offset of the grace entity id the player last rested at or warped to
+#[cfg(target_os = "linux")]
+const LAST_GRACE_OFFSET: i64 = 0xb6c;
+
+/// CSFlipper offset of the number of frames presented since the game started
+#[cfg(target_os = "linux")]
+const FRAME_COUNT_OFFSET: i64 = 0x264;
+
+#[cfg(target_os = "linux")]
+const PLAYER_CHR_SET_OFFSET: i64 = 0x10EF8;
+
+#[cfg(target_os = "linux")]
+const PLAYER_CHR_SET_CAPACITY: i64 = 6;
+
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(i64)]
+pub enum Attribute {
+    Vigor = 0x3c,
+    Mind = 0x40,
+    Endurance = 0x44,
+    Strength = 0x48,
+    Dexterity = 0x4c,
+    Intelligence = 0x50,
+    Faith = 0x54,
+    Arcane = 0x58,
+    RuneLevel = 0x68,
+}
 
 #[cfg(target_os = "linux")]
 #[derive(Debug, Clone, Copy, Default)]
@@ -384,6 +598,8 @@ pub struct EldenRing {
     pub world_chr_man: Pointer,
     pub menu_man_imp: Pointer,
     pub game_data_man: Pointer,
+    pub game_man: Pointer,
+    pub cs_flipper: Pointer,
     // Derived pointers
     pub igt: Pointer,
     pub player_ins: Pointer,
@@ -406,6 +622,8 @@ impl EldenRing {
             world_chr_man: Pointer::new(),
             menu_man_imp: Pointer::new(),
             game_data_man: Pointer::new(),
+            game_man: Pointer::new(),
+            cs_flipper: Pointer::new(),
             igt: Pointer::new(),
             player_ins: Pointer::new(),
             ng_level: Pointer::new(),
@@ -481,6 +699,24 @@ impl EldenRing {
             }
         }
 
+        // Scan for GameMan (warp destination)
+        let pattern = parse_pattern(GAME_MAN_PATTERN);
+        if let Some(found) = scan_pattern(pid, base, size, &pattern) {
+            if let Some(addr) = resolve_rip_relative(pid, found, 3, 7) {
+                self.game_man.initialize(pid, true, addr as i64, &[0x0]);
+                log::info!("ER: GameMan at 0x{:X}", addr);
+            }
+        }
+
+        // Scan for CSFlipper (frame counter)
+        let pattern = parse_pattern(CS_FLIPPER_PATTERN);
+        if let Some(found) = scan_pattern(pid, base, size, &pattern) {
+            if let Some(addr) = resolve_rip_relative(pid, found, 3, 7) {
+                self.cs_flipper.initialize(pid, true, addr as i64, &[0x0]);
+                log::info!("ER: CSFlipper at 0x{:X}", addr);
+            }
+        }
+
         true
     }
 
@@ -558,6 +794,10 @@ impl EldenRing {
         self.ng_level.read_i32(None)
     }
 
+    pub fn read_death_count(&self) -> i32 {
+        self.game_data_man.read_i32(Some(0x94))
+    }
+
     pub fn is_player_loaded(&self) -> bool {
         let addr = self.player_ins.get_address();
         if addr == 0 {
@@ -612,6 +852,76 @@ impl EldenRing {
             z: read_f32(self.pid, (addr + self.position_offset + 8) as usize).unwrap_or(0.0),
         }
     }
+
+    pub fn is_in_colosseum(&self) -> bool {
+        self.get_position().area == COLOSSEUM_MAP_AREA
+    }
+
+    pub fn remote_player_count(&self) -> usize {
+        let entries = self.world_chr_man.read_i64(Some(PLAYER_CHR_SET_OFFSET + 0x18));
+        if entries == 0 {
+            return 0;
+        }
+        let local = self.world_chr_man.read_i64(Some(self.player_ins_offset));
+
+        (0..PLAYER_CHR_SET_CAPACITY)
+            .filter_map(|slot| read_i64(self.pid, (entries + slot * 0x10) as usize))
+            .filter(|&chr| chr != 0 && chr != local)
+            .count()
+    }
+
+    pub fn get_attribute(&self, attribute: Attribute) -> i32 {
+        if self.player_game_data.is_null_ptr() {
+            return -1;
+        }
+        self.player_game_data.read_i32(Some(attribute as i64))
+    }
+
+    pub fn read_last_grace(&self) -> Option<u32> {
+        if self.game_man.is_null_ptr() {
+            return None;
+        }
+        let id = self.game_man.read_i32(Some(LAST_GRACE_OFFSET));
+        (id > 0).then_some(id as u32)
+    }
+
+    /// Frames presented since the game started (None if CSFlipper was not found)
+    pub fn read_frame_count(&self) -> Option<u32> {
+        if self.cs_flipper.is_null_ptr() {
+            return None;
+        }
+        Some(self.cs_flipper.read_i32(Some(FRAME_COUNT_OFFSET)) as u32)
+    }
+
+    pub fn read_attribute_by_name(&self, name: &str) -> Option<i64> {
+        let attribute = match name {
+            "igt" => return Some(self.get_in_game_time_milliseconds() as i64),
+            "ng_level" => return Some(self.read_ng_level() as i64),
+            "death_count" => return Some(self.read_death_count() as i64),
+            "player_loaded" => return Some(self.is_player_loaded() as i64),
+            "blackscreen" => return Some(self.is_blackscreen_active() as i64),
+            "in_colosseum" => return Some(self.is_in_colosseum() as i64),
+            "remote_players" => return Some(self.remote_player_count() as i64),
+            "last_grace" => return self.read_last_grace().map(|id| id as i64),
+            "frame_count" => return self.read_frame_count().map(|count| count as i64),
+            "rune_level" => Attribute::RuneLevel,
+            "vigor" => Attribute::Vigor,
+            "mind" => Attribute::Mind,
+            "endurance" => Attribute::Endurance,
+            "strength" => Attribute::Strength,
+            "dexterity" => Attribute::Dexterity,
+            "intelligence" => Attribute::Intelligence,
+            "faith" => Attribute::Faith,
+            "arcane" => Attribute::Arcane,
+            _ => return None,
+        };
+
+        // Stats are meaningless before a character is loaded
+        if !self.is_player_loaded() {
+            return None;
+        }
+        Some(self.get_attribute(attribute) as i64)
+    }
 }
 
 #[cfg(target_os = "linux")]
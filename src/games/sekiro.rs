@@ -25,6 +25,17 @@ pub const FADE_MAN_IMP_PATTERN: &str = "48 89 35 ? ? ? ? 48 8b c7 48 8b";
 #[cfg(target_os = "windows")]
 pub const PLAYER_GAME_DATA_PATTERN: &str = "48 8b 0d ? ? ? ? 48 8b 41 20 c6";
 
+/// Event flag set while the Demon Bell (Bell Demon) is active
+#[cfg(target_os = "windows")]
+pub const DEMON_BELL_ACTIVE_FLAG: u32 = 6800;
+/// Event flag set once Kuro's Charm has been returned
+#[cfg(target_os = "windows")]
+pub const KUROS_CHARM_RETURNED_FLAG: u32 = 6801;
+
+/// Attributes polled into the autosplitter state every tick
+#[cfg(target_os = "windows")]
+pub const ATTRIBUTE_NAMES: &[&str] = &["igt", "demon_bell_active", "kuros_charm_returned"];
+
 /// Player position as 3D vector
 #[cfg(target_os = "windows")]
 #[derive(Debug, Clone, Copy, Default)]
@@ -292,6 +303,30 @@ impl Sekiro {
         }
         read_i32(self.handle, (addr + attribute as i64) as usize).unwrap_or(-1)
     }
+
+    /// Check if the Demon Bell is currently rung (Bell Demon active)
+    pub fn is_demon_bell_active(&self) -> bool {
+        self.read_event_flag(DEMON_BELL_ACTIVE_FLAG)
+    }
+
+    /// Check if Kuro's Charm has been given back (charmless run)
+    pub fn is_kuros_charm_returned(&self) -> bool {
+        self.read_event_flag(KUROS_CHARM_RETURNED_FLAG)
+    }
+
+    /// Read a named attribute for custom triggers
+    pub fn read_attribute_by_name(&self, name: &str) -> Option<i64> {
+        match name {
+            "igt" => Some(self.get_in_game_time_milliseconds() as i64),
+            "player_loaded" => Some(self.is_player_loaded() as i64),
+            "blackscreen" => Some(self.is_blackscreen_active() as i64),
+            "demon_bell_active" => Some(self.is_demon_bell_active() as i64),
+            "kuros_charm_returned" => Some(self.is_kuros_charm_returned() as i64),
+            "vitality" => Some(self.get_attribute(Attribute::Vitality) as i64),
+            "attack_power" => Some(self.get_attribute(Attribute::AttackPower) as i64),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(target_os = "windows")]
@@ -324,6 +359,17 @@ pub const FADE_MAN_IMP_PATTERN: &str = "48 89 35 ? ? ? ? 48 8b c7 48 8b";
 #[cfg(target_os = "linux")]
 pub const PLAYER_GAME_DATA_PATTERN: &str = "48 8b 0d ? ? ? ? 48 8b 41 20 c6";
 
+/// Event flag set while the Demon Bell (Bell Demon) is active
+#[cfg(target_os = "linux")]
+pub const DEMON_BELL_ACTIVE_FLAG: u32 = 6800;
+/// Event flag set once Kuro's Charm has been returned
+#[cfg(target_os = "linux")]
+pub const KUROS_CHARM_RETURNED_FLAG: u32 = 6801;
+
+/// Attributes polled into the autosplitter state every tick
+#[cfg(target_os = "linux")]
+pub const ATTRIBUTE_NAMES: &[&str] = &["igt", "demon_bell_active", "kuros_charm_returned"];
+
 #[cfg(target_os = "linux")]
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Vector3f {
@@ -575,6 +621,27 @@ impl Sekiro {
         }
         read_i32(self.pid, (addr + attribute as i64) as usize).unwrap_or(-1)
     }
+
+    pub fn is_demon_bell_active(&self) -> bool {
+        self.read_event_flag(DEMON_BELL_ACTIVE_FLAG)
+    }
+
+    pub fn is_kuros_charm_returned(&self) -> bool {
+        self.read_event_flag(KUROS_CHARM_RETURNED_FLAG)
+    }
+
+    pub fn read_attribute_by_name(&self, name: &str) -> Option<i64> {
+        match name {
+            "igt" => Some(self.get_in_game_time_milliseconds() as i64),
+            "player_loaded" => Some(self.is_player_loaded() as i64),
+            "blackscreen" => Some(self.is_blackscreen_active() as i64),
+            "demon_bell_active" => Some(self.is_demon_bell_active() as i64),
+            "kuros_charm_returned" => Some(self.is_kuros_charm_returned() as i64),
+            "vitality" => Some(self.get_attribute(Attribute::Vitality) as i64),
+            "attack_power" => Some(self.get_attribute(Attribute::AttackPower) as i64),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(target_os = "linux")]
@@ -0,0 +1,110 @@
+//! Process handle health
+//!
+//! The loops detach when the game exits (exit code on Windows, `/proc/<pid>`
+//! on Linux), but a zombie process or a handle that lost access passes that
+//! check while every read fails, and the splitter would read unset flags
+//! forever. [`HandleHealth`] adds the zombie state (Linux) and the share of
+//! successful reads over the last ticks; when the handle is unhealthy the
+//! loop drops it and re-attaches, logging a `reattaching` event.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Ticks covered by the read success ratio
+pub const HEALTH_WINDOW_TICKS: usize = 30;
+
+/// Reads needed in the window before the ratio is judged
+pub const MIN_WINDOW_READS: u64 = 30;
+
+/// Share of successful reads below which the handle is unhealthy
+pub const MIN_SUCCESS_RATIO: f64 = 0.05;
+
+/// Why the loop dropped a handle to re-attach
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum ReattachReason {
+    /// The process exited but was not reaped yet (Linux)
+    Zombie,
+    /// Nearly every read of the last ticks failed
+    ReadsFailing,
+}
+
+/// Per-attach health tracking of the game handle
+#[derive(Debug, Default)]
+pub struct HandleHealth {
+    last_totals: Option<(u64, u64)>,
+    /// Reads and failed reads per tick
+    window: VecDeque<(u64, u64)>,
+}
+
+impl HandleHealth {
+    /// Sample the worker thread's read and failed-read counters once per tick
+    /// (see `read_stats::failure_totals`), so reads of other sessions don't
+    /// mask or cause failures of this handle; returns why the handle should be
+    /// replaced, if it should
+    pub fn observe(&mut self, zombie: bool, totals: (u64, u64)) -> Option<ReattachReason> {
+        if zombie {
+            return Some(ReattachReason::Zombie);
+        }
+
+        let last = self.last_totals.replace(totals)?;
+        if self.window.len() == HEALTH_WINDOW_TICKS {
+            self.window.pop_front();
+        }
+        self.window
+            .push_back((totals.0.saturating_sub(last.0), totals.1.saturating_sub(last.1)));
+        if self.window.len() < HEALTH_WINDOW_TICKS {
+            return None;
+        }
+
+        let reads: u64 = self.window.iter().map(|(reads, _)| reads).sum();
+        let failed: u64 = self.window.iter().map(|(_, failed)| failed).sum();
+        if reads < MIN_WINDOW_READS {
+            return None;
+        }
+        let success_ratio = reads.saturating_sub(failed) as f64 / reads as f64;
+        (success_ratio < MIN_SUCCESS_RATIO).then_some(ReattachReason::ReadsFailing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(health: &mut HandleHealth, ticks: usize, reads: u64, failed: u64, totals: &mut (u64, u64)) -> Option<ReattachReason> {
+        let mut result = None;
+        for _ in 0..ticks {
+            totals.0 += reads;
+            totals.1 += failed;
+            result = health.observe(false, *totals);
+        }
+        result
+    }
+
+    #[test]
+    fn test_failing_reads() {
+        let mut health = HandleHealth::default();
+        let mut totals = (0, 0);
+
+        // Menus fail some reads: healthy
+        assert_eq!(run(&mut health, HEALTH_WINDOW_TICKS + 1, 20, 15, &mut totals), None);
+        // Every read fails from now on: unhealthy once the successes left in
+        // the window drop below 5% (25 of the 30 ticks failing)
+        assert_eq!(run(&mut health, 24, 20, 20, &mut totals), None);
+        assert_eq!(
+            run(&mut health, 1, 20, 20, &mut totals),
+            Some(ReattachReason::ReadsFailing)
+        );
+    }
+
+    #[test]
+    fn test_idle_and_zombie() {
+        let mut health = HandleHealth::default();
+        let mut totals = (0, 0);
+
+        // Too few reads to judge
+        assert_eq!(run(&mut health, HEALTH_WINDOW_TICKS * 2, 0, 0, &mut totals), None);
+        assert_eq!(health.observe(true, totals), Some(ReattachReason::Zombie));
+    }
+}
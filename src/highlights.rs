@@ -0,0 +1,167 @@
+//! End-of-run highlight timestamps
+//!
+//! When an attempt ends (reset or stop) its memorable moments are collected
+//! from the run log into a `run_highlights` event: every split (marked when
+//! it set a new gold), every death and every rule violation. Each moment has
+//! a wall-clock timestamp for clip tools that work on live streams, and an
+//! offset aligned like the VOD chapters (see `chapters`) for tools that work
+//! on the recording.
+
+use crate::chapters;
+use crate::config::TriggerKind;
+use crate::run_log::{RunEvent, RunLogEntry};
+use crate::speech;
+use crate::storage;
+use serde::{Deserialize, Serialize};
+
+/// What made a moment memorable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum HighlightKind {
+    Split,
+    /// Split that set a new best segment
+    Gold,
+    Death,
+    RuleViolation,
+}
+
+/// A memorable moment of an attempt
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Highlight {
+    pub kind: HighlightKind,
+    /// Split name, or "Death 12" for deaths
+    pub label: String,
+    /// Wall-clock time in milliseconds since the Unix epoch
+    pub unix_ms: u64,
+    /// Milliseconds from the recording start (`recording_started_unix_ms`),
+    /// or from the start of the attempt when it is not known
+    pub offset_ms: u64,
+    #[serde(default)]
+    pub igt_ms: Option<i64>,
+}
+
+/// Collect the highlights of the current attempt (everything after the last
+/// reset); `golds` are the split keys that set a new gold
+pub fn build(
+    entries: &[RunLogEntry],
+    log_started_unix_ms: u64,
+    recording_started_unix_ms: Option<u64>,
+    golds: &[String],
+) -> Vec<Highlight> {
+    let attempt = storage::current_attempt(entries);
+    let attempt_start_ms = entries.len().checked_sub(attempt.len() + 1).map_or(0, |i| entries[i].elapsed_ms);
+    let origin = recording_started_unix_ms.map_or(attempt_start_ms as i64, |recording| {
+        recording as i64 - log_started_unix_ms as i64
+    });
+
+    attempt
+        .iter()
+        .filter_map(|entry| {
+            let (kind, label) = match &entry.event {
+                RunEvent::PlayerDied { death_count, .. } => (HighlightKind::Death, format!("Death {}", death_count)),
+                RunEvent::TriggerMatched {
+                    name,
+                    kind: TriggerKind::RuleViolation,
+                    ..
+                } => (HighlightKind::RuleViolation, name.clone()),
+                RunEvent::BossDefeated { .. } | RunEvent::TriggerMatched { .. } | RunEvent::SegmentTimed { .. } => {
+                    let key = speech::split_key(&entry.event)?;
+                    let kind = if golds.iter().any(|gold| gold == key) {
+                        HighlightKind::Gold
+                    } else {
+                        HighlightKind::Split
+                    };
+                    (kind, chapters::split_name(&entry.event)?.to_string())
+                }
+                _ => return None,
+            };
+            Some(Highlight {
+                kind,
+                label,
+                unix_ms: log_started_unix_ms + entry.elapsed_ms,
+                offset_ms: (entry.elapsed_ms as i64 - origin).max(0) as u64,
+                igt_ms: entry.igt_ms,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(elapsed_ms: u64, event: RunEvent) -> RunLogEntry {
+        RunLogEntry {
+            elapsed_ms,
+            igt_ms: Some(elapsed_ms as i64 - 500),
+            host_ms: None,
+            menu_time_ms: None,
+            event,
+        }
+    }
+
+    fn boss(boss_id: &str, boss_name: &str) -> RunEvent {
+        RunEvent::BossDefeated {
+            boss_id: boss_id.to_string(),
+            boss_name: boss_name.to_string(),
+            flag_id: 0,
+            suspicion: None,
+            display_name: None,
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_highlights_of_current_attempt() {
+        let entries = vec![
+            entry(1_000, boss("gundyr", "Iudex Gundyr")),
+            entry(5_000, RunEvent::Reset),
+            entry(65_000, boss("gundyr", "Iudex Gundyr")),
+            entry(80_000, RunEvent::PlayerDied { death_count: 3, position: None }),
+            entry(90_000, RunEvent::StatSnapshot { stats: Default::default() }),
+            entry(
+                95_000,
+                RunEvent::TriggerMatched {
+                    index: 0,
+                    trigger_id: "sl2".to_string(),
+                    name: "Soul level above 1".to_string(),
+                    kind: TriggerKind::RuleViolation,
+                    display_name: None,
+                    metadata: Default::default(),
+                },
+            ),
+            entry(
+                120_000,
+                RunEvent::TriggerMatched {
+                    index: 1,
+                    trigger_id: "dancer_warp".to_string(),
+                    name: "Dancer warp".to_string(),
+                    kind: TriggerKind::Split,
+                    display_name: None,
+                    metadata: Default::default(),
+                },
+            ),
+        ];
+
+        let highlights = build(&entries, 1_700_000_000_000, None, &["gundyr".to_string()]);
+        let summary: Vec<_> = highlights.iter().map(|h| (h.kind, h.label.as_str(), h.offset_ms)).collect();
+        assert_eq!(
+            summary,
+            [
+                (HighlightKind::Gold, "Iudex Gundyr", 60_000),
+                (HighlightKind::Death, "Death 3", 75_000),
+                (HighlightKind::RuleViolation, "Soul level above 1", 90_000),
+                (HighlightKind::Split, "Dancer warp", 115_000),
+            ]
+        );
+        assert_eq!(highlights[0].unix_ms, 1_700_000_065_000);
+        assert_eq!(highlights[0].igt_ms, Some(64_500));
+
+        // Aligned to the recording when its start is known
+        let highlights = build(&entries, 1_700_000_000_000, Some(1_700_000_030_000), &[]);
+        assert_eq!(highlights[0].kind, HighlightKind::Split);
+        assert_eq!(highlights[0].offset_ms, 35_000);
+    }
+}
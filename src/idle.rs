@@ -0,0 +1,107 @@
+//! Idle mode while the game is not running
+//!
+//! When no target process is found the loops back off exponentially (up to
+//! `AutosplitterConfig::idle_backoff_max_ms`) instead of enumerating processes
+//! every 2 seconds, so an always-running host pays almost nothing while the
+//! game is closed. Idle waits can be cut short with [`Waker::wake`], e.g. when
+//! the host launches the game itself or the autosplitter is stopped.
+
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+/// Exponential backoff between process searches
+#[derive(Debug, Default)]
+pub struct IdleBackoff {
+    current_ms: u64,
+}
+
+impl IdleBackoff {
+    /// Delay before the next search: `initial_ms`, then doubling up to `max_ms`
+    pub fn next_delay(&mut self, initial_ms: u64, max_ms: u64) -> Duration {
+        let max_ms = max_ms.max(initial_ms);
+        self.current_ms = if self.current_ms == 0 {
+            initial_ms
+        } else {
+            self.current_ms.saturating_mul(2).min(max_ms)
+        };
+        Duration::from_millis(self.current_ms)
+    }
+
+    /// Go back to the initial delay (after attaching or a wake-up)
+    pub fn reset(&mut self) {
+        self.current_ms = 0;
+    }
+}
+
+/// Interruptible sleep shared between the host and the autosplitter loop
+#[derive(Debug, Default)]
+pub struct Waker {
+    woken: Mutex<bool>,
+    condvar: Condvar,
+}
+
+impl Waker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wake a pending (or the next) `wait_timeout`
+    pub fn wake(&self) {
+        *self.woken.lock().unwrap() = true;
+        self.condvar.notify_all();
+    }
+
+    /// Sleep up to `timeout`; returns true if woken early
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let woken = self.woken.lock().unwrap();
+        let (mut woken, _) = self
+            .condvar
+            .wait_timeout_while(woken, timeout, |woken| !*woken)
+            .unwrap();
+        std::mem::take(&mut *woken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    #[test]
+    fn test_backoff_doubles_up_to_cap() {
+        let mut backoff = IdleBackoff::default();
+        let delays: Vec<u64> = (0..6)
+            .map(|_| backoff.next_delay(2000, 15_000).as_millis() as u64)
+            .collect();
+        assert_eq!(delays, vec![2000, 4000, 8000, 15_000, 15_000, 15_000]);
+
+        backoff.reset();
+        assert_eq!(backoff.next_delay(2000, 15_000), Duration::from_millis(2000));
+    }
+
+    #[test]
+    fn test_cap_below_initial() {
+        let mut backoff = IdleBackoff::default();
+        backoff.next_delay(2000, 500);
+        assert_eq!(backoff.next_delay(2000, 500), Duration::from_millis(2000));
+    }
+
+    #[test]
+    fn test_wake_interrupts_wait() {
+        let waker = Arc::new(Waker::new());
+        let remote = waker.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            remote.wake();
+        });
+
+        let start = Instant::now();
+        assert!(waker.wait_timeout(Duration::from_secs(10)));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        handle.join().unwrap();
+
+        // Wake is consumed
+        assert!(!waker.wait_timeout(Duration::from_millis(1)));
+    }
+}
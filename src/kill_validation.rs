@@ -0,0 +1,142 @@
+//! Boss kill validation
+//!
+//! Heuristic that marks boss flags as suspicious when they were most likely
+//! not earned during the run: the flag appeared while IGT had not advanced
+//! since attach or since the last save load (a finished save was loaded), or
+//! while the player was not loaded. Suspicious kills are still recorded, but
+//! flagged so hosts and moderators can treat them differently.
+
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Extra IGT allowed on top of wall-clock time before a jump counts as a save load
+const IGT_JUMP_TOLERANCE_MS: i64 = 5_000;
+
+/// Why a boss kill was marked suspicious
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum Suspicion {
+    /// IGT did not advance since attach or since the last save load
+    IgtNotAdvanced,
+    /// Player character was not loaded when the flag was set
+    PlayerNotLoaded,
+    /// Split by the host (`Autosplitter::split_boss`), not read from the game
+    Manual,
+}
+
+/// Per-attach IGT bookkeeping for kill validation
+#[derive(Debug, Default)]
+pub struct KillValidator {
+    /// IGT when the current save was first seen
+    baseline_igt: Option<i64>,
+    /// Last IGT read and when it was read
+    last_igt: Option<(i64, Instant)>,
+}
+
+impl KillValidator {
+    /// Record the current IGT (call once per tick, before checking flags)
+    ///
+    /// IGT going backwards or jumping forward faster than real time means a
+    /// different save was loaded, so the baseline restarts from there.
+    pub fn observe_igt(&mut self, igt_ms: Option<i64>) {
+        self.observe_igt_at(igt_ms, Instant::now());
+    }
+
+    /// `observe_igt` with the time of the read supplied by the caller
+    pub fn observe_igt_at(&mut self, igt_ms: Option<i64>, now: Instant) {
+        // IGT reads 0 on the main menu - wait for a save to be loaded
+        let Some(igt) = igt_ms.filter(|igt| *igt > 0) else {
+            return;
+        };
+
+        let save_loaded = match self.last_igt {
+            None => true,
+            Some((last, at)) => {
+                let elapsed = now.duration_since(at).as_millis() as i64;
+                igt < last || igt - last > elapsed + IGT_JUMP_TOLERANCE_MS
+            }
+        };
+
+        if save_loaded {
+            self.baseline_igt = Some(igt);
+        }
+        self.last_igt = Some((igt, now));
+    }
+
+    /// Check a newly set boss flag against the current IGT and player state
+    pub fn check(&self, igt_ms: Option<i64>, player_loaded: Option<bool>) -> Option<Suspicion> {
+        if player_loaded == Some(false) {
+            return Some(Suspicion::PlayerNotLoaded);
+        }
+
+        match (igt_ms, self.baseline_igt) {
+            (Some(igt), Some(baseline)) if igt <= baseline => Some(Suspicion::IgtNotAdvanced),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_kill_after_igt_advanced() {
+        let start = Instant::now();
+        let mut validator = KillValidator::default();
+        validator.observe_igt_at(Some(60_000), start);
+        validator.observe_igt_at(Some(61_000), start + Duration::from_secs(1));
+
+        assert_eq!(validator.check(Some(61_000), Some(true)), None);
+    }
+
+    #[test]
+    fn test_kill_on_first_igt_read_is_suspicious() {
+        let mut validator = KillValidator::default();
+        validator.observe_igt_at(Some(3_600_000), Instant::now());
+
+        assert_eq!(
+            validator.check(Some(3_600_000), Some(true)),
+            Some(Suspicion::IgtNotAdvanced)
+        );
+    }
+
+    #[test]
+    fn test_loading_finished_save_resets_baseline() {
+        let start = Instant::now();
+        let mut validator = KillValidator::default();
+        validator.observe_igt_at(Some(60_000), start);
+        // Loading a save 2 hours further in
+        validator.observe_igt_at(Some(7_260_000), start + Duration::from_secs(1));
+
+        assert_eq!(
+            validator.check(Some(7_260_000), Some(true)),
+            Some(Suspicion::IgtNotAdvanced)
+        );
+
+        // Going back to an earlier save also restarts the baseline
+        validator.observe_igt_at(Some(30_000), start + Duration::from_secs(2));
+        validator.observe_igt_at(Some(31_000), start + Duration::from_secs(3));
+        assert_eq!(validator.check(Some(31_000), None), None);
+    }
+
+    #[test]
+    fn test_menu_igt_is_ignored() {
+        let mut validator = KillValidator::default();
+        validator.observe_igt_at(Some(0), Instant::now());
+        validator.observe_igt_at(None, Instant::now());
+
+        assert_eq!(validator.check(Some(0), None), None);
+    }
+
+    #[test]
+    fn test_player_not_loaded() {
+        let validator = KillValidator::default();
+        assert_eq!(
+            validator.check(None, Some(false)),
+            Some(Suspicion::PlayerNotLoaded)
+        );
+    }
+}
@@ -29,24 +29,120 @@
 //! let game_data = parse_asl(asl_content, Some("ds3")).unwrap();
 //! ```
 
+pub mod ac6_arena;
+pub mod ac6_missions;
+pub mod achievements;
 pub mod asl;
+pub mod attributes;
+pub mod audio;
+pub mod boss_phases;
+pub mod bundle;
+pub mod cache;
+pub mod capabilities;
+pub mod chapters;
+pub mod clock;
 pub mod config;
+pub mod cross_check;
+pub mod daemon;
+pub mod data_dir;
+#[cfg(feature = "updates")]
+pub mod data_packs;
+pub mod death_tracking;
+pub mod demo;
+#[cfg(feature = "typescript")]
+pub mod docgen;
+pub mod ds2_triggers;
 pub mod engine;
+mod ffi_string;
+pub mod flag_history;
+pub mod flag_ids;
+pub mod frame_rate;
 pub mod game_data;
 pub mod games;
+pub mod handle_health;
+#[cfg(any(feature = "updates", feature = "signatures"))]
+mod hex;
+pub mod highlights;
+pub mod idle;
+pub mod kill_validation;
+pub mod limited_access;
 pub mod memory;
+pub mod menu_time;
+pub mod output;
+pub mod overlay;
+pub mod position_trail;
+pub mod read_plan;
+pub mod read_stats;
+pub mod run_log;
+pub mod sekiro_gauntlet;
+pub mod sekiro_idols;
+pub mod share;
+#[cfg(feature = "signatures")]
+pub mod signing;
+pub mod speech;
+pub mod split_names;
+pub mod storage;
+pub mod timing_rules;
+#[cfg(feature = "typescript")]
+pub mod typescript;
+pub mod warp;
+pub mod watches;
 
 // Re-export commonly used types
-pub use config::{AutosplitterState, BossFlag};
-pub use engine::GenericGame;
+pub use ac6_arena::ArenaOpponent;
+pub use ac6_missions::{MissionTiming, MissionTracker};
+pub use achievements::{Achievement, AchievementProgress, AchievementStatus, AchievementTracker};
+pub use attributes::{AttributeMapping, CanonicalAttribute};
+pub use audio::{Cue, CuePlayer};
+pub use boss_phases::{BossPhaseTracker, BossPhaseWatch, PhaseFlag, ValuePointer};
+pub use bundle::{BundleFile, BundleManifest, RunSetup};
+pub use cache::CompiledGameData;
+pub use chapters::{Chapter, ChapterFormat};
+pub use clock::{Clock, SimulatedClock, Sleeper, SystemClock};
+pub use config::{
+    AudioCueConfig, AutosplitterConfig, AutosplitterState, BossFlag, CompareOp, CustomTrigger, DefeatedBoss,
+    FlagRange, FlagSetMode, SplitMetadata, TriggerCondition, TriggerKind,
+};
+pub use cross_check::{CrossChecker, FlagDisagreement};
+pub use daemon::{Daemon, ServiceHooks};
+pub use death_tracking::{DeathTracker, RespawnPoint};
+pub use demo::{DemoAction, DemoGame, DemoScript, DemoStep};
+pub use engine::{register_engine, unregister_engine, Engine, GenericGame};
+pub use flag_history::{FlagHistory, FlagHistorySegment, FlagHistoryTick};
+pub use flag_ids::{Ds3FlagId, ErFlagId};
+pub use frame_rate::{FrameRate, FrameRateMeter};
 pub use game_data::GameData;
 pub use games::{ArmoredCore6, DarkSouls1, DarkSouls2, DarkSouls3, EldenRing, Sekiro};
-pub use memory::{parse_pattern, resolve_rip_relative, scan_pattern};
+pub use handle_health::{HandleHealth, ReattachReason};
+pub use highlights::{Highlight, HighlightKind};
+pub use idle::{IdleBackoff, Waker};
+pub use kill_validation::{KillValidator, Suspicion};
+pub use limited_access::LimitedAccess;
+pub use memory::{parse_pattern, resolve_rip_relative, scan_pattern, ReadLimits, SandboxedReader};
+pub use menu_time::MenuTimer;
+pub use output::{OutputConfig, OutputLimiter, OutputMessage};
+pub use overlay::SteamOverlay;
+pub use position_trail::{PositionTrail, TrailExport, TrailPoint, WorldPosition};
+pub use read_plan::{FlagBlockReader, ReadPlan, ReadPlanStats};
+pub use read_stats::{ReadMeter, ReadStats};
+pub use run_log::{RunEvent, RunLog, RunLogEntry};
+pub use sekiro_gauntlet::{Gauntlet, GauntletProgress};
+pub use sekiro_idols::SculptorsIdol;
+pub use share::SharedSplits;
+pub use speech::Announcer;
+pub use storage::{JsonFileStorage, MemoryStorage, RunStatistics, Storage, StoredRun};
+pub use timing_rules::{Anchor, AnchorEvent, RuleTimer, TimingRule};
+pub use warp::WarpTracker;
+pub use watches::{Watch, WatchTracker, WatchType};
+#[cfg(feature = "sqlite")]
+pub use storage::SqliteStorage;
+#[cfg(feature = "signatures")]
+pub use signing::{SignedStats, StatsVerification};
 
 // Re-export ASL types
 pub use asl::{parse_asl, AslError, AslResult};
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::CString;
 use std::os::raw::c_char;
 use std::sync::{
@@ -54,7 +150,7 @@ use std::sync::{
     Arc, Mutex,
 };
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use once_cell::sync::Lazy;
 
@@ -77,11 +173,24 @@ pub enum GameType {
 }
 
 impl GameType {
+    /// All built-in game types
+    pub const ALL: [GameType; 6] = [
+        GameType::DarkSouls1,
+        GameType::DarkSouls2,
+        GameType::DarkSouls3,
+        GameType::EldenRing,
+        GameType::Sekiro,
+        GameType::ArmoredCore6,
+    ];
+
     /// Get game type from process name
     pub fn from_process_name(name: &str) -> Option<Self> {
         let name_lower = name.to_lowercase();
         if name_lower.contains("darksoulsremastered") {
             Some(GameType::DarkSouls1)
+        } else if name_lower == "darksouls.exe" {
+            // Prepare to Die Edition
+            Some(GameType::DarkSouls1)
         } else if name_lower.contains("darksoulsiii") {
             Some(GameType::DarkSouls3)
         } else if name_lower.contains("darksoulsii") {
@@ -100,7 +209,7 @@ impl GameType {
     /// Get process names for this game
     pub fn process_names(&self) -> &'static [&'static str] {
         match self {
-            GameType::DarkSouls1 => &["DarkSoulsRemastered.exe"],
+            GameType::DarkSouls1 => &["DarkSoulsRemastered.exe", "DARKSOULS.exe"],
             GameType::DarkSouls2 => &["DarkSoulsII.exe"],
             GameType::DarkSouls3 => &["DarkSoulsIII.exe"],
             GameType::EldenRing => &["eldenring.exe"],
@@ -120,6 +229,23 @@ impl GameType {
             GameType::ArmoredCore6 => "Armored Core VI: Fires of Rubicon",
         }
     }
+
+    /// Built-in game with the flag layout of an engine id
+    pub fn from_engine_id(engine_id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|game| game.engine_id() == engine_id)
+    }
+
+    /// Engine id of the game's flag layout (as used in game data files)
+    pub fn engine_id(&self) -> &'static str {
+        match self {
+            GameType::DarkSouls1 => "ds1_remaster",
+            GameType::DarkSouls2 => "ds2_sotfs",
+            GameType::DarkSouls3 => "ds3",
+            GameType::EldenRing => "elden_ring",
+            GameType::Sekiro => "sekiro",
+            GameType::ArmoredCore6 => "ac6",
+        }
+    }
 }
 
 /// Game state holder for any supported game
@@ -133,6 +259,8 @@ enum GameState {
     ArmoredCore6(ArmoredCore6),
     /// Generic game using data-driven configuration
     Generic(GenericGame),
+    /// Synthetic game of the demo mode (see `demo`)
+    Demo(DemoGame),
 }
 
 #[cfg(target_os = "windows")]
@@ -146,6 +274,7 @@ impl GameState {
             GameState::Sekiro(g) => g.read_event_flag(flag_id),
             GameState::ArmoredCore6(g) => g.read_event_flag(flag_id),
             GameState::Generic(g) => g.read_event_flag(flag_id),
+            GameState::Demo(g) => g.read_event_flag(flag_id),
         }
     }
 
@@ -153,6 +282,7 @@ impl GameState {
         match self {
             GameState::DarkSouls2(g) => g.get_boss_kill_count_raw(flag_id).max(0) as u32,
             GameState::Generic(g) => g.get_kill_count(flag_id),
+            GameState::Demo(g) => g.get_kill_count(flag_id),
             _ => {
                 if self.read_event_flag(flag_id) {
                     1
@@ -172,11 +302,37 @@ impl GameState {
             GameState::Sekiro(g) => g.handle,
             GameState::ArmoredCore6(g) => g.handle,
             GameState::Generic(g) => g.handle,
+            GameState::Demo(_) => HANDLE::default(),
+        }
+    }
+
+    /// Read raw bytes from the game process
+    fn read_memory(&self, address: usize, size: usize) -> Option<Vec<u8>> {
+        if let GameState::Demo(_) = self {
+            return None;
         }
+        memory::reader::read_bytes(self.get_handle(), address, size)
+    }
+
+    /// Writable memory regions of the game process
+    fn writable_regions(&self) -> Vec<memory::MemoryRegion> {
+        if let GameState::Demo(_) = self {
+            return Vec::new();
+        }
+        memory::search::writable_regions(self.get_handle())
+    }
+
+    /// Whether the process exited without being reaped; an exited process
+    /// already fails the exit code check on Windows
+    fn is_zombie(&self) -> bool {
+        false
     }
 
     fn name(&self) -> &str {
         match self {
+            GameState::DarkSouls1(g) if g.version == games::dark_souls_1::Ds1Version::Ptde => {
+                "Dark Souls: Prepare to Die Edition"
+            }
             GameState::DarkSouls1(_) => "Dark Souls Remastered",
             GameState::DarkSouls2(_) => "Dark Souls 2 SOTFS",
             GameState::DarkSouls3(_) => "Dark Souls 3",
@@ -184,6 +340,192 @@ impl GameState {
             GameState::Sekiro(_) => "Sekiro",
             GameState::ArmoredCore6(_) => "Armored Core 6",
             GameState::Generic(g) => &g.game_data.game.name,
+            GameState::Demo(_) => demo::DEMO_GAME_NAME,
+        }
+    }
+
+    /// Built-in game type (None for data-driven games)
+    fn game_type(&self) -> Option<GameType> {
+        match self {
+            GameState::DarkSouls1(_) => Some(GameType::DarkSouls1),
+            GameState::DarkSouls2(_) => Some(GameType::DarkSouls2),
+            GameState::DarkSouls3(_) => Some(GameType::DarkSouls3),
+            GameState::EldenRing(_) => Some(GameType::EldenRing),
+            GameState::Sekiro(_) => Some(GameType::Sekiro),
+            GameState::ArmoredCore6(_) => Some(GameType::ArmoredCore6),
+            GameState::Generic(_) | GameState::Demo(_) => None,
+        }
+    }
+
+    /// Engine id of the flag layout (as used in game data files)
+    fn engine_id(&self) -> Option<&str> {
+        match self {
+            GameState::Generic(g) => Some(&g.game_data.autosplitter.engine),
+            _ => self.game_type().map(|game| game.engine_id()),
+        }
+    }
+
+    /// Read a named game attribute (used by attribute triggers)
+    fn read_attribute(&self, name: &str) -> Option<i64> {
+        // Canonical names (e.g., "health") map to the game's own attribute
+        let name = match self.game_type() {
+            Some(game) => attributes::resolve(game, name),
+            None => name,
+        };
+        match self {
+            GameState::DarkSouls3(g) => g.read_attribute_by_name(name),
+            GameState::EldenRing(g) => g.read_attribute_by_name(name),
+            GameState::Sekiro(g) => g.read_attribute_by_name(name),
+            GameState::ArmoredCore6(g) => g.read_attribute_by_name(name),
+            GameState::Generic(g) => g.read_attribute_by_name(name),
+            GameState::Demo(g) => g.read_attribute_by_name(name),
+            _ => None,
+        }
+    }
+
+    /// Attributes polled into the state every tick
+    fn attribute_names(&self) -> &'static [&'static str] {
+        match self {
+            GameState::DarkSouls3(_) => games::dark_souls_3::ATTRIBUTE_NAMES,
+            GameState::EldenRing(_) => games::elden_ring::ATTRIBUTE_NAMES,
+            GameState::Sekiro(_) => games::sekiro::ATTRIBUTE_NAMES,
+            GameState::ArmoredCore6(_) => games::armored_core_6::ATTRIBUTE_NAMES,
+            GameState::Generic(_) => engine::TIME_ATTRIBUTE_NAMES,
+            GameState::Demo(_) => demo::ATTRIBUTE_NAMES,
+            _ => &[],
+        }
+    }
+
+    /// Attributes recorded in run log stat snapshots
+    fn stat_names(&self) -> &'static [&'static str] {
+        match self {
+            GameState::DarkSouls3(_) => games::dark_souls_3::STAT_NAMES,
+            GameState::EldenRing(_) => games::elden_ring::STAT_NAMES,
+            GameState::Demo(_) => demo::STAT_NAMES,
+            _ => &[],
+        }
+    }
+
+    /// Character level attribute watched for level-up events
+    fn level_attribute(&self) -> Option<&'static str> {
+        match self {
+            GameState::DarkSouls3(_) => Some("soul_level"),
+            GameState::EldenRing(_) => Some("rune_level"),
+            GameState::Demo(_) => Some("soul_level"),
+            _ => None,
+        }
+    }
+
+    fn igt_milliseconds(&self) -> Option<i64> {
+        let igt = match self {
+            GameState::DarkSouls1(g) => g.get_in_game_time_milliseconds(),
+            GameState::DarkSouls2(g) => g.get_in_game_time_milliseconds(),
+            GameState::DarkSouls3(g) => g.get_in_game_time_milliseconds(),
+            GameState::EldenRing(g) => g.get_in_game_time_milliseconds(),
+            GameState::Sekiro(g) => g.get_in_game_time_milliseconds(),
+            GameState::ArmoredCore6(g) => g.get_in_game_time_milliseconds(),
+            GameState::Generic(g) => return g.game_time_milliseconds(),
+            GameState::Demo(g) => return Some(g.igt_milliseconds()),
+        };
+        Some(igt as i64)
+    }
+
+    fn position(&self) -> Option<WorldPosition> {
+        let (x, y, z, map_id) = match self {
+            GameState::DarkSouls1(g) if g.is_ptde() => return None,
+            GameState::DarkSouls1(g) => {
+                let p = g.get_position();
+                (p.x, p.y, p.z, None)
+            }
+            GameState::DarkSouls2(g) => {
+                let p = g.get_position();
+                (p.x, p.y, p.z, None)
+            }
+            GameState::DarkSouls3(g) => {
+                let p = g.get_position();
+                (p.x, p.y, p.z, None)
+            }
+            GameState::EldenRing(g) => {
+                let p = g.get_position();
+                let map_id = u32::from_be_bytes([p.area, p.block, p.region, p.size]);
+                (p.x, p.y, p.z, Some(map_id))
+            }
+            GameState::Sekiro(g) => {
+                let p = g.get_player_position();
+                (p.x, p.y, p.z, None)
+            }
+            GameState::Demo(g) => return Some(g.position()),
+            _ => return None,
+        };
+        Some(WorldPosition { x, y, z, map_id })
+    }
+
+    fn is_player_loaded(&self) -> Option<bool> {
+        match self {
+            GameState::DarkSouls1(g) if g.is_ptde() => None,
+            GameState::DarkSouls1(g) => Some(g.is_player_loaded()),
+            GameState::DarkSouls3(g) => Some(g.is_player_loaded()),
+            GameState::EldenRing(g) => Some(g.is_player_loaded()),
+            GameState::Sekiro(g) => Some(g.is_player_loaded()),
+            GameState::Demo(g) => Some(g.is_player_loaded()),
+            _ => None,
+        }
+    }
+
+    /// Multiplayer activity that can toggle flags outside a run: "colosseum"
+    /// or "online" (other players in the world); Elden Ring only
+    fn pvp_session(&self) -> Option<&'static str> {
+        match self {
+            GameState::EldenRing(g) if g.is_in_colosseum() => Some("colosseum"),
+            GameState::EldenRing(g) if g.remote_player_count() > 0 => Some("online"),
+            _ => None,
+        }
+    }
+
+    /// Whether the game is in the main menu: no save/character loaded, so
+    /// event flags and stats are unset or left over from the last save
+    fn in_main_menu(&self) -> bool {
+        match self {
+            GameState::DarkSouls1(g) => {
+                g.event_flags.is_null_ptr() || g.game_data_man.is_null_ptr() || (!g.is_ptde() && !g.is_player_loaded())
+            }
+            GameState::DarkSouls2(g) => g.event_flag_manager.is_null_ptr() || g.attributes.is_null_ptr(),
+            GameState::DarkSouls3(g) => {
+                g.sprj_event_flag_man.is_null_ptr() || g.game_data_man.is_null_ptr() || !g.is_player_loaded()
+            }
+            GameState::EldenRing(g) => {
+                g.virtual_memory_flag.is_null_ptr() || g.game_data_man.is_null_ptr() || !g.is_player_loaded()
+            }
+            GameState::Sekiro(g) => {
+                g.event_flag_man.is_null_ptr() || g.player_game_data.is_null_ptr() || !g.is_player_loaded()
+            }
+            GameState::ArmoredCore6(g) => g.cs_event_flag_man.is_null_ptr() || g.game_data_man.is_null_ptr(),
+            GameState::Generic(g) => {
+                let flags = if g.engine_type == engine::EngineType::Ds2Sotfs {
+                    "boss_counters"
+                } else {
+                    "event_flags"
+                };
+                g.pointers.get(flags).is_none_or(|p| p.is_null_ptr())
+            }
+            GameState::Demo(_) => false,
+        }
+    }
+
+    /// Death counter, for games with death/respawn tracking
+    fn death_count(&self) -> Option<i64> {
+        match self {
+            GameState::EldenRing(g) => Some(g.read_death_count() as i64),
+            GameState::Demo(g) => Some(g.death_count()),
+            _ => None,
+        }
+    }
+
+    /// Block-level flag access for read plans (data-driven engines only)
+    fn flag_block_reader(&self) -> Option<&dyn FlagBlockReader> {
+        match self {
+            GameState::Generic(g) if g.engine_type != engine::EngineType::Ds2Sotfs => Some(g),
+            _ => None,
         }
     }
 }
@@ -261,6 +603,10 @@ enum GameState {
     EldenRing(EldenRing),
     Sekiro(Sekiro),
     ArmoredCore6(ArmoredCore6),
+    /// Generic game using data-driven configuration
+    Generic(GenericGame),
+    /// Synthetic game of the demo mode (see `demo`)
+    Demo(DemoGame),
 }
 
 #[cfg(target_os = "linux")]
@@ -273,12 +619,16 @@ impl GameState {
             GameState::EldenRing(g) => g.read_event_flag(flag_id),
             GameState::Sekiro(g) => g.read_event_flag(flag_id),
             GameState::ArmoredCore6(g) => g.read_event_flag(flag_id),
+            GameState::Generic(g) => g.read_event_flag(flag_id),
+            GameState::Demo(g) => g.read_event_flag(flag_id),
         }
     }
 
     fn get_boss_kill_count(&self, flag_id: u32) -> u32 {
         match self {
             GameState::DarkSouls2(g) => g.get_boss_kill_count_raw(flag_id).max(0) as u32,
+            GameState::Generic(g) => g.get_kill_count(flag_id),
+            GameState::Demo(g) => g.get_kill_count(flag_id),
             _ => {
                 if self.read_event_flag(flag_id) {
                     1
@@ -297,17 +647,230 @@ impl GameState {
             GameState::EldenRing(g) => g.pid,
             GameState::Sekiro(g) => g.pid,
             GameState::ArmoredCore6(g) => g.pid,
+            GameState::Generic(g) => g.pid,
+            GameState::Demo(_) => 0,
+        }
+    }
+
+    /// Read raw bytes from the game process
+    fn read_memory(&self, address: usize, size: usize) -> Option<Vec<u8>> {
+        if let GameState::Demo(_) = self {
+            return None;
+        }
+        memory::reader::read_bytes(self.get_pid(), address, size)
+    }
+
+    /// Writable memory regions of the game process
+    fn writable_regions(&self) -> Vec<memory::MemoryRegion> {
+        if let GameState::Demo(_) = self {
+            return Vec::new();
+        }
+        memory::search::writable_regions(self.get_pid())
+    }
+
+    /// Whether the process exited without being reaped
+    fn is_zombie(&self) -> bool {
+        match self {
+            GameState::Demo(_) => false,
+            _ => memory::process::is_zombie(self.get_pid() as u32),
         }
     }
 
     fn name(&self) -> &str {
         match self {
+            GameState::DarkSouls1(g) if g.version == games::dark_souls_1::Ds1Version::Ptde => {
+                "Dark Souls: Prepare to Die Edition"
+            }
             GameState::DarkSouls1(_) => "Dark Souls Remastered",
             GameState::DarkSouls2(_) => "Dark Souls 2 SOTFS",
             GameState::DarkSouls3(_) => "Dark Souls 3",
             GameState::EldenRing(_) => "Elden Ring",
             GameState::Sekiro(_) => "Sekiro",
             GameState::ArmoredCore6(_) => "Armored Core 6",
+            GameState::Generic(g) => &g.game_data.game.name,
+            GameState::Demo(_) => demo::DEMO_GAME_NAME,
+        }
+    }
+
+    /// Built-in game type (None for data-driven games)
+    fn game_type(&self) -> Option<GameType> {
+        match self {
+            GameState::DarkSouls1(_) => Some(GameType::DarkSouls1),
+            GameState::DarkSouls2(_) => Some(GameType::DarkSouls2),
+            GameState::DarkSouls3(_) => Some(GameType::DarkSouls3),
+            GameState::EldenRing(_) => Some(GameType::EldenRing),
+            GameState::Sekiro(_) => Some(GameType::Sekiro),
+            GameState::ArmoredCore6(_) => Some(GameType::ArmoredCore6),
+            GameState::Generic(_) | GameState::Demo(_) => None,
+        }
+    }
+
+    /// Engine id of the flag layout (as used in game data files)
+    fn engine_id(&self) -> Option<&str> {
+        match self {
+            GameState::Generic(g) => Some(&g.game_data.autosplitter.engine),
+            _ => self.game_type().map(|game| game.engine_id()),
+        }
+    }
+
+    /// Read a named game attribute (used by attribute triggers)
+    fn read_attribute(&self, name: &str) -> Option<i64> {
+        // Canonical names (e.g., "health") map to the game's own attribute
+        let name = match self.game_type() {
+            Some(game) => attributes::resolve(game, name),
+            None => name,
+        };
+        match self {
+            GameState::DarkSouls3(g) => g.read_attribute_by_name(name),
+            GameState::EldenRing(g) => g.read_attribute_by_name(name),
+            GameState::Sekiro(g) => g.read_attribute_by_name(name),
+            GameState::ArmoredCore6(g) => g.read_attribute_by_name(name),
+            GameState::Generic(g) => g.read_attribute_by_name(name),
+            GameState::Demo(g) => g.read_attribute_by_name(name),
+            _ => None,
+        }
+    }
+
+    fn attribute_names(&self) -> &'static [&'static str] {
+        match self {
+            GameState::DarkSouls3(_) => games::dark_souls_3::ATTRIBUTE_NAMES,
+            GameState::EldenRing(_) => games::elden_ring::ATTRIBUTE_NAMES,
+            GameState::Sekiro(_) => games::sekiro::ATTRIBUTE_NAMES,
+            GameState::ArmoredCore6(_) => games::armored_core_6::ATTRIBUTE_NAMES,
+            GameState::Generic(_) => engine::TIME_ATTRIBUTE_NAMES,
+            GameState::Demo(_) => demo::ATTRIBUTE_NAMES,
+            _ => &[],
+        }
+    }
+
+    fn stat_names(&self) -> &'static [&'static str] {
+        match self {
+            GameState::DarkSouls3(_) => games::dark_souls_3::STAT_NAMES,
+            GameState::EldenRing(_) => games::elden_ring::STAT_NAMES,
+            GameState::Demo(_) => demo::STAT_NAMES,
+            _ => &[],
+        }
+    }
+
+    fn level_attribute(&self) -> Option<&'static str> {
+        match self {
+            GameState::DarkSouls3(_) => Some("soul_level"),
+            GameState::EldenRing(_) => Some("rune_level"),
+            GameState::Demo(_) => Some("soul_level"),
+            _ => None,
+        }
+    }
+
+    fn igt_milliseconds(&self) -> Option<i64> {
+        let igt = match self {
+            GameState::DarkSouls1(g) => g.get_in_game_time_milliseconds(),
+            GameState::DarkSouls2(g) => g.get_in_game_time_milliseconds(),
+            GameState::DarkSouls3(g) => g.get_in_game_time_milliseconds(),
+            GameState::EldenRing(g) => g.get_in_game_time_milliseconds(),
+            GameState::Sekiro(g) => g.get_in_game_time_milliseconds(),
+            GameState::ArmoredCore6(g) => g.get_in_game_time_milliseconds(),
+            GameState::Generic(g) => return g.game_time_milliseconds(),
+            GameState::Demo(g) => return Some(g.igt_milliseconds()),
+        };
+        Some(igt as i64)
+    }
+
+    fn position(&self) -> Option<WorldPosition> {
+        let (x, y, z, map_id) = match self {
+            GameState::DarkSouls1(g) if g.is_ptde() => return None,
+            GameState::DarkSouls1(g) => {
+                let p = g.get_position();
+                (p.x, p.y, p.z, None)
+            }
+            GameState::DarkSouls2(g) => {
+                let p = g.get_position();
+                (p.x, p.y, p.z, None)
+            }
+            GameState::DarkSouls3(g) => {
+                let p = g.get_position();
+                (p.x, p.y, p.z, None)
+            }
+            GameState::EldenRing(g) => {
+                let p = g.get_position();
+                let map_id = u32::from_be_bytes([p.area, p.block, p.region, p.size]);
+                (p.x, p.y, p.z, Some(map_id))
+            }
+            GameState::Sekiro(g) => {
+                let p = g.get_player_position();
+                (p.x, p.y, p.z, None)
+            }
+            GameState::Demo(g) => return Some(g.position()),
+            _ => return None,
+        };
+        Some(WorldPosition { x, y, z, map_id })
+    }
+
+    fn is_player_loaded(&self) -> Option<bool> {
+        match self {
+            GameState::DarkSouls1(g) if g.is_ptde() => None,
+            GameState::DarkSouls1(g) => Some(g.is_player_loaded()),
+            GameState::DarkSouls3(g) => Some(g.is_player_loaded()),
+            GameState::EldenRing(g) => Some(g.is_player_loaded()),
+            GameState::Sekiro(g) => Some(g.is_player_loaded()),
+            GameState::Demo(g) => Some(g.is_player_loaded()),
+            _ => None,
+        }
+    }
+
+    /// Multiplayer activity that can toggle flags outside a run: "colosseum"
+    /// or "online" (other players in the world); Elden Ring only
+    fn pvp_session(&self) -> Option<&'static str> {
+        match self {
+            GameState::EldenRing(g) if g.is_in_colosseum() => Some("colosseum"),
+            GameState::EldenRing(g) if g.remote_player_count() > 0 => Some("online"),
+            _ => None,
+        }
+    }
+
+    /// Whether the game is in the main menu: no save/character loaded, so
+    /// event flags and stats are unset or left over from the last save
+    fn in_main_menu(&self) -> bool {
+        match self {
+            GameState::DarkSouls1(g) => {
+                g.event_flags.is_null_ptr() || g.game_data_man.is_null_ptr() || (!g.is_ptde() && !g.is_player_loaded())
+            }
+            GameState::DarkSouls2(g) => g.event_flag_manager.is_null_ptr() || g.attributes.is_null_ptr(),
+            GameState::DarkSouls3(g) => {
+                g.sprj_event_flag_man.is_null_ptr() || g.game_data_man.is_null_ptr() || !g.is_player_loaded()
+            }
+            GameState::EldenRing(g) => {
+                g.virtual_memory_flag.is_null_ptr() || g.game_data_man.is_null_ptr() || !g.is_player_loaded()
+            }
+            GameState::Sekiro(g) => {
+                g.event_flag_man.is_null_ptr() || g.player_game_data.is_null_ptr() || !g.is_player_loaded()
+            }
+            GameState::ArmoredCore6(g) => g.cs_event_flag_man.is_null_ptr() || g.game_data_man.is_null_ptr(),
+            GameState::Generic(g) => {
+                let flags = if g.engine_type == engine::EngineType::Ds2Sotfs {
+                    "boss_counters"
+                } else {
+                    "event_flags"
+                };
+                g.pointers.get(flags).is_none_or(|p| p.is_null_ptr())
+            }
+            GameState::Demo(_) => false,
+        }
+    }
+
+    /// Death counter, for games with death/respawn tracking
+    fn death_count(&self) -> Option<i64> {
+        match self {
+            GameState::EldenRing(g) => Some(g.read_death_count() as i64),
+            GameState::Demo(g) => Some(g.death_count()),
+            _ => None,
+        }
+    }
+
+    /// Block-level flag access for read plans (data-driven engines only)
+    fn flag_block_reader(&self) -> Option<&dyn FlagBlockReader> {
+        match self {
+            GameState::Generic(g) if g.engine_type != engine::EngineType::Ds2Sotfs => Some(g),
+            _ => None,
         }
     }
 }
@@ -377,6 +940,21 @@ pub struct Autosplitter {
     state: Arc<Mutex<AutosplitterState>>,
     running: Arc<AtomicBool>,
     reset_requested: Arc<AtomicBool>,
+    triggers: Arc<Mutex<Vec<CustomTrigger>>>,
+    watches: Arc<Mutex<Vec<Watch>>>,
+    config: Arc<Mutex<AutosplitterConfig>>,
+    run_log: Arc<Mutex<RunLog>>,
+    position_trail: Arc<Mutex<PositionTrail>>,
+    flag_history: Arc<Mutex<FlagHistory>>,
+    audio: Arc<Mutex<CuePlayer>>,
+    announcer: Arc<Mutex<Announcer>>,
+    waker: Arc<Waker>,
+    storage: Arc<Mutex<Option<Box<dyn Storage>>>>,
+    output: Arc<Mutex<OutputLimiter>>,
+    run_setup: Arc<Mutex<Option<RunSetup>>>,
+    cross_check: Arc<Mutex<Option<Arc<CompiledGameData>>>>,
+    clock: Arc<dyn Clock>,
+    sleeper: Arc<dyn Sleeper>,
 }
 
 unsafe impl Send for Autosplitter {}
@@ -391,10 +969,53 @@ impl Default for Autosplitter {
 impl Autosplitter {
     /// Create a new autosplitter instance
     pub fn new() -> Self {
+        Self::with_clock(Arc::new(SystemClock), Arc::new(SystemClock))
+    }
+
+    /// Create an autosplitter whose run loops take time and sleeps from
+    /// `clock` and `sleeper` (e.g. a `SimulatedClock` in tests)
+    pub fn with_clock(clock: Arc<dyn Clock>, sleeper: Arc<dyn Sleeper>) -> Self {
         Self {
             state: Arc::new(Mutex::new(AutosplitterState::default())),
             running: Arc::new(AtomicBool::new(false)),
             reset_requested: Arc::new(AtomicBool::new(false)),
+            triggers: Arc::new(Mutex::new(Vec::new())),
+            watches: Arc::new(Mutex::new(Vec::new())),
+            config: Arc::new(Mutex::new(AutosplitterConfig::default())),
+            run_log: Arc::new(Mutex::new(RunLog::new())),
+            position_trail: Arc::new(Mutex::new(PositionTrail::new())),
+            flag_history: Arc::new(Mutex::new(FlagHistory::new())),
+            audio: Arc::new(Mutex::new(CuePlayer::new())),
+            announcer: Arc::new(Mutex::new(Announcer::new())),
+            waker: Arc::new(Waker::new()),
+            storage: Arc::new(Mutex::new(None)),
+            output: Arc::new(Mutex::new(OutputLimiter::new())),
+            run_setup: Arc::new(Mutex::new(None)),
+            cross_check: Arc::new(Mutex::new(None)),
+            clock,
+            sleeper,
+        }
+    }
+
+    fn loop_context(&self) -> LoopContext {
+        LoopContext {
+            running: self.running.clone(),
+            state: self.state.clone(),
+            reset_requested: self.reset_requested.clone(),
+            triggers: self.triggers.clone(),
+            watches: self.watches.clone(),
+            config: self.config.clone(),
+            run_log: self.run_log.clone(),
+            position_trail: self.position_trail.clone(),
+            flag_history: self.flag_history.clone(),
+            audio: self.audio.clone(),
+            announcer: self.announcer.clone(),
+            waker: self.waker.clone(),
+            storage: self.storage.clone(),
+            run_setup: self.run_setup.clone(),
+            cross_check: self.cross_check.clone(),
+            clock: self.clock.clone(),
+            sleeper: self.sleeper.clone(),
         }
     }
 
@@ -411,6 +1032,7 @@ impl Autosplitter {
     /// Stop the autosplitter
     pub fn stop(&self) {
         self.running.store(false, Ordering::SeqCst);
+        self.waker.wake();
         let mut state = self.state.lock().unwrap();
         state.running = false;
         state.process_attached = false;
@@ -418,23 +1040,295 @@ impl Autosplitter {
         log::info!("Autosplitter stopped");
     }
 
+    /// Search for the game process now instead of waiting for the idle backoff
+    /// (e.g., when the host has just launched the game)
+    pub fn wake(&self) {
+        self.waker.wake();
+    }
+
     /// Reset the autosplitter (re-check all flags)
     pub fn reset(&self) {
         self.reset_requested.store(true, Ordering::SeqCst);
         let mut state = self.state.lock().unwrap();
         state.bosses_defeated.clear();
+        state.suspicious_bosses.clear();
         state.boss_kill_counts.clear();
+        state.bosses_suppressed.clear();
         log::info!("Autosplitter reset - will re-check all flags");
     }
 
-    /// Get list of defeated boss IDs
+    /// Split a configured boss from the host (e.g., a split hotkey while
+    /// memory access is denied); logged as a `boss_defeated` event with the
+    /// `manual` suspicion. Returns false if the boss was already defeated.
+    pub fn split_boss(&self, boss_id: &str) -> Result<bool, String> {
+        let setup = self.run_setup.lock().unwrap().clone().ok_or("No run has been started")?;
+        let (split_index, boss) = setup
+            .boss_flags
+            .iter()
+            .enumerate()
+            .find(|(_, boss)| boss.boss_id == boss_id)
+            .ok_or_else(|| format!("Unknown boss '{}'", boss_id))?;
+
+        let elapsed_ms = self.run_log.lock().unwrap().elapsed_ms();
+        let mut state = self.state.lock().unwrap();
+        let igt_ms = state.attributes.get("igt").copied();
+        let recorded = state.record_defeat(DefeatedBoss {
+            split_index,
+            boss_id: boss.boss_id.clone(),
+            elapsed_ms,
+            igt_ms,
+        });
+        if !recorded {
+            return Ok(false);
+        }
+        state.suspicious_bosses.push(boss.boss_id.clone());
+        let kill_count = *state.boss_kill_counts.entry(boss.boss_id.clone()).or_insert(1);
+        drop(state);
+        log::info!("Boss split manually: {} (id={})", boss.boss_name, boss.boss_id);
+
+        let name_template = self.config.lock().unwrap().split_name_template.clone();
+        let display_name = name_template.as_deref().map(|template| {
+            split_names::render(
+                template,
+                &split_names::SplitContext {
+                    name: &boss.boss_name,
+                    id: &boss.boss_id,
+                    kill_count,
+                    igt_ms,
+                    metadata: &boss.metadata,
+                },
+            )
+        });
+        self.loop_context().log_event(RunEvent::BossDefeated {
+            boss_id: boss.boss_id.clone(),
+            boss_name: boss.boss_name.clone(),
+            flag_id: boss.flag_id,
+            suspicion: Some(Suspicion::Manual),
+            display_name,
+            metadata: boss.metadata.clone(),
+        });
+        Ok(true)
+    }
+
+    /// Calibrate against the host application's timer (`host_now_ms` is the
+    /// host's current reading); run log entries then carry `host_ms` as well.
+    /// Call again periodically to correct drift. Returns the offset between
+    /// the host timer and the run log's `elapsed_ms`.
+    pub fn calibrate_clock(&self, host_now_ms: i64) -> i64 {
+        let offset = self.run_log.lock().unwrap().calibrate_host_clock(host_now_ms);
+        log::info!("Host clock calibrated (offset {} ms)", offset);
+        offset
+    }
+
+    /// Replace the custom triggers checked alongside boss flags
+    /// Can be called while running; previously matched triggers are forgotten
+    pub fn set_triggers(&self, triggers: Vec<CustomTrigger>) {
+        log::info!("Custom triggers updated ({} triggers)", triggers.len());
+        *self.triggers.lock().unwrap() = triggers;
+        self.state.lock().unwrap().triggers_matched.clear();
+    }
+
+    /// Add a value watch, replacing the watch with the same id
+    pub fn add_watch(&self, watch: Watch) -> Result<(), String> {
+        watch.validate()?;
+        let mut watches = self.watches.lock().unwrap();
+        watches.retain(|existing| existing.id != watch.id);
+        log::info!("Watch added: {}", watch.id);
+        watches.push(watch);
+        Ok(())
+    }
+
+    /// Remove a value watch; false if no watch has the id
+    pub fn remove_watch(&self, id: &str) -> bool {
+        let mut watches = self.watches.lock().unwrap();
+        let count = watches.len();
+        watches.retain(|watch| watch.id != id);
+        watches.len() != count
+    }
+
+    /// Registered value watches
+    pub fn get_watches(&self) -> Vec<Watch> {
+        self.watches.lock().unwrap().clone()
+    }
+
+    /// Get current settings
+    pub fn get_config(&self) -> AutosplitterConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Replace settings (applied on the next tick)
+    pub fn set_config(&self, config: AutosplitterConfig) {
+        self.run_log.lock().unwrap().set_max_entries(config.run_log_max_entries);
+        *self.config.lock().unwrap() = config;
+    }
+
+    /// Get run log entries recorded since the previous call
+    pub fn poll_events(&self) -> Vec<RunLogEntry> {
+        self.run_log.lock().unwrap().poll_new()
+    }
+
+    /// Get new run log entries as rate-limited integration messages, with
+    /// floods of splits coalesced into bulk syncs (see `OutputConfig`)
+    /// Shares the cursor with `poll_events`; hosts should use one or the other
+    pub fn poll_output(&self) -> Vec<OutputMessage> {
+        let config = self.config.lock().unwrap().output.clone();
+        let entries = self.run_log.lock().unwrap().poll_new();
+        let mut output = self.output.lock().unwrap();
+        output.push(entries, &config);
+        output.drain(&config, self.clock.now())
+    }
+
+    /// Export the full run log as JSON
+    pub fn get_run_log_json(&self) -> String {
+        self.run_log.lock().unwrap().to_json()
+    }
+
+    /// Play an audio cue (e.g., `Cue::Gold` when the host detects a best segment)
+    pub fn play_cue(&self, cue: Cue) {
+        let audio_cues = self.config.lock().unwrap().audio_cues.clone();
+        self.audio.lock().unwrap().play(&audio_cues, cue);
+    }
+
+    /// Export the position trail with run events as JSON
+    pub fn get_position_trail_json(&self) -> String {
+        let events = self.run_log.lock().unwrap().entries().to_vec();
+        self.position_trail.lock().unwrap().to_json(&events)
+    }
+
+    /// Export the position trail with split waypoints as GPX-like XML
+    pub fn get_position_trail_gpx(&self) -> String {
+        let name = self.state.lock().unwrap().game_id.clone();
+        let events = self.run_log.lock().unwrap().entries().to_vec();
+        self.position_trail.lock().unwrap().to_gpx(&name, &events)
+    }
+
+    /// VOD chapters for the current attempt
+    pub fn get_chapters(&self, format: ChapterFormat) -> String {
+        let recording_started = self.config.lock().unwrap().recording_started_unix_ms;
+        let run_log = self.run_log.lock().unwrap();
+        let chapters = chapters::build(run_log.entries(), run_log.started_unix_ms(), recording_started);
+        format.render(&chapters)
+    }
+
+    /// Highlights of the current attempt so far as JSON (Highlight array);
+    /// golds are only known once the attempt is stored at its end
+    pub fn get_highlights_json(&self) -> String {
+        let recording_started = self.config.lock().unwrap().recording_started_unix_ms;
+        let run_log = self.run_log.lock().unwrap();
+        let highlights = highlights::build(run_log.entries(), run_log.started_unix_ms(), recording_started, &[]);
+        serde_json::to_string(&highlights).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Write a verification bundle (zip of the run log, splits, config and
+    /// diagnostics) of the current run to `path`
+    pub fn export_bundle(&self, path: &std::path::Path) -> Result<(), String> {
+        let zip = self.loop_context().bundle_bytes()?;
+        std::fs::write(path, zip).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Export the boss flags of the current run, the custom triggers and the
+    /// timing rules as a URL-safe share string (see `share`)
+    pub fn export_splits(&self) -> Result<String, String> {
+        let setup = self.run_setup.lock().unwrap().clone().ok_or("No run has been started")?;
+        let game = match &setup.game_data {
+            Some(game_data) => game_data.autosplitter.engine.clone(),
+            None => {
+                let game_id = self.state.lock().unwrap().game_id.clone();
+                GameType::ALL
+                    .iter()
+                    .find(|g| format!("{:?}", g) == game_id)
+                    .map_or(game_id, |g| g.engine_id().to_string())
+            }
+        };
+
+        share::encode(&SharedSplits {
+            game,
+            boss_flags: setup.boss_flags,
+            triggers: self.triggers.lock().unwrap().clone(),
+            timing_rules: self.config.lock().unwrap().timing_rules.clone(),
+        })
+    }
+
+    /// Import a share string: its custom triggers and timing rules replace
+    /// the current ones; the boss flags are returned for the next `start`
+    pub fn import_splits(&self, text: &str) -> Result<SharedSplits, String> {
+        let splits = share::decode(text)?;
+        log::info!(
+            "Imported {} split configuration ({} bosses, {} triggers, {} timing rules)",
+            splits.game,
+            splits.boss_flags.len(),
+            splits.triggers.len(),
+            splits.timing_rules.len()
+        );
+        self.set_triggers(splits.triggers.clone());
+        self.config.lock().unwrap().timing_rules = splits.timing_rules.clone();
+        Ok(splits)
+    }
+
+    /// Read boss flags through a second game definition as well, e.g. the
+    /// generic engine alongside a built-in game (applied on the next attach);
+    /// None turns cross-checking off. See `cross_check_flags` in the config.
+    pub fn set_cross_check(&self, compiled: Option<Arc<CompiledGameData>>) {
+        match &compiled {
+            Some(c) => log::info!("Cross-check reader set ({} engine)", c.game_data.autosplitter.engine),
+            None => log::info!("Cross-check reader cleared"),
+        }
+        *self.cross_check.lock().unwrap() = compiled;
+    }
+
+    /// Export the watched flags' values of the last `flag_history_seconds`,
+    /// tick by tick, as JSON
+    pub fn get_flag_history_json(&self) -> String {
+        self.flag_history.lock().unwrap().to_json()
+    }
+
+    /// Store finished attempts (on reset and when stopping) in `storage`
+    pub fn set_storage(&self, storage: Box<dyn Storage>) {
+        *self.storage.lock().unwrap() = Some(storage);
+    }
+
+    /// Stop storing attempts
+    pub fn clear_storage(&self) {
+        *self.storage.lock().unwrap() = None;
+    }
+
+    /// Export the stored golds and runs of a game, signed with the key at
+    /// `key_path` (generated on first use; see `signing`)
+    #[cfg(feature = "signatures")]
+    pub fn export_signed_stats(&self, game_id: &str, key_path: &std::path::Path) -> Result<SignedStats, String> {
+        let key = signing::LocalKey::load_or_generate(key_path)?;
+        let storage = self.storage.lock().unwrap();
+        let storage = storage.as_ref().ok_or("No storage set")?;
+        signing::export_stats(storage.as_ref(), game_id, &key)
+    }
+
+    /// Verify signed stats exported on another machine and store their runs
+    #[cfg(feature = "signatures")]
+    pub fn import_signed_stats(&self, stats: &SignedStats, trusted_keys: &[String]) -> Result<StatsVerification, String> {
+        let mut storage = self.storage.lock().unwrap();
+        let storage = storage.as_mut().ok_or("No storage set")?;
+        signing::import_stats(storage.as_mut(), stats, trusted_keys)
+    }
+
+    /// Get list of defeated boss IDs in split order
     pub fn get_defeated_bosses(&self) -> Vec<String> {
-        self.state.lock().unwrap().bosses_defeated.clone()
+        self.state.lock().unwrap().defeated_boss_ids()
     }
 
     /// Start autosplitter for a specific game with boss flags
+    ///
+    /// A definition of the game in the configured data directory replaces
+    /// the built-in reader (see `data_dir`).
+    pub fn start(&self, game_type: GameType, boss_flags: Vec<BossFlag>) -> Result<(), String> {
+        match self.data_dir_definition(game_type) {
+            Some(compiled) => self.start_generic(compiled, boss_flags),
+            None => self.start_builtin(game_type, boss_flags),
+        }
+    }
+
+    /// Start the built-in reader of a game
     #[cfg(target_os = "windows")]
-    pub fn start(
+    fn start_builtin(
         &self,
         game_type: GameType,
         boss_flags: Vec<BossFlag>,
@@ -453,21 +1347,9 @@ impl Autosplitter {
             boss_flags.len()
         );
 
-        self.running.store(true, Ordering::SeqCst);
-
-        {
-            let mut state = self.state.lock().unwrap();
-            state.running = true;
-            state.process_attached = false;
-            state.game_id = format!("{:?}", game_type);
-            state.process_id = None;
-            state.bosses_defeated.clear();
-            state.boss_kill_counts.clear();
-        }
+        self.reset_for_start(format!("{:?}", game_type), &boss_flags, None);
 
-        let running = self.running.clone();
-        let state = self.state.clone();
-        let reset_requested = self.reset_requested.clone();
+        let ctx = self.loop_context();
         let process_names: Vec<String> = game_type
             .process_names()
             .iter()
@@ -477,9 +1359,7 @@ impl Autosplitter {
         thread::spawn(move || {
             log::info!("Autosplitter thread started");
             run_autosplitter_loop(
-                running,
-                state,
-                reset_requested,
+                ctx,
                 game_type,
                 process_names,
                 boss_flags,
@@ -490,7 +1370,7 @@ impl Autosplitter {
     }
 
     #[cfg(target_os = "linux")]
-    pub fn start(
+    fn start_builtin(
         &self,
         game_type: GameType,
         boss_flags: Vec<BossFlag>,
@@ -509,21 +1389,9 @@ impl Autosplitter {
             boss_flags.len()
         );
 
-        self.running.store(true, Ordering::SeqCst);
-
-        {
-            let mut state = self.state.lock().unwrap();
-            state.running = true;
-            state.process_attached = false;
-            state.game_id = format!("{:?}", game_type);
-            state.process_id = None;
-            state.bosses_defeated.clear();
-            state.boss_kill_counts.clear();
-        }
+        self.reset_for_start(format!("{:?}", game_type), &boss_flags, None);
 
-        let running = self.running.clone();
-        let state = self.state.clone();
-        let reset_requested = self.reset_requested.clone();
+        let ctx = self.loop_context();
         let process_names: Vec<String> = game_type
             .process_names()
             .iter()
@@ -533,9 +1401,7 @@ impl Autosplitter {
         thread::spawn(move || {
             log::info!("Autosplitter thread started (Linux)");
             run_autosplitter_loop_linux(
-                running,
-                state,
-                reset_requested,
+                ctx,
                 game_type,
                 process_names,
                 boss_flags,
@@ -545,13 +1411,65 @@ impl Autosplitter {
         Ok(())
     }
 
-    /// Start autosplitter with data-driven game configuration
-    #[cfg(target_os = "windows")]
+    /// Definition of a built-in game in the configured data directory, which
+    /// replaces the built-in reader (see `data_dir`)
+    fn data_dir_definition(&self, game_type: GameType) -> Option<Arc<CompiledGameData>> {
+        let dir = self.config.lock().unwrap().data_dir.clone()?;
+        data_dir::load_definition(std::path::Path::new(&dir), game_type.engine_id())
+    }
+
+    /// Start autosplitter with data-driven game configuration
     pub fn start_with_game_data(
         &self,
         game_data: GameData,
         boss_flags: Vec<BossFlag>,
     ) -> Result<(), String> {
+        let compiled = CompiledGameData::compile(game_data)?;
+        self.start_with_compiled(Arc::new(compiled), boss_flags)
+    }
+
+    /// Start with the installed data pack for an engine id (e.g. "ds3")
+    /// instead of the built-in reader (see `data_packs`); the pack runs on the
+    /// generic engine even when it describes a built-in game
+    #[cfg(feature = "updates")]
+    pub fn start_with_data_pack(&self, game: &str, boss_flags: Vec<BossFlag>) -> Result<(), String> {
+        let compiled = data_packs::installed(game).ok_or_else(|| format!("No data pack installed for '{}'", game))?;
+        self.start_generic(compiled, boss_flags)
+    }
+
+    /// Start autosplitter with an already compiled game configuration
+    ///
+    /// Definitions of a built-in game (detected from its process names) run
+    /// the built-in reader, which is more reliable. Definitions from the data
+    /// directory (used by `start`) and data packs run on the generic engine
+    /// instead.
+    pub fn start_with_compiled(
+        &self,
+        compiled: Arc<CompiledGameData>,
+        mut boss_flags: Vec<BossFlag>,
+    ) -> Result<(), String> {
+        // Try to detect if this is a known game type - use hardcoded implementations for better reliability
+        let known_game_type = compiled.game_data.game.process_names.iter()
+            .find_map(|name| GameType::from_process_name(name));
+        let Some(game_type) = known_game_type else {
+            return self.start_generic(compiled, boss_flags);
+        };
+
+        self.prepare_compiled(&compiled, &mut boss_flags)?;
+        log::info!(
+            "Detected known game type {:?} from GameData, using hardcoded implementation",
+            game_type
+        );
+        self.start_builtin(game_type, boss_flags)?;
+        if let Some(setup) = self.run_setup.lock().unwrap().as_mut() {
+            setup.game_data = Some(compiled.game_data.clone());
+        }
+        Ok(())
+    }
+
+    /// Check the boss flags and apply a compiled definition's boss metadata
+    /// and triggers before starting
+    fn prepare_compiled(&self, compiled: &CompiledGameData, boss_flags: &mut [BossFlag]) -> Result<(), String> {
         if self.running.load(Ordering::SeqCst) {
             return Err("Autosplitter already running".to_string());
         }
@@ -559,50 +1477,41 @@ impl Autosplitter {
         if boss_flags.is_empty() {
             return Err("No boss flags defined".to_string());
         }
+        compiled.game_data.fill_boss_metadata(boss_flags);
 
-        // Try to detect if this is a known game type - use hardcoded implementations for better reliability
-        let known_game_type = game_data.game.process_names.iter()
-            .find_map(|name| GameType::from_process_name(name));
-
-        if let Some(game_type) = known_game_type {
-            log::info!(
-                "Detected known game type {:?} from GameData, using hardcoded implementation",
-                game_type
-            );
-            return self.start(game_type, boss_flags);
+        if !compiled.game_data.triggers.is_empty() {
+            self.set_triggers(compiled.game_data.triggers.clone());
         }
+        Ok(())
+    }
+
+    /// Start the generic engine with a compiled definition, even when it
+    /// describes a built-in game
+    #[cfg(target_os = "windows")]
+    fn start_generic(
+        &self,
+        compiled: Arc<CompiledGameData>,
+        mut boss_flags: Vec<BossFlag>,
+    ) -> Result<(), String> {
+        self.prepare_compiled(&compiled, &mut boss_flags)?;
 
         log::info!(
             "Starting autosplitter for {} (engine: {}) with {} boss flags",
-            game_data.game.name,
-            game_data.autosplitter.engine,
+            compiled.game_data.game.name,
+            compiled.game_data.autosplitter.engine,
             boss_flags.len()
         );
 
-        self.running.store(true, Ordering::SeqCst);
-
-        {
-            let mut state = self.state.lock().unwrap();
-            state.running = true;
-            state.process_attached = false;
-            state.game_id = game_data.game.id.clone();
-            state.process_id = None;
-            state.bosses_defeated.clear();
-            state.boss_kill_counts.clear();
-        }
+        self.reset_for_start(compiled.game_data.game.id.clone(), &boss_flags, Some(compiled.game_data.clone()));
 
-        let running = self.running.clone();
-        let state = self.state.clone();
-        let reset_requested = self.reset_requested.clone();
-        let process_names = game_data.game.process_names.clone();
+        let ctx = self.loop_context();
+        let process_names = compiled.game_data.game.process_names.clone();
 
         thread::spawn(move || {
             log::info!("Autosplitter thread started (generic engine)");
             run_generic_autosplitter_loop(
-                running,
-                state,
-                reset_requested,
-                game_data,
+                ctx,
+                compiled,
                 process_names,
                 boss_flags,
             );
@@ -612,305 +1521,1268 @@ impl Autosplitter {
     }
 
     #[cfg(target_os = "linux")]
-    pub fn start_with_game_data(
+    fn start_generic(
         &self,
-        game_data: GameData,
-        boss_flags: Vec<BossFlag>,
+        compiled: Arc<CompiledGameData>,
+        mut boss_flags: Vec<BossFlag>,
     ) -> Result<(), String> {
-        if self.running.load(Ordering::SeqCst) {
-            return Err("Autosplitter already running".to_string());
-        }
+        self.prepare_compiled(&compiled, &mut boss_flags)?;
 
-        if boss_flags.is_empty() {
-            return Err("No boss flags defined".to_string());
-        }
+        // The generic engine with Proton support
+        log::info!(
+            "Starting autosplitter for {} (engine: {}) with {} boss flags [Linux/Proton Generic]",
+            compiled.game_data.game.name,
+            compiled.game_data.autosplitter.engine,
+            boss_flags.len()
+        );
 
-        // Try to detect if this is a known game type - use hardcoded implementations for better reliability
-        let known_game_type = game_data.game.process_names.iter()
-            .find_map(|name| GameType::from_process_name(name));
+        self.reset_for_start(compiled.game_data.game.id.clone(), &boss_flags, Some(compiled.game_data.clone()));
 
-        if let Some(game_type) = known_game_type {
-            log::info!(
-                "Detected known game type {:?} from GameData, using hardcoded implementation (Linux)",
-                game_type
+        let ctx = self.loop_context();
+        let process_names = compiled.game_data.game.process_names.clone();
+
+        thread::spawn(move || {
+            log::info!("Autosplitter thread started (generic engine, Linux/Proton)");
+            run_generic_autosplitter_loop_linux(
+                ctx,
+                compiled,
+                process_names,
+                boss_flags,
             );
-            return self.start(game_type, boss_flags);
+        });
+
+        Ok(())
+    }
+
+    /// Start the demo mode: the run loop plays a synthetic game (see `demo`)
+    /// instead of attaching to a process. Empty `boss_flags` watches the demo
+    /// bosses; without a script the bosses are killed one after another.
+    pub fn start_demo(&self, boss_flags: Vec<BossFlag>, script: Option<DemoScript>) -> Result<(), String> {
+        if self.running.load(Ordering::SeqCst) {
+            return Err("Autosplitter already running".to_string());
         }
 
-        // For unknown games, use the generic engine with Proton support
+        let boss_flags = if boss_flags.is_empty() { demo::default_bosses() } else { boss_flags };
+        let script = script.unwrap_or_else(|| DemoScript::for_bosses(&boss_flags));
+        script.validate()?;
+
         log::info!(
-            "Starting autosplitter for {} (engine: {}) with {} boss flags [Linux/Proton Generic]",
-            game_data.game.name,
-            game_data.autosplitter.engine,
-            boss_flags.len()
+            "Starting demo with {} boss flags and {} scripted steps",
+            boss_flags.len(),
+            script.steps.len()
         );
 
+        self.reset_for_start(demo::DEMO_GAME_NAME.to_string(), &boss_flags, None);
+
+        let ctx = self.loop_context();
+
+        thread::spawn(move || {
+            log::info!("Autosplitter thread started (demo)");
+            run_demo_loop(ctx, boss_flags, script);
+        });
+
+        Ok(())
+    }
+    /// Reset the run state for a new start and record its setup
+    fn reset_for_start(&self, game_id: String, boss_flags: &[BossFlag], game_data: Option<GameData>) {
         self.running.store(true, Ordering::SeqCst);
 
         {
             let mut state = self.state.lock().unwrap();
             state.running = true;
             state.process_attached = false;
-            state.game_id = game_data.game.id.clone();
+            state.game_id = game_id;
             state.process_id = None;
             state.bosses_defeated.clear();
+            state.suspicious_bosses.clear();
             state.boss_kill_counts.clear();
+            state.bosses_suppressed.clear();
+            state.attributes.clear();
+            state.boss_metadata = boss_metadata(boss_flags);
         }
+        self.run_log.lock().unwrap().clear();
+        self.output.lock().unwrap().clear();
+        self.position_trail.lock().unwrap().clear();
+        self.flag_history.lock().unwrap().clear();
+        *self.run_setup.lock().unwrap() = Some(RunSetup {
+            boss_flags: boss_flags.to_vec(),
+            game_data,
+        });
+    }
+}
 
-        let running = self.running.clone();
-        let state = self.state.clone();
-        let reset_requested = self.reset_requested.clone();
-        let process_names = game_data.game.process_names.clone();
+/// Metadata of the bosses that have any, by boss id
+fn boss_metadata(boss_flags: &[BossFlag]) -> HashMap<String, SplitMetadata> {
+    boss_flags
+        .iter()
+        .filter(|boss| !boss.metadata.is_empty())
+        .map(|boss| (boss.boss_id.clone(), boss.metadata.clone()))
+        .collect()
+}
 
-        thread::spawn(move || {
-            log::info!("Autosplitter thread started (generic engine, Linux/Proton)");
-            run_generic_autosplitter_loop_linux(
-                running,
-                state,
-                reset_requested,
-                game_data,
-                process_names,
-                boss_flags,
-            );
-        });
+/// Read plan for custom trigger flags, with the flag list it was compiled for
+type TriggerPlan = (Vec<u32>, ReadPlan);
 
-        Ok(())
-    }
+/// A boss defeat seen this tick, not split yet
+#[derive(Debug, Clone, Copy)]
+struct BossSplit {
+    split_index: usize,
+    kill_count: u32,
+    elapsed_ms: u64,
+    suspicion: Option<Suspicion>,
 }
 
-// =============================================================================
-// Main Loop (Windows)
-// =============================================================================
+/// Splits that fired on one tick, resolved together by trigger group
+#[derive(Debug, Default)]
+struct TickSplits {
+    /// Defeats of bosses that are in a group
+    bosses: Vec<BossSplit>,
+    /// Indices of the custom triggers that fired
+    triggers: Vec<usize>,
+}
 
-#[cfg(target_os = "windows")]
-fn run_autosplitter_loop(
+/// Cross-check reader attached alongside the game, with its agreement tracking
+type CrossCheck = (GameState, CrossChecker);
+
+/// Shared handles passed from the Autosplitter to its worker thread
+#[derive(Clone)]
+struct LoopContext {
     running: Arc<AtomicBool>,
     state: Arc<Mutex<AutosplitterState>>,
     reset_requested: Arc<AtomicBool>,
-    game_type: GameType,
-    process_names: Vec<String>,
-    boss_flags: Vec<BossFlag>,
-) {
-    let mut game_state: Option<GameState> = None;
-    let mut current_handle: Option<HANDLE> = None;
-    let mut checked_flags: HashMap<u32, bool> = HashMap::new();
+    triggers: Arc<Mutex<Vec<CustomTrigger>>>,
+    watches: Arc<Mutex<Vec<Watch>>>,
+    config: Arc<Mutex<AutosplitterConfig>>,
+    run_log: Arc<Mutex<RunLog>>,
+    position_trail: Arc<Mutex<PositionTrail>>,
+    flag_history: Arc<Mutex<FlagHistory>>,
+    audio: Arc<Mutex<CuePlayer>>,
+    announcer: Arc<Mutex<Announcer>>,
+    waker: Arc<Waker>,
+    storage: Arc<Mutex<Option<Box<dyn Storage>>>>,
+    run_setup: Arc<Mutex<Option<RunSetup>>>,
+    cross_check: Arc<Mutex<Option<Arc<CompiledGameData>>>>,
+    clock: Arc<dyn Clock>,
+    sleeper: Arc<dyn Sleeper>,
+}
 
-    while running.load(Ordering::SeqCst) {
-        // Check for reset
-        if reset_requested.swap(false, Ordering::SeqCst) {
-            log::info!("Autosplitter: Reset detected");
-            if let Some(ref game) = game_state {
-                checked_flags.clear();
-                for boss in &boss_flags {
-                    if game.read_event_flag(boss.flag_id) {
-                        checked_flags.insert(boss.flag_id, true);
-                    }
-                }
-            } else {
-                checked_flags.clear();
-            }
-            let mut s = state.lock().unwrap();
-            s.bosses_defeated.clear();
-            s.boss_kill_counts.clear();
-            s.triggers_matched.clear();
-        }
+/// Per-attach bookkeeping for stat snapshots and level-up events
+#[derive(Default)]
+struct StatTracker {
+    last_snapshot: Option<Instant>,
+    last_level: Option<i64>,
+}
 
-        if let Some(ref game) = game_state {
-            // Check if process still running
-            if !memory::process::is_process_running(game.get_handle()) {
-                log::info!("{} process exited", game.name());
-                if let Some(handle) = current_handle.take() {
-                    unsafe {
-                        let _ = CloseHandle(handle);
-                    }
-                }
-                game_state = None;
-                checked_flags.clear();
+impl LoopContext {
+    /// Append an event to the run log, stamped with the current IGT,
+    /// and play its audio cue / announcement
+    fn log_event(&self, event: RunEvent) {
+        let igt = self.state.lock().unwrap().attributes.get("igt").copied();
+        let entry = {
+            let mut run_log = self.run_log.lock().unwrap();
+            run_log.push(event, igt);
+            run_log.entries().last().cloned()
+        };
+        let Some(entry) = entry else {
+            return;
+        };
 
-                let mut s = state.lock().unwrap();
-                s.process_attached = false;
-                s.process_id = None;
-                s.bosses_defeated.clear();
-                s.boss_kill_counts.clear();
-                thread::sleep(Duration::from_millis(1000));
-                continue;
+        let config = self.config.lock().unwrap().clone();
+        if let Some(cue) = Cue::for_event(&entry.event) {
+            self.audio.lock().unwrap().play(&config.audio_cues, cue);
+        }
+        if config.announce_splits {
+            if let Some(text) = speech::announcement_for(&entry, &config.comparison_igt_ms) {
+                self.announcer.lock().unwrap().speak(&text);
             }
+        }
+    }
 
-            // Check boss flags
-            for boss in &boss_flags {
-                let kill_count = game.get_boss_kill_count(boss.flag_id);
+    /// Read all boss flags through the compiled read plan, compiling it on
+    /// first use; None if the game has no plan (boss flags are read one by one)
+    fn read_planned_flags(
+        &self,
+        game: &GameState,
+        boss_flags: &[BossFlag],
+        read_plan: &mut Option<ReadPlan>,
+    ) -> Option<HashMap<u32, bool>> {
+        let reader = game.flag_block_reader()?;
+
+        if read_plan.is_none() {
+            let flag_ids: Vec<u32> = boss_flags.iter().map(|b| b.flag_id).collect();
+            *read_plan = ReadPlan::compile(reader, &flag_ids);
+
+            if let Some(plan) = read_plan {
+                let stats = plan.stats();
+                log::info!(
+                    "Compiled read plan: {} flags in {} blocks, {} reads per tick",
+                    stats.flags,
+                    stats.blocks,
+                    stats.expected_reads_per_tick
+                );
+                self.state.lock().unwrap().read_plan = Some(stats);
+            }
+        }
 
-                if kill_count > 0 {
-                    let mut s = state.lock().unwrap();
+        read_plan.as_ref().map(|plan| plan.execute(reader))
+    }
 
-                    let prev_count = s.boss_kill_counts.get(&boss.boss_id).copied().unwrap_or(0);
-                    if kill_count > prev_count {
-                        s.boss_kill_counts.insert(boss.boss_id.clone(), kill_count);
-                        log::info!(
-                            "Boss kill count updated: {} - count: {} -> {}",
-                            boss.boss_name,
-                            prev_count,
-                            kill_count
-                        );
-                    }
+    /// Check boss flags and record newly defeated bosses; returns the flags
+    /// read and the tick's splits, holding the defeats of grouped bosses
+    fn check_boss_flags(
+        &self,
+        game: &GameState,
+        boss_flags: &[BossFlag],
+        checked_flags: &mut HashMap<u32, bool>,
+        kill_validator: &mut KillValidator,
+        read_plan: &mut Option<ReadPlan>,
+        cross_check: &mut Option<CrossCheck>,
+    ) -> (HashMap<u32, bool>, TickSplits) {
+        let (validate, cross_check_flags) = {
+            let config = self.config.lock().unwrap();
+            (config.validate_boss_kills, config.cross_check_flags.clone())
+        };
+        let igt = if validate { game.igt_milliseconds() } else { None };
+        if validate {
+            kill_validator.observe_igt_at(igt, self.clock.now());
+        }
 
-                    if !s.bosses_defeated.contains(&boss.boss_id) {
-                        s.bosses_defeated.push(boss.boss_id.clone());
-                        checked_flags.insert(boss.flag_id, true);
-                        log::info!(
-                            "Boss defeated: {} (id={}, flag={})",
-                            boss.boss_name,
-                            boss.boss_id,
-                            boss.flag_id
-                        );
+        let flag_states = self.read_planned_flags(game, boss_flags, read_plan);
+        let mut values = HashMap::new();
+        let mut tick = TickSplits::default();
+
+        let elapsed_ms = self.run_log.lock().unwrap().elapsed_ms();
+        for (split_index, boss) in boss_flags.iter().enumerate() {
+            let mut kill_count = match &flag_states {
+                Some(states) => states.get(&boss.flag_id).copied().unwrap_or(false) as u32,
+                None => game.get_boss_kill_count(boss.flag_id),
+            };
+            values.insert(boss.flag_id, kill_count > 0);
+
+            if let Some((reader, checker)) = cross_check.as_mut() {
+                if cross_check::is_cross_checked(&cross_check_flags, boss.flag_id) {
+                    let secondary = reader.get_boss_kill_count(boss.flag_id) > 0;
+                    if !checker.reconcile(boss.flag_id, kill_count > 0, secondary) {
+                        kill_count = 0;
                     }
                 }
             }
-        } else {
-            // Try to connect
-            let process_name_refs: Vec<&str> = process_names.iter().map(|s| s.as_str()).collect();
-            if let Some((pid, name)) = memory::process::find_process_by_name(&process_name_refs) {
-                let handle = unsafe {
-                    match OpenProcess(PROCESS_VM_READ | PROCESS_QUERY_INFORMATION, false, pid) {
-                        Ok(h) => h,
-                        Err(_) => {
-                            thread::sleep(Duration::from_millis(2000));
-                            continue;
-                        }
-                    }
-                };
 
-                // Get module info
-                let mut base = 0usize;
-                let mut size = 0usize;
-                for attempt in 0..5 {
-                    if let Some((b, s)) = memory::process::get_module_base_and_size(pid) {
-                        base = b;
-                        size = s;
-                        break;
-                    }
-                    if attempt < 4 {
-                        thread::sleep(Duration::from_millis(500));
-                    }
+            if kill_count > 0 {
+                let mut s = self.state.lock().unwrap();
+
+                let prev_count = s.boss_kill_counts.get(&boss.boss_id).copied().unwrap_or(0);
+                if kill_count > prev_count {
+                    s.boss_kill_counts.insert(boss.boss_id.clone(), kill_count);
+                    log::info!(
+                        "Boss kill count updated: {} - count: {} -> {}",
+                        boss.boss_name,
+                        prev_count,
+                        kill_count
+                    );
                 }
 
-                if base == 0 {
-                    log::warn!("Failed to get module info for {}", name);
-                    unsafe {
-                        let _ = CloseHandle(handle);
+                if !s.is_defeated(&boss.boss_id) && !s.bosses_suppressed.contains(&boss.boss_id) {
+                    drop(s);
+                    checked_flags.insert(boss.flag_id, true);
+                    let suspicion = if validate {
+                        kill_validator.check(igt, game.is_player_loaded())
+                    } else {
+                        None
+                    };
+                    let defeat = BossSplit {
+                        split_index,
+                        kill_count,
+                        elapsed_ms,
+                        suspicion,
+                    };
+                    // Grouped bosses split with the triggers of the tick
+                    if boss.group.is_some() {
+                        tick.bosses.push(defeat);
+                    } else {
+                        self.split_boss_defeat(boss, defeat);
                     }
-                    thread::sleep(Duration::from_millis(2000));
-                    continue;
                 }
+            }
+        }
 
-                log::info!(
-                    "Found '{}' (PID: {}), base=0x{:X}, size=0x{:X}",
-                    name,
-                    pid,
-                    base,
-                    size
-                );
+        if let Some((_, checker)) = cross_check {
+            self.state.lock().unwrap().flag_disagreements = checker.disagreements();
+        }
+        (values, tick)
+    }
 
-                // Initialize game
-                if let Some(game) = init_game(game_type, handle, base, size) {
-                    log::info!("Connected to {}", game.name());
+    /// Record a boss defeat and log its split
+    fn split_boss_defeat(&self, boss: &BossFlag, defeat: BossSplit) {
+        let mut s = self.state.lock().unwrap();
+        let igt_ms = s.attributes.get("igt").copied();
+        s.record_defeat(DefeatedBoss {
+            split_index: defeat.split_index,
+            boss_id: boss.boss_id.clone(),
+            elapsed_ms: defeat.elapsed_ms,
+            igt_ms,
+        });
+        log::info!(
+            "Boss defeated: {} (id={}, flag={})",
+            boss.boss_name,
+            boss.boss_id,
+            boss.flag_id
+        );
+        if let Some(reason) = defeat.suspicion {
+            log::warn!("Suspicious boss flag: {} ({:?})", boss.boss_name, reason);
+            s.suspicious_bosses.push(boss.boss_id.clone());
+        }
+        drop(s);
+
+        let name_template = self.config.lock().unwrap().split_name_template.clone();
+        let display_name = name_template.as_deref().map(|template| {
+            split_names::render(
+                template,
+                &split_names::SplitContext {
+                    name: &boss.boss_name,
+                    id: &boss.boss_id,
+                    kill_count: defeat.kill_count,
+                    igt_ms,
+                    metadata: &boss.metadata,
+                },
+            )
+        });
+        self.log_event(RunEvent::BossDefeated {
+            boss_id: boss.boss_id.clone(),
+            boss_name: boss.boss_name.clone(),
+            flag_id: boss.flag_id,
+            suspicion: defeat.suspicion,
+            display_name,
+            metadata: boss.metadata.clone(),
+        });
+    }
 
-                    // Wait for save data to stabilize
-                    log::info!("Waiting for game save data to stabilize...");
-                    thread::sleep(Duration::from_millis(1500));
+    /// End the attempt on a reset and clear its splits; flags of the bosses
+    /// already defeated in the attached `game` are marked as checked
+    fn reset_run(
+        &self,
+        game: Option<&GameState>,
+        boss_flags: &[BossFlag],
+        checked_flags: &mut HashMap<u32, bool>,
+        rule_timer: &mut RuleTimer,
+    ) {
+        log::info!("Autosplitter: Reset detected");
+        let golds = self.store_attempt();
+        self.export_chapters();
+        self.log_highlights(&golds);
+        self.export_bundle();
+        self.log_event(RunEvent::Reset);
+
+        checked_flags.clear();
+        if let Some(game) = game {
+            for boss in boss_flags {
+                if game.read_event_flag(boss.flag_id) {
+                    checked_flags.insert(boss.flag_id, true);
+                }
+            }
+        }
+        let mut s = self.state.lock().unwrap();
+        s.bosses_defeated.clear();
+        s.suspicious_bosses.clear();
+        s.boss_kill_counts.clear();
+        s.triggers_matched.clear();
+        s.bosses_suppressed.clear();
+        rule_timer.reset();
+    }
 
-                    // Pre-populate checked flags
-                    checked_flags.clear();
-                    let mut pre_populated = Vec::new();
-                    for boss in &boss_flags {
-                        if game.read_event_flag(boss.flag_id) {
-                            checked_flags.insert(boss.flag_id, true);
-                            pre_populated.push(boss.boss_name.clone());
-                        }
-                    }
+    /// Hand the current attempt to the storage backend, if one is set and the
+    /// attempt reached at least one split
+    fn store_attempt(&self) -> Vec<String> {
+        let mut storage = self.storage.lock().unwrap();
+        let Some(storage) = storage.as_mut() else {
+            return Vec::new();
+        };
 
-                    if !pre_populated.is_empty() {
-                        log::info!(
-                            "Pre-populated {} already-defeated bosses",
-                            pre_populated.len()
-                        );
-                    }
+        let entries = storage::current_attempt(self.run_log.lock().unwrap().entries()).to_vec();
+        if !entries.iter().any(|e| speech::split_key(&e.event).is_some()) {
+            return Vec::new();
+        }
 
-                    game_state = Some(game);
-                    current_handle = Some(handle);
+        let run = StoredRun {
+            game_id: self.state.lock().unwrap().game_id.clone(),
+            saved_at_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            entries,
+        };
 
-                    let mut s = state.lock().unwrap();
-                    s.process_attached = true;
-                    s.process_id = Some(unsafe { GetProcessId(handle) });
-                } else {
-                    log::error!("Failed to initialize game for {}", name);
-                    unsafe {
-                        let _ = CloseHandle(handle);
-                    }
-                    thread::sleep(Duration::from_millis(2000));
+        match storage::record_run(storage.as_mut(), &run) {
+            Ok(new_golds) => {
+                if !new_golds.is_empty() {
+                    log::info!("New golds: {}", new_golds.join(", "));
+                    let audio_cues = self.config.lock().unwrap().audio_cues.clone();
+                    self.audio.lock().unwrap().play(&audio_cues, Cue::Gold);
                 }
-            } else {
-                thread::sleep(Duration::from_millis(2000));
+                new_golds
+            }
+            Err(e) => {
+                log::warn!("Failed to store run: {}", e);
+                Vec::new()
             }
         }
-
-        thread::sleep(Duration::from_millis(100));
     }
 
-    // Cleanup
-    if let Some(handle) = current_handle {
-        unsafe {
-            let _ = CloseHandle(handle);
+    /// Log the memorable moments of the attempt that just ended; `golds` are
+    /// the splits that set a new gold
+    fn log_highlights(&self, golds: &[String]) {
+        let recording_started = self.config.lock().unwrap().recording_started_unix_ms;
+        let highlights = {
+            let run_log = self.run_log.lock().unwrap();
+            highlights::build(run_log.entries(), run_log.started_unix_ms(), recording_started, golds)
+        };
+        if !highlights.is_empty() {
+            log::info!("Attempt ended with {} highlights", highlights.len());
+            self.log_event(RunEvent::RunHighlights { highlights });
         }
     }
 
-    let mut s = state.lock().unwrap();
-    s.running = false;
-    s.process_attached = false;
-    s.process_id = None;
-}
+    /// Write chapter files for the attempt that just ended to `chapters_dir`
+    fn export_chapters(&self) {
+        let (dir, recording_started) = {
+            let config = self.config.lock().unwrap();
+            (config.chapters_dir.clone(), config.recording_started_unix_ms)
+        };
+        let Some(dir) = dir else {
+            return;
+        };
 
-// =============================================================================
-// Generic Game Loop (Windows) - Uses data-driven configuration
-// =============================================================================
+        let (chapters, attempt_ended_unix_ms) = {
+            let run_log = self.run_log.lock().unwrap();
+            (
+                chapters::build(run_log.entries(), run_log.started_unix_ms(), recording_started),
+                run_log.started_unix_ms() + run_log.elapsed_ms(),
+            )
+        };
+        if chapters.is_empty() {
+            return;
+        }
 
-#[cfg(target_os = "windows")]
-fn run_generic_autosplitter_loop(
-    running: Arc<AtomicBool>,
-    state: Arc<Mutex<AutosplitterState>>,
-    reset_requested: Arc<AtomicBool>,
-    game_data: GameData,
+        let game_id = self.state.lock().unwrap().game_id.clone();
+        let dir = std::path::Path::new(&dir);
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            log::warn!("Failed to create chapters directory {}: {}", dir.display(), e);
+            return;
+        }
+        for format in [ChapterFormat::Ffmpeg, ChapterFormat::Youtube] {
+            let path = dir.join(format!("{}-{}.{}", game_id, attempt_ended_unix_ms, format.extension()));
+            match std::fs::write(&path, format.render(&chapters)) {
+                Ok(()) => log::info!("Wrote {} chapters to {}", chapters.len(), path.display()),
+                Err(e) => log::warn!("Failed to write {}: {}", path.display(), e),
+            }
+        }
+    }
+
+    /// Zip the run log, splits, config and diagnostics of the current attempt
+    fn bundle_bytes(&self) -> Result<Vec<u8>, String> {
+        let setup = self.run_setup.lock().unwrap().clone().ok_or("No run has been started")?;
+        let state = self.state.lock().unwrap().clone();
+        let splits = bundle::SplitsFile {
+            boss_flags: setup.boss_flags,
+            triggers: self.triggers.lock().unwrap().clone(),
+        };
+        let config = self.config.lock().unwrap().clone();
+
+        let mut files = vec![
+            ("run_log.json".to_string(), self.run_log.lock().unwrap().to_json().into_bytes()),
+            ("splits.json".to_string(), serde_json::to_vec_pretty(&splits).map_err(|e| e.to_string())?),
+            ("config.json".to_string(), serde_json::to_vec_pretty(&config).map_err(|e| e.to_string())?),
+        ];
+        if let Some(game_data) = &setup.game_data {
+            files.push(("game_data.json".to_string(), serde_json::to_vec_pretty(game_data).map_err(|e| e.to_string())?));
+        }
+        files.push(("diagnostics.json".to_string(), serde_json::to_vec_pretty(&state).map_err(|e| e.to_string())?));
+        files.push(("flag_history.json".to_string(), self.flag_history.lock().unwrap().to_json().into_bytes()));
+
+        let now_unix_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        Ok(bundle::build(&state.game_id, now_unix_ms, files))
+    }
+
+    /// Write a verification bundle for the attempt that just ended to
+    /// `bundle_dir`, if the attempt reached at least one split
+    fn export_bundle(&self) {
+        let Some(dir) = self.config.lock().unwrap().bundle_dir.clone() else {
+            return;
+        };
+
+        let attempt_ended_unix_ms = {
+            let run_log = self.run_log.lock().unwrap();
+            let attempt = storage::current_attempt(run_log.entries());
+            if !attempt.iter().any(|e| speech::split_key(&e.event).is_some()) {
+                return;
+            }
+            run_log.started_unix_ms() + run_log.elapsed_ms()
+        };
+
+        let game_id = self.state.lock().unwrap().game_id.clone();
+        let dir = std::path::Path::new(&dir);
+        let path = dir.join(format!("{}-{}.zip", game_id, attempt_ended_unix_ms));
+        let result = std::fs::create_dir_all(dir)
+            .map_err(|e| e.to_string())
+            .and_then(|_| self.bundle_bytes())
+            .and_then(|zip| std::fs::write(&path, zip).map_err(|e| e.to_string()));
+        match result {
+            Ok(()) => log::info!("Wrote verification bundle to {}", path.display()),
+            Err(e) => log::warn!("Failed to write verification bundle {}: {}", path.display(), e),
+        }
+    }
+
+    /// Track main menu transitions; returns true while in the main menu
+    fn update_menu_state(&self, game: &GameState) -> bool {
+        let in_main_menu = game.in_main_menu();
+        let mut s = self.state.lock().unwrap();
+        if s.in_main_menu != in_main_menu {
+            s.in_main_menu = in_main_menu;
+            if in_main_menu {
+                log::info!("{}: In main menu, pausing flag evaluation", game.name());
+            } else {
+                log::info!("{}: Save loaded, resuming flag evaluation", game.name());
+            }
+        }
+        in_main_menu
+    }
+
+    /// Add this tick's time in menus or paused to the run log's menu time
+    fn account_menu_time(&self, game: &GameState, in_main_menu: bool, timer: &mut MenuTimer) {
+        let loading = game.read_attribute("loading").is_some_and(|value| value != 0);
+        let mut run_log = self.run_log.lock().unwrap();
+        let menu_ms = timer.observe(run_log.elapsed_ms(), game.igt_milliseconds(), in_main_menu, loading);
+        run_log.add_menu_time(menu_ms);
+    }
+
+    /// Log when the game runs under x64 emulation (Windows on ARM) and warn
+    /// when its image is not x86 code, which patterns cannot match
+    fn check_architecture(&self, game: &GameState, base: usize) {
+        let Some(machine) = memory::arch::module_machine(base, |address, size| game.read_memory(address, size)) else {
+            return;
+        };
+        if !machine.is_x86_family() {
+            log::warn!("{}: unexpected image machine {:?}; memory patterns may not match", game.name(), machine);
+        } else if memory::arch::is_emulated(machine) {
+            log::info!("{}: {:?} game running under emulation on an ARM64 host", game.name(), machine);
+        }
+    }
+
+    /// Locate the Steam overlay in a freshly attached game; None if the
+    /// overlay is not loaded (disabled, or not launched through Steam)
+    fn locate_steam_overlay(&self, game: &GameState, pid: u32) -> Option<SteamOverlay> {
+        let (base, _) = overlay::STEAM_OVERLAY_MODULES
+            .iter()
+            .find_map(|module| memory::process::find_module(pid, module))?;
+        let read = |address, size| game.read_memory(address, size);
+        let overlay = SteamOverlay::locate(base, &read)?;
+        log::info!("Steam overlay found at 0x{:X}", base);
+        Some(overlay)
+    }
+
+    /// Attach the cross-check reader, if one is set, to the game's process
+    fn attach_cross_check(&self, game: &GameState, base: usize, size: usize) -> Option<CrossCheck> {
+        let compiled = self.cross_check.lock().unwrap().clone()?;
+        let mut reader = GenericGame::from_compiled(&compiled);
+        #[cfg(target_os = "windows")]
+        let attached = reader.init(game.get_handle(), base, size);
+        #[cfg(target_os = "linux")]
+        let attached = reader.init(game.get_pid(), base, size);
+
+        if !attached {
+            log::warn!("Cross-check reader failed to attach; boss flags are read once");
+            return None;
+        }
+        log::info!("Cross-check reader attached ({:?} engine)", reader.engine_type);
+        Some((GameState::Generic(reader), CrossChecker::default()))
+    }
+
+    /// Refresh `overlay_open`, logging when the overlay opens or closes
+    fn update_overlay_state(&self, game: &GameState, steam_overlay: Option<&SteamOverlay>) {
+        let read = |address, size| game.read_memory(address, size);
+        let overlay_open = steam_overlay.is_some_and(|o| o.is_open(&read));
+        let mut s = self.state.lock().unwrap();
+        if s.overlay_open != overlay_open {
+            s.overlay_open = overlay_open;
+            log::debug!("Steam overlay {}", if overlay_open { "opened" } else { "closed" });
+        }
+    }
+
+    /// Track Colosseum/online sessions; true while split evaluation is
+    /// suspended for one (`suspend_during_pvp`)
+    fn update_pvp_state(&self, game: &GameState) -> bool {
+        let session = game.pvp_session();
+        let suspend = self.config.lock().unwrap().suspend_during_pvp;
+        let mut s = self.state.lock().unwrap();
+        if s.pvp_session.as_deref() != session {
+            match session {
+                Some(kind) if suspend => log::info!("{}: {} session, pausing flag evaluation", game.name(), kind),
+                Some(kind) => log::info!("{}: {} session started", game.name(), kind),
+                None => log::info!("{}: Multiplayer session ended", game.name()),
+            }
+            s.pvp_session = session.map(str::to_string);
+        }
+        suspend && session.is_some()
+    }
+
+    /// Sekiro: track Reflections of Strength and gauntlet progress
+    fn update_gauntlet(&self, game: &GameState) {
+        let GameState::Sekiro(sekiro) = game else {
+            return;
+        };
+        let progress = sekiro_gauntlet::detect(|flag_id| sekiro.read_event_flag(flag_id));
+
+        let mut s = self.state.lock().unwrap();
+        if s.gauntlet == progress {
+            return;
+        }
+        match (&s.gauntlet, &progress) {
+            (None, Some(p)) => log::info!("Entered {}", p.name),
+            (Some(p), None) => log::info!("Left {}", p.name),
+            (_, Some(p)) => log::info!("{}: {}/{} defeated", p.name, p.defeated, p.total),
+            (None, None) => {}
+        }
+        s.gauntlet = progress;
+    }
+
+    /// AC6: track mission attempts, restarts and clears for IL timing
+    fn update_ac6_mission(&self, game: &GameState, tracker: &mut MissionTracker) {
+        let GameState::ArmoredCore6(ac6) = game else {
+            return;
+        };
+        let events = tracker.observe(ac6_missions::MissionSample {
+            loading: ac6.is_loading_screen_visible(),
+            igt_ms: ac6.get_in_game_time_milliseconds() as i64,
+            last_rank: ac6.get_last_mission_rank() as i32,
+            last_time_ms: ac6.get_last_mission_time_milliseconds() as i64,
+        });
+
+        self.state.lock().unwrap().ac6_mission = tracker.timing().cloned();
+        for event in events {
+            match &event {
+                RunEvent::MissionStarted => log::info!("AC6: Mission started"),
+                RunEvent::MissionRestarted { restarts, .. } => log::info!("AC6: Mission restarted ({})", restarts),
+                RunEvent::MissionCompleted { clear_time_ms, .. } => {
+                    log::info!("AC6: Mission cleared in {} ms", clear_time_ms)
+                }
+                _ => {}
+            }
+            self.log_event(event);
+        }
+    }
+
+    /// Enforce the configured read limits on this thread's reads of the game
+    fn attach_read_sandbox(&self, game: &GameState, base: usize, size: usize) {
+        let limits = self.config.lock().unwrap().read_limits.clone();
+        let module = memory::MemoryRegion::new(base, size);
+        memory::sandbox::install(memory::ReadSandbox::new(limits, module, game.writable_regions()));
+    }
+
+    /// Start a new tick of the read limits and count the reads they denied
+    fn enforce_read_limits(&self, game: &GameState) {
+        let limits = self.config.lock().unwrap().read_limits.clone();
+        let Some(denied) = memory::sandbox::end_tick(&limits) else {
+            return;
+        };
+        if denied.total() == 0 {
+            return;
+        }
+
+        let mut s = self.state.lock().unwrap();
+        if s.reads_denied == 0 {
+            log::warn!(
+                "Memory reads denied by read_limits: {} over the per-tick limit, {} too large, {} outside game memory",
+                denied.over_read_count,
+                denied.over_size,
+                denied.outside_regions
+            );
+        }
+        s.reads_denied += denied.total();
+        drop(s);
+
+        // The heap may have grown since the regions were read
+        if denied.outside_regions > 0 && memory::sandbox::regions_refresh_due() {
+            memory::sandbox::set_regions(game.writable_regions());
+        }
+    }
+
+    /// Whether the game handle should be replaced (see `handle_health`)
+    fn check_handle_health(&self, game: &GameState, health: &mut HandleHealth) -> Option<ReattachReason> {
+        health.observe(game.is_zombie(), read_stats::failure_totals())
+    }
+
+    /// Sample the memory read counters at the end of a tick
+    fn record_read_stats(&self, read_meter: &mut ReadMeter) {
+        let budget = self.config.lock().unwrap().read_budget_per_tick;
+        if let Some(stats) = read_meter.tick(read_stats::totals(), budget) {
+            self.state.lock().unwrap().read_stats = Some(stats);
+        }
+    }
+
+    /// Refresh the game attributes exposed in the state
+    fn poll_attributes(&self, game: &GameState) {
+        let names = game.attribute_names();
+        if names.is_empty() {
+            return;
+        }
+
+        let values: Vec<(&str, i64)> = names
+            .iter()
+            .filter_map(|name| game.read_attribute(name).map(|value| (*name, value)))
+            .collect();
+
+        let mut s = self.state.lock().unwrap();
+        for (name, value) in values {
+            s.attributes.insert(name.to_string(), value);
+        }
+    }
+
+    /// Batch-read the flags of pending triggers through a read plan when the
+    /// engine supports it; the plan is recompiled when the flag list changes
+    fn read_trigger_flags(
+        &self,
+        game: &GameState,
+        flag_ids: Vec<u32>,
+        trigger_plan: &mut Option<TriggerPlan>,
+    ) -> HashMap<u32, bool> {
+        let Some(reader) = game.flag_block_reader() else {
+            return HashMap::new();
+        };
+        if flag_ids.is_empty() {
+            return HashMap::new();
+        }
+
+        if trigger_plan.as_ref().is_none_or(|(planned, _)| *planned != flag_ids) {
+            *trigger_plan = ReadPlan::compile(reader, &flag_ids).map(|plan| (flag_ids, plan));
+        }
+
+        trigger_plan
+            .as_ref()
+            .map(|(_, plan)| plan.execute(reader))
+            .unwrap_or_default()
+    }
+
+    /// Check custom triggers that have not matched yet, adding those that
+    /// fired to the tick; returns the flags read in batch for them
+    fn check_custom_triggers(
+        &self,
+        game: &GameState,
+        trigger_plan: &mut Option<TriggerPlan>,
+        tick: &mut TickSplits,
+    ) -> HashMap<u32, bool> {
+        let triggers = self.triggers.lock().unwrap();
+        let matched_indices = self.state.lock().unwrap().triggers_matched.clone();
+
+        let mut flag_ids: Vec<u32> = triggers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !matched_indices.contains(index))
+            .flat_map(|(_, trigger)| trigger.condition.flag_ids())
+            .collect();
+        flag_ids.sort_unstable();
+        flag_ids.dedup();
+        let flag_states = self.read_trigger_flags(game, flag_ids, trigger_plan);
+
+        let fired: Vec<usize> = triggers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !matched_indices.contains(index))
+            .filter(|(_, trigger)| {
+                trigger.condition.evaluate(
+                    |flag_id| {
+                        flag_states
+                            .get(&flag_id)
+                            .copied()
+                            .unwrap_or_else(|| game.read_event_flag(flag_id))
+                    },
+                    |name| game.read_attribute(name),
+                )
+            })
+            .map(|(index, _)| index)
+            .collect();
+
+        tick.triggers.extend(fired);
+        flag_states
+    }
+
+    /// Split the grouped bosses and the triggers that fired on this tick;
+    /// fired triggers are marked as matched
+    fn split_tick(&self, boss_flags: &[BossFlag], mut tick: TickSplits) {
+        if tick.bosses.is_empty() && tick.triggers.is_empty() {
+            return;
+        }
+        let triggers = self.triggers.lock().unwrap();
+        tick.triggers.sort_unstable();
+        tick.triggers.dedup();
+        tick.triggers.retain(|&index| index < triggers.len());
+
+        // Splits of a group that fired on the same tick describe the same
+        // moment: only the highest priority splits, the others are consumed.
+        // Bosses come first, so they win ties.
+        let ranks: Vec<(Option<&str>, i32)> = tick
+            .bosses
+            .iter()
+            .map(|defeat| {
+                let boss = &boss_flags[defeat.split_index];
+                (boss.group.as_deref(), boss.priority)
+            })
+            .chain(tick.triggers.iter().map(|&index| (triggers[index].group.as_deref(), triggers[index].priority)))
+            .collect();
+        let (winners, suppressed) = config::resolve_groups(&ranks);
+        let boss_count = tick.bosses.len();
+
+        for position in suppressed {
+            if position < boss_count {
+                let boss = &boss_flags[tick.bosses[position].split_index];
+                self.state.lock().unwrap().bosses_suppressed.push(boss.boss_id.clone());
+                log::info!(
+                    "Boss split suppressed by a higher-priority trigger of group '{}': {} (id={})",
+                    boss.group.as_deref().unwrap_or_default(),
+                    boss.boss_name,
+                    boss.boss_id
+                );
+                continue;
+            }
+            let index = tick.triggers[position - boss_count];
+            let trigger = &triggers[index];
+            self.state.lock().unwrap().triggers_matched.push(index);
+            log::info!(
+                "Trigger suppressed by a higher-priority trigger of group '{}': {} (id={})",
+                trigger.group.as_deref().unwrap_or_default(),
+                trigger.name,
+                trigger.id
+            );
+        }
+
+        // One game event can set several watched flags within moments: split
+        // triggers firing shortly after a split are consumed
+        let (window_ms, name_template) = {
+            let config = self.config.lock().unwrap();
+            (config.split_suppression_ms, config.split_name_template.clone())
+        };
+        for position in winners {
+            if position < boss_count {
+                let defeat = tick.bosses[position];
+                self.split_boss_defeat(&boss_flags[defeat.split_index], defeat);
+                continue;
+            }
+            let index = tick.triggers[position - boss_count];
+            let trigger = &triggers[index];
+            self.state.lock().unwrap().triggers_matched.push(index);
+            let since_split = {
+                let run_log = self.run_log.lock().unwrap();
+                run_log.last_split_ms().map(|at| run_log.elapsed_ms().saturating_sub(at))
+            };
+            if trigger.suppressed_after_split(window_ms, since_split) {
+                log::info!(
+                    "Trigger suppressed {} ms after the previous split: {} (id={})",
+                    since_split.unwrap_or_default(),
+                    trigger.name,
+                    trigger.id
+                );
+                continue;
+            }
+            log::info!("Trigger matched: {} (id={}, index={})", trigger.name, trigger.id, index);
+            let display_name = match (&name_template, trigger.kind) {
+                (Some(template), TriggerKind::Split) => {
+                    let igt_ms = self.state.lock().unwrap().attributes.get("igt").copied();
+                    Some(split_names::render(
+                        template,
+                        &split_names::SplitContext {
+                            name: &trigger.name,
+                            id: &trigger.id,
+                            kill_count: 1,
+                            igt_ms,
+                            metadata: &trigger.metadata,
+                        },
+                    ))
+                }
+                _ => None,
+            };
+            self.log_event(RunEvent::TriggerMatched {
+                index,
+                trigger_id: trigger.id.clone(),
+                name: trigger.name.clone(),
+                kind: trigger.kind,
+                display_name,
+                metadata: trigger.metadata.clone(),
+            });
+        }
+    }
+
+    /// Record this tick's values of every watched flag in the flag history;
+    /// `values` holds the flags already read this tick
+    fn record_flag_history(&self, game: &GameState, boss_flags: &[BossFlag], mut values: HashMap<u32, bool>) {
+        let (window_ms, rules) = {
+            let config = self.config.lock().unwrap();
+            (config.flag_history_seconds * 1000, config.timing_rules.clone())
+        };
+        if window_ms == 0 {
+            self.flag_history.lock().unwrap().clear();
+            return;
+        }
+
+        let mut flag_ids: Vec<u32> = boss_flags.iter().map(|boss| boss.flag_id).collect();
+        flag_ids.extend(self.triggers.lock().unwrap().iter().flat_map(|t| t.condition.flag_ids()));
+        flag_ids.extend(rules.iter().flat_map(|rule| rule.flag_ids()));
+        flag_ids.sort_unstable();
+        flag_ids.dedup();
+        for &flag_id in &flag_ids {
+            values.entry(flag_id).or_insert_with(|| game.read_event_flag(flag_id));
+        }
+
+        let elapsed = self.run_log.lock().unwrap().elapsed_ms();
+        let igt = self.state.lock().unwrap().attributes.get("igt").copied();
+        self.flag_history.lock().unwrap().record(elapsed, igt, &flag_ids, &values, window_ms);
+    }
+
+    /// Evaluate the timing rules' anchors and log segments that ended
+    fn time_segments(&self, game: &GameState, timer: &mut RuleTimer) {
+        let rules = self.config.lock().unwrap().timing_rules.clone();
+        if !timer.is_pending(&rules) {
+            return;
+        }
+
+        let mut flag_ids: Vec<u32> = rules.iter().flat_map(|rule| rule.flag_ids()).collect();
+        flag_ids.sort_unstable();
+        flag_ids.dedup();
+        let observation = timing_rules::Observation {
+            loading: game.read_attribute("loading").map(|value| value != 0),
+            blackscreen: game.read_attribute("blackscreen").map(|value| value != 0),
+            area: game
+                .position()
+                .and_then(|position| position.map_id)
+                .map(|map_id| ((map_id >> 24) as u8, (map_id >> 16) as u8)),
+            flags: flag_ids.into_iter().map(|flag_id| (flag_id, game.read_event_flag(flag_id))).collect(),
+        };
+
+        let elapsed = self.run_log.lock().unwrap().elapsed_ms();
+        let igt = self.state.lock().unwrap().attributes.get("igt").copied();
+        for segment in timer.observe(&rules, observation, elapsed, igt) {
+            self.log_event(segment);
+        }
+    }
+
+    /// Wait before the next process search, backing off while the game is closed
+    fn idle_wait(&self, backoff: &mut IdleBackoff) {
+        let (initial_ms, max_ms) = {
+            let config = self.config.lock().unwrap();
+            (config.idle_backoff_initial_ms, config.idle_backoff_max_ms)
+        };
+
+        let delay = backoff.next_delay(initial_ms, max_ms);
+        if self.sleeper.sleep_until_woken(&self.waker, delay) {
+            backoff.reset();
+        }
+    }
+
+    /// Sample the player position into the position trail when due
+    fn record_position(&self, game: &GameState) {
+        let interval = self.config.lock().unwrap().position_trail_interval_ms;
+        let elapsed = self.run_log.lock().unwrap().elapsed_ms();
+        if !self.position_trail.lock().unwrap().is_due(elapsed, interval) {
+            return;
+        }
+
+        if let Some(position) = game.position() {
+            let igt = self.state.lock().unwrap().attributes.get("igt").copied();
+            self.position_trail.lock().unwrap().push(elapsed, igt, position);
+        }
+    }
+
+    /// Log deaths and respawns (with stake/grace classification where supported)
+    fn track_deaths(&self, game: &GameState, tracker: &mut DeathTracker) {
+        let Some(death_count) = game.death_count() else {
+            return;
+        };
+
+        if let Some(event) = tracker.observe(death_count, game.position()) {
+            self.log_event(event);
+        }
+    }
+
+    /// Update the state's frame rate from the game's frame counter
+    fn measure_frame_rate(&self, game: &GameState, meter: &mut FrameRateMeter) {
+        let Some(frame_count) = game.read_attribute("frame_count") else {
+            return;
+        };
+
+        let elapsed = self.run_log.lock().unwrap().elapsed_ms();
+        if let Some(frame_rate) = meter.observe(frame_count as u32, elapsed) {
+            self.state.lock().unwrap().frame_rate = Some(frame_rate);
+        }
+    }
+
+    /// Update the state's achievement progress and log unlocks when due
+    fn track_achievements(&self, game: &GameState, tracker: &mut AchievementTracker) {
+        let (mut tracked, data_dir) = {
+            let config = self.config.lock().unwrap();
+            (config.achievements.clone(), config.data_dir.clone())
+        };
+        if tracked.is_empty() {
+            if let Some(engine_id) = game.engine_id() {
+                tracked = data_dir
+                    .and_then(|dir| data_dir::achievements(std::path::Path::new(&dir), engine_id))
+                    .or_else(|| GameType::from_engine_id(engine_id).map(achievements::builtin))
+                    .unwrap_or_default();
+            }
+        }
+        if tracked.is_empty() {
+            return;
+        }
+
+        let elapsed = self.run_log.lock().unwrap().elapsed_ms();
+        let Some((progress, unlocks)) = tracker.observe(&tracked, elapsed, |flag_id| game.read_event_flag(flag_id)) else {
+            return;
+        };
+        self.state.lock().unwrap().achievements = Some(progress);
+        for event in unlocks {
+            if let RunEvent::AchievementUnlocked { name, .. } = &event {
+                log::info!("Achievement unlocked: {}", name);
+            }
+            self.log_event(event);
+        }
+    }
+
+    /// Log changes of the host-registered value watches
+    fn check_watches(&self, game: &GameState, tracker: &mut WatchTracker) {
+        let watches = self.watches.lock().unwrap().clone();
+        if watches.is_empty() {
+            return;
+        }
+
+        for event in tracker.observe(&watches, |address, size| game.read_memory(address, size)) {
+            self.log_event(event);
+        }
+    }
+
+    /// Attach in limited mode, or refresh its window state, when the game's
+    /// memory cannot be read; false if the fallback is disabled
+    fn update_limited_access(&self, process_name: &str, pid: u32) -> bool {
+        if !self.config.lock().unwrap().low_privilege_fallback {
+            return false;
+        }
+
+        let access = LimitedAccess::probe(process_name, pid);
+        let mut s = self.state.lock().unwrap();
+        let entered = s.limited_access.is_none();
+        s.process_attached = true;
+        s.process_id = Some(pid);
+        s.limited_access = Some(access);
+        drop(s);
+
+        if entered {
+            log::warn!(
+                "Cannot read process memory for {} (permission denied?); attached with limited access",
+                process_name
+            );
+            self.log_event(RunEvent::ProcessAttached { game: process_name.to_string() });
+        }
+        true
+    }
+
+    /// Leave limited mode, when in it: the game exited or its memory became
+    /// readable
+    fn leave_limited_access(&self, exited: bool) {
+        let mut s = self.state.lock().unwrap();
+        if s.limited_access.take().is_none() {
+            return;
+        }
+        s.process_attached = false;
+        s.process_id = None;
+        drop(s);
+
+        if exited {
+            log::info!("Game process exited (limited access)");
+            self.log_event(RunEvent::ProcessDetached);
+        }
+    }
+
+    /// Log HP thresholds and phase changes of the watched boss fights
+    fn track_boss_phases(&self, game: &GameState, tracker: &mut BossPhaseTracker) {
+        let watches = self.config.lock().unwrap().boss_phases.clone();
+        if watches.is_empty() {
+            return;
+        }
+
+        let events = tracker.observe(
+            &watches,
+            self.clock.now(),
+            |address, size| game.read_memory(address, size),
+            |flag_id| game.read_event_flag(flag_id),
+        );
+        for event in events {
+            self.log_event(event);
+        }
+    }
+
+    /// Log warps and add warp triggers whose destination matches to the tick
+    fn track_warps(&self, game: &GameState, tracker: &mut WarpTracker, tick: &mut TickSplits) {
+        let supported = game
+            .game_type()
+            .and_then(|game_type| attributes::game_name(game_type, attributes::CanonicalAttribute::LastWarpPoint))
+            .is_some();
+        if !supported {
+            return;
+        }
+
+        let destination = game.read_attribute("last_warp_point").map(|id| id as u32);
+        let transition = ["loading", "blackscreen"]
+            .iter()
+            .any(|name| game.read_attribute(name).is_some_and(|value| value != 0));
+        let elapsed = self.run_log.lock().unwrap().elapsed_ms();
+        let Some(event) = tracker.observe(destination, transition, elapsed) else {
+            return;
+        };
+        let RunEvent::WarpInitiated { destination } = event else {
+            return;
+        };
+        log::info!("Warp to {}", destination);
+        self.log_event(event);
+
+        let triggers = self.triggers.lock().unwrap();
+        let matched_indices = self.state.lock().unwrap().triggers_matched.clone();
+        let fired: Vec<usize> = triggers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !matched_indices.contains(index))
+            .filter(|(_, trigger)| match trigger.condition {
+                TriggerCondition::Warp { destination: target, after_flag } => {
+                    target == destination && after_flag.is_none_or(|flag_id| game.read_event_flag(flag_id))
+                }
+                _ => false,
+            })
+            .map(|(index, _)| index)
+            .collect();
+        tick.triggers.extend(fired);
+    }
+
+    /// Add position triggers whose area the player is in to the tick
+    fn check_position_triggers(&self, game: &GameState, tick: &mut TickSplits) {
+        let triggers = self.triggers.lock().unwrap();
+        if !triggers.iter().any(|trigger| matches!(trigger.condition, TriggerCondition::Position { .. })) {
+            return;
+        }
+        let Some(position) = game.position() else {
+            return;
+        };
+
+        let matched_indices = self.state.lock().unwrap().triggers_matched.clone();
+        let fired: Vec<usize> = triggers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !matched_indices.contains(index))
+            .filter(|(_, trigger)| {
+                trigger
+                    .condition
+                    .position_fires(&position, |flag_id| game.read_event_flag(flag_id))
+            })
+            .map(|(index, _)| index)
+            .collect();
+        tick.triggers.extend(fired);
+    }
+
+    /// Write periodic stat snapshots and level-up events to the run log
+    fn record_stats(&self, game: &GameState, tracker: &mut StatTracker) {
+        if let Some(attribute) = game.level_attribute() {
+            if let Some(level) = game.read_attribute(attribute) {
+                if let Some(previous) = tracker.last_level {
+                    if level > previous {
+                        log::info!("{} increased: {} -> {}", attribute, previous, level);
+                        self.log_event(RunEvent::LevelIncreased {
+                            attribute: attribute.to_string(),
+                            from: previous,
+                            to: level,
+                        });
+                    }
+                }
+                tracker.last_level = Some(level);
+            }
+        }
+
+        let interval = self.config.lock().unwrap().stat_snapshot_interval_ms;
+        if interval == 0 || game.stat_names().is_empty() {
+            return;
+        }
+        if let Some(last) = tracker.last_snapshot {
+            if self.clock.now().duration_since(last) < Duration::from_millis(interval) {
+                return;
+            }
+        }
+
+        let stats: BTreeMap<String, i64> = game
+            .stat_names()
+            .iter()
+            .filter_map(|name| game.read_attribute(name).map(|value| (name.to_string(), value)))
+            .collect();
+
+        // Character not loaded yet - try again next tick
+        if stats.is_empty() {
+            return;
+        }
+
+        tracker.last_snapshot = Some(self.clock.now());
+        self.log_event(RunEvent::StatSnapshot { stats });
+    }
+}
+
+// =============================================================================
+// Main Loop (Windows)
+// =============================================================================
+
+#[cfg(target_os = "windows")]
+fn run_autosplitter_loop(
+    ctx: LoopContext,
+    game_type: GameType,
     process_names: Vec<String>,
     boss_flags: Vec<BossFlag>,
 ) {
     let mut game_state: Option<GameState> = None;
     let mut current_handle: Option<HANDLE> = None;
     let mut checked_flags: HashMap<u32, bool> = HashMap::new();
-
-    while running.load(Ordering::SeqCst) {
+    let mut stat_tracker = StatTracker::default();
+    let mut kill_validator = KillValidator::default();
+    let mut read_plan: Option<ReadPlan> = None;
+    let mut trigger_plan: Option<TriggerPlan> = None;
+    let mut death_tracker = DeathTracker::default();
+    let mut steam_overlay: Option<SteamOverlay> = None;
+    let mut cross_check: Option<CrossCheck> = None;
+    let mut rule_timer = RuleTimer::default();
+    let mut warp_tracker = WarpTracker::default();
+    let mut frame_meter = FrameRateMeter::default();
+    let mut achievement_tracker = AchievementTracker::default();
+    let mut menu_timer = MenuTimer::default();
+    let mut watch_tracker = WatchTracker::default();
+    let mut boss_phase_tracker = BossPhaseTracker::default();
+    let mut mission_tracker = MissionTracker::default();
+    let mut read_meter = ReadMeter::default();
+    let mut handle_health = HandleHealth::default();
+    let mut idle_backoff = IdleBackoff::default();
+
+    while ctx.running.load(Ordering::SeqCst) {
         // Check for reset
-        if reset_requested.swap(false, Ordering::SeqCst) {
-            log::info!("Autosplitter: Reset detected");
-            if let Some(ref game) = game_state {
-                checked_flags.clear();
-                for boss in &boss_flags {
-                    if game.read_event_flag(boss.flag_id) {
-                        checked_flags.insert(boss.flag_id, true);
-                    }
-                }
-            } else {
-                checked_flags.clear();
-            }
-            let mut s = state.lock().unwrap();
-            s.bosses_defeated.clear();
-            s.boss_kill_counts.clear();
-            s.triggers_matched.clear();
+        if ctx.reset_requested.swap(false, Ordering::SeqCst) {
+            ctx.reset_run(game_state.as_ref(), &boss_flags, &mut checked_flags, &mut rule_timer);
         }
 
         if let Some(ref game) = game_state {
-            // Check if process still running
-            if !memory::process::is_process_running(game.get_handle()) {
-                log::info!("{} process exited", game.name());
+            // Check if process still running and its handle still readable
+            let exited = !memory::process::is_process_running(game.get_handle());
+            let unhealthy = if exited { None } else { ctx.check_handle_health(game, &mut handle_health) };
+            if exited || unhealthy.is_some() {
+                match unhealthy {
+                    Some(reason) => {
+                        log::warn!("{} handle unhealthy ({:?}), re-attaching", game.name(), reason);
+                        ctx.log_event(RunEvent::Reattaching { reason });
+                    }
+                    None => {
+                        log::info!("{} process exited", game.name());
+                        ctx.log_event(RunEvent::ProcessDetached);
+                    }
+                }
                 if let Some(handle) = current_handle.take() {
                     unsafe {
                         let _ = CloseHandle(handle);
@@ -919,45 +2791,68 @@ fn run_generic_autosplitter_loop(
                 game_state = None;
                 checked_flags.clear();
 
-                let mut s = state.lock().unwrap();
+                let mut s = ctx.state.lock().unwrap();
                 s.process_attached = false;
                 s.process_id = None;
-                s.bosses_defeated.clear();
+                // Defeated bosses are kept: re-attaching to the same save must not split them again
                 s.boss_kill_counts.clear();
-                thread::sleep(Duration::from_millis(1000));
+                s.attributes.clear();
+                s.read_plan = None;
+                s.in_main_menu = false;
+                s.overlay_open = false;
+                s.read_stats = None;
+                s.gauntlet = None;
+                s.ac6_mission = None;
+                s.pvp_session = None;
+                s.flag_disagreements.clear();
+                s.frame_rate = None;
+                s.achievements = None;
+                s.reads_denied = 0;
+                memory::sandbox::uninstall();
+                steam_overlay = None;
+                cross_check = None;
+                ctx.sleeper.sleep(Duration::from_millis(1000));
                 continue;
             }
 
-            // Check boss flags
-            for boss in &boss_flags {
-                let kill_count = game.get_boss_kill_count(boss.flag_id);
+            ctx.enforce_read_limits(game);
 
-                if kill_count > 0 {
-                    let mut s = state.lock().unwrap();
+            ctx.record_read_stats(&mut read_meter);
+            ctx.update_overlay_state(game, steam_overlay.as_ref());
 
-                    let prev_count = s.boss_kill_counts.get(&boss.boss_id).copied().unwrap_or(0);
-                    if kill_count > prev_count {
-                        s.boss_kill_counts.insert(boss.boss_id.clone(), kill_count);
-                        log::info!(
-                            "Boss kill count updated: {} - count: {} -> {}",
-                            boss.boss_name,
-                            prev_count,
-                            kill_count
-                        );
-                    }
+            // Flags and stats are unset or stale in the main menu
+            let in_main_menu = ctx.update_menu_state(game);
+            ctx.account_menu_time(game, in_main_menu, &mut menu_timer);
+            if in_main_menu {
+                ctx.sleeper.sleep(Duration::from_millis(100));
+                continue;
+            }
 
-                    if !s.bosses_defeated.contains(&boss.boss_id) {
-                        s.bosses_defeated.push(boss.boss_id.clone());
-                        checked_flags.insert(boss.flag_id, true);
-                        log::info!(
-                            "Boss defeated: {} (id={}, flag={})",
-                            boss.boss_name,
-                            boss.boss_id,
-                            boss.flag_id
-                        );
-                    }
-                }
+            // Colosseum and online sessions can toggle flags (optionally suspended)
+            if ctx.update_pvp_state(game) {
+                ctx.sleeper.sleep(Duration::from_millis(100));
+                continue;
             }
+
+            let (mut tick_flags, mut tick) = ctx.check_boss_flags(game, &boss_flags, &mut checked_flags, &mut kill_validator, &mut read_plan, &mut cross_check);
+
+            // Poll attributes, check custom triggers, record flags, stats, position and deaths
+            ctx.poll_attributes(game);
+            tick_flags.extend(ctx.check_custom_triggers(game, &mut trigger_plan, &mut tick));
+            ctx.record_flag_history(game, &boss_flags, tick_flags);
+            ctx.time_segments(game, &mut rule_timer);
+            ctx.update_gauntlet(game);
+            ctx.update_ac6_mission(game, &mut mission_tracker);
+            ctx.record_stats(game, &mut stat_tracker);
+            ctx.record_position(game);
+            ctx.track_deaths(game, &mut death_tracker);
+            ctx.track_warps(game, &mut warp_tracker, &mut tick);
+            ctx.check_position_triggers(game, &mut tick);
+            ctx.split_tick(&boss_flags, tick);
+            ctx.measure_frame_rate(game, &mut frame_meter);
+            ctx.track_achievements(game, &mut achievement_tracker);
+            ctx.check_watches(game, &mut watch_tracker);
+            ctx.track_boss_phases(game, &mut boss_phase_tracker);
         } else {
             // Try to connect
             let process_name_refs: Vec<&str> = process_names.iter().map(|s| s.as_str()).collect();
@@ -966,7 +2861,12 @@ fn run_generic_autosplitter_loop(
                     match OpenProcess(PROCESS_VM_READ | PROCESS_QUERY_INFORMATION, false, pid) {
                         Ok(h) => h,
                         Err(_) => {
-                            thread::sleep(Duration::from_millis(2000));
+                            let delay_ms = if ctx.update_limited_access(&name, pid) {
+                                limited_access::RETRY_INTERVAL_MS
+                            } else {
+                                2000
+                            };
+                            ctx.sleeper.sleep(Duration::from_millis(delay_ms));
                             continue;
                         }
                     }
@@ -982,7 +2882,7 @@ fn run_generic_autosplitter_loop(
                         break;
                     }
                     if attempt < 4 {
-                        thread::sleep(Duration::from_millis(500));
+                        ctx.sleeper.sleep(Duration::from_millis(500));
                     }
                 }
 
@@ -991,7 +2891,7 @@ fn run_generic_autosplitter_loop(
                     unsafe {
                         let _ = CloseHandle(handle);
                     }
-                    thread::sleep(Duration::from_millis(2000));
+                    ctx.sleeper.sleep(Duration::from_millis(2000));
                     continue;
                 }
 
@@ -1003,61 +2903,346 @@ fn run_generic_autosplitter_loop(
                     size
                 );
 
-                // Initialize generic game
-                match GenericGame::new(game_data.clone()) {
-                    Ok(mut game) => {
-                        if game.init(handle, base, size) {
-                            log::info!("Connected to {} (generic engine)", game.game_data.game.name);
-
-                            // Wait for save data to stabilize
-                            log::info!("Waiting for game save data to stabilize...");
-                            thread::sleep(Duration::from_millis(1500));
-
-                            // Pre-populate checked flags
-                            checked_flags.clear();
-                            let mut pre_populated = Vec::new();
-                            for boss in &boss_flags {
-                                if game.read_event_flag(boss.flag_id) {
-                                    checked_flags.insert(boss.flag_id, true);
-                                    pre_populated.push(boss.boss_name.clone());
-                                }
-                            }
+                // Initialize game
+                if let Some(game) = init_game(game_type, handle, base, size) {
+                    log::info!("Connected to {}", game.name());
 
-                            if !pre_populated.is_empty() {
-                                log::info!(
-                                    "Pre-populated {} already-defeated bosses",
-                                    pre_populated.len()
-                                );
-                            }
+                    // Wait for save data to stabilize
+                    log::info!("Waiting for game save data to stabilize...");
+                    ctx.sleeper.sleep(Duration::from_millis(1500));
 
-                            game_state = Some(GameState::Generic(game));
-                            current_handle = Some(handle);
+                    // Pre-populate checked flags
+                    checked_flags.clear();
+                    let mut pre_populated = Vec::new();
+                    for boss in &boss_flags {
+                        if game.read_event_flag(boss.flag_id) {
+                            checked_flags.insert(boss.flag_id, true);
+                            pre_populated.push(boss.boss_name.clone());
+                        }
+                    }
 
-                            let mut s = state.lock().unwrap();
-                            s.process_attached = true;
-                            s.process_id = Some(unsafe { GetProcessId(handle) });
-                        } else {
-                            log::error!("Failed to initialize generic game - patterns not found");
-                            unsafe {
-                                let _ = CloseHandle(handle);
-                            }
-                            thread::sleep(Duration::from_millis(2000));
+                    if !pre_populated.is_empty() {
+                        log::info!(
+                            "Pre-populated {} already-defeated bosses",
+                            pre_populated.len()
+                        );
+                    }
+
+                    ctx.leave_limited_access(false);
+                    ctx.log_event(RunEvent::ProcessAttached { game: game.name().to_string() });
+                    stat_tracker = StatTracker::default();
+                    kill_validator = KillValidator::default();
+                    read_plan = None;
+                    trigger_plan = None;
+                    death_tracker = DeathTracker::default();
+                    rule_timer.detach();
+                    warp_tracker = WarpTracker::default();
+                    frame_meter = FrameRateMeter::default();
+                    achievement_tracker = AchievementTracker::default();
+                    menu_timer = MenuTimer::default();
+                    let pointer_size = memory::arch::module_pointer_size(base, |address, size| {
+                        game.read_memory(address, size)
+                    });
+                    watch_tracker = WatchTracker::new(base, pointer_size);
+                    boss_phase_tracker = BossPhaseTracker::new(base, pointer_size);
+                    mission_tracker = MissionTracker::default();
+                    read_meter = ReadMeter::default();
+                    handle_health = HandleHealth::default();
+                    idle_backoff.reset();
+                    game_state = Some(game);
+                    steam_overlay = game_state.as_ref().and_then(|game| ctx.locate_steam_overlay(game, pid));
+                    cross_check = game_state.as_ref().and_then(|game| ctx.attach_cross_check(game, base, size));
+                    if let Some(ref game) = game_state {
+                        ctx.check_architecture(game, base);
+                        ctx.attach_read_sandbox(game, base, size);
+                    }
+                    current_handle = Some(handle);
+
+                    let mut s = ctx.state.lock().unwrap();
+                    s.process_attached = true;
+                    s.process_id = Some(unsafe { GetProcessId(handle) });
+                } else {
+                    log::error!("Failed to initialize game for {}", name);
+                    unsafe {
+                        let _ = CloseHandle(handle);
+                    }
+                    ctx.sleeper.sleep(Duration::from_millis(2000));
+                }
+            } else {
+                ctx.leave_limited_access(true);
+                ctx.idle_wait(&mut idle_backoff);
+            }
+        }
+
+        ctx.sleeper.sleep(Duration::from_millis(100));
+    }
+
+    // Cleanup
+    if let Some(handle) = current_handle {
+        unsafe {
+            let _ = CloseHandle(handle);
+        }
+    }
+
+    let golds = ctx.store_attempt();
+    ctx.export_chapters();
+    ctx.log_highlights(&golds);
+    ctx.export_bundle();
+
+    let mut s = ctx.state.lock().unwrap();
+    s.running = false;
+    s.process_attached = false;
+    s.process_id = None;
+    s.limited_access = None;
+}
+
+// =============================================================================
+// Generic Game Loop (Windows) - Uses data-driven configuration
+// =============================================================================
+
+#[cfg(target_os = "windows")]
+fn run_generic_autosplitter_loop(
+    ctx: LoopContext,
+    compiled: Arc<CompiledGameData>,
+    process_names: Vec<String>,
+    boss_flags: Vec<BossFlag>,
+) {
+    let mut game_state: Option<GameState> = None;
+    let mut current_handle: Option<HANDLE> = None;
+    let mut checked_flags: HashMap<u32, bool> = HashMap::new();
+    let mut stat_tracker = StatTracker::default();
+    let mut kill_validator = KillValidator::default();
+    let mut read_plan: Option<ReadPlan> = None;
+    let mut trigger_plan: Option<TriggerPlan> = None;
+    let mut death_tracker = DeathTracker::default();
+    let mut steam_overlay: Option<SteamOverlay> = None;
+    let mut cross_check: Option<CrossCheck> = None;
+    let mut rule_timer = RuleTimer::default();
+    let mut warp_tracker = WarpTracker::default();
+    let mut frame_meter = FrameRateMeter::default();
+    let mut achievement_tracker = AchievementTracker::default();
+    let mut menu_timer = MenuTimer::default();
+    let mut watch_tracker = WatchTracker::default();
+    let mut boss_phase_tracker = BossPhaseTracker::default();
+    let mut mission_tracker = MissionTracker::default();
+    let mut read_meter = ReadMeter::default();
+    let mut handle_health = HandleHealth::default();
+    let mut idle_backoff = IdleBackoff::default();
+
+    while ctx.running.load(Ordering::SeqCst) {
+        // Check for reset
+        if ctx.reset_requested.swap(false, Ordering::SeqCst) {
+            ctx.reset_run(game_state.as_ref(), &boss_flags, &mut checked_flags, &mut rule_timer);
+        }
+
+        if let Some(ref game) = game_state {
+            // Check if process still running and its handle still readable
+            let exited = !memory::process::is_process_running(game.get_handle());
+            let unhealthy = if exited { None } else { ctx.check_handle_health(game, &mut handle_health) };
+            if exited || unhealthy.is_some() {
+                match unhealthy {
+                    Some(reason) => {
+                        log::warn!("{} handle unhealthy ({:?}), re-attaching", game.name(), reason);
+                        ctx.log_event(RunEvent::Reattaching { reason });
+                    }
+                    None => {
+                        log::info!("{} process exited", game.name());
+                        ctx.log_event(RunEvent::ProcessDetached);
+                    }
+                }
+                if let Some(handle) = current_handle.take() {
+                    unsafe {
+                        let _ = CloseHandle(handle);
+                    }
+                }
+                game_state = None;
+                checked_flags.clear();
+
+                let mut s = ctx.state.lock().unwrap();
+                s.process_attached = false;
+                s.process_id = None;
+                // Defeated bosses are kept: re-attaching to the same save must not split them again
+                s.boss_kill_counts.clear();
+                s.attributes.clear();
+                s.read_plan = None;
+                s.in_main_menu = false;
+                s.overlay_open = false;
+                s.read_stats = None;
+                s.gauntlet = None;
+                s.ac6_mission = None;
+                s.pvp_session = None;
+                s.flag_disagreements.clear();
+                s.frame_rate = None;
+                s.achievements = None;
+                s.reads_denied = 0;
+                memory::sandbox::uninstall();
+                steam_overlay = None;
+                cross_check = None;
+                ctx.sleeper.sleep(Duration::from_millis(1000));
+                continue;
+            }
+
+            ctx.enforce_read_limits(game);
+
+            ctx.record_read_stats(&mut read_meter);
+            ctx.update_overlay_state(game, steam_overlay.as_ref());
+
+            // Flags and stats are unset or stale in the main menu
+            let in_main_menu = ctx.update_menu_state(game);
+            ctx.account_menu_time(game, in_main_menu, &mut menu_timer);
+            if in_main_menu {
+                ctx.sleeper.sleep(Duration::from_millis(100));
+                continue;
+            }
+
+            // Colosseum and online sessions can toggle flags (optionally suspended)
+            if ctx.update_pvp_state(game) {
+                ctx.sleeper.sleep(Duration::from_millis(100));
+                continue;
+            }
+
+            let (mut tick_flags, mut tick) = ctx.check_boss_flags(game, &boss_flags, &mut checked_flags, &mut kill_validator, &mut read_plan, &mut cross_check);
+
+            // Poll attributes, check custom triggers, record flags, stats, position and deaths
+            ctx.poll_attributes(game);
+            tick_flags.extend(ctx.check_custom_triggers(game, &mut trigger_plan, &mut tick));
+            ctx.record_flag_history(game, &boss_flags, tick_flags);
+            ctx.time_segments(game, &mut rule_timer);
+            ctx.update_gauntlet(game);
+            ctx.update_ac6_mission(game, &mut mission_tracker);
+            ctx.record_stats(game, &mut stat_tracker);
+            ctx.record_position(game);
+            ctx.track_deaths(game, &mut death_tracker);
+            ctx.track_warps(game, &mut warp_tracker, &mut tick);
+            ctx.check_position_triggers(game, &mut tick);
+            ctx.split_tick(&boss_flags, tick);
+            ctx.measure_frame_rate(game, &mut frame_meter);
+            ctx.track_achievements(game, &mut achievement_tracker);
+            ctx.check_watches(game, &mut watch_tracker);
+            ctx.track_boss_phases(game, &mut boss_phase_tracker);
+        } else {
+            // Try to connect
+            let process_name_refs: Vec<&str> = process_names.iter().map(|s| s.as_str()).collect();
+            if let Some((pid, name)) = memory::process::find_process_by_name(&process_name_refs) {
+                let handle = unsafe {
+                    match OpenProcess(PROCESS_VM_READ | PROCESS_QUERY_INFORMATION, false, pid) {
+                        Ok(h) => h,
+                        Err(_) => {
+                            let delay_ms = if ctx.update_limited_access(&name, pid) {
+                                limited_access::RETRY_INTERVAL_MS
+                            } else {
+                                2000
+                            };
+                            ctx.sleeper.sleep(Duration::from_millis(delay_ms));
+                            continue;
                         }
                     }
-                    Err(e) => {
-                        log::error!("Failed to create generic game: {}", e);
-                        unsafe {
-                            let _ = CloseHandle(handle);
+                };
+
+                // Get module info
+                let mut base = 0usize;
+                let mut size = 0usize;
+                for attempt in 0..5 {
+                    if let Some((b, s)) = memory::process::get_module_base_and_size(pid) {
+                        base = b;
+                        size = s;
+                        break;
+                    }
+                    if attempt < 4 {
+                        ctx.sleeper.sleep(Duration::from_millis(500));
+                    }
+                }
+
+                if base == 0 {
+                    log::warn!("Failed to get module info for {}", name);
+                    unsafe {
+                        let _ = CloseHandle(handle);
+                    }
+                    ctx.sleeper.sleep(Duration::from_millis(2000));
+                    continue;
+                }
+
+                log::info!(
+                    "Found '{}' (PID: {}), base=0x{:X}, size=0x{:X}",
+                    name,
+                    pid,
+                    base,
+                    size
+                );
+
+                // Initialize generic game
+                let mut game = GenericGame::from_compiled(&compiled);
+                if game.init(handle, base, size) {
+                    log::info!("Connected to {} (generic engine)", game.game_data.game.name);
+
+                    // Wait for save data to stabilize
+                    log::info!("Waiting for game save data to stabilize...");
+                    ctx.sleeper.sleep(Duration::from_millis(1500));
+
+                    // Pre-populate checked flags
+                    checked_flags.clear();
+                    let mut pre_populated = Vec::new();
+                    for boss in &boss_flags {
+                        if game.read_event_flag(boss.flag_id) {
+                            checked_flags.insert(boss.flag_id, true);
+                            pre_populated.push(boss.boss_name.clone());
                         }
-                        thread::sleep(Duration::from_millis(2000));
                     }
+
+                    if !pre_populated.is_empty() {
+                        log::info!(
+                            "Pre-populated {} already-defeated bosses",
+                            pre_populated.len()
+                        );
+                    }
+
+                    ctx.leave_limited_access(false);
+                    ctx.log_event(RunEvent::ProcessAttached { game: game.game_data.game.name.clone() });
+                    stat_tracker = StatTracker::default();
+                    kill_validator = KillValidator::default();
+                    read_plan = None;
+                    trigger_plan = None;
+                    death_tracker = DeathTracker::default();
+                    rule_timer.detach();
+                    warp_tracker = WarpTracker::default();
+                    frame_meter = FrameRateMeter::default();
+                    achievement_tracker = AchievementTracker::default();
+                    menu_timer = MenuTimer::default();
+                    let pointer_size = memory::arch::module_pointer_size(base, |address, size| {
+                        memory::reader::read_bytes(game.handle, address, size)
+                    });
+                    watch_tracker = WatchTracker::new(base, pointer_size);
+                    boss_phase_tracker = BossPhaseTracker::new(base, pointer_size);
+                    mission_tracker = MissionTracker::default();
+                    read_meter = ReadMeter::default();
+                    handle_health = HandleHealth::default();
+                    idle_backoff.reset();
+                    game_state = Some(GameState::Generic(game));
+                    steam_overlay = game_state.as_ref().and_then(|game| ctx.locate_steam_overlay(game, pid));
+                    cross_check = game_state.as_ref().and_then(|game| ctx.attach_cross_check(game, base, size));
+                    if let Some(ref game) = game_state {
+                        ctx.check_architecture(game, base);
+                        ctx.attach_read_sandbox(game, base, size);
+                    }
+                    current_handle = Some(handle);
+
+                    let mut s = ctx.state.lock().unwrap();
+                    s.process_attached = true;
+                    s.process_id = Some(unsafe { GetProcessId(handle) });
+                } else {
+                    log::error!("Failed to initialize generic game - patterns not found");
+                    unsafe {
+                        let _ = CloseHandle(handle);
+                    }
+                    ctx.sleeper.sleep(Duration::from_millis(2000));
                 }
             } else {
-                thread::sleep(Duration::from_millis(2000));
+                ctx.leave_limited_access(true);
+                ctx.idle_wait(&mut idle_backoff);
             }
         }
 
-        thread::sleep(Duration::from_millis(100));
+        ctx.sleeper.sleep(Duration::from_millis(100));
     }
 
     // Cleanup
@@ -1067,10 +3252,16 @@ fn run_generic_autosplitter_loop(
         }
     }
 
-    let mut s = state.lock().unwrap();
+    let golds = ctx.store_attempt();
+    ctx.export_chapters();
+    ctx.log_highlights(&golds);
+    ctx.export_bundle();
+
+    let mut s = ctx.state.lock().unwrap();
     s.running = false;
     s.process_attached = false;
     s.process_id = None;
+    s.limited_access = None;
 }
 
 // =============================================================================
@@ -1079,9 +3270,7 @@ fn run_generic_autosplitter_loop(
 
 #[cfg(target_os = "linux")]
 fn run_autosplitter_loop_linux(
-    running: Arc<AtomicBool>,
-    state: Arc<Mutex<AutosplitterState>>,
-    reset_requested: Arc<AtomicBool>,
+    ctx: LoopContext,
     game_type: GameType,
     process_names: Vec<String>,
     boss_flags: Vec<BossFlag>,
@@ -1089,74 +3278,112 @@ fn run_autosplitter_loop_linux(
     let mut game_state: Option<GameState> = None;
     let mut current_pid: Option<i32> = None;
     let mut checked_flags: HashMap<u32, bool> = HashMap::new();
-
-    while running.load(Ordering::SeqCst) {
+    let mut stat_tracker = StatTracker::default();
+    let mut kill_validator = KillValidator::default();
+    let mut read_plan: Option<ReadPlan> = None;
+    let mut trigger_plan: Option<TriggerPlan> = None;
+    let mut death_tracker = DeathTracker::default();
+    let mut steam_overlay: Option<SteamOverlay> = None;
+    let mut cross_check: Option<CrossCheck> = None;
+    let mut rule_timer = RuleTimer::default();
+    let mut warp_tracker = WarpTracker::default();
+    let mut frame_meter = FrameRateMeter::default();
+    let mut achievement_tracker = AchievementTracker::default();
+    let mut menu_timer = MenuTimer::default();
+    let mut watch_tracker = WatchTracker::default();
+    let mut boss_phase_tracker = BossPhaseTracker::default();
+    let mut mission_tracker = MissionTracker::default();
+    let mut read_meter = ReadMeter::default();
+    let mut handle_health = HandleHealth::default();
+    let mut idle_backoff = IdleBackoff::default();
+
+    while ctx.running.load(Ordering::SeqCst) {
         // Check for reset
-        if reset_requested.swap(false, Ordering::SeqCst) {
-            log::info!("Autosplitter: Reset detected");
-            if let Some(ref game) = game_state {
-                checked_flags.clear();
-                for boss in &boss_flags {
-                    if game.read_event_flag(boss.flag_id) {
-                        checked_flags.insert(boss.flag_id, true);
-                    }
-                }
-            } else {
-                checked_flags.clear();
-            }
-            let mut s = state.lock().unwrap();
-            s.bosses_defeated.clear();
-            s.boss_kill_counts.clear();
-            s.triggers_matched.clear();
+        if ctx.reset_requested.swap(false, Ordering::SeqCst) {
+            ctx.reset_run(game_state.as_ref(), &boss_flags, &mut checked_flags, &mut rule_timer);
         }
 
         if let Some(ref game) = game_state {
-            // Check if process still running
-            if !memory::process::is_process_running_by_pid(game.get_pid() as u32) {
-                log::info!("{} process exited", game.name());
+            // Check if process still running and its handle still readable
+            let exited = !memory::process::is_process_running_by_pid(game.get_pid() as u32);
+            let unhealthy = if exited { None } else { ctx.check_handle_health(game, &mut handle_health) };
+            if exited || unhealthy.is_some() {
+                match unhealthy {
+                    Some(reason) => {
+                        log::warn!("{} handle unhealthy ({:?}), re-attaching", game.name(), reason);
+                        ctx.log_event(RunEvent::Reattaching { reason });
+                    }
+                    None => {
+                        log::info!("{} process exited", game.name());
+                        ctx.log_event(RunEvent::ProcessDetached);
+                    }
+                }
                 game_state = None;
                 current_pid = None;
                 checked_flags.clear();
 
-                let mut s = state.lock().unwrap();
+                let mut s = ctx.state.lock().unwrap();
                 s.process_attached = false;
                 s.process_id = None;
-                s.bosses_defeated.clear();
+                // Defeated bosses are kept: re-attaching to the same save must not split them again
                 s.boss_kill_counts.clear();
-                thread::sleep(Duration::from_millis(1000));
+                s.attributes.clear();
+                s.read_plan = None;
+                s.in_main_menu = false;
+                s.overlay_open = false;
+                s.read_stats = None;
+                s.gauntlet = None;
+                s.ac6_mission = None;
+                s.pvp_session = None;
+                s.flag_disagreements.clear();
+                s.frame_rate = None;
+                s.achievements = None;
+                s.reads_denied = 0;
+                memory::sandbox::uninstall();
+                steam_overlay = None;
+                cross_check = None;
+                ctx.sleeper.sleep(Duration::from_millis(1000));
                 continue;
             }
 
-            // Check boss flags
-            for boss in &boss_flags {
-                let kill_count = game.get_boss_kill_count(boss.flag_id);
+            ctx.enforce_read_limits(game);
 
-                if kill_count > 0 {
-                    let mut s = state.lock().unwrap();
+            ctx.record_read_stats(&mut read_meter);
+            ctx.update_overlay_state(game, steam_overlay.as_ref());
 
-                    let prev_count = s.boss_kill_counts.get(&boss.boss_id).copied().unwrap_or(0);
-                    if kill_count > prev_count {
-                        s.boss_kill_counts.insert(boss.boss_id.clone(), kill_count);
-                        log::info!(
-                            "Boss kill count updated: {} - count: {} -> {}",
-                            boss.boss_name,
-                            prev_count,
-                            kill_count
-                        );
-                    }
+            // Flags and stats are unset or stale in the main menu
+            let in_main_menu = ctx.update_menu_state(game);
+            ctx.account_menu_time(game, in_main_menu, &mut menu_timer);
+            if in_main_menu {
+                ctx.sleeper.sleep(Duration::from_millis(100));
+                continue;
+            }
 
-                    if !s.bosses_defeated.contains(&boss.boss_id) {
-                        s.bosses_defeated.push(boss.boss_id.clone());
-                        checked_flags.insert(boss.flag_id, true);
-                        log::info!(
-                            "Boss defeated: {} (id={}, flag={})",
-                            boss.boss_name,
-                            boss.boss_id,
-                            boss.flag_id
-                        );
-                    }
-                }
+            // Colosseum and online sessions can toggle flags (optionally suspended)
+            if ctx.update_pvp_state(game) {
+                ctx.sleeper.sleep(Duration::from_millis(100));
+                continue;
             }
+
+            let (mut tick_flags, mut tick) = ctx.check_boss_flags(game, &boss_flags, &mut checked_flags, &mut kill_validator, &mut read_plan, &mut cross_check);
+
+            // Poll attributes, check custom triggers, record flags, stats, position and deaths
+            ctx.poll_attributes(game);
+            tick_flags.extend(ctx.check_custom_triggers(game, &mut trigger_plan, &mut tick));
+            ctx.record_flag_history(game, &boss_flags, tick_flags);
+            ctx.time_segments(game, &mut rule_timer);
+            ctx.update_gauntlet(game);
+            ctx.update_ac6_mission(game, &mut mission_tracker);
+            ctx.record_stats(game, &mut stat_tracker);
+            ctx.record_position(game);
+            ctx.track_deaths(game, &mut death_tracker);
+            ctx.track_warps(game, &mut warp_tracker, &mut tick);
+            ctx.check_position_triggers(game, &mut tick);
+            ctx.split_tick(&boss_flags, tick);
+            ctx.measure_frame_rate(game, &mut frame_meter);
+            ctx.track_achievements(game, &mut achievement_tracker);
+            ctx.check_watches(game, &mut watch_tracker);
+            ctx.track_boss_phases(game, &mut boss_phase_tracker);
         } else {
             // Try to connect
             let process_name_refs: Vec<&str> = process_names.iter().map(|s| s.as_str()).collect();
@@ -1173,13 +3400,13 @@ fn run_autosplitter_loop_linux(
                             break;
                         }
                         if attempt < 4 {
-                            thread::sleep(Duration::from_millis(500));
+                            ctx.sleeper.sleep(Duration::from_millis(500));
                         }
                     }
 
                     if base == 0 {
                         log::warn!("Failed to get module info for {}", name);
-                        thread::sleep(Duration::from_millis(2000));
+                        ctx.sleeper.sleep(Duration::from_millis(2000));
                         continue;
                     }
 
@@ -1197,7 +3424,7 @@ fn run_autosplitter_loop_linux(
 
                         // Wait for save data to stabilize
                         log::info!("Waiting for game save data to stabilize...");
-                        thread::sleep(Duration::from_millis(1500));
+                        ctx.sleeper.sleep(Duration::from_millis(1500));
 
                         // Pre-populate checked flags
                         checked_flags.clear();
@@ -1217,32 +3444,68 @@ fn run_autosplitter_loop_linux(
                         }
 
                         current_pid = Some(pid as i32);
+                        ctx.leave_limited_access(false);
+                        ctx.log_event(RunEvent::ProcessAttached { game: game.name().to_string() });
+                        stat_tracker = StatTracker::default();
+                        kill_validator = KillValidator::default();
+                        read_plan = None;
+                        trigger_plan = None;
+                        death_tracker = DeathTracker::default();
+                        rule_timer.detach();
+                        warp_tracker = WarpTracker::default();
+                        frame_meter = FrameRateMeter::default();
+                        achievement_tracker = AchievementTracker::default();
+                        menu_timer = MenuTimer::default();
+                        let pointer_size = memory::arch::module_pointer_size(base, |address, size| {
+                            game.read_memory(address, size)
+                        });
+                        watch_tracker = WatchTracker::new(base, pointer_size);
+                        boss_phase_tracker = BossPhaseTracker::new(base, pointer_size);
+                        mission_tracker = MissionTracker::default();
+                        read_meter = ReadMeter::default();
+                        handle_health = HandleHealth::default();
+                        idle_backoff.reset();
                         game_state = Some(game);
+                        steam_overlay = game_state.as_ref().and_then(|game| ctx.locate_steam_overlay(game, pid));
+                        cross_check = game_state.as_ref().and_then(|game| ctx.attach_cross_check(game, base, size));
+                        if let Some(ref game) = game_state {
+                            ctx.check_architecture(game, base);
+                            ctx.attach_read_sandbox(game, base, size);
+                        }
 
-                        let mut s = state.lock().unwrap();
+                        let mut s = ctx.state.lock().unwrap();
                         s.process_attached = true;
                         s.process_id = Some(pid);
                     } else {
                         log::error!("Failed to initialize game for {}", name);
-                        thread::sleep(Duration::from_millis(2000));
+                        ctx.sleeper.sleep(Duration::from_millis(2000));
                     }
+                } else if ctx.update_limited_access(&name, pid) {
+                    ctx.sleeper.sleep(Duration::from_millis(limited_access::RETRY_INTERVAL_MS));
                 } else {
                     log::warn!("Cannot read process memory for {} (permission denied?)", name);
-                    thread::sleep(Duration::from_millis(2000));
+                    ctx.sleeper.sleep(Duration::from_millis(2000));
                 }
             } else {
-                thread::sleep(Duration::from_millis(2000));
+                ctx.leave_limited_access(true);
+                ctx.idle_wait(&mut idle_backoff);
             }
         }
 
-        thread::sleep(Duration::from_millis(100));
+        ctx.sleeper.sleep(Duration::from_millis(100));
     }
 
+    let golds = ctx.store_attempt();
+    ctx.export_chapters();
+    ctx.log_highlights(&golds);
+    ctx.export_bundle();
+
     // Cleanup
-    let mut s = state.lock().unwrap();
+    let mut s = ctx.state.lock().unwrap();
     s.running = false;
     s.process_attached = false;
     s.process_id = None;
+    s.limited_access = None;
 }
 
 // =============================================================================
@@ -1251,84 +3514,118 @@ fn run_autosplitter_loop_linux(
 
 #[cfg(target_os = "linux")]
 fn run_generic_autosplitter_loop_linux(
-    running: Arc<AtomicBool>,
-    state: Arc<Mutex<AutosplitterState>>,
-    reset_requested: Arc<AtomicBool>,
-    game_data: GameData,
+    ctx: LoopContext,
+    compiled: Arc<CompiledGameData>,
     process_names: Vec<String>,
     boss_flags: Vec<BossFlag>,
 ) {
-    use crate::engine::GenericGame;
-
-    let mut game: Option<GenericGame> = None;
+    let mut game: Option<GameState> = None;
     let mut checked_flags: HashMap<u32, bool> = HashMap::new();
-
-    while running.load(Ordering::SeqCst) {
+    let mut stat_tracker = StatTracker::default();
+    let mut kill_validator = KillValidator::default();
+    let mut read_plan: Option<ReadPlan> = None;
+    let mut trigger_plan: Option<TriggerPlan> = None;
+    let mut death_tracker = DeathTracker::default();
+    let mut steam_overlay: Option<SteamOverlay> = None;
+    let mut cross_check: Option<CrossCheck> = None;
+    let mut rule_timer = RuleTimer::default();
+    let mut warp_tracker = WarpTracker::default();
+    let mut frame_meter = FrameRateMeter::default();
+    let mut achievement_tracker = AchievementTracker::default();
+    let mut menu_timer = MenuTimer::default();
+    let mut watch_tracker = WatchTracker::default();
+    let mut boss_phase_tracker = BossPhaseTracker::default();
+    let mut mission_tracker = MissionTracker::default();
+    let mut read_meter = ReadMeter::default();
+    let mut handle_health = HandleHealth::default();
+    let mut idle_backoff = IdleBackoff::default();
+
+    while ctx.running.load(Ordering::SeqCst) {
         // Check for reset
-        if reset_requested.swap(false, Ordering::SeqCst) {
-            log::info!("Autosplitter: Reset detected");
-            if let Some(ref g) = game {
-                checked_flags.clear();
-                for boss in &boss_flags {
-                    if g.read_event_flag(boss.flag_id) {
-                        checked_flags.insert(boss.flag_id, true);
-                    }
-                }
-            } else {
-                checked_flags.clear();
-            }
-            let mut s = state.lock().unwrap();
-            s.bosses_defeated.clear();
-            s.boss_kill_counts.clear();
-            s.triggers_matched.clear();
+        if ctx.reset_requested.swap(false, Ordering::SeqCst) {
+            ctx.reset_run(game.as_ref(), &boss_flags, &mut checked_flags, &mut rule_timer);
         }
 
         if let Some(ref g) = game {
-            // Check if process still running
-            if !memory::process::is_process_running_by_pid(g.pid as u32) {
-                log::info!("{} process exited", g.game_data.game.name);
+            // Check if process still running and its handle still readable
+            let exited = !memory::process::is_process_running_by_pid(g.get_pid() as u32);
+            let unhealthy = if exited { None } else { ctx.check_handle_health(g, &mut handle_health) };
+            if exited || unhealthy.is_some() {
+                match unhealthy {
+                    Some(reason) => {
+                        log::warn!("{} handle unhealthy ({:?}), re-attaching", g.name(), reason);
+                        ctx.log_event(RunEvent::Reattaching { reason });
+                    }
+                    None => {
+                        log::info!("{} process exited", g.name());
+                        ctx.log_event(RunEvent::ProcessDetached);
+                    }
+                }
                 game = None;
                 checked_flags.clear();
 
-                let mut s = state.lock().unwrap();
+                let mut s = ctx.state.lock().unwrap();
                 s.process_attached = false;
                 s.process_id = None;
-                s.bosses_defeated.clear();
+                // Defeated bosses are kept: re-attaching to the same save must not split them again
                 s.boss_kill_counts.clear();
-                thread::sleep(Duration::from_millis(1000));
+                s.attributes.clear();
+                s.read_plan = None;
+                s.in_main_menu = false;
+                s.overlay_open = false;
+                s.read_stats = None;
+                s.gauntlet = None;
+                s.ac6_mission = None;
+                s.pvp_session = None;
+                s.flag_disagreements.clear();
+                s.frame_rate = None;
+                s.achievements = None;
+                s.reads_denied = 0;
+                memory::sandbox::uninstall();
+                steam_overlay = None;
+                cross_check = None;
+                ctx.sleeper.sleep(Duration::from_millis(1000));
                 continue;
             }
 
-            // Check boss flags
-            for boss in &boss_flags {
-                let kill_count = g.get_kill_count(boss.flag_id);
+            ctx.enforce_read_limits(g);
 
-                if kill_count > 0 {
-                    let mut s = state.lock().unwrap();
+            ctx.record_read_stats(&mut read_meter);
+            ctx.update_overlay_state(g, steam_overlay.as_ref());
 
-                    let prev_count = s.boss_kill_counts.get(&boss.boss_id).copied().unwrap_or(0);
-                    if kill_count > prev_count {
-                        s.boss_kill_counts.insert(boss.boss_id.clone(), kill_count);
-                        log::info!(
-                            "Boss kill count updated: {} - count: {} -> {}",
-                            boss.boss_name,
-                            prev_count,
-                            kill_count
-                        );
-                    }
+            // Flags and stats are unset or stale in the main menu
+            let in_main_menu = ctx.update_menu_state(g);
+            ctx.account_menu_time(g, in_main_menu, &mut menu_timer);
+            if in_main_menu {
+                ctx.sleeper.sleep(Duration::from_millis(100));
+                continue;
+            }
 
-                    if !s.bosses_defeated.contains(&boss.boss_id) {
-                        s.bosses_defeated.push(boss.boss_id.clone());
-                        checked_flags.insert(boss.flag_id, true);
-                        log::info!(
-                            "Boss defeated: {} (id={}, flag={})",
-                            boss.boss_name,
-                            boss.boss_id,
-                            boss.flag_id
-                        );
-                    }
-                }
+            // Colosseum and online sessions can toggle flags (optionally suspended)
+            if ctx.update_pvp_state(g) {
+                ctx.sleeper.sleep(Duration::from_millis(100));
+                continue;
             }
+
+            let (mut tick_flags, mut tick) = ctx.check_boss_flags(g, &boss_flags, &mut checked_flags, &mut kill_validator, &mut read_plan, &mut cross_check);
+
+            // Poll attributes, check custom triggers, record flags, stats, position and deaths
+            ctx.poll_attributes(g);
+            tick_flags.extend(ctx.check_custom_triggers(g, &mut trigger_plan, &mut tick));
+            ctx.record_flag_history(g, &boss_flags, tick_flags);
+            ctx.time_segments(g, &mut rule_timer);
+            ctx.update_gauntlet(g);
+            ctx.update_ac6_mission(g, &mut mission_tracker);
+            ctx.record_stats(g, &mut stat_tracker);
+            ctx.record_position(g);
+            ctx.track_deaths(g, &mut death_tracker);
+            ctx.track_warps(g, &mut warp_tracker, &mut tick);
+            ctx.check_position_triggers(g, &mut tick);
+            ctx.split_tick(&boss_flags, tick);
+            ctx.measure_frame_rate(g, &mut frame_meter);
+            ctx.track_achievements(g, &mut achievement_tracker);
+            ctx.check_watches(g, &mut watch_tracker);
+            ctx.track_boss_phases(g, &mut boss_phase_tracker);
         } else {
             // Try to connect
             let process_name_refs: Vec<&str> = process_names.iter().map(|s| s.as_str()).collect();
@@ -1345,149 +3642,1006 @@ fn run_generic_autosplitter_loop_linux(
                             break;
                         }
                         if attempt < 4 {
-                            thread::sleep(Duration::from_millis(500));
+                            ctx.sleeper.sleep(Duration::from_millis(500));
                         }
                     }
 
-                    if base == 0 {
-                        log::warn!("Failed to get module info for {}", name);
-                        thread::sleep(Duration::from_millis(2000));
-                        continue;
-                    }
+                    if base == 0 {
+                        log::warn!("Failed to get module info for {}", name);
+                        ctx.sleeper.sleep(Duration::from_millis(2000));
+                        continue;
+                    }
+
+                    log::info!(
+                        "Found '{}' (PID: {}), base=0x{:X}, size=0x{:X} [Generic Engine]",
+                        name,
+                        pid,
+                        base,
+                        size
+                    );
+
+                    // Initialize generic game
+                    let mut g = GenericGame::from_compiled(&compiled);
+                    if g.init(pid as i32, base, size) {
+                        log::info!("Connected to {} via generic engine (Linux/Proton)", g.game_data.game.name);
+
+                        // Wait for save data to stabilize
+                        log::info!("Waiting for game save data to stabilize...");
+                        ctx.sleeper.sleep(Duration::from_millis(1500));
+
+                        // Pre-populate checked flags
+                        checked_flags.clear();
+                        let mut pre_populated = Vec::new();
+                        for boss in &boss_flags {
+                            if g.read_event_flag(boss.flag_id) {
+                                checked_flags.insert(boss.flag_id, true);
+                                pre_populated.push(boss.boss_name.clone());
+                            }
+                        }
+
+                        if !pre_populated.is_empty() {
+                            log::info!(
+                                "Pre-populated {} already-defeated bosses",
+                                pre_populated.len()
+                            );
+                        }
+
+                        ctx.leave_limited_access(false);
+                        ctx.log_event(RunEvent::ProcessAttached { game: g.game_data.game.name.clone() });
+                        stat_tracker = StatTracker::default();
+                        kill_validator = KillValidator::default();
+                        read_plan = None;
+                        trigger_plan = None;
+                        death_tracker = DeathTracker::default();
+                        rule_timer.detach();
+                        warp_tracker = WarpTracker::default();
+                        frame_meter = FrameRateMeter::default();
+                        achievement_tracker = AchievementTracker::default();
+                        menu_timer = MenuTimer::default();
+                        let pointer_size = memory::arch::module_pointer_size(base, |address, size| {
+                            memory::reader::read_bytes(g.pid, address, size)
+                        });
+                        watch_tracker = WatchTracker::new(base, pointer_size);
+                        boss_phase_tracker = BossPhaseTracker::new(base, pointer_size);
+                        mission_tracker = MissionTracker::default();
+                        read_meter = ReadMeter::default();
+                        handle_health = HandleHealth::default();
+                        idle_backoff.reset();
+                        game = Some(GameState::Generic(g));
+                        steam_overlay = game.as_ref().and_then(|game| ctx.locate_steam_overlay(game, pid));
+                        cross_check = game.as_ref().and_then(|game| ctx.attach_cross_check(game, base, size));
+                        if let Some(ref game) = game {
+                            ctx.check_architecture(game, base);
+                            ctx.attach_read_sandbox(game, base, size);
+                        }
+
+                        let mut s = ctx.state.lock().unwrap();
+                        s.process_attached = true;
+                        s.process_id = Some(pid);
+                    } else {
+                        log::error!("Failed to initialize generic game - patterns not found");
+                        ctx.sleeper.sleep(Duration::from_millis(2000));
+                    }
+                } else if ctx.update_limited_access(&name, pid) {
+                    ctx.sleeper.sleep(Duration::from_millis(limited_access::RETRY_INTERVAL_MS));
+                } else {
+                    log::warn!("Cannot read process memory for {} (permission denied?)", name);
+                    ctx.sleeper.sleep(Duration::from_millis(2000));
+                }
+            } else {
+                ctx.leave_limited_access(true);
+                ctx.idle_wait(&mut idle_backoff);
+            }
+        }
+
+        ctx.sleeper.sleep(Duration::from_millis(100));
+    }
+
+    let golds = ctx.store_attempt();
+    ctx.export_chapters();
+    ctx.log_highlights(&golds);
+    ctx.export_bundle();
+
+    // Cleanup
+    let mut s = ctx.state.lock().unwrap();
+    s.running = false;
+    s.process_attached = false;
+    s.process_id = None;
+    s.limited_access = None;
+}
+
+// =============================================================================
+// Demo Loop - Synthetic game, no process (see `demo`)
+// =============================================================================
+
+fn run_demo_loop(ctx: LoopContext, boss_flags: Vec<BossFlag>, script: DemoScript) {
+    let mut checked_flags: HashMap<u32, bool> = HashMap::new();
+    let mut stat_tracker = StatTracker::default();
+    let mut kill_validator = KillValidator::default();
+    let mut read_plan: Option<ReadPlan> = None;
+    let mut trigger_plan: Option<TriggerPlan> = None;
+    let mut death_tracker = DeathTracker::default();
+    let mut cross_check: Option<CrossCheck> = None;
+    let mut rule_timer = RuleTimer::default();
+    let mut achievement_tracker = AchievementTracker::default();
+    let mut menu_timer = MenuTimer::default();
+    let mut watch_tracker = WatchTracker::default();
+    let mut boss_phase_tracker = BossPhaseTracker::default();
+
+    let started = ctx.clock.now();
+    let mut game = GameState::Demo(DemoGame::new(script));
+    log::info!("Connected to {}", game.name());
+    ctx.log_event(RunEvent::ProcessAttached { game: game.name().to_string() });
+    ctx.state.lock().unwrap().process_attached = true;
+
+    while ctx.running.load(Ordering::SeqCst) {
+        let now_ms = ctx.clock.now().duration_since(started).as_millis() as u64;
+        let GameState::Demo(demo) = &mut game else {
+            unreachable!("demo loop runs the demo game");
+        };
+        let restarted = demo.advance(now_ms);
+
+        // Check for reset; the script restarting is a reset by the runner
+        if ctx.reset_requested.swap(false, Ordering::SeqCst) || restarted {
+            if !restarted {
+                demo.restart(now_ms);
+            }
+            ctx.reset_run(Some(&game), &boss_flags, &mut checked_flags, &mut rule_timer);
+        }
+
+        ctx.account_menu_time(&game, false, &mut menu_timer);
+
+        let (mut tick_flags, mut tick) = ctx.check_boss_flags(&game, &boss_flags, &mut checked_flags, &mut kill_validator, &mut read_plan, &mut cross_check);
+
+        // Poll attributes, check custom triggers, record flags, stats, position and deaths
+        ctx.poll_attributes(&game);
+        tick_flags.extend(ctx.check_custom_triggers(&game, &mut trigger_plan, &mut tick));
+        ctx.split_tick(&boss_flags, tick);
+        ctx.record_flag_history(&game, &boss_flags, tick_flags);
+        ctx.time_segments(&game, &mut rule_timer);
+        ctx.record_stats(&game, &mut stat_tracker);
+        ctx.record_position(&game);
+        ctx.track_deaths(&game, &mut death_tracker);
+        ctx.track_achievements(&game, &mut achievement_tracker);
+        ctx.check_watches(&game, &mut watch_tracker);
+        ctx.track_boss_phases(&game, &mut boss_phase_tracker);
+
+        ctx.sleeper.sleep(Duration::from_millis(100));
+    }
+
+    let golds = ctx.store_attempt();
+    ctx.export_chapters();
+    ctx.log_highlights(&golds);
+    ctx.export_bundle();
+
+    // Cleanup
+    let mut s = ctx.state.lock().unwrap();
+    s.running = false;
+    s.process_attached = false;
+    s.process_id = None;
+    s.limited_access = None;
+}
+
+// =============================================================================
+// FFI Interface for Dynamic Loading
+// =============================================================================
+
+static AUTOSPLITTER: Lazy<Mutex<Option<Autosplitter>>> = Lazy::new(|| Mutex::new(None));
+
+/// Initialize the autosplitter (call once at startup)
+#[no_mangle]
+pub extern "C" fn autosplitter_init() -> bool {
+    let mut guard = AUTOSPLITTER.lock().unwrap();
+    if guard.is_none() {
+        *guard = Some(Autosplitter::new());
+        true
+    } else {
+        false
+    }
+}
+
+/// Check if autosplitter is initialized
+#[no_mangle]
+pub extern "C" fn autosplitter_is_initialized() -> bool {
+    AUTOSPLITTER.lock().unwrap().is_some()
+}
+
+/// Stop the autosplitter
+#[no_mangle]
+pub extern "C" fn autosplitter_stop() {
+    if let Some(ref autosplitter) = *AUTOSPLITTER.lock().unwrap() {
+        autosplitter.stop();
+    }
+}
+
+/// Reset the autosplitter
+#[no_mangle]
+pub extern "C" fn autosplitter_reset() {
+    if let Some(ref autosplitter) = *AUTOSPLITTER.lock().unwrap() {
+        autosplitter.reset();
+    }
+}
+
+/// Split a configured boss by id from the host (e.g., a split hotkey while
+/// memory access is denied); splitting a defeated boss again does nothing
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_split_boss(boss_id: *const c_char) -> *mut c_char {
+    if boss_id.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let boss_id = unsafe { std::ffi::CStr::from_ptr(boss_id).to_string_lossy() };
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => match autosplitter.split_boss(&boss_id) {
+            Ok(_) => std::ptr::null_mut(),
+            Err(e) => ffi_string::error(e),
+        },
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Calibrate against the host timer; host_now_ms is the host's current reading
+/// Run log entries then carry `host_ms` alongside `elapsed_ms`
+/// Returns false if the autosplitter is not initialized
+#[no_mangle]
+pub extern "C" fn autosplitter_calibrate_clock(host_now_ms: i64) -> bool {
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => {
+            autosplitter.calibrate_clock(host_now_ms);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Check if autosplitter is running
+#[no_mangle]
+pub extern "C" fn autosplitter_is_running() -> bool {
+    AUTOSPLITTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|a| a.is_running())
+        .unwrap_or(false)
+}
+
+/// Get autosplitter state as JSON string
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_state_json() -> *mut c_char {
+    ffi_string::to_c_string(state_json())
+}
+
+/// Get autosplitter state as NUL-terminated UTF-16 JSON string
+/// Caller must free the returned string with autosplitter_free_string_utf16
+#[no_mangle]
+pub extern "C" fn autosplitter_get_state_json_utf16() -> *mut u16 {
+    ffi_string::to_utf16(state_json())
+}
+
+fn state_json() -> String {
+    let state = AUTOSPLITTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|a| a.get_state())
+        .unwrap_or_default();
+
+    serde_json::to_string(&state).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Set custom triggers checked alongside boss flags
+/// triggers_json: JSON array of CustomTrigger objects
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_set_triggers(triggers_json: *const c_char) -> *mut c_char {
+    if triggers_json.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let triggers_str = unsafe { std::ffi::CStr::from_ptr(triggers_json).to_string_lossy() };
+
+    let triggers: Vec<CustomTrigger> = match serde_json::from_str(&triggers_str) {
+        Ok(triggers) => triggers,
+        Err(e) => return ffi_string::error(format!("Failed to parse triggers: {}", e)),
+    };
+    if let Err(e) = triggers.iter().try_for_each(CustomTrigger::validate) {
+        return ffi_string::error(e);
+    }
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => {
+            autosplitter.set_triggers(triggers);
+            std::ptr::null_mut()
+        }
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Watch a value for changes, replacing the watch with the same id; changes
+/// arrive as `value_changed` run events (see autosplitter_poll_events_json)
+/// watch_json: JSON Watch object
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_add_watch(watch_json: *const c_char) -> *mut c_char {
+    if watch_json.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let watch_str = unsafe { std::ffi::CStr::from_ptr(watch_json).to_string_lossy() };
+
+    let watch: Watch = match serde_json::from_str(&watch_str) {
+        Ok(watch) => watch,
+        Err(e) => return ffi_string::error(format!("Failed to parse watch: {}", e)),
+    };
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => match autosplitter.add_watch(watch) {
+            Ok(()) => std::ptr::null_mut(),
+            Err(e) => ffi_string::error(e),
+        },
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Remove a value watch by id
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_remove_watch(watch_id: *const c_char) -> *mut c_char {
+    if watch_id.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let watch_id = unsafe { std::ffi::CStr::from_ptr(watch_id).to_string_lossy() };
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) if autosplitter.remove_watch(&watch_id) => std::ptr::null_mut(),
+        Some(_) => ffi_string::error(format!("Unknown watch: {}", watch_id)),
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Get the registered value watches as JSON (Watch array)
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_watches_json() -> *mut c_char {
+    let json = AUTOSPLITTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|a| serde_json::to_string(&a.get_watches()).unwrap_or_else(|_| "[]".to_string()))
+        .unwrap_or_else(|| "[]".to_string());
+    ffi_string::to_c_string(json)
+}
+
+/// Set autosplitter settings
+/// config_json: JSON AutosplitterConfig object (missing fields use defaults)
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_set_config(config_json: *const c_char) -> *mut c_char {
+    if config_json.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let config_str = unsafe { std::ffi::CStr::from_ptr(config_json).to_string_lossy() };
+
+    let config: AutosplitterConfig = match serde_json::from_str(&config_str) {
+        Ok(config) => config,
+        Err(e) => return ffi_string::error(format!("Failed to parse config: {}", e)),
+    };
+    if let Some(template) = &config.split_name_template {
+        if let Err(e) = split_names::validate(template) {
+            return ffi_string::error(e);
+        }
+    }
+    if let Err(e) = config.boss_phases.iter().try_for_each(|watch| watch.validate()) {
+        return ffi_string::error(e);
+    }
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => {
+            autosplitter.set_config(config);
+            std::ptr::null_mut()
+        }
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Play an audio cue configured in `audio_cues` of the config
+/// cue: "split", "gold", "boss_defeated", "rule_violation" or "boss_phase"
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_play_cue(cue: *const c_char) -> *mut c_char {
+    if cue.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let cue_str = unsafe { std::ffi::CStr::from_ptr(cue).to_string_lossy() };
+    let cue = match Cue::from_name(&cue_str) {
+        Some(cue) => cue,
+        None => return ffi_string::error(format!("Unknown audio cue: {}", cue_str)),
+    };
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => {
+            autosplitter.play_cue(cue);
+            std::ptr::null_mut()
+        }
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Store finished attempts, golds and statistics as JSON files under a directory
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_set_storage_dir(dir: *const c_char) -> *mut c_char {
+    if dir.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let dir_str = unsafe { std::ffi::CStr::from_ptr(dir).to_string_lossy() };
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => {
+            autosplitter.set_storage(Box::new(JsonFileStorage::new(dir_str.as_ref())));
+            std::ptr::null_mut()
+        }
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Store finished attempts, golds and statistics in a SQLite database file
+/// Returns error message or null on success (caller must free error string)
+#[cfg(feature = "sqlite")]
+#[no_mangle]
+pub extern "C" fn autosplitter_set_storage_sqlite(path: *const c_char) -> *mut c_char {
+    if path.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let path_str = unsafe { std::ffi::CStr::from_ptr(path).to_string_lossy() };
+    let storage = match SqliteStorage::open(path_str.as_ref()) {
+        Ok(storage) => storage,
+        Err(e) => return ffi_string::error(e),
+    };
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => {
+            autosplitter.set_storage(Box::new(storage));
+            std::ptr::null_mut()
+        }
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Export the stored golds and runs of a game, signed with the key file at
+/// key_path (generated on first use)
+/// Returns the SignedStats as JSON, or an error message prefixed with "ERROR: "
+/// Caller must free the returned string with autosplitter_free_string
+#[cfg(feature = "signatures")]
+#[no_mangle]
+pub extern "C" fn autosplitter_export_signed_stats(game_id: *const c_char, key_path: *const c_char) -> *mut c_char {
+    if game_id.is_null() || key_path.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
+    }
+    let game_id = unsafe { std::ffi::CStr::from_ptr(game_id).to_string_lossy() };
+    let key_path = unsafe { std::ffi::CStr::from_ptr(key_path).to_string_lossy() };
+
+    let stats = match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => autosplitter.export_signed_stats(&game_id, std::path::Path::new(key_path.as_ref())),
+        None => Err("Autosplitter not initialized".to_string()),
+    };
+    match stats.and_then(|stats| serde_json::to_string(&stats).map_err(|e| e.to_string())) {
+        Ok(json) => ffi_string::to_c_string(json),
+        Err(e) => ffi_string::error(format!("ERROR: {}", e)),
+    }
+}
+
+/// Verify SignedStats (JSON) exported on another machine and store their runs
+/// trusted_keys_json: JSON array of trusted public keys (hex), or null to
+/// accept any key
+/// Returns the StatsVerification as JSON, or an error message prefixed with
+/// "ERROR: " when the stats were tampered with or rejected
+/// Caller must free the returned string with autosplitter_free_string
+#[cfg(feature = "signatures")]
+#[no_mangle]
+pub extern "C" fn autosplitter_import_signed_stats(stats_json: *const c_char, trusted_keys_json: *const c_char) -> *mut c_char {
+    if stats_json.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
+    }
+    let stats_str = unsafe { std::ffi::CStr::from_ptr(stats_json).to_string_lossy() };
+    let stats: SignedStats = match serde_json::from_str(&stats_str) {
+        Ok(stats) => stats,
+        Err(e) => return ffi_string::error(format!("ERROR: Failed to parse signed stats: {}", e)),
+    };
+    let trusted_keys: Vec<String> = if trusted_keys_json.is_null() {
+        Vec::new()
+    } else {
+        let keys_str = unsafe { std::ffi::CStr::from_ptr(trusted_keys_json).to_string_lossy() };
+        match serde_json::from_str(&keys_str) {
+            Ok(keys) => keys,
+            Err(e) => return ffi_string::error(format!("ERROR: Failed to parse trusted keys: {}", e)),
+        }
+    };
+
+    let verification = match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => autosplitter.import_signed_stats(&stats, &trusted_keys),
+        None => Err("Autosplitter not initialized".to_string()),
+    };
+    match verification.and_then(|verification| serde_json::to_string(&verification).map_err(|e| e.to_string())) {
+        Ok(json) => ffi_string::to_c_string(json),
+        Err(e) => ffi_string::error(format!("ERROR: {}", e)),
+    }
+}
+
+/// Check the manifest URL of an UpdateConfig (JSON) for newer signed data
+/// packs and install them. Blocks while downloading.
+/// Returns the installed packs (InstalledPack array) as JSON, or an error
+/// message prefixed with "ERROR: "
+/// Caller must free the returned string with autosplitter_free_string
+#[cfg(feature = "updates")]
+#[no_mangle]
+pub extern "C" fn autosplitter_check_data_pack_updates(config_json: *const c_char) -> *mut c_char {
+    if config_json.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
+    }
+    let config_str = unsafe { std::ffi::CStr::from_ptr(config_json).to_string_lossy() };
+
+    let installed = serde_json::from_str::<data_packs::UpdateConfig>(&config_str)
+        .map_err(|e| format!("Failed to parse update config: {}", e))
+        .and_then(|config| data_packs::check_for_updates(&config));
+    match installed {
+        Ok(packs) => ffi_string::to_c_string(serde_json::to_string(&packs).unwrap_or_else(|_| "[]".to_string())),
+        Err(e) => ffi_string::error(format!("ERROR: {}", e)),
+    }
+}
+
+/// Install the data packs kept in a cache directory by an earlier update
+/// check (no network access). Returns the same as
+/// autosplitter_check_data_pack_updates
+/// Caller must free the returned string with autosplitter_free_string
+#[cfg(feature = "updates")]
+#[no_mangle]
+pub extern "C" fn autosplitter_load_cached_data_packs(dir: *const c_char, public_key: *const c_char) -> *mut c_char {
+    if dir.is_null() || public_key.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
+    }
+    let dir = unsafe { std::ffi::CStr::from_ptr(dir).to_string_lossy() };
+    let public_key = unsafe { std::ffi::CStr::from_ptr(public_key).to_string_lossy() };
+
+    match data_packs::load_cached(std::path::Path::new(dir.as_ref()), &public_key) {
+        Ok(packs) => ffi_string::to_c_string(serde_json::to_string(&packs).unwrap_or_else(|_| "[]".to_string())),
+        Err(e) => ffi_string::error(format!("ERROR: {}", e)),
+    }
+}
+
+/// Get the installed data packs as JSON (InstalledPack array)
+/// Caller must free the returned string with autosplitter_free_string
+#[cfg(feature = "updates")]
+#[no_mangle]
+pub extern "C" fn autosplitter_get_data_packs_json() -> *mut c_char {
+    ffi_string::to_c_string(serde_json::to_string(&data_packs::list()).unwrap_or_else(|_| "[]".to_string()))
+}
+
+/// Start autosplitter with the installed data pack for an engine id
+/// boss_flags_json: JSON array of BossFlag objects
+/// Returns error message or null on success (caller must free error string)
+#[cfg(feature = "updates")]
+#[no_mangle]
+pub extern "C" fn autosplitter_start_with_data_pack(game: *const c_char, boss_flags_json: *const c_char) -> *mut c_char {
+    if game.is_null() || boss_flags_json.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+    let game = unsafe { std::ffi::CStr::from_ptr(game).to_string_lossy() };
+    let boss_flags_str = unsafe { std::ffi::CStr::from_ptr(boss_flags_json).to_string_lossy() };
+
+    let boss_flags: Vec<BossFlag> = match serde_json::from_str(&boss_flags_str) {
+        Ok(flags) => flags,
+        Err(e) => return ffi_string::error(format!("Failed to parse boss flags: {}", e)),
+    };
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => match autosplitter.start_with_data_pack(&game, boss_flags) {
+            Ok(()) => std::ptr::null_mut(),
+            Err(e) => ffi_string::error(e),
+        },
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Start the demo mode against a built-in synthetic game (no game needed)
+/// boss_flags_json: JSON array of BossFlag objects, or null for the demo bosses
+/// script_json: DemoScript object, or null to kill the bosses one after another
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_start_demo(boss_flags_json: *const c_char, script_json: *const c_char) -> *mut c_char {
+    let boss_flags: Vec<BossFlag> = if boss_flags_json.is_null() {
+        Vec::new()
+    } else {
+        let boss_flags_str = unsafe { std::ffi::CStr::from_ptr(boss_flags_json).to_string_lossy() };
+        match serde_json::from_str(&boss_flags_str) {
+            Ok(flags) => flags,
+            Err(e) => return ffi_string::error(format!("Failed to parse boss flags: {}", e)),
+        }
+    };
+
+    let script: Option<DemoScript> = if script_json.is_null() {
+        None
+    } else {
+        let script_str = unsafe { std::ffi::CStr::from_ptr(script_json).to_string_lossy() };
+        match serde_json::from_str(&script_str) {
+            Ok(script) => Some(script),
+            Err(e) => return ffi_string::error(format!("Failed to parse demo script: {}", e)),
+        }
+    };
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => match autosplitter.start_demo(boss_flags, script) {
+            Ok(()) => std::ptr::null_mut(),
+            Err(e) => ffi_string::error(e),
+        },
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Search for the game process immediately instead of waiting for the idle backoff
+/// Call when the host launches the game or gets a process-start notification
+#[no_mangle]
+pub extern "C" fn autosplitter_wake() {
+    if let Some(ref autosplitter) = *AUTOSPLITTER.lock().unwrap() {
+        autosplitter.wake();
+    }
+}
+
+/// Get run log entries recorded since the previous call as JSON array
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_poll_events_json() -> *mut c_char {
+    ffi_string::to_c_string(poll_events_json())
+}
+
+/// Get new run log entries as rate-limited integration messages (JSON array
+/// of OutputMessage objects); waiting messages are released on later calls
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_poll_output_json() -> *mut c_char {
+    let messages = AUTOSPLITTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|a| a.poll_output())
+        .unwrap_or_default();
+
+    ffi_string::to_c_string(serde_json::to_string(&messages).unwrap_or_else(|_| "[]".to_string()))
+}
+
+/// Get run log entries recorded since the previous call as UTF-16 JSON array
+/// Caller must free the returned string with autosplitter_free_string_utf16
+#[no_mangle]
+pub extern "C" fn autosplitter_poll_events_json_utf16() -> *mut u16 {
+    ffi_string::to_utf16(poll_events_json())
+}
+
+fn poll_events_json() -> String {
+    let events = AUTOSPLITTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|a| a.poll_events())
+        .unwrap_or_default();
+
+    serde_json::to_string(&events).unwrap_or_else(|_| "[]".to_string())
+}
+
+/// Get the full run log as JSON array
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_run_log_json() -> *mut c_char {
+    let json = AUTOSPLITTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|a| a.get_run_log_json())
+        .unwrap_or_else(|| "[]".to_string());
+
+    ffi_string::to_c_string(json)
+}
+
+/// Get the recent tick-by-tick values of the watched flags as JSON
+/// (FlagHistorySegment array) for "why did/didn't it split" diagnostics
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_flag_history_json() -> *mut c_char {
+    let json = AUTOSPLITTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|a| a.get_flag_history_json())
+        .unwrap_or_else(|| "[]".to_string());
+    ffi_string::to_c_string(json)
+}
+
+/// Get the memorable moments (splits, deaths, rule violations) of the current
+/// attempt so far as JSON (Highlight array)
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_highlights_json() -> *mut c_char {
+    let json = AUTOSPLITTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|a| a.get_highlights_json())
+        .unwrap_or_else(|| "[]".to_string());
+    ffi_string::to_c_string(json)
+}
+
+/// Get the position trail with run events as JSON (`{"points": [...], "events": [...]}`)
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_position_trail_json() -> *mut c_char {
+    let json = AUTOSPLITTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|a| a.get_position_trail_json())
+        .unwrap_or_else(|| r#"{"points":[],"events":[]}"#.to_string());
+
+    ffi_string::to_c_string(json)
+}
+
+/// Get the position trail as GPX-like XML with split waypoints
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_position_trail_gpx() -> *mut c_char {
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(a) => ffi_string::to_c_string(a.get_position_trail_gpx()),
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
+
+/// Get VOD chapters for the current attempt
+/// format: "ffmpeg" (FFmpeg metadata file) or "youtube" (description timestamps)
+/// Returns the chapters on success, or an error message prefixed with "ERROR: " on failure
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_chapters(format: *const c_char) -> *mut c_char {
+    if format.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
+    }
+
+    let format_str = unsafe { std::ffi::CStr::from_ptr(format).to_string_lossy() };
+    let Some(format) = ChapterFormat::parse(&format_str) else {
+        return ffi_string::error(format!("ERROR: Unknown chapter format: {}", format_str));
+    };
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(a) => ffi_string::to_c_string(a.get_chapters(format)),
+        None => ffi_string::error("ERROR: Autosplitter not initialized"),
+    }
+}
+
+/// Write a verification bundle (zip of the run log, splits, config and
+/// diagnostics) of the current run to a file
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_export_bundle(path: *const c_char) -> *mut c_char {
+    if path.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let path_str = unsafe { std::ffi::CStr::from_ptr(path).to_string_lossy() };
 
-                    log::info!(
-                        "Found '{}' (PID: {}), base=0x{:X}, size=0x{:X} [Generic Engine]",
-                        name,
-                        pid,
-                        base,
-                        size
-                    );
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => match autosplitter.export_bundle(std::path::Path::new(path_str.as_ref())) {
+            Ok(()) => std::ptr::null_mut(),
+            Err(e) => ffi_string::error(e),
+        },
+        None => ffi_string::error("Autosplitter not initialized"),
+    }
+}
 
-                    // Initialize generic game
-                    match GenericGame::new(game_data.clone()) {
-                        Ok(mut g) => {
-                            if g.init(pid as i32, base, size) {
-                                log::info!("Connected to {} via generic engine (Linux/Proton)", g.game_data.game.name);
-
-                                // Wait for save data to stabilize
-                                log::info!("Waiting for game save data to stabilize...");
-                                thread::sleep(Duration::from_millis(1500));
-
-                                // Pre-populate checked flags
-                                checked_flags.clear();
-                                let mut pre_populated = Vec::new();
-                                for boss in &boss_flags {
-                                    if g.read_event_flag(boss.flag_id) {
-                                        checked_flags.insert(boss.flag_id, true);
-                                        pre_populated.push(boss.boss_name.clone());
-                                    }
-                                }
-
-                                if !pre_populated.is_empty() {
-                                    log::info!(
-                                        "Pre-populated {} already-defeated bosses",
-                                        pre_populated.len()
-                                    );
-                                }
-
-                                game = Some(g);
-
-                                let mut s = state.lock().unwrap();
-                                s.process_attached = true;
-                                s.process_id = Some(pid);
-                            } else {
-                                log::error!("Failed to initialize generic game - patterns not found");
-                                thread::sleep(Duration::from_millis(2000));
-                            }
-                        }
-                        Err(e) => {
-                            log::error!("Failed to create generic game: {}", e);
-                            thread::sleep(Duration::from_millis(2000));
-                        }
-                    }
-                } else {
-                    log::warn!("Cannot read process memory for {} (permission denied?)", name);
-                    thread::sleep(Duration::from_millis(2000));
-                }
-            } else {
-                thread::sleep(Duration::from_millis(2000));
-            }
-        }
+/// Export the current run's split configuration as a URL-safe share string
+/// Returns the string, or an error message prefixed with "ERROR: "
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_export_splits_string() -> *mut c_char {
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(a) => match a.export_splits() {
+            Ok(text) => ffi_string::to_c_string(text),
+            Err(e) => ffi_string::error(format!("ERROR: {}", e)),
+        },
+        None => ffi_string::error("ERROR: Autosplitter not initialized"),
+    }
+}
 
-        thread::sleep(Duration::from_millis(100));
+/// Import a share string; its triggers and timing rules replace the current
+/// ones. Returns the decoded configuration (SharedSplits) as JSON, whose
+/// boss_flags can be passed to the next start, or an error message prefixed
+/// with "ERROR: " if the string is invalid
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_import_splits_string(text: *const c_char) -> *mut c_char {
+    if text.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
     }
+    let text = unsafe { std::ffi::CStr::from_ptr(text).to_string_lossy() };
 
-    // Cleanup
-    let mut s = state.lock().unwrap();
-    s.running = false;
-    s.process_attached = false;
-    s.process_id = None;
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(a) => match a.import_splits(&text) {
+            Ok(splits) => ffi_string::to_c_string(serde_json::to_string(&splits).unwrap_or_else(|_| "{}".to_string())),
+            Err(e) => ffi_string::error(format!("ERROR: {}", e)),
+        },
+        None => ffi_string::error("ERROR: Autosplitter not initialized"),
+    }
 }
 
-// =============================================================================
-// FFI Interface for Dynamic Loading
-// =============================================================================
+/// Encode a split configuration (SharedSplits JSON) as a share string without
+/// an autosplitter instance
+/// Returns the string, or an error message prefixed with "ERROR: "
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_encode_splits_json(json: *const c_char) -> *mut c_char {
+    if json.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
+    }
+    let json = unsafe { std::ffi::CStr::from_ptr(json).to_string_lossy() };
+
+    let encoded = serde_json::from_str::<SharedSplits>(&json)
+        .map_err(|e| format!("Invalid split configuration: {}", e))
+        .and_then(|splits| splits.validate().and_then(|()| share::encode(&splits)));
+    match encoded {
+        Ok(text) => ffi_string::to_c_string(text),
+        Err(e) => ffi_string::error(format!("ERROR: {}", e)),
+    }
+}
 
-static AUTOSPLITTER: Lazy<Mutex<Option<Autosplitter>>> = Lazy::new(|| Mutex::new(None));
+/// Get supported games, engines and flag algorithms as JSON string
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_capabilities_json() -> *mut c_char {
+    let json = serde_json::to_string(&capabilities::describe()).unwrap_or_else(|_| "{}".to_string());
+    ffi_string::to_c_string(json)
+}
 
-/// Initialize the autosplitter (call once at startup)
+/// Get the Armored Core 6 Arena opponents (split choices) as JSON string
+/// Caller must free the returned string with autosplitter_free_string
 #[no_mangle]
-pub extern "C" fn autosplitter_init() -> bool {
-    let mut guard = AUTOSPLITTER.lock().unwrap();
-    if guard.is_none() {
-        *guard = Some(Autosplitter::new());
-        true
+pub extern "C" fn autosplitter_get_ac6_arena_opponents_json() -> *mut c_char {
+    let json = serde_json::to_string(&ac6_arena::opponents()).unwrap_or_else(|_| "[]".to_string());
+    ffi_string::to_c_string(json)
+}
+
+/// Build Armored Core 6 Arena completion triggers for the selected opponents
+/// opponent_ids_json: JSON array of opponent ids; empty array or null selects all (All-Arena)
+/// Returns a JSON array of CustomTrigger objects for autosplitter_set_triggers,
+/// or an error message prefixed with "ERROR: " on failure
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_ac6_arena_triggers_json(opponent_ids_json: *const c_char) -> *mut c_char {
+    let selected: Vec<String> = if opponent_ids_json.is_null() {
+        Vec::new()
     } else {
-        false
+        let ids_str = unsafe { std::ffi::CStr::from_ptr(opponent_ids_json).to_string_lossy() };
+        match serde_json::from_str(&ids_str) {
+            Ok(ids) => ids,
+            Err(e) => return ffi_string::error(format!("ERROR: Failed to parse opponent ids: {}", e)),
+        }
+    };
+
+    match ac6_arena::arena_triggers(&selected) {
+        Ok(triggers) => ffi_string::to_c_string(serde_json::to_string(&triggers).unwrap_or_else(|_| "[]".to_string())),
+        Err(e) => ffi_string::error(format!("ERROR: {}", e)),
     }
 }
 
-/// Check if autosplitter is initialized
+/// Get the Dark Souls 2 landmarks (Primal Bonfires) as JSON string
+/// Caller must free the returned string with autosplitter_free_string
 #[no_mangle]
-pub extern "C" fn autosplitter_is_initialized() -> bool {
-    AUTOSPLITTER.lock().unwrap().is_some()
+pub extern "C" fn autosplitter_get_ds2_landmarks_json() -> *mut c_char {
+    let json = serde_json::to_string(&ds2_triggers::landmarks()).unwrap_or_else(|_| "[]".to_string());
+    ffi_string::to_c_string(json)
 }
 
-/// Stop the autosplitter
+/// Build Dark Souls 2 landmark triggers from positions recorded by the runner
+/// positions_json: JSON object of [x, y, z] keyed by landmark id
+/// Returns a JSON array of CustomTrigger objects for autosplitter_set_triggers,
+/// or an error message prefixed with "ERROR: " on failure
+/// Caller must free the returned string with autosplitter_free_string
 #[no_mangle]
-pub extern "C" fn autosplitter_stop() {
-    if let Some(ref autosplitter) = *AUTOSPLITTER.lock().unwrap() {
-        autosplitter.stop();
+pub extern "C" fn autosplitter_get_ds2_triggers_json(positions_json: *const c_char) -> *mut c_char {
+    if positions_json.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
+    }
+    let json = unsafe { std::ffi::CStr::from_ptr(positions_json).to_string_lossy() };
+    let positions: HashMap<String, [f32; 3]> = match serde_json::from_str(&json) {
+        Ok(positions) => positions,
+        Err(e) => return ffi_string::error(format!("ERROR: Failed to parse landmark positions: {}", e)),
+    };
+
+    match ds2_triggers::landmark_triggers(&positions) {
+        Ok(triggers) => ffi_string::to_c_string(serde_json::to_string(&triggers).unwrap_or_else(|_| "[]".to_string())),
+        Err(e) => ffi_string::error(format!("ERROR: {}", e)),
     }
 }
 
-/// Reset the autosplitter
+/// Get the Sekiro gauntlets (with their bosses in fight order) as JSON string
+/// Caller must free the returned string with autosplitter_free_string
 #[no_mangle]
-pub extern "C" fn autosplitter_reset() {
-    if let Some(ref autosplitter) = *AUTOSPLITTER.lock().unwrap() {
-        autosplitter.reset();
+pub extern "C" fn autosplitter_get_sekiro_gauntlets_json() -> *mut c_char {
+    let json = serde_json::to_string(&sekiro_gauntlet::gauntlets()).unwrap_or_else(|_| "[]".to_string());
+    ffi_string::to_c_string(json)
+}
+
+/// Build Sekiro gauntlet progress triggers (one split per boss defeated inside the gauntlet)
+/// Returns a JSON array of CustomTrigger objects for autosplitter_set_triggers,
+/// or an error message prefixed with "ERROR: " on failure
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_get_sekiro_gauntlet_triggers_json(gauntlet_id: *const c_char) -> *mut c_char {
+    if gauntlet_id.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
+    }
+    let id = unsafe { std::ffi::CStr::from_ptr(gauntlet_id).to_string_lossy() };
+
+    match sekiro_gauntlet::gauntlet_triggers(&id) {
+        Ok(triggers) => ffi_string::to_c_string(serde_json::to_string(&triggers).unwrap_or_else(|_| "[]".to_string())),
+        Err(e) => ffi_string::error(format!("ERROR: {}", e)),
     }
 }
 
-/// Check if autosplitter is running
+/// Get the Sekiro Sculptor's Idols (split choices, by area) as JSON string
+/// Caller must free the returned string with autosplitter_free_string
 #[no_mangle]
-pub extern "C" fn autosplitter_is_running() -> bool {
-    AUTOSPLITTER
-        .lock()
-        .unwrap()
-        .as_ref()
-        .map(|a| a.is_running())
-        .unwrap_or(false)
+pub extern "C" fn autosplitter_get_sekiro_idols_json() -> *mut c_char {
+    let json = serde_json::to_string(&sekiro_idols::idols()).unwrap_or_else(|_| "[]".to_string());
+    ffi_string::to_c_string(json)
 }
 
-/// Get autosplitter state as JSON string
+/// Build Sekiro idol triggers (one split per idol, on the first rest at it)
+/// idol_ids_json: JSON array of idol ids
+/// Returns a JSON array of CustomTrigger objects for autosplitter_set_triggers,
+/// or an error message prefixed with "ERROR: " on failure
 /// Caller must free the returned string with autosplitter_free_string
 #[no_mangle]
-pub extern "C" fn autosplitter_get_state_json() -> *mut c_char {
-    let state = AUTOSPLITTER
-        .lock()
-        .unwrap()
-        .as_ref()
-        .map(|a| a.get_state())
-        .unwrap_or_default();
+pub extern "C" fn autosplitter_get_sekiro_idol_triggers_json(idol_ids_json: *const c_char) -> *mut c_char {
+    if idol_ids_json.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
+    }
+    let ids_str = unsafe { std::ffi::CStr::from_ptr(idol_ids_json).to_string_lossy() };
+    let selected: Vec<String> = match serde_json::from_str(&ids_str) {
+        Ok(ids) => ids,
+        Err(e) => return ffi_string::error(format!("ERROR: Failed to parse idol ids: {}", e)),
+    };
 
-    let json = serde_json::to_string(&state).unwrap_or_else(|_| "{}".to_string());
-    CString::new(json).unwrap().into_raw()
+    match sekiro_idols::idol_triggers(&selected) {
+        Ok(triggers) => ffi_string::to_c_string(serde_json::to_string(&triggers).unwrap_or_else(|_| "[]".to_string())),
+        Err(e) => ffi_string::error(format!("ERROR: {}", e)),
+    }
+}
+
+/// Split an event flag id into its parts for an engine ("ds3" or "elden_ring")
+/// Returns a JSON object (Ds3FlagId or ErFlagId), or an error message prefixed
+/// with "ERROR: " if the id can never exist in that game
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_decompose_flag_id_json(engine: *const c_char, flag_id: u32) -> *mut c_char {
+    if engine.is_null() {
+        return ffi_string::error("ERROR: Null pointer passed");
+    }
+    let engine = unsafe { std::ffi::CStr::from_ptr(engine).to_string_lossy() };
+
+    let parts = match engine::EngineType::from_str(&engine) {
+        Some(engine::EngineType::Ds3) => Ds3FlagId::decompose(flag_id).map(|id| serde_json::to_string(&id)),
+        Some(engine::EngineType::EldenRing) => ErFlagId::decompose(flag_id).map(|id| serde_json::to_string(&id)),
+        Some(_) => Err(format!("Engine '{}' has no flag id scheme", engine)),
+        None => Err(format!("Unknown engine type: {}", engine)),
+    };
+    match parts {
+        Ok(json) => ffi_string::to_c_string(json.unwrap_or_else(|_| "{}".to_string())),
+        Err(e) => ffi_string::error(format!("ERROR: {}", e)),
+    }
 }
 
 /// Free a string returned by the autosplitter
@@ -1500,6 +4654,41 @@ pub extern "C" fn autosplitter_free_string(s: *mut c_char) {
     }
 }
 
+/// Free a UTF-16 string returned by one of the `_utf16` functions
+#[no_mangle]
+pub extern "C" fn autosplitter_free_string_utf16(s: *mut u16) {
+    unsafe {
+        ffi_string::free_utf16(s);
+    }
+}
+
+/// Get the most recent error message reported by any FFI function
+/// Returns null if no error occurred since the last clear
+/// Caller must free the returned string with autosplitter_free_string
+#[no_mangle]
+pub extern "C" fn autosplitter_last_error() -> *mut c_char {
+    match ffi_string::last_error() {
+        Some(message) => ffi_string::to_c_string(message),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// UTF-16 variant of autosplitter_last_error
+/// Caller must free the returned string with autosplitter_free_string_utf16
+#[no_mangle]
+pub extern "C" fn autosplitter_last_error_utf16() -> *mut u16 {
+    match ffi_string::last_error() {
+        Some(message) => ffi_string::to_utf16(message),
+        None => std::ptr::null_mut(),
+    }
+}
+
+/// Forget the last error message
+#[no_mangle]
+pub extern "C" fn autosplitter_clear_last_error() {
+    ffi_string::clear_last_error();
+}
+
 /// Get library version
 #[no_mangle]
 pub extern "C" fn autosplitter_version() -> *const c_char {
@@ -1517,7 +4706,7 @@ pub extern "C" fn autosplitter_start(
     boss_flags_json: *const c_char,
 ) -> *mut c_char {
     if game_type.is_null() || boss_flags_json.is_null() {
-        return CString::new("Null pointer passed").unwrap().into_raw();
+        return ffi_string::error("Null pointer passed");
     }
 
     let game_type_str = unsafe { std::ffi::CStr::from_ptr(game_type).to_string_lossy() };
@@ -1530,23 +4719,23 @@ pub extern "C" fn autosplitter_start(
         "EldenRing" => GameType::EldenRing,
         "Sekiro" => GameType::Sekiro,
         "ArmoredCore6" => GameType::ArmoredCore6,
-        _ => return CString::new(format!("Unknown game type: {}", game_type_str)).unwrap().into_raw(),
+        _ => return ffi_string::error(format!("Unknown game type: {}", game_type_str)),
     };
 
     let boss_flags: Vec<BossFlag> = match serde_json::from_str(&boss_flags_str) {
         Ok(flags) => flags,
-        Err(e) => return CString::new(format!("Failed to parse boss flags: {}", e)).unwrap().into_raw(),
+        Err(e) => return ffi_string::error(format!("Failed to parse boss flags: {}", e)),
     };
 
     let guard = AUTOSPLITTER.lock().unwrap();
     let autosplitter = match guard.as_ref() {
         Some(a) => a,
-        None => return CString::new("Autosplitter not initialized").unwrap().into_raw(),
+        None => return ffi_string::error("Autosplitter not initialized"),
     };
 
     match autosplitter.start(game, boss_flags) {
         Ok(()) => std::ptr::null_mut(), // null means success
-        Err(e) => CString::new(e).unwrap().into_raw(),
+        Err(e) => ffi_string::error(e),
     }
 }
 
@@ -1560,7 +4749,7 @@ pub extern "C" fn autosplitter_start_autodetect(
     boss_flags_json: *const c_char,
 ) -> *mut c_char {
     if process_names_json.is_null() || boss_flags_json.is_null() {
-        return CString::new("Null pointer passed").unwrap().into_raw();
+        return ffi_string::error("Null pointer passed");
     }
 
     let process_names_str = unsafe { std::ffi::CStr::from_ptr(process_names_json).to_string_lossy() };
@@ -1568,18 +4757,18 @@ pub extern "C" fn autosplitter_start_autodetect(
 
     let process_names: Vec<String> = match serde_json::from_str(&process_names_str) {
         Ok(names) => names,
-        Err(e) => return CString::new(format!("Failed to parse process names: {}", e)).unwrap().into_raw(),
+        Err(e) => return ffi_string::error(format!("Failed to parse process names: {}", e)),
     };
 
     let boss_flags: Vec<BossFlag> = match serde_json::from_str(&boss_flags_str) {
         Ok(flags) => flags,
-        Err(e) => return CString::new(format!("Failed to parse boss flags: {}", e)).unwrap().into_raw(),
+        Err(e) => return ffi_string::error(format!("Failed to parse boss flags: {}", e)),
     };
 
     let guard = AUTOSPLITTER.lock().unwrap();
     let autosplitter = match guard.as_ref() {
         Some(a) => a,
-        None => return CString::new("Autosplitter not initialized").unwrap().into_raw(),
+        None => return ffi_string::error("Autosplitter not initialized"),
     };
 
     // Detect game type from process names
@@ -1589,9 +4778,9 @@ pub extern "C" fn autosplitter_start_autodetect(
     match game_type {
         Some(game) => match autosplitter.start(game, boss_flags) {
             Ok(()) => std::ptr::null_mut(),
-            Err(e) => CString::new(e).unwrap().into_raw(),
+            Err(e) => ffi_string::error(e),
         },
-        None => CString::new("No supported game detected from process names").unwrap().into_raw(),
+        None => ffi_string::error("No supported game detected from process names"),
     }
 }
 
@@ -1605,31 +4794,55 @@ pub extern "C" fn autosplitter_start_with_game_data(
     boss_flags_json: *const c_char,
 ) -> *mut c_char {
     if game_data_toml.is_null() || boss_flags_json.is_null() {
-        return CString::new("Null pointer passed").unwrap().into_raw();
+        return ffi_string::error("Null pointer passed");
     }
 
     let game_data_str = unsafe { std::ffi::CStr::from_ptr(game_data_toml).to_string_lossy() };
     let boss_flags_str = unsafe { std::ffi::CStr::from_ptr(boss_flags_json).to_string_lossy() };
 
-    let game_data: GameData = match GameData::from_toml(&game_data_str) {
-        Ok(data) => data,
-        Err(e) => return CString::new(format!("Failed to parse game data TOML: {}", e)).unwrap().into_raw(),
+    let compiled = match cache::precompile_game_data(&game_data_str) {
+        Ok(compiled) => compiled,
+        Err(e) => return ffi_string::error(e),
     };
 
     let boss_flags: Vec<BossFlag> = match serde_json::from_str(&boss_flags_str) {
         Ok(flags) => flags,
-        Err(e) => return CString::new(format!("Failed to parse boss flags: {}", e)).unwrap().into_raw(),
+        Err(e) => return ffi_string::error(format!("Failed to parse boss flags: {}", e)),
     };
 
     let guard = AUTOSPLITTER.lock().unwrap();
     let autosplitter = match guard.as_ref() {
         Some(a) => a,
-        None => return CString::new("Autosplitter not initialized").unwrap().into_raw(),
+        None => return ffi_string::error("Autosplitter not initialized"),
     };
 
-    match autosplitter.start_with_game_data(game_data, boss_flags) {
+    match autosplitter.start_with_compiled(compiled, boss_flags) {
         Ok(()) => std::ptr::null_mut(),
-        Err(e) => CString::new(e).unwrap().into_raw(),
+        Err(e) => ffi_string::error(e),
+    }
+}
+
+/// Read boss flags through a second game definition as well (see `cross_check_flags`)
+/// game_data_toml: TOML string containing the cross-check game definition, or null to clear
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_set_cross_check_game_data(game_data_toml: *const c_char) -> *mut c_char {
+    let compiled = if game_data_toml.is_null() {
+        None
+    } else {
+        let game_data_str = unsafe { std::ffi::CStr::from_ptr(game_data_toml).to_string_lossy() };
+        match cache::precompile_game_data(&game_data_str) {
+            Ok(compiled) => Some(compiled),
+            Err(e) => return ffi_string::error(e),
+        }
+    };
+
+    match AUTOSPLITTER.lock().unwrap().as_ref() {
+        Some(autosplitter) => {
+            autosplitter.set_cross_check(compiled);
+            std::ptr::null_mut()
+        }
+        None => ffi_string::error("Autosplitter not initialized"),
     }
 }
 
@@ -1645,7 +4858,7 @@ pub extern "C" fn autosplitter_start_with_asl(
     engine_hint: *const c_char,
 ) -> *mut c_char {
     if asl_content.is_null() || boss_flags_json.is_null() {
-        return CString::new("Null pointer passed").unwrap().into_raw();
+        return ffi_string::error("Null pointer passed");
     }
 
     let asl_str = unsafe { std::ffi::CStr::from_ptr(asl_content).to_string_lossy() };
@@ -1656,33 +4869,77 @@ pub extern "C" fn autosplitter_start_with_asl(
         Some(unsafe { std::ffi::CStr::from_ptr(engine_hint).to_string_lossy() })
     };
 
-    // Parse ASL and convert to GameData
-    let game_data = match asl::parse_asl(&asl_str, hint.as_deref()) {
-        Ok(data) => data,
-        Err(e) => return CString::new(format!("Failed to parse ASL: {}", e)).unwrap().into_raw(),
+    // Parse ASL and convert to GameData (cached per script and hint)
+    let compiled = match cache::precompile_asl(&asl_str, hint.as_deref()) {
+        Ok(compiled) => compiled,
+        Err(e) => return ffi_string::error(e),
     };
 
     let boss_flags: Vec<BossFlag> = match serde_json::from_str(&boss_flags_str) {
         Ok(flags) => flags,
-        Err(e) => {
-            return CString::new(format!("Failed to parse boss flags: {}", e))
-                .unwrap()
-                .into_raw()
-        }
+        Err(e) => return ffi_string::error(format!("Failed to parse boss flags: {}", e)),
     };
 
     let guard = AUTOSPLITTER.lock().unwrap();
     let autosplitter = match guard.as_ref() {
         Some(a) => a,
-        None => return CString::new("Autosplitter not initialized").unwrap().into_raw(),
+        None => return ffi_string::error("Autosplitter not initialized"),
     };
 
-    match autosplitter.start_with_game_data(game_data, boss_flags) {
+    match autosplitter.start_with_compiled(compiled, boss_flags) {
         Ok(()) => std::ptr::null_mut(),
-        Err(e) => CString::new(e).unwrap().into_raw(),
+        Err(e) => ffi_string::error(e),
+    }
+}
+
+/// Validate and compile a GameData TOML definition ahead of time
+/// Subsequent starts with the same TOML reuse the compiled result
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_precompile_game_data(game_data_toml: *const c_char) -> *mut c_char {
+    if game_data_toml.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let game_data_str = unsafe { std::ffi::CStr::from_ptr(game_data_toml).to_string_lossy() };
+
+    match cache::precompile_game_data(&game_data_str) {
+        Ok(_) => std::ptr::null_mut(),
+        Err(e) => ffi_string::error(e),
+    }
+}
+
+/// Parse and compile an ASL script ahead of time
+/// engine_hint: Optional engine hint (e.g., "ds3", "elden_ring"), can be null
+/// Returns error message or null on success (caller must free error string)
+#[no_mangle]
+pub extern "C" fn autosplitter_precompile_asl(
+    asl_content: *const c_char,
+    engine_hint: *const c_char,
+) -> *mut c_char {
+    if asl_content.is_null() {
+        return ffi_string::error("Null pointer passed");
+    }
+
+    let asl_str = unsafe { std::ffi::CStr::from_ptr(asl_content).to_string_lossy() };
+    let hint = if engine_hint.is_null() {
+        None
+    } else {
+        Some(unsafe { std::ffi::CStr::from_ptr(engine_hint).to_string_lossy() })
+    };
+
+    match cache::precompile_asl(&asl_str, hint.as_deref()) {
+        Ok(_) => std::ptr::null_mut(),
+        Err(e) => ffi_string::error(e),
     }
 }
 
+/// Drop all cached compiled configurations
+#[no_mangle]
+pub extern "C" fn autosplitter_clear_compiled_cache() {
+    cache::clear();
+}
+
 /// Parse ASL content and return GameData as TOML string
 /// asl_content: ASL script content as a string
 /// engine_hint: Optional engine hint (e.g., "ds3", "elden_ring"), can be null
@@ -1694,7 +4951,7 @@ pub extern "C" fn autosplitter_parse_asl(
     engine_hint: *const c_char,
 ) -> *mut c_char {
     if asl_content.is_null() {
-        return CString::new("ERROR: Null pointer passed").unwrap().into_raw();
+        return ffi_string::error("ERROR: Null pointer passed");
     }
 
     let asl_str = unsafe { std::ffi::CStr::from_ptr(asl_content).to_string_lossy() };
@@ -1707,21 +4964,13 @@ pub extern "C" fn autosplitter_parse_asl(
     // Parse ASL and convert to GameData
     let game_data = match asl::parse_asl(&asl_str, hint.as_deref()) {
         Ok(data) => data,
-        Err(e) => {
-            return CString::new(format!("ERROR: Failed to parse ASL: {}", e))
-                .unwrap()
-                .into_raw()
-        }
+        Err(e) => return ffi_string::error(format!("ERROR: Failed to parse ASL: {}", e)),
     };
 
     // Serialize to TOML
     match toml::to_string_pretty(&game_data) {
-        Ok(toml_str) => CString::new(toml_str).unwrap().into_raw(),
-        Err(e) => {
-            CString::new(format!("ERROR: Failed to serialize to TOML: {}", e))
-                .unwrap()
-                .into_raw()
-        }
+        Ok(toml_str) => ffi_string::to_c_string(toml_str),
+        Err(e) => ffi_string::error(format!("ERROR: Failed to serialize to TOML: {}", e)),
     }
 }
 
@@ -1747,6 +4996,7 @@ mod tests {
             GameType::from_process_name("DARKSOULSREMASTERED.EXE"),
             Some(GameType::DarkSouls1)
         );
+        assert_eq!(GameType::from_process_name("DARKSOULS.exe"), Some(GameType::DarkSouls1));
     }
 
     #[test]
@@ -1813,7 +5063,8 @@ mod tests {
     fn test_game_type_from_process_name_unknown() {
         assert_eq!(GameType::from_process_name("notepad.exe"), None);
         assert_eq!(GameType::from_process_name(""), None);
-        assert_eq!(GameType::from_process_name("darksouls.exe"), None); // Not specific enough
+        assert_eq!(GameType::from_process_name("darksouls_launcher.exe"), None); // Not specific enough
+        assert_eq!(GameType::from_process_name("DATA.exe"), None);
     }
 
     #[test]
@@ -1830,7 +5081,7 @@ mod tests {
     fn test_game_type_process_names() {
         assert_eq!(
             GameType::DarkSouls1.process_names(),
-            &["DarkSoulsRemastered.exe"]
+            &["DarkSoulsRemastered.exe", "DARKSOULS.exe"]
         );
         assert_eq!(
             GameType::DarkSouls2.process_names(),
@@ -1956,6 +5207,303 @@ mod tests {
         assert!(state.boss_kill_counts.is_empty());
     }
 
+    #[test]
+    fn test_idle_wait_simulated() {
+        let clock = Arc::new(SimulatedClock::new());
+        let autosplitter = Autosplitter::with_clock(clock.clone(), clock.clone());
+        let ctx = autosplitter.loop_context();
+        let mut backoff = IdleBackoff::default();
+
+        for _ in 0..3 {
+            ctx.idle_wait(&mut backoff);
+        }
+        autosplitter.wake();
+        ctx.idle_wait(&mut backoff);
+        ctx.idle_wait(&mut backoff);
+
+        let sleeps: Vec<u64> = clock.sleeps().iter().map(|d| d.as_millis() as u64).collect();
+        assert_eq!(sleeps, vec![2000, 4000, 8000, 0, 2000]);
+    }
+
+    /// Simulated sleeps that stop the run loop once `stop_at` of simulated
+    /// time has passed, so a loop can run to completion on the test thread
+    struct StopAt {
+        clock: Arc<SimulatedClock>,
+        running: Arc<AtomicBool>,
+        stop_at: Duration,
+    }
+
+    impl StopAt {
+        fn check(&self) {
+            if self.clock.elapsed() >= self.stop_at {
+                self.running.store(false, Ordering::SeqCst);
+            }
+        }
+    }
+
+    impl Sleeper for StopAt {
+        fn sleep(&self, duration: Duration) {
+            self.clock.sleep(duration);
+            self.check();
+        }
+
+        fn sleep_until_woken(&self, waker: &Waker, timeout: Duration) -> bool {
+            let woken = self.clock.sleep_until_woken(waker, timeout);
+            self.check();
+            woken
+        }
+    }
+
+    /// Loop context of `autosplitter` whose loop stops after `stop_at` of
+    /// simulated time
+    fn simulated_context(autosplitter: &Autosplitter, clock: &Arc<SimulatedClock>, stop_at: Duration) -> LoopContext {
+        let mut ctx = autosplitter.loop_context();
+        ctx.sleeper = Arc::new(StopAt {
+            clock: clock.clone(),
+            running: ctx.running.clone(),
+            stop_at,
+        });
+        ctx
+    }
+
+    #[test]
+    fn test_loop_backoff_simulated() {
+        let clock = Arc::new(SimulatedClock::new());
+        let autosplitter = Autosplitter::with_clock(clock.clone(), clock.clone());
+        let flags = vec![BossFlag {
+            boss_id: "iudex_gundyr".to_string(),
+            boss_name: "Iudex Gundyr".to_string(),
+            flag_id: 14000800,
+            is_dlc: false,
+            metadata: Default::default(),
+            group: None,
+            priority: 0,
+        }];
+        autosplitter.reset_for_start("DarkSouls3".to_string(), &flags, None);
+        let ctx = simulated_context(&autosplitter, &clock, Duration::from_secs(120));
+
+        // No process name can match, so the loop backs off between searches
+        #[cfg(target_os = "windows")]
+        run_autosplitter_loop(ctx, GameType::DarkSouls3, Vec::new(), flags);
+        #[cfg(target_os = "linux")]
+        run_autosplitter_loop_linux(ctx, GameType::DarkSouls3, Vec::new(), flags);
+
+        let idle: Vec<u64> = clock
+            .sleeps()
+            .iter()
+            .map(|d| d.as_millis() as u64)
+            .filter(|ms| *ms != 100)
+            .take(6)
+            .collect();
+        assert_eq!(idle, vec![2000, 4000, 8000, 16_000, 30_000, 30_000]);
+        assert!(!autosplitter.get_state().process_attached);
+    }
+
+    #[test]
+    fn test_demo_simulated() {
+        let clock = Arc::new(SimulatedClock::new());
+        let autosplitter = Autosplitter::with_clock(clock.clone(), clock.clone());
+        let boss_flags = demo::default_bosses();
+        let script = DemoScript::for_bosses(&boss_flags);
+        autosplitter.reset_for_start(demo::DEMO_GAME_NAME.to_string(), &boss_flags, None);
+
+        // The last boss dies at 120 s and the script restarts 15 s later
+        let ctx = simulated_context(&autosplitter, &clock, Duration::from_secs(150));
+        run_demo_loop(ctx, boss_flags.clone(), script);
+
+        let entries = autosplitter.run_log.lock().unwrap().entries().to_vec();
+        let splits: Vec<&str> = entries
+            .iter()
+            .filter_map(|entry| match &entry.event {
+                RunEvent::BossDefeated { boss_id, .. } => Some(boss_id.as_str()),
+                _ => None,
+            })
+            .take(6)
+            .collect();
+        let expected: Vec<String> = boss_flags.into_iter().map(|boss| boss.boss_id).collect();
+        assert_eq!(splits, expected);
+        assert!(entries.iter().any(|entry| matches!(entry.event, RunEvent::PlayerDied { death_count: 1, .. })));
+        assert!(entries.iter().any(|entry| matches!(entry.event, RunEvent::PlayerRespawned { .. })));
+        assert!(entries.iter().any(|entry| entry.event == RunEvent::Reset));
+    }
+
+    fn iudex_flags() -> Vec<BossFlag> {
+        vec![BossFlag {
+            boss_id: "iudex_gundyr".to_string(),
+            boss_name: "Iudex Gundyr".to_string(),
+            flag_id: 13000800,
+            is_dlc: false,
+            metadata: Default::default(),
+            group: None,
+            priority: 0,
+        }]
+    }
+
+    #[test]
+    fn test_start_uses_data_dir_definition() {
+        let dir = std::env::temp_dir().join(format!("nyacore-start-data-dir-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("ds3.toml"), include_str!("../schemas/ds3.toml")).unwrap();
+
+        let autosplitter = Autosplitter::new();
+        autosplitter.set_config(AutosplitterConfig {
+            data_dir: Some(dir.to_string_lossy().into_owned()),
+            ..Default::default()
+        });
+        autosplitter.start(GameType::DarkSouls3, iudex_flags()).unwrap();
+        let state = autosplitter.get_state();
+        let game_data = autosplitter.run_setup.lock().unwrap().as_ref().and_then(|setup| setup.game_data.clone());
+        autosplitter.stop();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        // The definition runs on the generic engine (the built-in reader
+        // reports "DarkSouls3")
+        assert_eq!(state.game_id, "ds3");
+        assert_eq!(game_data.unwrap().game.process_names, ["DarkSoulsIII.exe"]);
+    }
+
+    #[test]
+    fn test_trigger_groups_span_the_tick() {
+        let autosplitter = Autosplitter::new();
+        let triggers: Vec<CustomTrigger> = serde_json::from_str(r#"[
+            { "id": "flag", "name": "Dancer flag", "condition": { "type": "event_flag", "flag_id": 13000890 }, "group": "dancer", "priority": 10 },
+            { "id": "warp", "name": "Warp", "condition": { "type": "warp", "destination": 4001950 }, "group": "dancer", "priority": 1 },
+            { "id": "iudex_area", "name": "Iudex area", "condition": { "type": "attribute", "name": "igt", "op": "ge", "value": 0 }, "group": "iudex", "priority": 1 },
+            { "id": "vordt_area", "name": "Vordt area", "condition": { "type": "attribute", "name": "igt", "op": "ge", "value": 0 }, "group": "vordt", "priority": 1 }
+        ]"#).unwrap();
+        autosplitter.set_triggers(triggers);
+        let mut boss_flags: Vec<BossFlag> = demo::default_bosses().into_iter().take(2).collect();
+        boss_flags[0].group = Some("iudex".to_string());
+        boss_flags[0].priority = 10;
+        boss_flags[1].group = Some("vordt".to_string());
+
+        let script = DemoScript {
+            steps: [13000800, 13100800, 13000890]
+                .into_iter()
+                .map(|flag_id| demo::DemoStep { at_ms: 0, action: demo::DemoAction::SetFlag { flag_id } })
+                .collect(),
+            restart_after_ms: None,
+        };
+        let mut demo = DemoGame::new(script);
+        demo.advance(0);
+        let game = GameState::Demo(demo);
+
+        let ctx = autosplitter.loop_context();
+        let mut checked_flags = HashMap::new();
+        let mut kill_validator = KillValidator::default();
+        let mut trigger_plan = None;
+        let mut run_tick = |warped: bool| {
+            let (_, mut tick) =
+                ctx.check_boss_flags(&game, &boss_flags, &mut checked_flags, &mut kill_validator, &mut None, &mut None);
+            ctx.check_custom_triggers(&game, &mut trigger_plan, &mut tick);
+            // The warp to Firelink seen by `track_warps` on the same tick
+            if warped {
+                tick.triggers.push(1);
+            }
+            ctx.split_tick(&boss_flags, tick);
+        };
+        run_tick(true);
+        run_tick(false);
+
+        let splits: Vec<String> = autosplitter
+            .run_log
+            .lock()
+            .unwrap()
+            .entries()
+            .iter()
+            .filter_map(|entry| match &entry.event {
+                RunEvent::BossDefeated { boss_id, .. } => Some(boss_id.clone()),
+                RunEvent::TriggerMatched { trigger_id, .. } => Some(trigger_id.clone()),
+                _ => None,
+            })
+            .collect();
+        // One split per group: the boss beats its area entry, the area entry
+        // beats Vordt (priority 0), the flag beats the warp; the losers do
+        // not split a tick later
+        assert_eq!(splits, ["iudex_gundyr", "flag", "vordt_area"]);
+        let mut state = autosplitter.get_state();
+        state.triggers_matched.sort_unstable();
+        assert_eq!(state.triggers_matched, [0, 1, 2, 3]);
+        assert_eq!(state.bosses_suppressed, ["vordt"]);
+        assert_eq!(state.defeated_boss_ids(), ["iudex_gundyr"]);
+    }
+
+    #[test]
+    fn test_autosplitter_bundle() {
+        let autosplitter = Autosplitter::new();
+        assert!(autosplitter.loop_context().bundle_bytes().is_err());
+
+        *autosplitter.run_setup.lock().unwrap() = Some(RunSetup {
+            boss_flags: Vec::new(),
+            game_data: None,
+        });
+        let zip = autosplitter.loop_context().bundle_bytes().unwrap();
+        let manifest = bundle::read_manifest(&zip).unwrap();
+        let names: Vec<_> = manifest.files.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["run_log.json", "splits.json", "config.json", "diagnostics.json", "flag_history.json"]);
+    }
+
+    #[test]
+    fn test_autosplitter_split_boss() {
+        let autosplitter = Autosplitter::new();
+        assert!(autosplitter.split_boss("vordt").is_err());
+
+        *autosplitter.run_setup.lock().unwrap() = Some(RunSetup {
+            boss_flags: vec![BossFlag {
+                boss_id: "vordt".to_string(),
+                boss_name: "Vordt of the Boreal Valley".to_string(),
+                flag_id: 14000800,
+                is_dlc: false,
+                metadata: Default::default(),
+                group: None,
+                priority: 0,
+            }],
+            game_data: None,
+        });
+        assert!(autosplitter.split_boss("iudex").is_err());
+        assert_eq!(autosplitter.split_boss("vordt"), Ok(true));
+        assert_eq!(autosplitter.split_boss("vordt"), Ok(false));
+
+        let state = autosplitter.get_state();
+        assert_eq!(state.defeated_boss_ids(), ["vordt"]);
+        assert_eq!(state.suspicious_bosses, ["vordt"]);
+        let entries = autosplitter.run_log.lock().unwrap().entries().to_vec();
+        assert!(matches!(
+            entries[..],
+            [RunLogEntry {
+                event: RunEvent::BossDefeated { suspicion: Some(Suspicion::Manual), .. },
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_autosplitter_share_splits() {
+        let autosplitter = Autosplitter::new();
+        assert!(autosplitter.export_splits().is_err());
+
+        autosplitter.state.lock().unwrap().game_id = "DarkSouls3".to_string();
+        *autosplitter.run_setup.lock().unwrap() = Some(RunSetup {
+            boss_flags: vec![BossFlag {
+                boss_id: "vordt".to_string(),
+                boss_name: "Vordt of the Boreal Valley".to_string(),
+                flag_id: 14000800,
+                is_dlc: false,
+                metadata: Default::default(),
+                group: None,
+                priority: 0,
+            }],
+            game_data: None,
+        });
+        let text = autosplitter.export_splits().unwrap();
+
+        let other = Autosplitter::new();
+        let splits = other.import_splits(&text).unwrap();
+        assert_eq!(splits.game, "ds3");
+        assert_eq!(splits.boss_flags[0].flag_id, 14000800);
+        assert!(other.import_splits("nya1.not-a-config").is_err());
+    }
+
     // =============================================================================
     // BossFlag and AutosplitterState re-export tests
     // =============================================================================
@@ -1967,6 +5515,9 @@ mod tests {
             boss_name: "Test Boss".to_string(),
             flag_id: 12345,
             is_dlc: false,
+            metadata: Default::default(),
+            group: None,
+            priority: 0,
         };
 
         assert_eq!(flag.boss_id, "test_boss");
@@ -0,0 +1,101 @@
+//! Low-privilege fallback
+//!
+//! Anti-cheat, a game running elevated or a hardened ptrace scope can deny
+//! memory access altogether. Instead of appearing dead, the loops then attach
+//! in a limited mode (unless `AutosplitterConfig::low_privilege_fallback` is
+//! off): the process is reported attached with a `process_attached` event,
+//! `AutosplitterState::limited_access` tells whether the game window exists
+//! and is in the foreground, and splits come from the host through
+//! `Autosplitter::split_boss`. The loop keeps trying to open the process and
+//! attaches fully once it can.
+//!
+//! Window heuristics need Windows; on Linux (Proton) the window fields stay
+//! unknown and only the process is tracked.
+
+use serde::{Deserialize, Serialize};
+
+/// Delay between attempts to get full access while in limited mode
+pub const RETRY_INTERVAL_MS: u64 = 1000;
+
+/// What is known about a game whose memory cannot be read
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct LimitedAccess {
+    /// Process name of the game
+    pub process_name: String,
+    /// A visible top-level window of the game exists (None: unknown on
+    /// this platform)
+    pub window_found: Option<bool>,
+    /// The game window has the keyboard focus (None: unknown)
+    pub foreground: Option<bool>,
+    /// Title of the game window
+    pub window_title: Option<String>,
+}
+
+impl LimitedAccess {
+    /// Look up the windows of the process
+    pub fn probe(process_name: &str, pid: u32) -> Self {
+        #[cfg(target_os = "windows")]
+        {
+            let window = game_window(pid);
+            Self {
+                process_name: process_name.to_string(),
+                window_found: Some(window.is_some()),
+                foreground: Some(window.as_ref().is_some_and(|(_, foreground)| *foreground)),
+                window_title: window.map(|(title, _)| title).filter(|title| !title.is_empty()),
+            }
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = pid;
+            Self {
+                process_name: process_name.to_string(),
+                ..Default::default()
+            }
+        }
+    }
+}
+
+/// Title of the first visible top-level window of `pid`, and whether the
+/// foreground window belongs to the process
+#[cfg(target_os = "windows")]
+fn game_window(pid: u32) -> Option<(String, bool)> {
+    use windows::Win32::Foundation::{BOOL, HWND, LPARAM};
+    use windows::Win32::UI::WindowsAndMessaging::{
+        EnumWindows, GetForegroundWindow, GetWindowTextW, GetWindowThreadProcessId, IsWindowVisible,
+    };
+
+    struct Search {
+        pid: u32,
+        window: Option<HWND>,
+    }
+
+    unsafe extern "system" fn visit(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let search = &mut *(lparam.0 as *mut Search);
+        let mut window_pid = 0u32;
+        GetWindowThreadProcessId(hwnd, Some(&mut window_pid as *mut u32));
+        if window_pid == search.pid && IsWindowVisible(hwnd).as_bool() {
+            search.window = Some(hwnd);
+            // Stop enumerating
+            return BOOL(0);
+        }
+        BOOL(1)
+    }
+
+    let mut search = Search { pid, window: None };
+    unsafe {
+        // Fails when the callback stops the enumeration early
+        let _ = EnumWindows(Some(visit), LPARAM(&mut search as *mut Search as isize));
+    }
+    let window = search.window?;
+
+    let mut title = [0u16; 256];
+    let length = unsafe { GetWindowTextW(window, &mut title) }.max(0) as usize;
+    let title = String::from_utf16_lossy(&title[..length]);
+
+    let mut foreground_pid = 0u32;
+    unsafe {
+        GetWindowThreadProcessId(GetForegroundWindow(), Some(&mut foreground_pid as *mut u32));
+    }
+    Some((title, foreground_pid == pid))
+}
@@ -0,0 +1,141 @@
+//! Process architecture helpers
+//!
+//! The supported games are x64 (or x86) executables. On Windows on ARM they
+//! run under x64 emulation while the autosplitter itself may be a native
+//! ARM64 build (aarch64-pc-windows-msvc). Reading another process's memory
+//! works the same either way, but nothing may assume the autosplitter and the
+//! game share an architecture: pointer widths come from the game's image and
+//! RIP-relative operands are decoded from the game's x86-64 code with plain
+//! 64-bit arithmetic rather than host pointer math.
+
+/// Machine type of a PE image (COFF header `Machine` field)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageMachine {
+    X86,
+    X64,
+    Arm64,
+    Other(u16),
+}
+
+impl ImageMachine {
+    pub fn from_coff(machine: u16) -> Self {
+        match machine {
+            0x014C => ImageMachine::X86,
+            0x8664 => ImageMachine::X64,
+            0xAA64 => ImageMachine::Arm64,
+            other => ImageMachine::Other(other),
+        }
+    }
+
+    /// Size of a pointer in the game's address space
+    pub fn pointer_size(&self) -> usize {
+        match self {
+            ImageMachine::X86 => 4,
+            _ => 8,
+        }
+    }
+
+    pub fn is_64_bit(&self) -> bool {
+        self.pointer_size() == 8
+    }
+
+    /// Whether the image contains x86/x64 code (patterns and RIP-relative
+    /// resolution only apply to these)
+    pub fn is_x86_family(&self) -> bool {
+        matches!(self, ImageMachine::X86 | ImageMachine::X64)
+    }
+}
+
+/// Architecture the autosplitter was built for
+pub fn host_machine() -> ImageMachine {
+    if cfg!(target_arch = "aarch64") {
+        ImageMachine::Arm64
+    } else if cfg!(target_arch = "x86") {
+        ImageMachine::X86
+    } else {
+        ImageMachine::X64
+    }
+}
+
+/// Machine type of the module loaded at `base`, read from its PE header
+pub fn module_machine(base: usize, read: impl Fn(usize, usize) -> Option<Vec<u8>>) -> Option<ImageMachine> {
+    if read(base, 2)? != b"MZ" {
+        return None;
+    }
+    let e_lfanew = read(base + 0x3C, 4)?;
+    let pe = base + u32::from_le_bytes([e_lfanew[0], e_lfanew[1], e_lfanew[2], e_lfanew[3]]) as usize;
+    let header = read(pe, 6)?;
+    if &header[0..4] != b"PE\0\0" {
+        return None;
+    }
+    Some(ImageMachine::from_coff(u16::from_le_bytes([header[4], header[5]])))
+}
+
+/// Pointer size of the module loaded at `base`; 8 when its header cannot be read
+pub fn module_pointer_size(base: usize, read: impl Fn(usize, usize) -> Option<Vec<u8>>) -> usize {
+    module_machine(base, read).map_or(8, |machine| machine.pointer_size())
+}
+
+/// Whether the game runs under emulation on this host (x64 game, ARM64 host)
+pub fn is_emulated(game: ImageMachine) -> bool {
+    host_machine() == ImageMachine::Arm64 && game.is_x86_family()
+}
+
+/// Target of a RIP-relative operand: the address of the next instruction
+/// plus the signed 32-bit displacement
+pub fn rip_relative_target(instruction_addr: usize, instruction_len: usize, rel_offset: i32) -> usize {
+    (instruction_addr as u64)
+        .wrapping_add(instruction_len as u64)
+        .wrapping_add(rel_offset as i64 as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pe_image(machine: u16) -> Vec<u8> {
+        let mut image = vec![0u8; 0x100];
+        image[0..2].copy_from_slice(b"MZ");
+        image[0x3C..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        image[0x80..0x84].copy_from_slice(b"PE\0\0");
+        image[0x84..0x86].copy_from_slice(&machine.to_le_bytes());
+        image
+    }
+
+    #[test]
+    fn test_module_machine() {
+        const BASE: usize = 0x1_4000_0000;
+        for (machine, expected) in [
+            (0x8664, ImageMachine::X64),
+            (0x014C, ImageMachine::X86),
+            (0xAA64, ImageMachine::Arm64),
+            (0xA641, ImageMachine::Other(0xA641)),
+        ] {
+            let image = pe_image(machine);
+            let read = |address: usize, size: usize| {
+                let offset = address.checked_sub(BASE)?;
+                image.get(offset..offset + size).map(|b| b.to_vec())
+            };
+            assert_eq!(module_machine(BASE, read), Some(expected));
+        }
+        assert_eq!(module_machine(0, |_, size| Some(vec![0; size])), None);
+    }
+
+    #[test]
+    fn test_pointer_sizes() {
+        assert_eq!(ImageMachine::X64.pointer_size(), 8);
+        assert_eq!(ImageMachine::X86.pointer_size(), 4);
+        assert!(ImageMachine::Arm64.is_64_bit());
+        assert!(!ImageMachine::Arm64.is_x86_family());
+    }
+
+    #[test]
+    fn test_rip_relative_target() {
+        // lea rax, [rip+0x1234] at a typical x64 image address
+        assert_eq!(rip_relative_target(0x1_4000_1000, 7, 0x1234), 0x1_4000_223B);
+        // Negative displacement
+        assert_eq!(rip_relative_target(0x1_4000_1000, 7, -0x1007), 0x1_4000_0000);
+        // High addresses used by emulated processes keep all 64 bits
+        assert_eq!(rip_relative_target(0x7FF6_0000_0000, 6, 0x10), 0x7FF6_0000_0016);
+    }
+}
@@ -1,15 +1,22 @@
 //! Memory reading utilities for cross-platform autosplitter
 //!
-//! Provides memory reading primitives, pattern scanning, and process management.
+//! Provides memory reading primitives, pattern scanning, value/pointer-chain
+//! search, and process management.
 
+pub mod arch;
 pub mod reader;
+pub mod sandbox;
 pub mod pointer;
 pub mod process;
+pub mod search;
 pub mod traits;
 pub mod abstract_pointer;
 
+pub use arch::ImageMachine;
 pub use reader::*;
+pub use sandbox::{ReadLimits, ReadSandbox, SandboxedReader};
 pub use pointer::Pointer;
 pub use process::*;
+pub use search::{MemoryRegion, PointerChain, PointerScanOptions, ProcessMemory, SearchValue, ValueScan};
 pub use traits::{MemoryReader, ProcessFinder, MockMemoryReader, MockProcessFinder};
 pub use abstract_pointer::AbstractPointer;
@@ -74,6 +74,43 @@ pub fn get_module_base_and_size(pid: u32) -> Option<(usize, usize)> {
     }
 }
 
+/// Find a loaded module by file name (case-insensitive)
+/// Returns (base, size) if the module is loaded
+#[cfg(target_os = "windows")]
+pub fn find_module(pid: u32, module_name: &str) -> Option<(usize, usize)> {
+    unsafe {
+        let snapshot =
+            CreateToolhelp32Snapshot(TH32CS_SNAPMODULE | TH32CS_SNAPMODULE32, pid).ok()?;
+
+        let mut entry = MODULEENTRY32W {
+            dwSize: std::mem::size_of::<MODULEENTRY32W>() as u32,
+            ..Default::default()
+        };
+
+        let target = module_name.to_lowercase();
+        if Module32FirstW(snapshot, &mut entry).is_ok() {
+            loop {
+                let name = String::from_utf16_lossy(&entry.szModule)
+                    .trim_end_matches('\0')
+                    .to_lowercase();
+                if name == target {
+                    let base = entry.modBaseAddr as usize;
+                    let size = entry.modBaseSize as usize;
+                    let _ = CloseHandle(snapshot);
+                    return Some((base, size));
+                }
+
+                if Module32NextW(snapshot, &mut entry).is_err() {
+                    break;
+                }
+            }
+        }
+
+        let _ = CloseHandle(snapshot);
+        None
+    }
+}
+
 /// Check if a process is still running
 #[cfg(target_os = "windows")]
 pub fn is_process_running(handle: HANDLE) -> bool {
@@ -95,6 +132,7 @@ pub fn is_process_running(handle: HANDLE) -> bool {
 ///
 /// This works with both native Linux processes and Wine/Proton processes.
 /// For Proton games, the process name is typically the Windows executable name.
+/// Zombies (e.g., left by a crashed game) are skipped as they cannot be read.
 #[cfg(target_os = "linux")]
 pub fn find_process_by_name(target_names: &[&str]) -> Option<(u32, String)> {
     let proc_dir = Path::new("/proc");
@@ -121,21 +159,21 @@ pub fn find_process_by_name(target_names: &[&str]) -> Option<(u32, String)> {
         // Try multiple methods to get process name
         // Method 1: Read /proc/[pid]/comm (simple process name)
         if let Some(name) = read_proc_comm(pid) {
-            if matches_target(&name, target_names) {
+            if matches_target(&name, target_names) && !is_zombie(pid) {
                 return Some((pid, name));
             }
         }
 
         // Method 2: Read /proc/[pid]/cmdline (full command line, useful for Wine)
         if let Some(name) = read_proc_cmdline_exe(pid) {
-            if matches_target(&name, target_names) {
+            if matches_target(&name, target_names) && !is_zombie(pid) {
                 return Some((pid, name));
             }
         }
 
         // Method 3: Read /proc/[pid]/exe symlink (actual executable)
         if let Some(name) = read_proc_exe(pid) {
-            if matches_target(&name, target_names) {
+            if matches_target(&name, target_names) && !is_zombie(pid) {
                 return Some((pid, name));
             }
         }
@@ -273,7 +311,7 @@ pub fn get_module_base_and_size(pid: u32) -> Option<(usize, usize)> {
 /// Read the SizeOfImage from a PE header in process memory (Linux)
 #[cfg(target_os = "linux")]
 fn read_pe_image_size(pid: i32, base: usize) -> Option<usize> {
-    use super::memory::read_bytes;
+    use super::reader::read_bytes;
 
     // Read DOS header (first 64 bytes)
     let dos_header = read_bytes(pid, base, 64)?;
@@ -345,6 +383,47 @@ fn find_first_executable_region(pid: u32) -> Option<usize> {
     None
 }
 
+/// Find a loaded module by file name (Linux)
+///
+/// Wine maps DLLs from their files, so the module is the mapping whose path
+/// ends with the name (case-insensitive). The size is the PE SizeOfImage, or
+/// the span of the file's mappings if the header cannot be read.
+#[cfg(target_os = "linux")]
+pub fn find_module(pid: u32, module_name: &str) -> Option<(usize, usize)> {
+    let maps_path = format!("/proc/{}/maps", pid);
+    let maps = fs::read_to_string(&maps_path).ok()?;
+    let target = module_name.to_lowercase();
+
+    let mut range: Option<(usize, usize)> = None;
+    for line in maps.lines() {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        if parts.len() < 6 {
+            continue;
+        }
+
+        let pathname = parts[5..].join(" ").to_lowercase();
+        let filename = pathname.rsplit(['/', '\\']).next().unwrap_or_default();
+        if filename != target {
+            continue;
+        }
+
+        let Some((start, end)) = parts[0].split_once('-') else {
+            continue;
+        };
+        let (Ok(start), Ok(end)) = (usize::from_str_radix(start, 16), usize::from_str_radix(end, 16)) else {
+            continue;
+        };
+        range = Some(match range {
+            Some((first, last)) => (first.min(start), last.max(end)),
+            None => (start, end),
+        });
+    }
+
+    let (base, end) = range?;
+    let size = read_pe_image_size(pid as i32, base).unwrap_or(end - base);
+    Some((base, size))
+}
+
 /// Check if a process is still running (Linux)
 /// On Linux, we use the PID directly instead of a handle
 #[cfg(target_os = "linux")]
@@ -354,6 +433,19 @@ pub fn is_process_running_by_pid(pid: u32) -> bool {
     Path::new(&proc_path).exists()
 }
 
+/// Check if a process is a zombie: exited but not yet reaped, so `/proc/[pid]`
+/// still exists while its memory can no longer be read (Linux)
+#[cfg(target_os = "linux")]
+pub fn is_zombie(pid: u32) -> bool {
+    let Ok(stat) = fs::read_to_string(format!("/proc/{}/stat", pid)) else {
+        return false;
+    };
+    // The state follows the parenthesized command name, which may contain spaces
+    stat.rsplit_once(')')
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .is_some_and(|state| state == "Z" || state == "X")
+}
+
 /// Open a process for memory reading (Linux)
 /// Returns the PID if successful (we don't need a handle on Linux)
 #[cfg(target_os = "linux")]
@@ -362,10 +454,7 @@ pub fn open_process(pid: u32) -> Option<i32> {
     // and that we have permission to read its memory
     let mem_path = format!("/proc/{}/mem", pid);
 
-    // Check if we can access the process memory file
-    if Path::new(&mem_path).exists() {
-        Some(pid as i32)
-    } else {
-        None
-    }
+    // Opening the memory file runs the ptrace access check (ptrace_scope,
+    // other users' processes), which a plain existence check would miss
+    fs::File::open(&mem_path).ok().map(|_| pid as i32)
 }
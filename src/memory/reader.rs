@@ -12,24 +12,29 @@ use windows::Win32::System::Diagnostics::Debug::ReadProcessMemory;
 /// Read raw bytes from process memory
 #[cfg(target_os = "windows")]
 pub fn read_bytes(handle: HANDLE, address: usize, size: usize) -> Option<Vec<u8>> {
+    if !super::sandbox::check(address, size) {
+        return None;
+    }
     let mut buffer = vec![0u8; size];
     let mut bytes_read = 0usize;
 
-    unsafe {
-        if ReadProcessMemory(
+    let result = unsafe {
+        ReadProcessMemory(
             handle,
             address as *const _,
             buffer.as_mut_ptr() as *mut _,
             size,
             Some(&mut bytes_read),
         )
-        .is_ok()
-            && bytes_read == size
-        {
-            return Some(buffer);
-        }
+    };
+    crate::read_stats::record_read(bytes_read);
+
+    if result.is_ok() && bytes_read == size {
+        Some(buffer)
+    } else {
+        crate::read_stats::record_failed_read();
+        None
     }
-    None
 }
 
 /// Read a u8 from process memory
@@ -187,8 +192,7 @@ pub fn resolve_rip_relative(
     instruction_len: usize,
 ) -> Option<usize> {
     let rel_offset = read_i32(handle, instruction_addr + offset_pos)?;
-    let rip = instruction_addr + instruction_len;
-    Some((rip as i64 + rel_offset as i64) as usize)
+    Some(super::arch::rip_relative_target(instruction_addr, instruction_len, rel_offset))
 }
 
 // =============================================================================
@@ -203,6 +207,10 @@ pub fn resolve_rip_relative(
 pub fn read_bytes(pid: i32, address: usize, size: usize) -> Option<Vec<u8>> {
     use std::io::IoSliceMut;
 
+    if !super::sandbox::check(address, size) {
+        return None;
+    }
+
     let mut buffer = vec![0u8; size];
 
     // Use process_vm_readv syscall for efficient memory reading
@@ -224,10 +232,16 @@ pub fn read_bytes(pid: i32, address: usize, size: usize) -> Option<Vec<u8>> {
     };
 
     if bytes_read == size as isize {
+        crate::read_stats::record_read(size);
         Some(buffer)
     } else {
         // Fallback: try reading via /proc/[pid]/mem
-        read_bytes_via_proc_mem(pid, address, size)
+        let buffer = read_bytes_via_proc_mem(pid, address, size);
+        crate::read_stats::record_read(buffer.as_ref().map_or(0, |b| b.len()));
+        if buffer.is_none() {
+            crate::read_stats::record_failed_read();
+        }
+        buffer
     }
 }
 
@@ -374,8 +388,7 @@ pub fn resolve_rip_relative(
     instruction_len: usize,
 ) -> Option<usize> {
     let rel_offset = read_i32(pid, instruction_addr + offset_pos)?;
-    let rip = instruction_addr + instruction_len;
-    Some((rip as i64 + rel_offset as i64) as usize)
+    Some(super::arch::rip_relative_target(instruction_addr, instruction_len, rel_offset))
 }
 
 #[cfg(test)]
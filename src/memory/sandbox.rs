@@ -0,0 +1,252 @@
+//! Per-session limits on memory reads
+//!
+//! A misconfigured game file, trigger list or host plugin can read far more
+//! than the autosplitter needs, or read memory unrelated to the game state.
+//! [`ReadLimits`] caps the reads per tick and the bytes per read, and can
+//! restrict reads to the main module and the writable regions (heap) found
+//! when attaching. Denied reads fail as if the memory were unreadable.
+//!
+//! The run loop installs a [`ReadSandbox`] for its own thread when it attaches,
+//! so every read of the session (`reader::read_bytes`) is checked, while other
+//! sessions and host threads are unaffected. [`SandboxedReader`] applies the
+//! same limits to any [`MemoryReader`].
+
+use super::search::MemoryRegion;
+use super::traits::MemoryReader;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::sync::Mutex;
+
+/// Min ticks between re-reads of the writable regions after reads outside
+/// them were denied (new heap allocations)
+pub const REGION_REFRESH_TICKS: u64 = 10;
+
+/// Read caps of a session; the default imposes none
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ReadLimits {
+    /// Reads allowed per tick (0 = unlimited); further reads in the tick fail
+    pub max_reads_per_tick: u64,
+    /// Largest single read in bytes (0 = unlimited)
+    pub max_bytes_per_read: usize,
+    /// Only allow reads inside the main module and the writable regions of
+    /// the process
+    pub restrict_to_game_memory: bool,
+}
+
+/// Reads denied during one tick, by reason
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeniedReads {
+    pub over_read_count: u64,
+    pub over_size: u64,
+    pub outside_regions: u64,
+}
+
+impl DeniedReads {
+    pub fn total(&self) -> u64 {
+        self.over_read_count + self.over_size + self.outside_regions
+    }
+}
+
+/// Enforces [`ReadLimits`] over a sequence of reads
+#[derive(Debug)]
+pub struct ReadSandbox {
+    limits: ReadLimits,
+    /// Main module of the process
+    module: MemoryRegion,
+    /// Writable regions of the process (only used with `restrict_to_game_memory`)
+    regions: Vec<MemoryRegion>,
+    reads_this_tick: u64,
+    denied: DeniedReads,
+    ticks_since_refresh: u64,
+}
+
+impl ReadSandbox {
+    pub fn new(limits: ReadLimits, module: MemoryRegion, regions: Vec<MemoryRegion>) -> Self {
+        Self {
+            limits,
+            module,
+            regions,
+            reads_this_tick: 0,
+            denied: DeniedReads::default(),
+            ticks_since_refresh: 0,
+        }
+    }
+
+    /// Replace the writable regions (e.g., after the heap grew)
+    pub fn set_regions(&mut self, regions: Vec<MemoryRegion>) {
+        self.regions = regions;
+        self.ticks_since_refresh = 0;
+    }
+
+    /// Whether the writable regions are old enough to be read again
+    pub fn regions_refresh_due(&self) -> bool {
+        self.ticks_since_refresh >= REGION_REFRESH_TICKS
+    }
+
+    /// Check a read of `size` bytes at `address`, counting it when allowed
+    pub fn allow(&mut self, address: usize, size: usize) -> bool {
+        let limits = &self.limits;
+        if limits.max_bytes_per_read > 0 && size > limits.max_bytes_per_read {
+            self.denied.over_size += 1;
+            return false;
+        }
+        if limits.max_reads_per_tick > 0 && self.reads_this_tick >= limits.max_reads_per_tick {
+            self.denied.over_read_count += 1;
+            return false;
+        }
+        if limits.restrict_to_game_memory {
+            let end = address.saturating_add(size);
+            let inside = std::iter::once(&self.module)
+                .chain(&self.regions)
+                .any(|region| region.contains(address) && end <= region.end());
+            if !inside {
+                self.denied.outside_regions += 1;
+                return false;
+            }
+        }
+        self.reads_this_tick += 1;
+        true
+    }
+
+    /// Start a new tick with the current limits; returns the reads denied
+    /// during the tick that ended
+    pub fn end_tick(&mut self, limits: &ReadLimits) -> DeniedReads {
+        self.limits = limits.clone();
+        self.reads_this_tick = 0;
+        self.ticks_since_refresh += 1;
+        std::mem::take(&mut self.denied)
+    }
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<ReadSandbox>> = const { RefCell::new(None) };
+}
+
+/// Enforce `sandbox` on the reads of the calling thread
+pub fn install(sandbox: ReadSandbox) {
+    ACTIVE.with(|active| *active.borrow_mut() = Some(sandbox));
+}
+
+/// Lift the calling thread's read limits
+pub fn uninstall() {
+    ACTIVE.with(|active| *active.borrow_mut() = None);
+}
+
+/// Check a read against the calling thread's sandbox (allowed without one)
+pub fn check(address: usize, size: usize) -> bool {
+    ACTIVE.with(|active| active.borrow_mut().as_mut().is_none_or(|sandbox| sandbox.allow(address, size)))
+}
+
+/// [`ReadSandbox::end_tick`] on the calling thread's sandbox
+pub fn end_tick(limits: &ReadLimits) -> Option<DeniedReads> {
+    ACTIVE.with(|active| active.borrow_mut().as_mut().map(|sandbox| sandbox.end_tick(limits)))
+}
+
+/// [`ReadSandbox::regions_refresh_due`] on the calling thread's sandbox
+pub fn regions_refresh_due() -> bool {
+    ACTIVE.with(|active| active.borrow().as_ref().is_some_and(ReadSandbox::regions_refresh_due))
+}
+
+/// [`ReadSandbox::set_regions`] on the calling thread's sandbox
+pub fn set_regions(regions: Vec<MemoryRegion>) {
+    ACTIVE.with(|active| {
+        if let Some(sandbox) = active.borrow_mut().as_mut() {
+            sandbox.set_regions(regions);
+        }
+    });
+}
+
+/// [`MemoryReader`] that enforces read limits on another reader; call
+/// [`SandboxedReader::end_tick`] once per tick
+pub struct SandboxedReader<R: MemoryReader> {
+    inner: R,
+    sandbox: Mutex<ReadSandbox>,
+}
+
+impl<R: MemoryReader> SandboxedReader<R> {
+    /// Wrap `inner`; its main module is always an allowed region
+    pub fn new(inner: R, limits: ReadLimits, regions: Vec<MemoryRegion>) -> Self {
+        let module = MemoryRegion::new(inner.base_address(), inner.module_size());
+        Self {
+            inner,
+            sandbox: Mutex::new(ReadSandbox::new(limits, module, regions)),
+        }
+    }
+
+    pub fn end_tick(&self, limits: &ReadLimits) -> DeniedReads {
+        self.sandbox.lock().unwrap().end_tick(limits)
+    }
+}
+
+impl<R: MemoryReader> MemoryReader for SandboxedReader<R> {
+    fn read_bytes(&self, address: usize, size: usize) -> Option<Vec<u8>> {
+        if !self.sandbox.lock().unwrap().allow(address, size) {
+            return None;
+        }
+        self.inner.read_bytes(address, size)
+    }
+
+    fn is_valid(&self) -> bool {
+        self.inner.is_valid()
+    }
+
+    fn base_address(&self) -> usize {
+        self.inner.base_address()
+    }
+
+    fn module_size(&self) -> usize {
+        self.inner.module_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MockMemoryReader;
+
+    fn reader(limits: ReadLimits) -> SandboxedReader<MockMemoryReader> {
+        let mut mock = MockMemoryReader::new().with_base(0x140000000).with_size(0x1000);
+        mock.write_u32(0x140000010, 7);
+        mock.write_u32(0x200000000, 9);
+        SandboxedReader::new(mock, limits, vec![MemoryRegion::new(0x300000000, 0x100)])
+    }
+
+    #[test]
+    fn test_reads_per_tick() {
+        let limits = ReadLimits {
+            max_reads_per_tick: 2,
+            ..Default::default()
+        };
+        let reader = reader(limits.clone());
+        assert_eq!(reader.read_u32(0x140000010), Some(7));
+        assert_eq!(reader.read_u32(0x140000010), Some(7));
+        assert_eq!(reader.read_u32(0x140000010), None);
+        assert_eq!(reader.end_tick(&limits).over_read_count, 1);
+        assert_eq!(reader.read_u32(0x140000010), Some(7));
+    }
+
+    #[test]
+    fn test_bytes_per_read_and_regions() {
+        let limits = ReadLimits {
+            max_bytes_per_read: 4,
+            restrict_to_game_memory: true,
+            ..Default::default()
+        };
+        let reader = reader(limits.clone());
+        assert_eq!(reader.read_u32(0x140000010), Some(7));
+        assert_eq!(reader.read_u64(0x140000010), None);
+        // Readable, but outside the module and the allowed regions
+        assert_eq!(reader.read_u32(0x200000000), None);
+        // Straddling the end of a region
+        assert_eq!(reader.read_u32(0x3000000fe), None);
+
+        let denied = reader.end_tick(&limits);
+        assert_eq!((denied.over_size, denied.outside_regions), (1, 2));
+        // Limits lifted on the next tick
+        let unlimited = ReadLimits::default();
+        reader.end_tick(&unlimited);
+        assert_eq!(reader.read_u32(0x200000000), Some(9));
+    }
+}
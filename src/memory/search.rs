@@ -0,0 +1,594 @@
+//! Value search and pointer-chain scanning
+//!
+//! A small Cheat Engine-style workflow for plugin authors locating new values:
+//! 1. Scan the writable memory of the game for a known value (e.g., current HP)
+//! 2. Change the value in game and narrow the candidates with more samples
+//! 3. Search pointer chains from the main module to the remaining address
+//! 4. Restart the game and keep only the chains that still resolve to the value
+//!
+//! All searches work on a [`MemoryReader`], so they can run against a live
+//! process through [`ProcessMemory`] or against a mock in tests.
+
+use super::reader::parse_pattern;
+use super::traits::MemoryReader;
+use std::collections::HashSet;
+use std::fmt;
+
+#[cfg(target_os = "windows")]
+use windows::Win32::Foundation::HANDLE;
+#[cfg(target_os = "windows")]
+use windows::Win32::System::Memory::{
+    VirtualQueryEx, MEMORY_BASIC_INFORMATION, MEM_COMMIT, PAGE_EXECUTE_READWRITE,
+    PAGE_EXECUTE_WRITECOPY, PAGE_GUARD, PAGE_READWRITE, PAGE_WRITECOPY,
+};
+
+/// Bytes read per request when scanning a region
+const SCAN_CHUNK_SIZE: usize = 0x10000;
+
+/// A contiguous range of process memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryRegion {
+    pub start: usize,
+    pub size: usize,
+}
+
+impl MemoryRegion {
+    pub fn new(start: usize, size: usize) -> Self {
+        Self { start, size }
+    }
+
+    pub fn end(&self) -> usize {
+        self.start + self.size
+    }
+
+    pub fn contains(&self, address: usize) -> bool {
+        address >= self.start && address < self.end()
+    }
+}
+
+/// Committed, writable regions of a process (where game data lives)
+#[cfg(target_os = "windows")]
+pub fn writable_regions(handle: HANDLE) -> Vec<MemoryRegion> {
+    let writable = PAGE_READWRITE.0
+        | PAGE_WRITECOPY.0
+        | PAGE_EXECUTE_READWRITE.0
+        | PAGE_EXECUTE_WRITECOPY.0;
+    let mut regions = Vec::new();
+    let mut address = 0usize;
+
+    loop {
+        let mut info = MEMORY_BASIC_INFORMATION::default();
+        let written = unsafe {
+            VirtualQueryEx(
+                handle,
+                Some(address as *const _),
+                &mut info,
+                std::mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+            )
+        };
+        if written == 0 || info.RegionSize == 0 {
+            break;
+        }
+
+        let start = info.BaseAddress as usize;
+        if info.State == MEM_COMMIT
+            && info.Protect.0 & writable != 0
+            && info.Protect.0 & PAGE_GUARD.0 == 0
+        {
+            regions.push(MemoryRegion::new(start, info.RegionSize));
+        }
+
+        match start.checked_add(info.RegionSize) {
+            Some(next) => address = next,
+            None => break,
+        }
+    }
+
+    regions
+}
+
+/// Readable and writable mappings of a process, from /proc/[pid]/maps (Linux)
+#[cfg(target_os = "linux")]
+pub fn writable_regions(pid: i32) -> Vec<MemoryRegion> {
+    let maps = match std::fs::read_to_string(format!("/proc/{}/maps", pid)) {
+        Ok(maps) => maps,
+        Err(_) => return Vec::new(),
+    };
+
+    maps.lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let range = parts.next()?;
+            let perms = parts.next()?;
+            if !perms.starts_with("rw") {
+                return None;
+            }
+
+            let (start, end) = range.split_once('-')?;
+            let start = usize::from_str_radix(start, 16).ok()?;
+            let end = usize::from_str_radix(end, 16).ok()?;
+            Some(MemoryRegion::new(start, end.saturating_sub(start)))
+        })
+        .collect()
+}
+
+/// [`MemoryReader`] over a live process
+#[cfg(target_os = "windows")]
+pub struct ProcessMemory {
+    handle: HANDLE,
+    base: usize,
+    size: usize,
+}
+
+#[cfg(target_os = "windows")]
+unsafe impl Send for ProcessMemory {}
+#[cfg(target_os = "windows")]
+unsafe impl Sync for ProcessMemory {}
+
+#[cfg(target_os = "windows")]
+impl ProcessMemory {
+    pub fn new(handle: HANDLE, base: usize, size: usize) -> Self {
+        Self { handle, base, size }
+    }
+
+    /// Writable regions of this process
+    pub fn regions(&self) -> Vec<MemoryRegion> {
+        writable_regions(self.handle)
+    }
+}
+
+#[cfg(target_os = "windows")]
+impl MemoryReader for ProcessMemory {
+    fn read_bytes(&self, address: usize, size: usize) -> Option<Vec<u8>> {
+        super::reader::read_bytes(self.handle, address, size)
+    }
+
+    fn is_valid(&self) -> bool {
+        super::process::is_process_running(self.handle)
+    }
+
+    fn base_address(&self) -> usize {
+        self.base
+    }
+
+    fn module_size(&self) -> usize {
+        self.size
+    }
+}
+
+/// [`MemoryReader`] over a live process
+#[cfg(target_os = "linux")]
+pub struct ProcessMemory {
+    pid: i32,
+    base: usize,
+    size: usize,
+}
+
+#[cfg(target_os = "linux")]
+impl ProcessMemory {
+    pub fn new(pid: i32, base: usize, size: usize) -> Self {
+        Self { pid, base, size }
+    }
+
+    pub fn regions(&self) -> Vec<MemoryRegion> {
+        writable_regions(self.pid)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl MemoryReader for ProcessMemory {
+    fn read_bytes(&self, address: usize, size: usize) -> Option<Vec<u8>> {
+        super::reader::read_bytes(self.pid, address, size)
+    }
+
+    fn is_valid(&self) -> bool {
+        super::process::is_process_running_by_pid(self.pid as u32)
+    }
+
+    fn base_address(&self) -> usize {
+        self.base
+    }
+
+    fn module_size(&self) -> usize {
+        self.size
+    }
+}
+
+/// A value to search for
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchValue {
+    I32(i32),
+    U32(u32),
+    I64(i64),
+    /// Matches within 0.5 so displayed (rounded) values can be typed in
+    F32(f32),
+    F64(f64),
+    /// Byte pattern with wildcards (e.g., "48 8b ? ? 05")
+    Pattern(Vec<Option<u8>>),
+}
+
+impl SearchValue {
+    /// Byte pattern from a string like "48 8b ? ? 05"
+    pub fn pattern(pattern: &str) -> Self {
+        SearchValue::Pattern(parse_pattern(pattern))
+    }
+
+    /// Number of bytes compared at each address
+    pub fn size(&self) -> usize {
+        match self {
+            SearchValue::I32(_) | SearchValue::U32(_) | SearchValue::F32(_) => 4,
+            SearchValue::I64(_) | SearchValue::F64(_) => 8,
+            SearchValue::Pattern(pattern) => pattern.len(),
+        }
+    }
+
+    /// Default scan alignment (values are usually naturally aligned)
+    pub fn alignment(&self) -> usize {
+        match self {
+            SearchValue::Pattern(_) => 1,
+            _ => 4,
+        }
+    }
+
+    /// Check whether `bytes` (at least `size()` long) hold this value
+    pub fn matches(&self, bytes: &[u8]) -> bool {
+        if bytes.len() < self.size() {
+            return false;
+        }
+
+        match self {
+            SearchValue::I32(v) => bytes[..4] == v.to_le_bytes(),
+            SearchValue::U32(v) => bytes[..4] == v.to_le_bytes(),
+            SearchValue::I64(v) => bytes[..8] == v.to_le_bytes(),
+            SearchValue::F32(v) => {
+                let actual = f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                (actual - v).abs() < 0.5
+            }
+            SearchValue::F64(v) => {
+                let mut raw = [0u8; 8];
+                raw.copy_from_slice(&bytes[..8]);
+                (f64::from_le_bytes(raw) - v).abs() < 0.5
+            }
+            SearchValue::Pattern(pattern) => pattern
+                .iter()
+                .zip(bytes)
+                .all(|(expected, actual)| expected.is_none_or(|b| b == *actual)),
+        }
+    }
+}
+
+/// Read a region in chunks, calling `visit` with each chunk and its address
+///
+/// Chunks overlap by `overlap` bytes so values spanning a chunk border are seen.
+/// Unreadable chunks are skipped.
+fn for_each_chunk(
+    reader: &dyn MemoryReader,
+    region: &MemoryRegion,
+    overlap: usize,
+    mut visit: impl FnMut(usize, &[u8]),
+) {
+    let mut address = region.start;
+    while address < region.end() {
+        let len = (region.end() - address).min(SCAN_CHUNK_SIZE + overlap);
+        if let Some(bytes) = reader.read_bytes(address, len) {
+            visit(address, &bytes);
+        }
+        address += SCAN_CHUNK_SIZE;
+    }
+}
+
+/// Candidate addresses narrowed over multiple samples
+#[derive(Debug, Clone)]
+pub struct ValueScan {
+    size: usize,
+    /// Candidate addresses and the bytes read at the last sample
+    candidates: Vec<(usize, Vec<u8>)>,
+}
+
+impl ValueScan {
+    /// First scan: every aligned address in `regions` holding `value`
+    pub fn first(reader: &dyn MemoryReader, regions: &[MemoryRegion], value: &SearchValue) -> Self {
+        let size = value.size();
+        let alignment = value.alignment();
+        let mut candidates = Vec::new();
+
+        if size > 0 {
+            for region in regions {
+                for_each_chunk(reader, region, size - 1, |chunk_start, bytes| {
+                    let mut offset = (alignment - chunk_start % alignment) % alignment;
+                    while offset + size <= bytes.len() && offset < SCAN_CHUNK_SIZE {
+                        if value.matches(&bytes[offset..]) {
+                            candidates.push((chunk_start + offset, bytes[offset..offset + size].to_vec()));
+                        }
+                        offset += alignment;
+                    }
+                });
+            }
+        }
+
+        Self { size, candidates }
+    }
+
+    /// Keep candidates that now hold `value`
+    pub fn next_value(&mut self, reader: &dyn MemoryReader, value: &SearchValue) {
+        self.refine(reader, |_, current| value.matches(current));
+    }
+
+    /// Keep candidates whose bytes changed since the last sample
+    pub fn next_changed(&mut self, reader: &dyn MemoryReader) {
+        self.refine(reader, |previous, current| previous != current);
+    }
+
+    /// Keep candidates whose bytes did not change since the last sample
+    pub fn next_unchanged(&mut self, reader: &dyn MemoryReader) {
+        self.refine(reader, |previous, current| previous == current);
+    }
+
+    fn refine(&mut self, reader: &dyn MemoryReader, keep: impl Fn(&[u8], &[u8]) -> bool) {
+        let size = self.size;
+        self.candidates.retain_mut(|(address, previous)| {
+            match reader.read_bytes(*address, size) {
+                Some(current) if keep(previous, &current) => {
+                    *previous = current;
+                    true
+                }
+                _ => false,
+            }
+        });
+    }
+
+    /// Remaining candidate addresses
+    pub fn addresses(&self) -> Vec<usize> {
+        self.candidates.iter().map(|(address, _)| *address).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+}
+
+/// A pointer chain from the main module to a value
+///
+/// Resolves as `[[base + module_offset] + offsets[0]] ... + offsets[n-1]`:
+/// the static pointer is read, each offset except the last is added and
+/// dereferenced, and the last offset is added to get the value address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PointerChain {
+    pub module_offset: usize,
+    pub offsets: Vec<usize>,
+}
+
+impl PointerChain {
+    /// Follow the chain and return the value address
+    pub fn resolve(&self, reader: &dyn MemoryReader) -> Option<usize> {
+        let mut address = reader.base_address() + self.module_offset;
+        for offset in &self.offsets {
+            address = reader.read_ptr(address)?.checked_add(*offset)?;
+        }
+        Some(address)
+    }
+}
+
+impl fmt::Display for PointerChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "base+0x{:X}", self.module_offset)?;
+        for offset in &self.offsets {
+            write!(f, " -> 0x{:X}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+/// Limits for pointer-chain searches
+#[derive(Debug, Clone)]
+pub struct PointerScanOptions {
+    /// Maximum number of dereferences in a chain
+    pub max_depth: usize,
+    /// Maximum offset added after each dereference
+    pub max_offset: usize,
+    /// Stop after this many chains
+    pub max_results: usize,
+    /// Maximum addresses expanded per level (keeps deep searches bounded)
+    pub max_nodes_per_level: usize,
+}
+
+impl Default for PointerScanOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            max_offset: 0x1000,
+            max_results: 100,
+            max_nodes_per_level: 10_000,
+        }
+    }
+}
+
+/// Find pointer chains from the main module to `target`
+///
+/// Builds a map of every 8-byte aligned pointer in `regions`, then walks
+/// backwards from the target. Chains rooted in the module image survive game
+/// restarts, so they are the ones returned (shortest first).
+pub fn find_pointer_chains(
+    reader: &dyn MemoryReader,
+    regions: &[MemoryRegion],
+    target: usize,
+    options: &PointerScanOptions,
+) -> Vec<PointerChain> {
+    let pointers = collect_pointers(reader, regions);
+    let module = MemoryRegion::new(reader.base_address(), reader.module_size());
+
+    let mut results = Vec::new();
+    let mut visited: HashSet<usize> = HashSet::new();
+    // (address to reach, offsets from that address to the target)
+    let mut frontier: Vec<(usize, Vec<usize>)> = vec![(target, Vec::new())];
+
+    for _ in 0..options.max_depth {
+        let mut next = Vec::new();
+
+        for (address, offsets) in &frontier {
+            // Pointers to anything in [address - max_offset, address]
+            let low = address.saturating_sub(options.max_offset);
+            let first = pointers.partition_point(|(value, _)| *value < low);
+
+            for (value, slot) in pointers[first..].iter().take_while(|(value, _)| value <= address) {
+                let mut chain_offsets = Vec::with_capacity(offsets.len() + 1);
+                chain_offsets.push(address - value);
+                chain_offsets.extend_from_slice(offsets);
+
+                if module.contains(*slot) {
+                    results.push(PointerChain {
+                        module_offset: slot - module.start,
+                        offsets: chain_offsets,
+                    });
+                    if results.len() >= options.max_results {
+                        return results;
+                    }
+                } else if visited.insert(*slot) && next.len() < options.max_nodes_per_level {
+                    next.push((*slot, chain_offsets));
+                }
+            }
+        }
+
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+
+    results
+}
+
+/// Keep the chains that still resolve to an address holding `value`
+///
+/// Run after restarting the game to drop chains that only worked by chance.
+pub fn filter_chains(reader: &dyn MemoryReader, chains: &mut Vec<PointerChain>, value: &SearchValue) {
+    chains.retain(|chain| {
+        chain
+            .resolve(reader)
+            .and_then(|address| reader.read_bytes(address, value.size()))
+            .is_some_and(|bytes| value.matches(&bytes))
+    });
+}
+
+/// All (pointer value, slot address) pairs pointing into `regions` or the module, sorted by value
+fn collect_pointers(reader: &dyn MemoryReader, regions: &[MemoryRegion]) -> Vec<(usize, usize)> {
+    let module = MemoryRegion::new(reader.base_address(), reader.module_size());
+    let points_into = |value: usize| module.contains(value) || regions.iter().any(|r| r.contains(value));
+
+    let mut scanned: Vec<MemoryRegion> = regions.to_vec();
+    // Static pointers live in the module image (.data), which may not be listed as writable
+    if !regions.iter().any(|r| r.start <= module.start && r.end() >= module.end()) {
+        scanned.push(module);
+    }
+
+    let mut pointers = Vec::new();
+    let mut seen_slots = HashSet::new();
+    for region in &scanned {
+        for_each_chunk(reader, region, 0, |chunk_start, bytes| {
+            let first = (8 - chunk_start % 8) % 8;
+            for offset in (first..bytes.len().saturating_sub(7)).step_by(8) {
+                let mut raw = [0u8; 8];
+                raw.copy_from_slice(&bytes[offset..offset + 8]);
+                let value = u64::from_le_bytes(raw) as usize;
+                let slot = chunk_start + offset;
+                if value != 0 && points_into(value) && seen_slots.insert(slot) {
+                    pointers.push((value, slot));
+                }
+            }
+        });
+    }
+
+    pointers.sort_unstable();
+    pointers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MockMemoryReader;
+
+    const BASE: usize = 0x140000000;
+    const HEAP: usize = 0x20000000;
+
+    /// Module with a static pointer at base+0x100 -> heap object -> player at +0x80, HP at player+0x10
+    fn mock_game(hp: i32) -> MockMemoryReader {
+        let mut reader = MockMemoryReader::new().with_base(BASE).with_size(0x1000);
+
+        let mut module = vec![0u8; 0x1000];
+        module[0x100..0x108].copy_from_slice(&(HEAP as u64).to_le_bytes());
+        reader.write_memory_block(BASE, &module);
+
+        let mut heap = vec![0u8; 0x1000];
+        heap[0x80..0x88].copy_from_slice(&((HEAP + 0x800) as u64).to_le_bytes());
+        heap[0x810..0x814].copy_from_slice(&hp.to_le_bytes());
+        // Unrelated value that happens to match the first sample
+        heap[0x400..0x404].copy_from_slice(&454i32.to_le_bytes());
+        reader.write_memory_block(HEAP, &heap);
+
+        reader
+    }
+
+    fn regions() -> Vec<MemoryRegion> {
+        vec![MemoryRegion::new(HEAP, 0x1000)]
+    }
+
+    #[test]
+    fn test_search_value_matches() {
+        assert!(SearchValue::I32(-5).matches(&(-5i32).to_le_bytes()));
+        assert!(SearchValue::F32(100.0).matches(&100.3f32.to_le_bytes()));
+        assert!(!SearchValue::F32(100.0).matches(&101.0f32.to_le_bytes()));
+        assert!(SearchValue::pattern("48 ? 05").matches(&[0x48, 0xFF, 0x05]));
+        assert!(!SearchValue::I64(1).matches(&[1, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_value_scan_narrows_candidates() {
+        let reader = mock_game(454);
+        let mut scan = ValueScan::first(&reader, &regions(), &SearchValue::I32(454));
+        assert_eq!(scan.len(), 2);
+
+        // Player took damage
+        let reader = mock_game(300);
+        scan.next_value(&reader, &SearchValue::I32(300));
+        assert_eq!(scan.addresses(), vec![HEAP + 0x810]);
+
+        scan.next_unchanged(&reader);
+        assert_eq!(scan.len(), 1);
+        scan.next_changed(&reader);
+        assert!(scan.is_empty());
+    }
+
+    #[test]
+    fn test_find_pointer_chains() {
+        let reader = mock_game(454);
+        let chains = find_pointer_chains(&reader, &regions(), HEAP + 0x810, &PointerScanOptions::default());
+
+        let expected = PointerChain {
+            module_offset: 0x100,
+            offsets: vec![0x80, 0x10],
+        };
+        assert!(chains.contains(&expected));
+        assert_eq!(expected.resolve(&reader), Some(HEAP + 0x810));
+        assert_eq!(expected.to_string(), "base+0x100 -> 0x80 -> 0x10");
+    }
+
+    #[test]
+    fn test_filter_chains() {
+        let reader = mock_game(454);
+        let mut chains = vec![
+            PointerChain { module_offset: 0x100, offsets: vec![0x80, 0x10] },
+            PointerChain { module_offset: 0x100, offsets: vec![0x400] },
+        ];
+
+        filter_chains(&reader, &mut chains, &SearchValue::I32(454));
+        assert_eq!(chains.len(), 2);
+
+        let reader = mock_game(300);
+        filter_chains(&reader, &mut chains, &SearchValue::I32(300));
+        assert_eq!(chains, vec![PointerChain { module_offset: 0x100, offsets: vec![0x80, 0x10] }]);
+    }
+}
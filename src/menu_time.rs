@@ -0,0 +1,57 @@
+//! Menu and pause time accounting
+//!
+//! Time in the main menu, and time the in-game clock stood still outside of
+//! loads (a paused game, quitouts), is added up separately from active
+//! gameplay. The run log stamps every entry with the menu time of the
+//! current attempt (`menu_time_ms`), so timing rules that
+//! exclude menu time can be applied to any split afterwards, and practice
+//! tools can tell how much of an attempt was spent in menus.
+//!
+//! Games without an in-game clock only count main menu time.
+
+/// Per-attach menu time sampler
+#[derive(Debug, Default)]
+pub struct MenuTimer {
+    /// (elapsed_ms, IGT) of the previous sample
+    last: Option<(u64, Option<i64>)>,
+}
+
+impl MenuTimer {
+    /// Sample once per tick; returns the milliseconds since the previous
+    /// sample that were spent in menus
+    pub fn observe(&mut self, elapsed_ms: u64, igt_ms: Option<i64>, in_main_menu: bool, loading: bool) -> u64 {
+        let Some((last_ms, last_igt)) = self.last.replace((elapsed_ms, igt_ms)) else {
+            return 0;
+        };
+
+        let interval = elapsed_ms.saturating_sub(last_ms);
+        let paused = !loading && igt_ms.is_some() && igt_ms == last_igt;
+        if in_main_menu || paused {
+            interval
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_menu_and_pause_time() {
+        let mut timer = MenuTimer::default();
+        assert_eq!(timer.observe(0, Some(0), true, false), 0);
+        // Main menu
+        assert_eq!(timer.observe(1000, Some(0), true, false), 1000);
+        // Playing: IGT advances
+        assert_eq!(timer.observe(1100, Some(100), false, false), 0);
+        // Paused: IGT stands still
+        assert_eq!(timer.observe(1200, Some(100), false, false), 100);
+        // A load stops IGT too, but is not menu time
+        assert_eq!(timer.observe(1300, Some(100), false, true), 0);
+        // No clock: only the main menu counts
+        assert_eq!(timer.observe(1400, None, false, false), 0);
+        assert_eq!(timer.observe(1500, None, true, false), 100);
+    }
+}
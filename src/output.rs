@@ -0,0 +1,220 @@
+//! Output batching and rate limiting for integrations
+//!
+//! Loading a late-game save (or attaching to one) can set dozens of boss flags
+//! in a single tick. Forwarding each as its own command floods integrations
+//! such as LiveSplit Server. [`OutputLimiter`] sits between the run log and the
+//! integration: when enough splits are waiting at once they are coalesced into
+//! a single [`OutputMessage::BulkSync`], and messages are released through a
+//! token bucket so a burst is spread out instead of dropped.
+
+use crate::run_log::RunLogEntry;
+use crate::speech::split_key;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+/// Rate limiting and coalescing policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct OutputConfig {
+    /// Messages released per second, also the burst size (0 = unlimited)
+    pub max_messages_per_second: u32,
+    /// Waiting splits that are coalesced into one bulk sync message (0 = never)
+    pub bulk_sync_threshold: usize,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            max_messages_per_second: 10,
+            bulk_sync_threshold: 3,
+        }
+    }
+}
+
+/// A message for an integration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum OutputMessage {
+    /// A single run log entry
+    Event { entry: RunLogEntry },
+    /// Several splits that happened at once (e.g., a late-game save was
+    /// loaded); integrations should update their state in one step instead of
+    /// replaying every split
+    BulkSync { entries: Vec<RunLogEntry> },
+}
+
+impl OutputMessage {
+    fn is_split(&self) -> bool {
+        matches!(self, OutputMessage::Event { entry } if split_key(&entry.event).is_some())
+    }
+}
+
+/// Queue between the run log and an integration
+#[derive(Debug, Default)]
+pub struct OutputLimiter {
+    queue: VecDeque<OutputMessage>,
+    tokens: f64,
+    last_refill: Option<Instant>,
+}
+
+impl OutputLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue new run log entries, coalescing waiting splits when there are
+    /// at least `bulk_sync_threshold` of them
+    pub fn push(&mut self, entries: Vec<RunLogEntry>, config: &OutputConfig) {
+        self.queue
+            .extend(entries.into_iter().map(|entry| OutputMessage::Event { entry }));
+
+        let waiting_splits = self.queue.iter().filter(|m| m.is_split()).count();
+        if config.bulk_sync_threshold == 0 || waiting_splits < config.bulk_sync_threshold {
+            return;
+        }
+
+        // Merge the waiting splits (and any earlier bulk sync) at the position
+        // of the first one; other events keep their order
+        let mut merged = Vec::new();
+        let mut position = None;
+        let mut kept = VecDeque::with_capacity(self.queue.len());
+        for message in self.queue.drain(..) {
+            match message {
+                OutputMessage::BulkSync { entries } => {
+                    position.get_or_insert(kept.len());
+                    merged.extend(entries);
+                }
+                OutputMessage::Event { entry } if split_key(&entry.event).is_some() => {
+                    position.get_or_insert(kept.len());
+                    merged.push(entry);
+                }
+                message => kept.push_back(message),
+            }
+        }
+        kept.insert(position.unwrap_or(0), OutputMessage::BulkSync { entries: merged });
+        self.queue = kept;
+    }
+
+    /// Release the messages allowed at `now`
+    pub fn drain(&mut self, config: &OutputConfig, now: Instant) -> Vec<OutputMessage> {
+        if config.max_messages_per_second == 0 {
+            self.last_refill = Some(now);
+            return self.queue.drain(..).collect();
+        }
+
+        let rate = config.max_messages_per_second as f64;
+        self.tokens = match self.last_refill {
+            Some(last) => (self.tokens + now.duration_since(last).as_secs_f64() * rate).min(rate),
+            None => rate,
+        };
+        self.last_refill = Some(now);
+
+        let count = (self.tokens.floor() as usize).min(self.queue.len());
+        self.tokens -= count as f64;
+        self.queue.drain(..count).collect()
+    }
+
+    /// Messages still waiting for tokens
+    pub fn pending(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_log::RunEvent;
+    use std::time::Duration;
+
+    fn boss(id: &str) -> RunLogEntry {
+        RunLogEntry {
+            elapsed_ms: 0,
+            igt_ms: None,
+            host_ms: None,
+            menu_time_ms: None,
+            event: RunEvent::BossDefeated {
+                boss_id: id.to_string(),
+                boss_name: id.to_string(),
+                flag_id: 0,
+                suspicion: None,
+                display_name: None,
+                metadata: Default::default(),
+            },
+        }
+    }
+
+    fn attached() -> RunLogEntry {
+        RunLogEntry {
+            elapsed_ms: 0,
+            igt_ms: None,
+            host_ms: None,
+            menu_time_ms: None,
+            event: RunEvent::ProcessAttached { game: "Dark Souls 3".to_string() },
+        }
+    }
+
+    #[test]
+    fn test_few_splits_pass_through() {
+        let config = OutputConfig::default();
+        let mut limiter = OutputLimiter::new();
+        limiter.push(vec![boss("a"), boss("b")], &config);
+
+        let messages = limiter.drain(&config, Instant::now());
+        assert_eq!(messages.len(), 2);
+        assert!(messages.iter().all(|m| matches!(m, OutputMessage::Event { .. })));
+    }
+
+    #[test]
+    fn test_flood_is_coalesced_into_bulk_sync() {
+        let config = OutputConfig::default();
+        let mut limiter = OutputLimiter::new();
+        limiter.push(vec![attached(), boss("a"), boss("b"), boss("c"), boss("d")], &config);
+
+        let messages = limiter.drain(&config, Instant::now());
+        assert_eq!(messages.len(), 2);
+        assert!(matches!(&messages[0], OutputMessage::Event { entry } if entry.event == attached().event));
+        assert!(matches!(&messages[1], OutputMessage::BulkSync { entries } if entries.len() == 4));
+    }
+
+    #[test]
+    fn test_rate_limit_spreads_messages() {
+        let config = OutputConfig {
+            max_messages_per_second: 2,
+            bulk_sync_threshold: 0,
+        };
+        let mut limiter = OutputLimiter::new();
+        limiter.push((0..5).map(|i| boss(&i.to_string())).collect(), &config);
+
+        let start = Instant::now();
+        assert_eq!(limiter.drain(&config, start).len(), 2);
+        assert_eq!(limiter.drain(&config, start).len(), 0);
+        assert_eq!(limiter.drain(&config, start + Duration::from_millis(500)).len(), 1);
+        assert_eq!(limiter.drain(&config, start + Duration::from_secs(10)).len(), 2);
+        assert_eq!(limiter.pending(), 0);
+    }
+
+    #[test]
+    fn test_backlog_is_coalesced() {
+        let config = OutputConfig {
+            max_messages_per_second: 1,
+            bulk_sync_threshold: 3,
+        };
+        let mut limiter = OutputLimiter::new();
+        let start = Instant::now();
+
+        limiter.push(vec![boss("a"), boss("b")], &config);
+        assert_eq!(limiter.drain(&config, start).len(), 1);
+        // "b" is still waiting; two more splits reach the threshold
+        limiter.push(vec![boss("c"), boss("d")], &config);
+
+        let messages = limiter.drain(&config, start + Duration::from_secs(1));
+        assert!(matches!(&messages[0], OutputMessage::BulkSync { entries } if entries.len() == 3));
+    }
+}
@@ -0,0 +1,196 @@
+//! Steam overlay detection
+//!
+//! Navigating the Steam overlay (Shift+Tab) sends keys and clicks that hosts
+//! can mistake for split or reset hotkeys. When the overlay renderer module is
+//! loaded into the game, its `SteamOverlayIsUsingMouse`/`Keyboard` exports
+//! report whether the overlay currently captures input. Each export is a tiny
+//! getter for a global flag; the flag addresses are decoded from the getter
+//! code once per attach and read every tick, so `overlay_open` costs two byte
+//! reads.
+//!
+//! Reads go through a `read(address, size)` callback so the same code serves
+//! Windows handles and Linux pids.
+
+/// Overlay renderer module names (64-bit first)
+pub const STEAM_OVERLAY_MODULES: &[&str] = &["GameOverlayRenderer64.dll", "GameOverlayRenderer.dll"];
+
+/// Exports that return true while the overlay captures input
+pub const OVERLAY_STATE_EXPORTS: &[&str] = &["SteamOverlayIsUsingMouse", "SteamOverlayIsUsingKeyboard"];
+
+/// Read `size` bytes at an address in the game process
+pub type ReadMemory<'a> = &'a dyn Fn(usize, usize) -> Option<Vec<u8>>;
+
+fn read_u16(read: ReadMemory, address: usize) -> Option<u16> {
+    let bytes = read(address, 2)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(read: ReadMemory, address: usize) -> Option<u32> {
+    let bytes = read(address, 4)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Address of a named export of a module loaded at `module_base`
+pub fn find_export(module_base: usize, name: &str, read: ReadMemory) -> Option<usize> {
+    if read(module_base, 2)? != b"MZ" {
+        return None;
+    }
+    let pe = module_base + read_u32(read, module_base + 0x3C)? as usize;
+    if read(pe, 4)? != b"PE\0\0" {
+        return None;
+    }
+
+    // Export directory entry: first data directory of the optional header
+    let optional = pe + 24;
+    let data_directories = match read_u16(read, optional)? {
+        0x20B => optional + 112, // PE32+
+        0x10B => optional + 96,  // PE32
+        _ => return None,
+    };
+    let export_rva = read_u32(read, data_directories)? as usize;
+    if export_rva == 0 {
+        return None;
+    }
+
+    let exports = module_base + export_rva;
+    let name_count = read_u32(read, exports + 0x18)? as usize;
+    let functions = module_base + read_u32(read, exports + 0x1C)? as usize;
+    let names = module_base + read_u32(read, exports + 0x20)? as usize;
+    let ordinals = module_base + read_u32(read, exports + 0x24)? as usize;
+
+    for index in 0..name_count.min(0x10000) {
+        let name_address = module_base + read_u32(read, names + index * 4)? as usize;
+        let Some(bytes) = read(name_address, name.len() + 1) else {
+            continue;
+        };
+        if &bytes[..name.len()] == name.as_bytes() && bytes[name.len()] == 0 {
+            let ordinal = read_u16(read, ordinals + index * 2)? as usize;
+            let function_rva = read_u32(read, functions + ordinal * 4)? as usize;
+            return Some(module_base + function_rva);
+        }
+    }
+    None
+}
+
+/// Decode a getter of the form `load byte [rip+disp32]; ...` and return the
+/// address of the global it reads
+fn decode_flag_getter(code: &[u8], address: usize) -> Option<usize> {
+    let (instruction_len, disp_at) = match code {
+        // movzx eax, byte ptr [rip+disp32]
+        [0x0F, 0xB6, 0x05, ..] => (7, 3),
+        // mov al, byte ptr [rip+disp32]
+        [0x8A, 0x05, ..] => (6, 2),
+        // cmp byte ptr [rip+disp32], imm8
+        [0x80, 0x3D, ..] => (7, 2),
+        _ => return None,
+    };
+    let disp = code.get(disp_at..disp_at + 4)?;
+    let disp = i32::from_le_bytes([disp[0], disp[1], disp[2], disp[3]]);
+    Some((address as i64 + instruction_len as i64 + disp as i64) as usize)
+}
+
+/// Located overlay state flags of an attached process
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteamOverlay {
+    flag_addresses: Vec<usize>,
+}
+
+impl SteamOverlay {
+    /// Locate the input-capture flags in the overlay module loaded at
+    /// `module_base`; None if no getter could be decoded
+    pub fn locate(module_base: usize, read: ReadMemory) -> Option<Self> {
+        let flag_addresses: Vec<usize> = OVERLAY_STATE_EXPORTS
+            .iter()
+            .filter_map(|export| {
+                let function = find_export(module_base, export, read)?;
+                decode_flag_getter(&read(function, 16)?, function)
+            })
+            .collect();
+
+        if flag_addresses.is_empty() {
+            log::warn!("Steam overlay module found but its state getters were not recognized");
+            return None;
+        }
+        Some(Self { flag_addresses })
+    }
+
+    /// Whether the overlay currently captures mouse or keyboard input
+    pub fn is_open(&self, read: ReadMemory) -> bool {
+        self.flag_addresses
+            .iter()
+            .any(|&address| read(address, 1).is_some_and(|b| b[0] != 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: usize = 0x7FF0_0000_0000;
+
+    /// Minimal PE32+ image exporting the two overlay getters
+    fn fake_module() -> Vec<u8> {
+        let mut image = vec![0u8; 0x1000];
+        let put_u16 = |image: &mut Vec<u8>, at: usize, v: u16| image[at..at + 2].copy_from_slice(&v.to_le_bytes());
+        let put_u32 = |image: &mut Vec<u8>, at: usize, v: u32| image[at..at + 4].copy_from_slice(&v.to_le_bytes());
+
+        image[0..2].copy_from_slice(b"MZ");
+        put_u32(&mut image, 0x3C, 0x80);
+        image[0x80..0x84].copy_from_slice(b"PE\0\0");
+        put_u16(&mut image, 0x80 + 24, 0x20B);
+        put_u32(&mut image, 0x80 + 24 + 112, 0x200); // export directory RVA
+
+        put_u32(&mut image, 0x200 + 0x18, 2); // NumberOfNames
+        put_u32(&mut image, 0x200 + 0x1C, 0x300); // AddressOfFunctions
+        put_u32(&mut image, 0x200 + 0x20, 0x320); // AddressOfNames
+        put_u32(&mut image, 0x200 + 0x24, 0x340); // AddressOfNameOrdinals
+
+        put_u32(&mut image, 0x320, 0x400);
+        put_u32(&mut image, 0x324, 0x440);
+        image[0x400..0x400 + 24].copy_from_slice(b"SteamOverlayIsUsingMouse");
+        image[0x440..0x440 + 27].copy_from_slice(b"SteamOverlayIsUsingKeyboard");
+        put_u16(&mut image, 0x340, 0);
+        put_u16(&mut image, 0x342, 1);
+        put_u32(&mut image, 0x300, 0x500);
+        put_u32(&mut image, 0x304, 0x520);
+
+        // movzx eax, byte [rip+0x100] ; ret  -> flag at 0x500 + 7 + 0x100
+        image[0x500..0x508].copy_from_slice(&[0x0F, 0xB6, 0x05, 0x00, 0x01, 0x00, 0x00, 0xC3]);
+        // mov al, byte [rip+0xE1] ; ret      -> flag at 0x520 + 6 + 0xE1 (0x607)
+        image[0x520..0x527].copy_from_slice(&[0x8A, 0x05, 0xE1, 0x00, 0x00, 0x00, 0xC3]);
+        image
+    }
+
+    fn reader(image: &[u8]) -> impl Fn(usize, usize) -> Option<Vec<u8>> + '_ {
+        move |address, size| {
+            let offset = address.checked_sub(BASE)?;
+            image.get(offset..offset + size).map(|b| b.to_vec())
+        }
+    }
+
+    #[test]
+    fn test_find_export() {
+        let image = fake_module();
+        let read = reader(&image);
+        assert_eq!(find_export(BASE, "SteamOverlayIsUsingKeyboard", &read), Some(BASE + 0x520));
+        assert_eq!(find_export(BASE, "SteamOverlayIsUsing", &read), None);
+        assert_eq!(find_export(BASE + 0x10, "SteamOverlayIsUsingMouse", &read), None);
+    }
+
+    #[test]
+    fn test_overlay_state() {
+        let mut image = fake_module();
+        let overlay = SteamOverlay::locate(BASE, &reader(&image)).unwrap();
+        assert_eq!(overlay.flag_addresses, vec![BASE + 0x607, BASE + 0x607]);
+        assert!(!overlay.is_open(&reader(&image)));
+
+        image[0x607] = 1;
+        assert!(overlay.is_open(&reader(&image)));
+    }
+
+    #[test]
+    fn test_unrecognized_getter() {
+        assert_eq!(decode_flag_getter(&[0xB0, 0x01, 0xC3], 0), None);
+        assert_eq!(decode_flag_getter(&[0x80, 0x3D, 0x10, 0x00, 0x00, 0x00, 0x00], 0x100), Some(0x117));
+    }
+}
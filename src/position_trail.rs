@@ -0,0 +1,266 @@
+//! Position trail
+//!
+//! Samples the player position at a configurable rate during a session so
+//! route visualization and verification tools (e.g., "did the runner enter a
+//! forbidden area") can replay the route. Exports merge the trail with the run
+//! log so splits can be placed on the route.
+
+use crate::run_log::{RunEvent, RunLogEntry};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Trail length cap; the oldest points are dropped beyond this
+pub const MAX_TRAIL_POINTS: usize = 200_000;
+
+/// Player position in game world coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct WorldPosition {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    /// Packed map id, for games where coordinates are local to a map (Elden Ring)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map_id: Option<u32>,
+}
+
+impl WorldPosition {
+    /// All-zero coordinates are what the games report while no character is loaded
+    pub fn is_origin(&self) -> bool {
+        self.x == 0.0 && self.y == 0.0 && self.z == 0.0
+    }
+}
+
+/// A sampled position
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct TrailPoint {
+    /// Milliseconds since the run log was started
+    pub elapsed_ms: u64,
+    #[serde(default)]
+    pub igt_ms: Option<i64>,
+    #[serde(flatten)]
+    pub position: WorldPosition,
+}
+
+/// Trail together with the run events that happened along it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct TrailExport {
+    pub points: Vec<TrailPoint>,
+    pub events: Vec<RunLogEntry>,
+}
+
+/// Recorded player positions
+#[derive(Debug, Default)]
+pub struct PositionTrail {
+    points: Vec<TrailPoint>,
+    last_sample_ms: Option<u64>,
+}
+
+impl PositionTrail {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a new sample is due at `elapsed_ms` for the given interval (0 disables)
+    pub fn is_due(&self, elapsed_ms: u64, interval_ms: u64) -> bool {
+        if interval_ms == 0 {
+            return false;
+        }
+        self.last_sample_ms
+            .is_none_or(|last| elapsed_ms.saturating_sub(last) >= interval_ms)
+    }
+
+    /// Append a sample, skipping positions of unloaded characters
+    pub fn push(&mut self, elapsed_ms: u64, igt_ms: Option<i64>, position: WorldPosition) {
+        self.last_sample_ms = Some(elapsed_ms);
+        if position.is_origin() {
+            return;
+        }
+
+        if self.points.len() >= MAX_TRAIL_POINTS {
+            self.points.remove(0);
+        }
+        self.points.push(TrailPoint {
+            elapsed_ms,
+            igt_ms,
+            position,
+        });
+    }
+
+    pub fn points(&self) -> &[TrailPoint] {
+        &self.points
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Combine the trail with run log entries
+    pub fn export(&self, events: &[RunLogEntry]) -> TrailExport {
+        TrailExport {
+            points: self.points.clone(),
+            events: events.to_vec(),
+        }
+    }
+
+    /// Export as JSON (`{"points": [...], "events": [...]}`)
+    pub fn to_json(&self, events: &[RunLogEntry]) -> String {
+        serde_json::to_string(&self.export(events))
+            .unwrap_or_else(|_| r#"{"points":[],"events":[]}"#.to_string())
+    }
+
+    /// Export as GPX-like XML
+    ///
+    /// Game coordinates are not geographic, so points carry `x`/`y`/`z`
+    /// attributes instead of `lat`/`lon`. Split events become waypoints at the
+    /// last position sampled before them.
+    pub fn to_gpx(&self, name: &str, events: &[RunLogEntry]) -> String {
+        let mut gpx = String::new();
+        gpx.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        gpx.push_str("<gpx version=\"1.1\" creator=\"nyacore-autosplitter\">\n");
+
+        for entry in events {
+            let Some(label) = split_label(&entry.event) else {
+                continue;
+            };
+            let Some(point) = self.position_at(entry.elapsed_ms) else {
+                continue;
+            };
+            let _ = writeln!(
+                gpx,
+                "  <wpt {}><time_ms>{}</time_ms><name>{}</name></wpt>",
+                coordinates(&point.position),
+                entry.elapsed_ms,
+                escape_xml(&label)
+            );
+        }
+
+        gpx.push_str("  <trk>\n");
+        let _ = writeln!(gpx, "    <name>{}</name>", escape_xml(name));
+        gpx.push_str("    <trkseg>\n");
+        for point in &self.points {
+            let _ = write!(
+                gpx,
+                "      <trkpt {}><time_ms>{}</time_ms>",
+                coordinates(&point.position),
+                point.elapsed_ms
+            );
+            if let Some(igt) = point.igt_ms {
+                let _ = write!(gpx, "<igt_ms>{}</igt_ms>", igt);
+            }
+            gpx.push_str("</trkpt>\n");
+        }
+        gpx.push_str("    </trkseg>\n  </trk>\n</gpx>\n");
+        gpx
+    }
+
+    /// Last point sampled at or before `elapsed_ms`
+    fn position_at(&self, elapsed_ms: u64) -> Option<&TrailPoint> {
+        let index = self.points.partition_point(|p| p.elapsed_ms <= elapsed_ms);
+        index.checked_sub(1).map(|i| &self.points[i])
+    }
+}
+
+/// Waypoint name for events worth marking on the route
+fn split_label(event: &RunEvent) -> Option<String> {
+    match event {
+        RunEvent::BossDefeated { boss_name, display_name, .. } => Some(display_name.as_ref().unwrap_or(boss_name).clone()),
+        RunEvent::TriggerMatched { name, display_name, .. } => Some(display_name.as_ref().unwrap_or(name).clone()),
+        RunEvent::SegmentTimed { name, .. } => Some(name.clone()),
+        _ => None,
+    }
+}
+
+fn coordinates(position: &WorldPosition) -> String {
+    let mut attrs = format!("x=\"{}\" y=\"{}\" z=\"{}\"", position.x, position.y, position.z);
+    if let Some(map_id) = position.map_id {
+        let _ = write!(attrs, " map=\"{:08X}\"", map_id);
+    }
+    attrs
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(x: f32) -> WorldPosition {
+        WorldPosition {
+            x,
+            y: 1.0,
+            z: 2.0,
+            map_id: None,
+        }
+    }
+
+    fn boss_entry(elapsed_ms: u64) -> RunLogEntry {
+        RunLogEntry {
+            elapsed_ms,
+            igt_ms: None,
+            host_ms: None,
+            menu_time_ms: None,
+            event: RunEvent::BossDefeated {
+                boss_id: "iudex".to_string(),
+                boss_name: "Iudex <Gundyr>".to_string(),
+                flag_id: 14000800,
+                suspicion: None,
+                display_name: None,
+                metadata: Default::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_is_due() {
+        let mut trail = PositionTrail::new();
+        assert!(trail.is_due(0, 500));
+        assert!(!trail.is_due(0, 0));
+
+        trail.push(1000, None, pos(1.0));
+        assert!(!trail.is_due(1200, 500));
+        assert!(trail.is_due(1500, 500));
+    }
+
+    #[test]
+    fn test_origin_is_skipped() {
+        let mut trail = PositionTrail::new();
+        trail.push(0, None, WorldPosition { x: 0.0, y: 0.0, z: 0.0, map_id: None });
+        trail.push(500, Some(10), pos(3.0));
+
+        assert_eq!(trail.points().len(), 1);
+        assert_eq!(trail.points()[0].igt_ms, Some(10));
+    }
+
+    #[test]
+    fn test_json_export() {
+        let mut trail = PositionTrail::new();
+        trail.push(0, None, pos(1.0));
+
+        let json = trail.to_json(&[boss_entry(100)]);
+        assert!(json.contains("\"x\":1.0"));
+        assert!(json.contains("\"boss_defeated\""));
+
+        let parsed: TrailExport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.points.len(), 1);
+        assert_eq!(parsed.events.len(), 1);
+    }
+
+    #[test]
+    fn test_gpx_waypoint_uses_last_position() {
+        let mut trail = PositionTrail::new();
+        trail.push(0, None, pos(1.0));
+        trail.push(1000, None, pos(5.0));
+
+        let gpx = trail.to_gpx("Dark Souls 3", &[boss_entry(1500)]);
+        assert!(gpx.contains("<wpt x=\"5\" y=\"1\" z=\"2\"><time_ms>1500</time_ms><name>Iudex &lt;Gundyr&gt;</name></wpt>"));
+        assert_eq!(gpx.matches("<trkpt").count(), 2);
+    }
+}
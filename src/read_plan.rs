@@ -0,0 +1,240 @@
+//! Boss flag read plans
+//!
+//! Reading boss flags one at a time walks the full pointer chain (or, for
+//! Elden Ring and AC6, the flag category tree) for every flag on every tick.
+//! Flags that share a block (the same DS3 area/category, the same ER
+//! category) share all of that traversal. A [`ReadPlan`] is compiled once per attach:
+//! flags are sorted and grouped by block and by 32-bit word inside the block,
+//! so each tick resolves every block address once and reads every word once.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Where a flag lives: a block resolved through the game's flag structures,
+/// plus a word offset and bit mask inside that block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlagLocation {
+    pub block: u32,
+    pub offset: i64,
+    pub mask: u32,
+}
+
+/// Flag storage of an engine, split into the shared and per-flag parts
+pub trait FlagBlockReader {
+    /// Location of a flag, or None if the engine does not store it as a bit
+    fn flag_location(&self, flag_id: u32) -> Option<FlagLocation>;
+
+    /// Resolve the base address of a block (the shared pointer traversal)
+    fn block_address(&self, block: u32) -> Option<i64>;
+
+    /// Read a 32-bit little-endian flag word
+    fn read_flag_word(&self, address: i64) -> Option<u32>;
+
+    /// Read a single flag, resolving its block on the spot
+    fn read_flag(&self, flag_id: u32) -> bool {
+        let Some(location) = self.flag_location(flag_id) else {
+            return false;
+        };
+        self.block_address(location.block)
+            .filter(|&address| address != 0)
+            .and_then(|base| self.read_flag_word(base + location.offset))
+            .is_some_and(|value| value & location.mask != 0)
+    }
+}
+
+/// Expected memory traffic of a compiled plan
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ReadPlanStats {
+    /// Watched flags
+    pub flags: usize,
+    /// Distinct blocks (one address resolution each per tick)
+    pub blocks: usize,
+    /// Distinct flag words (one read each per tick)
+    pub word_reads: usize,
+    /// Block resolutions plus word reads per tick
+    pub expected_reads_per_tick: usize,
+}
+
+#[derive(Debug, Clone)]
+struct PlannedWord {
+    offset: i64,
+    flags: Vec<(u32, u32)>,
+}
+
+#[derive(Debug, Clone)]
+struct PlannedBlock {
+    block: u32,
+    words: Vec<PlannedWord>,
+}
+
+/// Watch list compiled into grouped block/word reads
+#[derive(Debug, Clone)]
+pub struct ReadPlan {
+    blocks: Vec<PlannedBlock>,
+    flags: usize,
+}
+
+impl ReadPlan {
+    /// Compile a plan; returns None if any flag cannot be located (the caller
+    /// then falls back to per-flag reads, and may retry later, e.g. once Elden
+    /// Ring has loaded its flag divisor)
+    pub fn compile(reader: &dyn FlagBlockReader, flag_ids: &[u32]) -> Option<Self> {
+        let mut grouped: BTreeMap<u32, BTreeMap<i64, Vec<(u32, u32)>>> = BTreeMap::new();
+        let mut flag_ids = flag_ids.to_vec();
+        flag_ids.sort_unstable();
+        flag_ids.dedup();
+
+        for &flag_id in &flag_ids {
+            let location = reader.flag_location(flag_id)?;
+            grouped
+                .entry(location.block)
+                .or_default()
+                .entry(location.offset)
+                .or_default()
+                .push((flag_id, location.mask));
+        }
+
+        let blocks = grouped
+            .into_iter()
+            .map(|(block, words)| PlannedBlock {
+                block,
+                words: words
+                    .into_iter()
+                    .map(|(offset, flags)| PlannedWord { offset, flags })
+                    .collect(),
+            })
+            .collect();
+
+        Some(Self {
+            blocks,
+            flags: flag_ids.len(),
+        })
+    }
+
+    /// Read every watched flag; flags in unresolvable blocks read as unset
+    pub fn execute(&self, reader: &dyn FlagBlockReader) -> HashMap<u32, bool> {
+        let mut results = HashMap::with_capacity(self.flags);
+
+        for block in &self.blocks {
+            let base = reader.block_address(block.block).filter(|&address| address != 0);
+            for word in &block.words {
+                let value = base
+                    .and_then(|base| reader.read_flag_word(base + word.offset))
+                    .unwrap_or(0);
+                for &(flag_id, mask) in &word.flags {
+                    results.insert(flag_id, value & mask != 0);
+                }
+            }
+        }
+
+        results
+    }
+
+    pub fn stats(&self) -> ReadPlanStats {
+        let word_reads = self.blocks.iter().map(|b| b.words.len()).sum();
+        ReadPlanStats {
+            flags: self.flags,
+            blocks: self.blocks.len(),
+            word_reads,
+            expected_reads_per_tick: self.blocks.len() + word_reads,
+        }
+    }
+}
+
+/// Location of a flag stored as bit `bit` of byte `byte` in its block,
+/// expressed as an aligned little-endian word
+pub fn byte_bit_location(block: u32, byte: u32, bit: u32) -> FlagLocation {
+    FlagLocation {
+        block,
+        offset: (byte & !3) as i64,
+        mask: 1u32 << ((byte & 3) * 8 + bit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    /// DS3-style layout: block = flag / 1000, big-endian bit order in words
+    struct FakeReader {
+        words: HashMap<i64, u32>,
+        block_resolutions: Cell<usize>,
+        word_reads: Cell<usize>,
+    }
+
+    impl FlagBlockReader for FakeReader {
+        fn flag_location(&self, flag_id: u32) -> Option<FlagLocation> {
+            let index = flag_id % 1000;
+            Some(FlagLocation {
+                block: flag_id / 1000,
+                offset: ((index >> 5) * 4) as i64,
+                mask: 1u32 << (31 - (index & 31)),
+            })
+        }
+
+        fn block_address(&self, block: u32) -> Option<i64> {
+            self.block_resolutions.set(self.block_resolutions.get() + 1);
+            (block != 99_000).then_some(block as i64 * 0x1000)
+        }
+
+        fn read_flag_word(&self, address: i64) -> Option<u32> {
+            self.word_reads.set(self.word_reads.get() + 1);
+            self.words.get(&address).copied()
+        }
+    }
+
+    fn reader(words: &[(i64, u32)]) -> FakeReader {
+        FakeReader {
+            words: words.iter().copied().collect(),
+            block_resolutions: Cell::new(0),
+            word_reads: Cell::new(0),
+        }
+    }
+
+    #[test]
+    fn test_groups_flags_by_block_and_word() {
+        let reader = reader(&[]);
+        let plan = ReadPlan::compile(&reader, &[13000800, 13000801, 13000830, 13000900, 14000800, 13000800]).unwrap();
+
+        assert_eq!(
+            plan.stats(),
+            ReadPlanStats {
+                flags: 5,
+                blocks: 2,
+                word_reads: 3,
+                expected_reads_per_tick: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_execute_reads_each_block_and_word_once() {
+        // Flag 13000800: word 25 (offset 100), bit 31 - 0
+        let reader = reader(&[(13000 * 0x1000 + 100, 0x8000_0000)]);
+        let plan = ReadPlan::compile(&reader, &[13000800, 13000801, 13000900]).unwrap();
+
+        let results = plan.execute(&reader);
+        assert_eq!(results.get(&13000800), Some(&true));
+        assert_eq!(results.get(&13000801), Some(&false));
+        assert_eq!(results.get(&13000900), Some(&false));
+        assert_eq!(reader.block_resolutions.get(), 1);
+        assert_eq!(reader.word_reads.get(), 2);
+    }
+
+    #[test]
+    fn test_unresolved_block_reads_unset() {
+        let reader = reader(&[]);
+        let plan = ReadPlan::compile(&reader, &[99000001]).unwrap();
+
+        assert_eq!(plan.execute(&reader).get(&99000001), Some(&false));
+        assert_eq!(reader.word_reads.get(), 0);
+    }
+
+    #[test]
+    fn test_byte_bit_location() {
+        assert_eq!(byte_bit_location(7, 0, 3), FlagLocation { block: 7, offset: 0, mask: 1 << 3 });
+        assert_eq!(byte_bit_location(7, 6, 1), FlagLocation { block: 7, offset: 4, mask: 1 << 17 });
+    }
+}
@@ -0,0 +1,172 @@
+//! Memory read instrumentation
+//!
+//! Every process memory read (`ReadProcessMemory` on Windows,
+//! `process_vm_readv` on Linux) goes through `memory::reader::read_bytes`,
+//! which bumps the counters below. The counters are per thread, like the read
+//! sandbox, so a worker thread only sees the reads of its own run loop and not
+//! those of another session or of host calls. The worker samples them once per
+//! tick with a [`ReadMeter`] to report the reads and bytes of the last tick
+//! plus rolling averages, and to warn when large watch lists (boss flags,
+//! triggers, attributes) push the reads per tick past a budget and start to
+//! cost game performance.
+
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::VecDeque;
+
+/// Ticks covered by the rolling averages
+pub const ROLLING_WINDOW_TICKS: usize = 50;
+
+thread_local! {
+    static READ_CALLS: Cell<u64> = const { Cell::new(0) };
+    static READ_BYTES: Cell<u64> = const { Cell::new(0) };
+    static READ_FAILURES: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Count one memory read of `bytes` bytes (0 if it failed) on the calling thread
+pub fn record_read(bytes: usize) {
+    READ_CALLS.with(|calls| calls.set(calls.get() + 1));
+    READ_BYTES.with(|total| total.set(total.get() + bytes as u64));
+}
+
+/// Count a read (already passed to `record_read`) as failed or incomplete
+pub fn record_failed_read() {
+    READ_FAILURES.with(|failures| failures.set(failures.get() + 1));
+}
+
+/// Reads and failed reads of the calling thread
+pub fn failure_totals() -> (u64, u64) {
+    (READ_CALLS.with(Cell::get), READ_FAILURES.with(Cell::get))
+}
+
+/// Reads and bytes transferred by the calling thread
+pub fn totals() -> (u64, u64) {
+    (READ_CALLS.with(Cell::get), READ_BYTES.with(Cell::get))
+}
+
+/// Memory traffic of the attached game
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ReadStats {
+    /// Reads during the last tick
+    pub reads_last_tick: u64,
+    /// Bytes transferred during the last tick
+    pub bytes_last_tick: u64,
+    /// Average reads per tick over the rolling window
+    pub avg_reads_per_tick: f64,
+    /// Average bytes per tick over the rolling window
+    pub avg_bytes_per_tick: f64,
+    /// The average exceeds `read_budget_per_tick`
+    pub over_budget: bool,
+}
+
+/// Per-attach sampler of the read counters
+#[derive(Debug, Default)]
+pub struct ReadMeter {
+    last_totals: Option<(u64, u64)>,
+    window: VecDeque<(u64, u64)>,
+    warned: bool,
+}
+
+impl ReadMeter {
+    /// Sample the counters at the end of a tick; the first sample only sets
+    /// the baseline. `budget` is the allowed average reads per tick (0 = none).
+    pub fn tick(&mut self, totals: (u64, u64), budget: u64) -> Option<ReadStats> {
+        let last = self.last_totals.replace(totals)?;
+        let tick = (totals.0.saturating_sub(last.0), totals.1.saturating_sub(last.1));
+
+        if self.window.len() == ROLLING_WINDOW_TICKS {
+            self.window.pop_front();
+        }
+        self.window.push_back(tick);
+
+        let ticks = self.window.len() as f64;
+        let avg_reads_per_tick = self.window.iter().map(|(reads, _)| *reads).sum::<u64>() as f64 / ticks;
+        let avg_bytes_per_tick = self.window.iter().map(|(_, bytes)| *bytes).sum::<u64>() as f64 / ticks;
+
+        // Judge the budget on a full window so attach-time bursts don't count
+        let over_budget = budget > 0
+            && self.window.len() == ROLLING_WINDOW_TICKS
+            && avg_reads_per_tick > budget as f64;
+        if over_budget && !self.warned {
+            self.warned = true;
+            log::warn!(
+                "Memory reads average {:.0} per tick ({:.0} bytes), over the budget of {}; \
+                 large boss, trigger or attribute lists may affect game performance",
+                avg_reads_per_tick,
+                avg_bytes_per_tick,
+                budget
+            );
+        }
+
+        Some(ReadStats {
+            reads_last_tick: tick.0,
+            bytes_last_tick: tick.1,
+            avg_reads_per_tick,
+            avg_bytes_per_tick,
+            over_budget,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_per_tick_deltas_and_averages() {
+        let mut meter = ReadMeter::default();
+        assert_eq!(meter.tick((100, 4000), 0), None);
+
+        let stats = meter.tick((110, 4040), 0).unwrap();
+        assert_eq!((stats.reads_last_tick, stats.bytes_last_tick), (10, 40));
+
+        let stats = meter.tick((140, 4200), 0).unwrap();
+        assert_eq!(stats.reads_last_tick, 30);
+        assert_eq!(stats.avg_reads_per_tick, 20.0);
+        assert_eq!(stats.avg_bytes_per_tick, 100.0);
+        assert!(!stats.over_budget);
+    }
+
+    #[test]
+    fn test_budget_needs_full_window() {
+        let mut meter = ReadMeter::default();
+        let mut totals = (0, 0);
+        meter.tick(totals, 100);
+
+        for tick in 1..=ROLLING_WINDOW_TICKS {
+            totals = (totals.0 + 150, totals.1 + 600);
+            let stats = meter.tick(totals, 100).unwrap();
+            assert_eq!(stats.over_budget, tick == ROLLING_WINDOW_TICKS);
+        }
+
+        // No budget configured
+        assert!(!meter.tick((totals.0 + 150, totals.1), 0).unwrap().over_budget);
+    }
+
+    #[test]
+    fn test_record_read_counts() {
+        let (reads, bytes) = totals();
+        record_read(8);
+        record_read(0);
+        assert_eq!(totals(), (reads + 2, bytes + 8));
+
+        // Reads of another thread are not counted here
+        std::thread::spawn(|| record_read(64)).join().unwrap();
+        assert_eq!(totals(), (reads + 2, bytes + 8));
+    }
+
+    #[test]
+    fn test_failures_are_per_thread() {
+        let (reads, failed) = failure_totals();
+        record_read(0);
+        record_failed_read();
+        std::thread::spawn(|| {
+            record_read(0);
+            record_failed_read();
+        })
+        .join()
+        .unwrap();
+        assert_eq!(failure_totals(), (reads + 1, failed + 1));
+    }
+}
@@ -0,0 +1,485 @@
+//! Run log
+//!
+//! Chronological record of a session: process attach/detach, boss kills,
+//! matched triggers and stat snapshots. Hosts poll new entries as events and
+//! can export the full log as JSON as evidence for run verification.
+//!
+//! Entries are stamped with the library's monotonic clock. Hosts that render
+//! their own timer can calibrate the log against it once; entries then also
+//! carry the host timer reading, so both sides agree on when a split happened.
+//!
+//! The log keeps at most `max_entries` entries (`run_log_max_entries` in the
+//! config). When it is full, the oldest tenth is dropped at once, so a session
+//! left running for days stays bounded and trimming stays cheap.
+
+use crate::config::{SplitMetadata, TriggerKind};
+use crate::death_tracking::RespawnPoint;
+use crate::handle_health::ReattachReason;
+use crate::highlights::Highlight;
+use crate::kill_validation::Suspicion;
+use crate::position_trail::WorldPosition;
+use crate::speech;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Entries kept by default (see `AutosplitterConfig::run_log_max_entries`)
+pub const DEFAULT_MAX_ENTRIES: usize = 50_000;
+
+/// Something that happened during a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum RunEvent {
+    ProcessAttached {
+        game: String,
+    },
+    ProcessDetached,
+    /// The game handle stopped working while the process looked alive; the
+    /// loop drops it and attaches again (see `handle_health`)
+    Reattaching {
+        reason: ReattachReason,
+    },
+    Reset,
+    BossDefeated {
+        boss_id: String,
+        boss_name: String,
+        flag_id: u32,
+        /// Set when kill validation flagged the split
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        suspicion: Option<Suspicion>,
+        /// Name rendered from `split_name_template`, when one is set
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        display_name: Option<String>,
+        /// Host metadata of the boss (see `BossFlag::metadata`)
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        metadata: SplitMetadata,
+    },
+    TriggerMatched {
+        index: usize,
+        trigger_id: String,
+        name: String,
+        #[serde(default)]
+        kind: TriggerKind,
+        /// Name rendered from `split_name_template`, when one is set
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        display_name: Option<String>,
+        /// Host metadata of the trigger (see `CustomTrigger::metadata`)
+        #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+        metadata: SplitMetadata,
+    },
+    /// A timing rule's segment ended (see `timing_rules`); `elapsed_ms` of
+    /// the entry is the end of the segment
+    SegmentTimed {
+        rule_id: String,
+        name: String,
+        start_elapsed_ms: u64,
+        duration_ms: u64,
+        #[serde(default)]
+        igt_duration_ms: Option<i64>,
+    },
+    /// Periodic snapshot of character stats (e.g., rune level and attributes)
+    StatSnapshot {
+        stats: BTreeMap<String, i64>,
+    },
+    /// Character level attribute went up (e.g., "rune_level", "soul_level")
+    LevelIncreased {
+        attribute: String,
+        from: i64,
+        to: i64,
+    },
+    /// Death counter went up; `position` is where the player died, if known
+    PlayerDied {
+        death_count: i64,
+        #[serde(default)]
+        position: Option<WorldPosition>,
+    },
+    /// Player is back in the world after a death
+    PlayerRespawned {
+        position: WorldPosition,
+        respawn: RespawnPoint,
+    },
+    /// Player warped; `destination` is the bonfire or grace entity id (see `warp`)
+    WarpInitiated {
+        destination: u32,
+    },
+    /// Memorable moments of the attempt that just ended (see `highlights`)
+    RunHighlights {
+        highlights: Vec<Highlight>,
+    },
+    /// A tracked achievement's flags are all set (see `achievements`)
+    AchievementUnlocked {
+        achievement_id: String,
+        name: String,
+    },
+    /// A host-registered watch changed value (see `watches`)
+    ValueChanged {
+        watch_id: String,
+        from: i64,
+        to: i64,
+    },
+    /// Practice: a watched boss's HP dropped to or below `percent` of its
+    /// max HP; `fight_ms` is the time since the fight started (see
+    /// `boss_phases`)
+    BossHpThreshold {
+        boss_id: String,
+        percent: u8,
+        hp: i64,
+        max_hp: i64,
+        fight_ms: u64,
+    },
+    /// Practice: a watched boss's phase flag was set (see `boss_phases`)
+    BossPhaseChanged {
+        boss_id: String,
+        phase: String,
+        flag_id: u32,
+        fight_ms: u64,
+    },
+    /// AC6: a mission attempt started (see `ac6_missions`)
+    MissionStarted,
+    /// AC6: the mission was restarted or reloaded from a checkpoint;
+    /// `attempt_igt_ms` is the IGT since the attempt started
+    MissionRestarted {
+        restarts: u32,
+        attempt_igt_ms: i64,
+    },
+    /// AC6: the mission was cleared; `clear_time_ms` and `rank` are the
+    /// result reported by the game
+    MissionCompleted {
+        #[serde(default)]
+        rank: Option<String>,
+        clear_time_ms: i64,
+        attempt_igt_ms: i64,
+        restarts: u32,
+    },
+}
+
+/// A run event with its timestamps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct RunLogEntry {
+    /// Milliseconds since the log was started
+    pub elapsed_ms: u64,
+    /// In-game time when the event was recorded, if the game exposes it
+    #[serde(default)]
+    pub igt_ms: Option<i64>,
+    /// Host timer reading for the event, once the host clock is calibrated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host_ms: Option<i64>,
+    /// Time spent in menus or paused since the attempt started (see
+    /// `menu_time`), once a game has been attached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub menu_time_ms: Option<u64>,
+    pub event: RunEvent,
+}
+
+/// Chronological log of run events
+#[derive(Debug)]
+pub struct RunLog {
+    started: Instant,
+    /// Wall-clock time of `started`, in milliseconds since the Unix epoch
+    started_unix_ms: u64,
+    entries: Vec<RunLogEntry>,
+    /// Index of the first entry not yet returned by `poll_new`
+    poll_cursor: usize,
+    /// Host timer reading and the monotonic instant it was taken at
+    host_clock: Option<(Instant, i64)>,
+    /// Menu time of the current attempt
+    menu_time_ms: Option<u64>,
+    /// Entries kept before the oldest are dropped (0 = no limit)
+    max_entries: usize,
+}
+
+impl Default for RunLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunLog {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            started_unix_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            entries: Vec::new(),
+            poll_cursor: 0,
+            host_clock: None,
+            menu_time_ms: None,
+            max_entries: DEFAULT_MAX_ENTRIES,
+        }
+    }
+
+    /// Limit the number of entries kept (0 = no limit); trims the log if
+    /// it already holds more
+    pub fn set_max_entries(&mut self, max_entries: usize) {
+        self.max_entries = max_entries;
+        if max_entries > 0 && self.entries.len() > max_entries {
+            self.drop_oldest(self.entries.len() - max_entries);
+        }
+    }
+
+    /// Drop the `count` oldest entries, keeping unpolled ones unpolled
+    fn drop_oldest(&mut self, count: usize) {
+        self.entries.drain(..count);
+        self.poll_cursor = self.poll_cursor.saturating_sub(count);
+    }
+
+    /// Milliseconds since the log was started
+    pub fn elapsed_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+
+    /// Wall-clock time the log was started, in milliseconds since the Unix epoch
+    pub fn started_unix_ms(&self) -> u64 {
+        self.started_unix_ms
+    }
+
+    /// Pair the host timer with the monotonic clock; returns the offset
+    /// (host time minus `elapsed_ms`). Entries recorded before the first
+    /// calibration are stamped retroactively; later calls correct drift for
+    /// subsequent entries.
+    pub fn calibrate_host_clock(&mut self, host_now_ms: i64) -> i64 {
+        self.host_clock = Some((Instant::now(), host_now_ms));
+        let offset = self.host_offset_ms().unwrap_or(0);
+        for entry in self.entries.iter_mut().filter(|e| e.host_ms.is_none()) {
+            entry.host_ms = Some(entry.elapsed_ms as i64 + offset);
+        }
+        offset
+    }
+
+    /// Host time minus `elapsed_ms`, if the host clock is calibrated
+    pub fn host_offset_ms(&self) -> Option<i64> {
+        let (at, host_ms) = self.host_clock?;
+        let at_elapsed_ms = match at.checked_duration_since(self.started) {
+            Some(after) => after.as_millis() as i64,
+            None => -(self.started.duration_since(at).as_millis() as i64),
+        };
+        Some(host_ms - at_elapsed_ms)
+    }
+
+    /// Current time on the host timer, if the host clock is calibrated
+    pub fn host_now_ms(&self) -> Option<i64> {
+        Some(self.elapsed_ms() as i64 + self.host_offset_ms()?)
+    }
+
+    /// Add time spent in menus or paused to the current attempt
+    pub fn add_menu_time(&mut self, ms: u64) {
+        *self.menu_time_ms.get_or_insert(0) += ms;
+    }
+
+    /// Menu time of the current attempt, once any was accounted
+    pub fn menu_time_ms(&self) -> Option<u64> {
+        self.menu_time_ms
+    }
+
+    /// Append an event; a reset starts a new attempt's menu time. A full log
+    /// drops its oldest tenth first.
+    pub fn push(&mut self, event: RunEvent, igt_ms: Option<i64>) {
+        if event == RunEvent::Reset {
+            self.menu_time_ms = self.menu_time_ms.map(|_| 0);
+        }
+        if self.max_entries > 0 && self.entries.len() >= self.max_entries {
+            self.drop_oldest(self.entries.len() - self.max_entries + (self.max_entries / 10).max(1));
+        }
+        let elapsed_ms = self.elapsed_ms();
+        self.entries.push(RunLogEntry {
+            elapsed_ms,
+            igt_ms,
+            host_ms: self.host_offset_ms().map(|offset| elapsed_ms as i64 + offset),
+            menu_time_ms: self.menu_time_ms,
+            event,
+        });
+    }
+
+    /// All entries recorded so far
+    pub fn entries(&self) -> &[RunLogEntry] {
+        &self.entries
+    }
+
+    /// When the most recent split (boss, split trigger or timed segment) was logged
+    pub fn last_split_ms(&self) -> Option<u64> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| speech::split_key(&entry.event).is_some())
+            .map(|entry| entry.elapsed_ms)
+    }
+
+    /// Entries recorded since the previous call
+    pub fn poll_new(&mut self) -> Vec<RunLogEntry> {
+        let new_entries = self.entries[self.poll_cursor..].to_vec();
+        self.poll_cursor = self.entries.len();
+        new_entries
+    }
+
+    /// Drop all entries and restart the clock (the host clock calibration
+    /// and the entry limit are kept)
+    pub fn clear(&mut self) {
+        let host_clock = self.host_clock;
+        let max_entries = self.max_entries;
+        *self = Self::new();
+        self.host_clock = host_clock;
+        self.max_entries = max_entries;
+    }
+
+    /// Export the full log as JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&self.entries).unwrap_or_else(|_| "[]".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_entries() {
+        let mut log = RunLog::new();
+        log.push(RunEvent::ProcessAttached { game: "Elden Ring".to_string() }, None);
+        log.push(RunEvent::ProcessDetached, Some(1000));
+
+        assert_eq!(log.entries().len(), 2);
+        assert_eq!(log.entries()[1].igt_ms, Some(1000));
+        assert_eq!(log.entries()[1].event, RunEvent::ProcessDetached);
+    }
+
+    #[test]
+    fn test_poll_new_returns_each_entry_once() {
+        let mut log = RunLog::new();
+        log.push(RunEvent::Reset, None);
+
+        assert_eq!(log.poll_new().len(), 1);
+        assert!(log.poll_new().is_empty());
+
+        log.push(RunEvent::ProcessDetached, None);
+        let new_entries = log.poll_new();
+        assert_eq!(new_entries.len(), 1);
+        assert_eq!(new_entries[0].event, RunEvent::ProcessDetached);
+    }
+
+    #[test]
+    fn test_menu_time_per_attempt() {
+        let mut log = RunLog::new();
+        log.push(RunEvent::ProcessDetached, None);
+        assert_eq!(log.entries()[0].menu_time_ms, None);
+
+        log.add_menu_time(1500);
+        log.add_menu_time(500);
+        log.push(RunEvent::ProcessDetached, None);
+        assert_eq!(log.entries()[1].menu_time_ms, Some(2000));
+
+        log.push(RunEvent::Reset, None);
+        assert_eq!(log.entries()[2].menu_time_ms, Some(0));
+    }
+
+    #[test]
+    fn test_last_split_ms() {
+        let mut log = RunLog::new();
+        log.push(RunEvent::Reset, None);
+        assert_eq!(log.last_split_ms(), None);
+
+        log.push(
+            RunEvent::TriggerMatched {
+                index: 0,
+                trigger_id: "sl2".to_string(),
+                name: "Level up".to_string(),
+                kind: TriggerKind::RuleViolation,
+                display_name: None,
+                metadata: Default::default(),
+            },
+            None,
+        );
+        assert_eq!(log.last_split_ms(), None);
+
+        log.push(
+            RunEvent::BossDefeated {
+                boss_id: "vordt".to_string(),
+                boss_name: "Vordt of the Boreal Valley".to_string(),
+                flag_id: 13000800,
+                suspicion: None,
+                display_name: None,
+                metadata: Default::default(),
+            },
+            None,
+        );
+        log.push(RunEvent::ProcessDetached, None);
+        assert_eq!(log.last_split_ms(), Some(log.entries()[2].elapsed_ms));
+    }
+
+    #[test]
+    fn test_max_entries() {
+        let mut log = RunLog::new();
+        log.set_max_entries(20);
+        for igt_ms in 0..20 {
+            log.push(RunEvent::Reset, Some(igt_ms));
+        }
+        assert_eq!(log.poll_new().len(), 20);
+
+        // Full: the oldest tenth makes room for the new entry
+        log.push(RunEvent::ProcessDetached, Some(20));
+        assert_eq!(log.entries().len(), 19);
+        assert_eq!(log.entries()[0].igt_ms, Some(2));
+        let new_entries = log.poll_new();
+        assert_eq!(new_entries.len(), 1);
+        assert_eq!(new_entries[0].event, RunEvent::ProcessDetached);
+
+        log.set_max_entries(5);
+        assert_eq!(log.entries().len(), 5);
+        assert_eq!(log.entries()[4].igt_ms, Some(20));
+        assert!(log.poll_new().is_empty());
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut log = RunLog::new();
+        log.push(RunEvent::Reset, None);
+        log.clear();
+
+        assert!(log.entries().is_empty());
+        assert!(log.poll_new().is_empty());
+    }
+
+    #[test]
+    fn test_host_clock_calibration() {
+        let mut log = RunLog::new();
+        log.push(RunEvent::Reset, None);
+        assert_eq!(log.entries()[0].host_ms, None);
+
+        // Host timer reads far ahead of the log clock
+        let offset = log.calibrate_host_clock(5_000_000);
+        assert!((5_000_000 - 1000..=5_000_000).contains(&offset));
+        assert_eq!(log.entries()[0].host_ms, Some(log.entries()[0].elapsed_ms as i64 + offset));
+
+        log.push(RunEvent::ProcessDetached, None);
+        let entry = &log.entries()[1];
+        assert_eq!(entry.host_ms, Some(entry.elapsed_ms as i64 + offset));
+
+        // Calibration survives a restart of the log clock
+        log.clear();
+        let host_now = log.host_now_ms().unwrap();
+        assert!((5_000_000..5_000_000 + 1000).contains(&host_now));
+    }
+
+    #[test]
+    fn test_json_format() {
+        let mut log = RunLog::new();
+        log.push(
+            RunEvent::LevelIncreased {
+                attribute: "rune_level".to_string(),
+                from: 1,
+                to: 2,
+            },
+            Some(60000),
+        );
+
+        let json = log.to_json();
+        assert!(json.contains("\"type\":\"level_increased\""));
+        assert!(json.contains("\"igt_ms\":60000"));
+
+        let parsed: Vec<RunLogEntry> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 1);
+    }
+}
@@ -0,0 +1,231 @@
+//! Sekiro Reflections of Strength and gauntlet tracking
+//!
+//! Boss rematches from the Sculptor's Idol (Reflections of Strength) and the
+//! gauntlets built on them never touch the world boss flags, so gauntlet
+//! categories cannot split on the usual boss list. While a reflection is
+//! loaded the game sets a reflection marker flag plus a selection flag for
+//! the chosen gauntlet, and every reflection victory sets that boss's
+//! reflection flag. [`detect`] turns those into [`GauntletProgress`] for the
+//! state, and [`gauntlet_triggers`] builds "boss N of M" split triggers.
+//! Reflection flags follow the world boss flags of `schemas/sekiro.toml`
+//! offset by 400.
+
+use crate::config::{CustomTrigger, FlagSetMode, TriggerCondition, TriggerKind};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of trigger ids built by [`gauntlet_triggers`]
+pub const TRIGGER_ID_PREFIX: &str = "gauntlet.";
+
+/// Set while the player is inside any Reflection of Strength
+pub const REFLECTION_ACTIVE_FLAG: u32 = 9800;
+
+/// (boss id, name, reflection victory flag)
+const REFLECTION_BOSSES: &[(&str, &str, u32)] = &[
+    ("gyoubu", "Gyoubu Oniwa", 9701),
+    ("lady_butterfly", "Lady Butterfly", 9702),
+    ("genichiro", "Genichiro Ashina", 9703),
+    ("guardian_ape", "Guardian Ape", 9704),
+    ("folding_screen_monkeys", "Folding Screen Monkeys", 9705),
+    ("corrupted_monk_illusion", "Corrupted Monk", 9706),
+    ("headless_ape", "Guardian Ape (Headless)", 9707),
+    ("great_shinobi_owl", "Great Shinobi Owl", 9708),
+    ("true_corrupted_monk", "True Corrupted Monk", 9709),
+    ("divine_dragon", "Divine Dragon", 9710),
+    ("isshin_sword_saint", "Isshin, the Sword Saint", 9712),
+    ("demon_of_hatred", "Demon of Hatred", 9713),
+    ("emma", "Emma, the Gentle Blade", 9715),
+    ("isshin_ashina", "Isshin Ashina", 9716),
+    ("owl_father", "Owl (Father)", 9717),
+];
+
+/// (id, name, selection flag, boss ids in fight order)
+const GAUNTLETS: &[(&str, &str, u32, &[&str])] = &[
+    (
+        "gauntlet_of_strength",
+        "Gauntlet of Strength",
+        9810,
+        &[
+            "gyoubu",
+            "lady_butterfly",
+            "genichiro",
+            "folding_screen_monkeys",
+            "guardian_ape",
+            "corrupted_monk_illusion",
+            "great_shinobi_owl",
+            "true_corrupted_monk",
+            "divine_dragon",
+            "isshin_sword_saint",
+        ],
+    ),
+    (
+        "gauntlet_of_legends",
+        "Gauntlet of Legends",
+        9811,
+        &["genichiro", "owl_father", "emma", "isshin_ashina", "demon_of_hatred", "isshin_sword_saint"],
+    ),
+];
+
+/// A boss fought as a reflection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct ReflectionBoss {
+    pub id: String,
+    pub name: String,
+    /// Event flag set when the reflection is won
+    pub flag_id: u32,
+}
+
+/// A gauntlet a host can offer as a category
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Gauntlet {
+    pub id: String,
+    pub name: String,
+    /// Event flag set while this gauntlet is being fought
+    pub flag_id: u32,
+    /// Bosses in fight order
+    pub bosses: Vec<ReflectionBoss>,
+}
+
+/// Where the player is inside a reflection
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct GauntletProgress {
+    /// Gauntlet id, or None for a single Reflection of Strength
+    pub gauntlet_id: Option<String>,
+    pub name: String,
+    /// Bosses defeated so far in this gauntlet
+    pub defeated: usize,
+    pub total: usize,
+    /// Name of the boss being fought (None once all are defeated)
+    pub current_boss: Option<String>,
+}
+
+fn reflection_boss(id: &str) -> Option<ReflectionBoss> {
+    REFLECTION_BOSSES
+        .iter()
+        .find(|(boss_id, _, _)| *boss_id == id)
+        .map(|&(id, name, flag_id)| ReflectionBoss {
+            id: id.to_string(),
+            name: name.to_string(),
+            flag_id,
+        })
+}
+
+/// All gauntlets with their bosses
+pub fn gauntlets() -> Vec<Gauntlet> {
+    GAUNTLETS
+        .iter()
+        .map(|&(id, name, flag_id, bosses)| Gauntlet {
+            id: id.to_string(),
+            name: name.to_string(),
+            flag_id,
+            bosses: bosses.iter().filter_map(|boss| reflection_boss(boss)).collect(),
+        })
+        .collect()
+}
+
+/// Detect whether the player is inside a reflection and how far a gauntlet
+/// has progressed; None outside of reflections
+pub fn detect(read_flag: impl Fn(u32) -> bool) -> Option<GauntletProgress> {
+    if !read_flag(REFLECTION_ACTIVE_FLAG) {
+        return None;
+    }
+
+    let Some(gauntlet) = gauntlets().into_iter().find(|g| read_flag(g.flag_id)) else {
+        let defeated = REFLECTION_BOSSES.iter().any(|&(_, _, flag_id)| read_flag(flag_id));
+        return Some(GauntletProgress {
+            gauntlet_id: None,
+            name: "Reflection of Strength".to_string(),
+            defeated: defeated as usize,
+            total: 1,
+            current_boss: None,
+        });
+    };
+
+    // Bosses are fought in order, so progress is the run of won reflections
+    let defeated = gauntlet.bosses.iter().take_while(|boss| read_flag(boss.flag_id)).count();
+    Some(GauntletProgress {
+        gauntlet_id: Some(gauntlet.id),
+        name: gauntlet.name,
+        defeated,
+        total: gauntlet.bosses.len(),
+        current_boss: gauntlet.bosses.get(defeated).map(|boss| boss.name.clone()),
+    })
+}
+
+/// Build one split trigger per boss of a gauntlet ("boss N of M defeated")
+///
+/// Each trigger requires the reflection and gauntlet flags as well, so
+/// victories outside the gauntlet do not split.
+pub fn gauntlet_triggers(gauntlet_id: &str) -> Result<Vec<CustomTrigger>, String> {
+    let gauntlet = gauntlets()
+        .into_iter()
+        .find(|g| g.id == gauntlet_id)
+        .ok_or_else(|| format!("Unknown gauntlet: {}", gauntlet_id))?;
+
+    let total = gauntlet.bosses.len();
+    Ok(gauntlet
+        .bosses
+        .iter()
+        .enumerate()
+        .map(|(index, boss)| CustomTrigger {
+            id: format!("{}{}.{}", TRIGGER_ID_PREFIX, gauntlet.id, index + 1),
+            name: format!("{}: {} ({}/{})", gauntlet.name, boss.name, index + 1, total),
+            condition: TriggerCondition::FlagSet {
+                flags: vec![REFLECTION_ACTIVE_FLAG, gauntlet.flag_id, boss.flag_id],
+                range: None,
+                mode: FlagSetMode::All,
+            },
+            kind: TriggerKind::Split,
+            metadata: Default::default(),
+            group: None,
+            priority: 0,
+            suppression_ms: None,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gauntlet_bosses_resolve() {
+        for (gauntlet, &(_, _, _, bosses)) in gauntlets().iter().zip(GAUNTLETS) {
+            assert_eq!(gauntlet.bosses.len(), bosses.len(), "{}", gauntlet.id);
+        }
+    }
+
+    #[test]
+    fn test_detect_progress() {
+        assert_eq!(detect(|_| false), None);
+
+        // Gauntlet of Legends, Genichiro and Owl (Father) defeated
+        let set = [REFLECTION_ACTIVE_FLAG, 9811, 9703, 9717];
+        let progress = detect(|flag| set.contains(&flag)).unwrap();
+        assert_eq!(progress.gauntlet_id.as_deref(), Some("gauntlet_of_legends"));
+        assert_eq!((progress.defeated, progress.total), (2, 6));
+        assert_eq!(progress.current_boss.as_deref(), Some("Emma, the Gentle Blade"));
+
+        // Single reflection
+        let progress = detect(|flag| flag == REFLECTION_ACTIVE_FLAG).unwrap();
+        assert_eq!(progress.gauntlet_id, None);
+        assert_eq!((progress.defeated, progress.total), (0, 1));
+    }
+
+    #[test]
+    fn test_gauntlet_triggers() {
+        let triggers = gauntlet_triggers("gauntlet_of_legends").unwrap();
+        assert_eq!(triggers.len(), 6);
+        assert_eq!(triggers[1].id, "gauntlet.gauntlet_of_legends.2");
+        assert_eq!(triggers[1].name, "Gauntlet of Legends: Owl (Father) (2/6)");
+
+        let set = [REFLECTION_ACTIVE_FLAG, 9811, 9717];
+        assert!(triggers[1].condition.evaluate(|flag| set.contains(&flag), |_| None));
+        // The same victory outside the gauntlet does not split
+        assert!(!triggers[1].condition.evaluate(|flag| flag == 9717, |_| None));
+
+        assert!(gauntlet_triggers("boss_rush").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+//! Sekiro Sculptor's Idol triggers
+//!
+//! Several Sekiro categories split on reaching an idol ("split on Ashina
+//! Castle idol") rather than on a boss. Every idol has an activation flag
+//! that the game sets the first time the player rests at it. [`idols`] lists
+//! them by area for host pickers, and [`idol_triggers`] turns a selection
+//! into custom split triggers. Flag ids follow the numbering used by
+//! `schemas/sekiro.toml`, in blocks of ten per area.
+//!
+//! Resting at an idol again, or teleporting to one, leaves its flag
+//! unchanged, so only the first rest is detected.
+
+use crate::config::{CustomTrigger, TriggerCondition, TriggerKind};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of trigger ids built by [`idol_triggers`]
+pub const TRIGGER_ID_PREFIX: &str = "idol.";
+
+/// (id, name, area, activation flag) in area order
+const IDOLS: &[(&str, &str, &str, u32)] = &[
+    ("dilapidated_temple", "Dilapidated Temple", "Dilapidated Temple", 9100),
+    ("ashina_outskirts", "Ashina Outskirts", "Ashina Outskirts", 9110),
+    ("outskirts_wall_gate_path", "Outskirts Wall - Gate Path", "Ashina Outskirts", 9111),
+    ("outskirts_wall_stairway", "Outskirts Wall - Stairway", "Ashina Outskirts", 9112),
+    ("underbridge_valley", "Underbridge Valley", "Ashina Outskirts", 9113),
+    ("ashina_castle_fortress", "Ashina Castle Fortress", "Ashina Outskirts", 9114),
+    ("dragonspring_hirata_estate", "Dragonspring - Hirata Estate", "Hirata Estate", 9120),
+    ("estate_path", "Estate Path", "Hirata Estate", 9121),
+    ("bamboo_thicket_slope", "Bamboo Thicket Slope", "Hirata Estate", 9122),
+    ("hirata_estate_main_hall", "Hirata Estate - Main Hall", "Hirata Estate", 9123),
+    ("hirata_audience_chamber", "Hirata Audience Chamber", "Hirata Estate", 9124),
+    ("hirata_estate_hidden_temple", "Hirata Estate - Hidden Temple", "Hirata Estate", 9125),
+    ("ashina_castle_gate", "Ashina Castle Gate", "Ashina Castle", 9130),
+    ("ashina_castle", "Ashina Castle", "Ashina Castle", 9131),
+    ("upper_tower_antechamber", "Upper Tower - Antechamber", "Ashina Castle", 9132),
+    ("upper_tower_ashina_dojo", "Upper Tower - Ashina Dojo", "Ashina Castle", 9133),
+    ("castle_tower_lookout", "Castle Tower Lookout", "Ashina Castle", 9134),
+    ("upper_tower_kuros_room", "Upper Tower - Kuro's Room", "Ashina Castle", 9135),
+    ("old_grave", "Old Grave", "Ashina Castle", 9136),
+    ("great_serpent_shrine", "Great Serpent Shrine", "Ashina Castle", 9137),
+    ("abandoned_dungeon_entrance", "Abandoned Dungeon Entrance", "Ashina Castle", 9138),
+    ("ashina_reservoir", "Ashina Reservoir", "Ashina Reservoir", 9140),
+    ("near_secret_passage", "Near Secret Passage", "Ashina Reservoir", 9141),
+    ("underground_waterway", "Underground Waterway", "Abandoned Dungeon", 9150),
+    ("bottomless_hole", "Bottomless Hole", "Abandoned Dungeon", 9151),
+    ("senpou_temple_mt_kongo", "Senpou Temple, Mt. Kongo", "Senpou Temple", 9160),
+    ("shugendo", "Shugendo", "Senpou Temple", 9161),
+    ("temple_grounds", "Temple Grounds", "Senpou Temple", 9162),
+    ("main_hall", "Main Hall", "Senpou Temple", 9163),
+    ("inner_sanctum", "Inner Sanctum", "Senpou Temple", 9164),
+    ("sunken_valley_cavern", "Sunken Valley Cavern", "Senpou Temple", 9165),
+    ("bodhisattva_valley", "Bodhisattva Valley", "Senpou Temple", 9166),
+    ("under_shrine_valley", "Under-Shrine Valley", "Sunken Valley", 9170),
+    ("sunken_valley", "Sunken Valley", "Sunken Valley", 9171),
+    ("gun_fort", "Gun Fort", "Sunken Valley", 9172),
+    ("riven_cave", "Riven Cave", "Sunken Valley", 9173),
+    ("guardian_apes_burrow", "Guardian Ape's Burrow", "Sunken Valley", 9174),
+    ("ashina_depths", "Ashina Depths", "Ashina Depths", 9180),
+    ("poison_pool", "Poison Pool", "Ashina Depths", 9181),
+    ("guardian_apes_watering_hole", "Guardian Ape's Watering Hole", "Ashina Depths", 9182),
+    ("hidden_forest", "Hidden Forest", "Ashina Depths", 9183),
+    ("mibu_village", "Mibu Village", "Ashina Depths", 9184),
+    ("water_mill", "Water Mill", "Ashina Depths", 9185),
+    ("wedding_cave_door", "Wedding Cave Door", "Ashina Depths", 9186),
+    ("fountainhead_palace", "Fountainhead Palace", "Fountainhead Palace", 9190),
+    ("vermilion_bridge", "Vermilion Bridge", "Fountainhead Palace", 9191),
+    ("mibu_manor", "Mibu Manor", "Fountainhead Palace", 9192),
+    ("flower_viewing_stage", "Flower Viewing Stage", "Fountainhead Palace", 9193),
+    ("great_sakura", "Great Sakura", "Fountainhead Palace", 9194),
+    ("palace_grounds", "Palace Grounds", "Fountainhead Palace", 9195),
+    ("feeding_grounds", "Feeding Grounds", "Fountainhead Palace", 9196),
+    ("near_pot_noble", "Near Pot Noble", "Fountainhead Palace", 9197),
+    ("sanctuary", "Sanctuary", "Fountainhead Palace", 9198),
+];
+
+/// A Sculptor's Idol a host can offer as a split choice
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct SculptorsIdol {
+    pub id: String,
+    pub name: String,
+    /// Area the idol is in, for grouping in pickers
+    pub area: String,
+    /// Event flag set when the player first rests at the idol
+    pub flag_id: u32,
+}
+
+/// All idols in area order
+pub fn idols() -> Vec<SculptorsIdol> {
+    IDOLS
+        .iter()
+        .map(|&(id, name, area, flag_id)| SculptorsIdol {
+            id: id.to_string(),
+            name: name.to_string(),
+            area: area.to_string(),
+            flag_id,
+        })
+        .collect()
+}
+
+/// Build split triggers for the selected idols, in selection order
+pub fn idol_triggers(selected: &[String]) -> Result<Vec<CustomTrigger>, String> {
+    if selected.is_empty() {
+        return Err("No idols selected".to_string());
+    }
+    let all = idols();
+    let chosen: Vec<&SculptorsIdol> = selected
+        .iter()
+        .map(|id| {
+            all.iter()
+                .find(|idol| &idol.id == id)
+                .ok_or_else(|| format!("Unknown idol: {}", id))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(chosen
+        .into_iter()
+        .map(|idol| CustomTrigger {
+            id: format!("{}{}", TRIGGER_ID_PREFIX, idol.id),
+            name: format!("Idol: {}", idol.name),
+            condition: TriggerCondition::EventFlag { flag_id: idol.flag_id },
+            kind: TriggerKind::Split,
+            metadata: Default::default(),
+            group: None,
+            priority: 0,
+            suppression_ms: None,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_idol_ids_and_flags_are_unique() {
+        let idols = idols();
+        let ids: HashSet<_> = idols.iter().map(|idol| idol.id.as_str()).collect();
+        let flags: HashSet<_> = idols.iter().map(|idol| idol.flag_id).collect();
+        assert_eq!(ids.len(), idols.len());
+        assert_eq!(flags.len(), idols.len());
+    }
+
+    #[test]
+    fn test_idol_triggers() {
+        let triggers = idol_triggers(&["ashina_castle".to_string(), "dilapidated_temple".to_string()]).unwrap();
+        assert_eq!(triggers.len(), 2);
+        assert_eq!(triggers[0].id, "idol.ashina_castle");
+        assert_eq!(triggers[0].name, "Idol: Ashina Castle");
+        assert_eq!(triggers[1].condition, TriggerCondition::EventFlag { flag_id: 9100 });
+
+        assert!(idol_triggers(&[]).is_err());
+        assert!(idol_triggers(&["firelink_shrine".to_string()]).is_err());
+    }
+}
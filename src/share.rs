@@ -0,0 +1,211 @@
+//! Shareable split configuration strings
+//!
+//! Packs the boss flags, custom triggers and timing rules of a run into one
+//! URL-safe string that runners can paste into chat or append to a link:
+//!
+//! ```text
+//! nya1.<base64url(deflate(json))>
+//! ```
+//!
+//! The number after `nya` is the format version. Strings from a newer format
+//! are rejected with a message asking to update instead of being misread.
+//! On import the payload is size-limited, parsed and validated: ids must be
+//! unique and flag ids must fit the game's id scheme (see `flag_ids`).
+
+use crate::config::{BossFlag, CustomTrigger};
+use crate::engine::EngineType;
+use crate::flag_ids;
+use crate::timing_rules::TimingRule;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Version of the share string format
+pub const SHARE_FORMAT_VERSION: u32 = 1;
+
+/// Prefix of every share string, followed by the format version and a dot
+pub const SHARE_PREFIX: &str = "nya";
+
+/// Largest accepted decompressed payload
+const MAX_PAYLOAD_SIZE: usize = 1 << 20;
+
+const BASE64_URL: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// A split configuration as shared between runners
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedSplits {
+    /// Engine id of the game (e.g., "ds3", "elden_ring"); flag ids are
+    /// validated against it when known
+    pub game: String,
+    #[serde(default)]
+    pub boss_flags: Vec<BossFlag>,
+    #[serde(default)]
+    pub triggers: Vec<CustomTrigger>,
+    #[serde(default)]
+    pub timing_rules: Vec<TimingRule>,
+}
+
+impl SharedSplits {
+    /// Check ids for duplicates and flag ids against the game's id scheme
+    pub fn validate(&self) -> Result<(), String> {
+        if self.boss_flags.is_empty() && self.triggers.is_empty() && self.timing_rules.is_empty() {
+            return Err("Split configuration is empty".to_string());
+        }
+
+        let mut ids = HashSet::new();
+        let all_ids = self
+            .boss_flags
+            .iter()
+            .map(|b| &b.boss_id)
+            .chain(self.triggers.iter().map(|t| &t.id))
+            .chain(self.timing_rules.iter().map(|r| &r.id));
+        for id in all_ids {
+            if !ids.insert(id) {
+                return Err(format!("Duplicate split id '{}'", id));
+            }
+        }
+
+        let Some(engine) = EngineType::from_str(&self.game) else {
+            return Ok(());
+        };
+        for boss in &self.boss_flags {
+            flag_ids::validate(engine, boss.flag_id).map_err(|e| format!("Boss '{}': {}", boss.boss_id, e))?;
+        }
+        for trigger in &self.triggers {
+            for flag_id in trigger.condition.flag_ids() {
+                flag_ids::validate(engine, flag_id).map_err(|e| format!("Trigger '{}': {}", trigger.id, e))?;
+            }
+        }
+        for rule in &self.timing_rules {
+            for flag_id in rule.flag_ids() {
+                flag_ids::validate(engine, flag_id).map_err(|e| format!("Timing rule '{}': {}", rule.id, e))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Encode a split configuration as a share string
+pub fn encode(splits: &SharedSplits) -> Result<String, String> {
+    let json = serde_json::to_vec(splits).map_err(|e| format!("Failed to serialize splits: {}", e))?;
+    let compressed = miniz_oxide::deflate::compress_to_vec(&json, 9);
+    Ok(format!("{}{}.{}", SHARE_PREFIX, SHARE_FORMAT_VERSION, base64_url_encode(&compressed)))
+}
+
+/// Decode and validate a share string
+pub fn decode(text: &str) -> Result<SharedSplits, String> {
+    let text = text.trim();
+    let (header, payload) = text
+        .strip_prefix(SHARE_PREFIX)
+        .and_then(|rest| rest.split_once('.'))
+        .ok_or("Not a split configuration string")?;
+    let version: u32 = header
+        .parse()
+        .map_err(|_| "Not a split configuration string".to_string())?;
+    if version > SHARE_FORMAT_VERSION {
+        return Err(format!(
+            "Split configuration uses format version {}; update the autosplitter to import it",
+            version
+        ));
+    }
+
+    let compressed = base64_url_decode(payload).ok_or("Split configuration string is corrupted")?;
+    let json = miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, MAX_PAYLOAD_SIZE)
+        .map_err(|_| "Split configuration string is corrupted or too large".to_string())?;
+    let splits: SharedSplits =
+        serde_json::from_slice(&json).map_err(|e| format!("Invalid split configuration: {}", e))?;
+    splits.validate()?;
+    Ok(splits)
+}
+
+/// Unpadded base64 with the URL-safe alphabet
+fn base64_url_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bits = chunk.iter().enumerate().fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            out.push(BASE64_URL[(bits >> (18 - 6 * i) & 0x3F) as usize] as char);
+        }
+    }
+    out
+}
+
+fn base64_url_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut bits, mut count) = (0u32, 0);
+    for c in text.bytes() {
+        let value = BASE64_URL.iter().position(|&b| b == c)? as u32;
+        bits = (bits << 6) | value;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            out.push((bits >> count) as u8);
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timing_rules::{Anchor, AnchorEvent};
+
+    fn splits() -> SharedSplits {
+        SharedSplits {
+            game: "ds3".to_string(),
+            boss_flags: vec![BossFlag {
+                boss_id: "vordt".to_string(),
+                boss_name: "Vordt of the Boreal Valley".to_string(),
+                flag_id: 14000800,
+                is_dlc: false,
+                metadata: Default::default(),
+                group: None,
+                priority: 0,
+            }],
+            triggers: Vec::new(),
+            timing_rules: vec![TimingRule {
+                id: "vordt_to_load".to_string(),
+                name: "Vordt to load".to_string(),
+                start: Anchor { on: AnchorEvent::FlagSet { flag_id: 14000800 }, after_flag: None },
+                end: Anchor { on: AnchorEvent::LoadStart, after_flag: None },
+            }],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let text = encode(&splits()).unwrap();
+        assert!(text.starts_with("nya1."));
+        assert!(text
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_' || b == b'.'));
+        let decoded = decode(&text).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(splits()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_base64_url() {
+        for data in [&b""[..], b"f", b"fo", b"foo", b"foob", b"\xfb\xff"] {
+            assert_eq!(base64_url_decode(&base64_url_encode(data)).unwrap(), data);
+        }
+        assert_eq!(base64_url_encode(b"\xfb\xff"), "-_8");
+    }
+
+    #[test]
+    fn test_rejects_invalid_strings() {
+        assert!(decode("https://example.com").is_err());
+        assert!(decode("nya1.!!!").is_err());
+        assert!(decode("nya1.AAAA").is_err());
+        assert!(decode("nya2.AAAA").unwrap_err().contains("update"));
+
+        let mut duplicate = splits();
+        duplicate.timing_rules[0].id = "vordt".to_string();
+        assert!(decode(&encode(&duplicate).unwrap()).unwrap_err().contains("Duplicate"));
+
+        let mut bad_flag = splits();
+        bad_flag.boss_flags[0].flag_id = 140000800;
+        assert!(decode(&encode(&bad_flag).unwrap()).unwrap_err().contains("vordt"));
+    }
+}
@@ -0,0 +1,301 @@
+//! Signed gold and run log exports
+//!
+//! Golds and run logs are plain files or database rows, so a sum of best
+//! imported from another machine may have been edited or generated by a
+//! tool. [`export_stats`] bundles a game's golds and stored runs into
+//! [`SignedStats`], signed with an Ed25519 key generated on first use and
+//! kept in a local key file ([`LocalKey`]). [`verify_stats`] checks the
+//! signature and that every gold is backed by a segment of a signed run;
+//! [`import_stats`] stores the runs of verified stats and recomputes the
+//! golds and statistics from them, so imported golds are never taken as is.
+//!
+//! A valid signature shows the stats were not changed since they were
+//! exported with the key. Whose key it is stays up to the importer, who can
+//! pass the public keys they trust. Only built with the `signatures` feature.
+
+use crate::hex::{decode_hex, encode_hex};
+use crate::storage::{self, Storage, StoredRun};
+use ed25519_zebra::{Signature, SigningKey, VerificationKey};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Version of the export layout this library understands
+pub const STATS_FORMAT_VERSION: u32 = 1;
+
+/// Golds and stored runs of a game, signed by the exporting machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedStats {
+    pub format_version: u32,
+    pub game_id: String,
+    /// Best segment IGT per split
+    pub golds: BTreeMap<String, i64>,
+    pub runs: Vec<StoredRun>,
+    /// Ed25519 public key of the exporting machine (hex)
+    pub public_key: String,
+    /// Signature over all other fields (hex)
+    pub signature: String,
+}
+
+/// Result of checking signed stats whose signature verified
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatsVerification {
+    /// The stats were signed with one of the trusted keys
+    pub trusted_key: bool,
+    /// Golds faster than every segment of the signed runs for that split
+    pub unbacked_golds: Vec<String>,
+}
+
+/// The fields covered by the signature
+#[derive(Serialize)]
+struct SignedContent<'a> {
+    format_version: u32,
+    game_id: &'a str,
+    golds: &'a BTreeMap<String, i64>,
+    runs: &'a [StoredRun],
+    public_key: &'a str,
+}
+
+impl SignedStats {
+    /// Canonical bytes of the signed fields (JSON with sorted keys)
+    fn content(&self) -> Result<Vec<u8>, String> {
+        let content = SignedContent {
+            format_version: self.format_version,
+            game_id: &self.game_id,
+            golds: &self.golds,
+            runs: &self.runs,
+            public_key: &self.public_key,
+        };
+        // Through a Value so maps inside the runs serialize in key order
+        let value = serde_json::to_value(&content).map_err(|e| e.to_string())?;
+        serde_json::to_vec(&value).map_err(|e| e.to_string())
+    }
+}
+
+/// Signing key of this machine
+pub struct LocalKey {
+    key: SigningKey,
+}
+
+impl LocalKey {
+    /// Load the key from `path`, generating and saving a new one on first use
+    pub fn load_or_generate(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(hex) => {
+                let secret: [u8; 32] = decode_hex(hex.trim())
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(|| format!("{} is not a signing key", path.display()))?;
+                Ok(Self { key: SigningKey::from(secret) })
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                let key = Self::generate();
+                key.save(path)?;
+                log::info!("Generated signing key {}", path.display());
+                Ok(key)
+            }
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// A new random key (not saved)
+    pub fn generate() -> Self {
+        Self {
+            key: SigningKey::new(rand_core::OsRng),
+        }
+    }
+
+    fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(path, encode_hex(self.key.as_ref()))
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        // Readable by the owner only
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+                .map_err(|e| format!("Failed to restrict {}: {}", path.display(), e))?;
+        }
+        Ok(())
+    }
+
+    /// Public key (hex) importers can choose to trust
+    pub fn public_key(&self) -> String {
+        encode_hex(VerificationKey::from(&self.key).as_ref())
+    }
+}
+
+/// Export and sign the golds and stored runs of a game
+pub fn export_stats(storage: &dyn Storage, game_id: &str, key: &LocalKey) -> Result<SignedStats, String> {
+    let mut stats = SignedStats {
+        format_version: STATS_FORMAT_VERSION,
+        game_id: game_id.to_string(),
+        golds: storage.load_golds(game_id)?.into_iter().collect(),
+        runs: storage.load_runs(game_id)?,
+        public_key: key.public_key(),
+        signature: String::new(),
+    };
+    stats.signature = encode_hex(&<[u8; 64]>::from(key.key.sign(&stats.content()?)));
+    Ok(stats)
+}
+
+/// Check the signature of exported stats and that their golds are backed by
+/// their runs; fails when the stats were changed after signing
+pub fn verify_stats(stats: &SignedStats, trusted_keys: &[String]) -> Result<StatsVerification, String> {
+    if stats.format_version > STATS_FORMAT_VERSION {
+        return Err(format!(
+            "Stats format version {} is not supported; update the autosplitter",
+            stats.format_version
+        ));
+    }
+
+    let key: [u8; 32] = decode_hex(&stats.public_key)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Public key must be 32 bytes of hex")?;
+    let signature: [u8; 64] = decode_hex(&stats.signature)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or("Signature must be 64 bytes of hex")?;
+    let key = VerificationKey::try_from(key).map_err(|e| format!("Invalid public key: {}", e))?;
+    key.verify(&Signature::from(signature), &stats.content()?)
+        .map_err(|_| "Stats signature does not verify; they were changed after export".to_string())?;
+
+    if let Some(run) = stats.runs.iter().find(|run| run.game_id != stats.game_id) {
+        return Err(format!("Run of '{}' in stats of '{}'", run.game_id, stats.game_id));
+    }
+
+    let mut best_segments: BTreeMap<String, i64> = BTreeMap::new();
+    for run in &stats.runs {
+        for (key, time) in storage::segment_times(&run.entries) {
+            let best = best_segments.entry(key).or_insert(time);
+            *best = (*best).min(time);
+        }
+    }
+    let unbacked_golds = stats
+        .golds
+        .iter()
+        .filter(|(key, gold)| best_segments.get(*key).is_none_or(|best| best > gold))
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    Ok(StatsVerification {
+        trusted_key: trusted_keys.iter().any(|trusted| trusted.eq_ignore_ascii_case(&stats.public_key)),
+        unbacked_golds,
+    })
+}
+
+/// Verify exported stats and store their runs that are not stored yet;
+/// golds and statistics are recomputed from the runs. With `trusted_keys`,
+/// stats signed with other keys are rejected.
+pub fn import_stats(
+    storage: &mut dyn Storage,
+    stats: &SignedStats,
+    trusted_keys: &[String],
+) -> Result<StatsVerification, String> {
+    let verification = verify_stats(stats, trusted_keys)?;
+    if !trusted_keys.is_empty() && !verification.trusted_key {
+        return Err(format!("Stats signed with untrusted key {}", stats.public_key));
+    }
+    if !verification.unbacked_golds.is_empty() {
+        return Err(format!(
+            "Golds not backed by any run: {}",
+            verification.unbacked_golds.join(", ")
+        ));
+    }
+
+    let stored: Vec<u64> = storage.load_runs(&stats.game_id)?.iter().map(|run| run.saved_at_ms).collect();
+    for run in stats.runs.iter().filter(|run| !stored.contains(&run.saved_at_ms)) {
+        storage::record_run(storage, run)?;
+    }
+    log::info!("Imported stats of '{}' signed by {}", stats.game_id, stats.public_key);
+    Ok(verification)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run_log::{RunEvent, RunLogEntry};
+    use crate::storage::MemoryStorage;
+
+    fn run(saved_at_ms: u64, times: &[(&str, i64)]) -> StoredRun {
+        StoredRun {
+            game_id: "DarkSouls3".to_string(),
+            saved_at_ms,
+            entries: times
+                .iter()
+                .map(|(boss_id, igt_ms)| RunLogEntry {
+                    elapsed_ms: 0,
+                    igt_ms: Some(*igt_ms),
+                    host_ms: None,
+                    menu_time_ms: None,
+                    event: RunEvent::BossDefeated {
+                        boss_id: boss_id.to_string(),
+                        boss_name: boss_id.to_string(),
+                        flag_id: 0,
+                        suspicion: None,
+                        display_name: None,
+                        metadata: Default::default(),
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    fn exported(key: &LocalKey) -> SignedStats {
+        let mut storage = MemoryStorage::new();
+        storage::record_run(&mut storage, &run(1, &[("iudex", 60_000), ("vordt", 300_000)])).unwrap();
+        storage::record_run(&mut storage, &run(2, &[("iudex", 70_000), ("vordt", 250_000)])).unwrap();
+        export_stats(&storage, "DarkSouls3", key).unwrap()
+    }
+
+    #[test]
+    fn test_export_and_import() {
+        let key = LocalKey::generate();
+        let stats = exported(&key);
+
+        // Survives a round trip through JSON
+        let stats: SignedStats = serde_json::from_str(&serde_json::to_string(&stats).unwrap()).unwrap();
+        let verification = verify_stats(&stats, &[key.public_key()]).unwrap();
+        assert!(verification.trusted_key);
+        assert!(verification.unbacked_golds.is_empty());
+
+        let mut storage = MemoryStorage::new();
+        import_stats(&mut storage, &stats, &[]).unwrap();
+        assert_eq!(storage.load_golds("DarkSouls3").unwrap().get("vordt"), Some(&180_000));
+        // Importing again adds no runs
+        import_stats(&mut storage, &stats, &[]).unwrap();
+        assert_eq!(storage.load_runs("DarkSouls3").unwrap().len(), 2);
+
+        assert!(import_stats(&mut storage, &stats, &[LocalKey::generate().public_key()]).is_err());
+    }
+
+    #[test]
+    fn test_tampering_detected() {
+        let key = LocalKey::generate();
+
+        // An edited gold breaks the signature
+        let mut stats = exported(&key);
+        stats.golds.insert("vordt".to_string(), 100_000);
+        assert!(verify_stats(&stats, &[]).is_err());
+
+        // A fabricated gold re-signed with the key is not backed by a run
+        let mut stats = exported(&key);
+        stats.golds.insert("vordt".to_string(), 100_000);
+        stats.signature = encode_hex(&<[u8; 64]>::from(key.key.sign(&stats.content().unwrap())));
+        let verification = verify_stats(&stats, &[]).unwrap();
+        assert_eq!(verification.unbacked_golds, vec!["vordt".to_string()]);
+        assert!(import_stats(&mut MemoryStorage::new(), &stats, &[]).is_err());
+    }
+
+    #[test]
+    fn test_key_file() {
+        let path = std::env::temp_dir().join(format!("nyacore-signing-test-{}", std::process::id())).join("key");
+        let _ = std::fs::remove_file(&path);
+
+        let generated = LocalKey::load_or_generate(&path).unwrap();
+        let loaded = LocalKey::load_or_generate(&path).unwrap();
+        assert_eq!(generated.public_key(), loaded.public_key());
+
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
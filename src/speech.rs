@@ -0,0 +1,212 @@
+//! Text-to-speech split announcements
+//!
+//! Announces splits for blind and low-vision runners, e.g.
+//! "Vordt of the Boreal Valley defeated, 12 seconds ahead". Deltas come from
+//! the comparison split times in [`AutosplitterConfig::comparison_igt_ms`].
+//!
+//! Speech uses the platform synthesizer through the `tts` crate (WinRT/SAPI
+//! voices on Windows, speech-dispatcher on Linux) and is only compiled with
+//! the `tts` feature; without it announcements are logged instead.
+//!
+//! [`AutosplitterConfig::comparison_igt_ms`]: crate::config::AutosplitterConfig::comparison_igt_ms
+
+use crate::config::TriggerKind;
+use crate::run_log::{RunEvent, RunLogEntry};
+use std::collections::HashMap;
+
+#[cfg(feature = "tts")]
+use std::sync::mpsc::{self, Sender};
+
+/// Key of the split an event completes, used to look up comparison times
+pub fn split_key(event: &RunEvent) -> Option<&str> {
+    match event {
+        RunEvent::BossDefeated { boss_id, .. } => Some(boss_id),
+        RunEvent::TriggerMatched {
+            trigger_id,
+            kind: TriggerKind::Split,
+            ..
+        } => Some(trigger_id),
+        RunEvent::SegmentTimed { rule_id, .. } => Some(rule_id),
+        _ => None,
+    }
+}
+
+/// Delta against the comparison (negative = ahead), if both times are known
+pub fn split_delta_ms(entry: &RunLogEntry, comparison_igt_ms: &HashMap<String, i64>) -> Option<i64> {
+    let key = split_key(&entry.event)?;
+    let comparison = comparison_igt_ms.get(key)?;
+    Some(entry.igt_ms? - comparison)
+}
+
+/// Spoken form of a delta: "12 seconds ahead", "1 minute 5 seconds behind"
+pub fn format_delta(delta_ms: i64) -> String {
+    let seconds = (delta_ms.abs() + 500) / 1000;
+    if seconds == 0 {
+        return "even".to_string();
+    }
+
+    let (minutes, seconds) = (seconds / 60, seconds % 60);
+    let mut parts = Vec::new();
+    if minutes > 0 {
+        parts.push(format!("{} minute{}", minutes, if minutes == 1 { "" } else { "s" }));
+    }
+    if seconds > 0 {
+        parts.push(format!("{} second{}", seconds, if seconds == 1 { "" } else { "s" }));
+    }
+
+    let direction = if delta_ms < 0 { "ahead" } else { "behind" };
+    format!("{} {}", parts.join(" "), direction)
+}
+
+/// Announcement text for a run event, if it is worth announcing
+pub fn announcement_for(entry: &RunLogEntry, comparison_igt_ms: &HashMap<String, i64>) -> Option<String> {
+    let text = match &entry.event {
+        RunEvent::BossDefeated { boss_name, display_name, .. } => match display_name {
+            Some(display_name) => display_name.clone(),
+            None => format!("{} defeated", boss_name),
+        },
+        RunEvent::TriggerMatched { name, kind, display_name, .. } => match kind {
+            TriggerKind::Split => display_name.as_ref().unwrap_or(name).clone(),
+            TriggerKind::RuleViolation => format!("Rule violation: {}", name),
+        },
+        RunEvent::SegmentTimed { name, .. } => name.clone(),
+        _ => return None,
+    };
+
+    match split_delta_ms(entry, comparison_igt_ms) {
+        Some(delta) => Some(format!("{}, {}", text, format_delta(delta))),
+        None => Some(text),
+    }
+}
+
+/// Speaks announcements on a background thread (started on first use)
+#[derive(Default)]
+pub struct Announcer {
+    #[cfg(feature = "tts")]
+    sender: Option<Sender<String>>,
+}
+
+impl Announcer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[cfg(feature = "tts")]
+    pub fn speak(&mut self, text: &str) {
+        log::debug!("Announcing: {}", text);
+        let sender = self.sender.get_or_insert_with(spawn_speech_thread);
+        if sender.send(text.to_string()).is_err() {
+            // Speech thread exited (no synthesizer) - retry with a new one next time
+            self.sender = None;
+        }
+    }
+
+    #[cfg(not(feature = "tts"))]
+    pub fn speak(&mut self, text: &str) {
+        log::debug!("Announcement not spoken (built without the `tts` feature): {}", text);
+    }
+}
+
+#[cfg(feature = "tts")]
+fn spawn_speech_thread() -> Sender<String> {
+    let (sender, receiver) = mpsc::channel::<String>();
+
+    std::thread::spawn(move || {
+        let mut tts = match tts::Tts::default() {
+            Ok(tts) => tts,
+            Err(e) => {
+                log::warn!("Text-to-speech unavailable: {}", e);
+                return;
+            }
+        };
+
+        for text in receiver {
+            // Queue rather than interrupt so back-to-back splits are all heard
+            if let Err(e) = tts.speak(text, false) {
+                log::warn!("Text-to-speech failed: {}", e);
+            }
+        }
+    });
+
+    sender
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boss_entry(igt_ms: Option<i64>) -> RunLogEntry {
+        RunLogEntry {
+            elapsed_ms: 0,
+            igt_ms,
+            host_ms: None,
+            menu_time_ms: None,
+            event: RunEvent::BossDefeated {
+                boss_id: "vordt".to_string(),
+                boss_name: "Vordt of the Boreal Valley".to_string(),
+                flag_id: 13000800,
+                suspicion: None,
+                display_name: None,
+                metadata: Default::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_format_delta() {
+        assert_eq!(format_delta(-12_300), "12 seconds ahead");
+        assert_eq!(format_delta(65_000), "1 minute 5 seconds behind");
+        assert_eq!(format_delta(120_000), "2 minutes behind");
+        assert_eq!(format_delta(-1_000), "1 second ahead");
+        assert_eq!(format_delta(200), "even");
+    }
+
+    #[test]
+    fn test_announcement_with_delta() {
+        let mut comparison = HashMap::new();
+        comparison.insert("vordt".to_string(), 600_000);
+
+        let text = announcement_for(&boss_entry(Some(588_000)), &comparison);
+        assert_eq!(text.as_deref(), Some("Vordt of the Boreal Valley defeated, 12 seconds ahead"));
+    }
+
+    #[test]
+    fn test_announcement_without_comparison() {
+        let text = announcement_for(&boss_entry(Some(588_000)), &HashMap::new());
+        assert_eq!(text.as_deref(), Some("Vordt of the Boreal Valley defeated"));
+
+        let reset = RunLogEntry {
+            elapsed_ms: 0,
+            igt_ms: None,
+            host_ms: None,
+            menu_time_ms: None,
+            event: RunEvent::Reset,
+        };
+        assert_eq!(announcement_for(&reset, &HashMap::new()), None);
+    }
+
+    #[test]
+    fn test_rule_violation_has_no_delta() {
+        let mut comparison = HashMap::new();
+        comparison.insert("sl_cap".to_string(), 0);
+
+        let entry = RunLogEntry {
+            elapsed_ms: 0,
+            igt_ms: Some(10_000),
+            host_ms: None,
+            menu_time_ms: None,
+            event: RunEvent::TriggerMatched {
+                index: 0,
+                trigger_id: "sl_cap".to_string(),
+                name: "Soul level above 1".to_string(),
+                kind: TriggerKind::RuleViolation,
+                display_name: None,
+                metadata: Default::default(),
+            },
+        };
+        assert_eq!(
+            announcement_for(&entry, &comparison).as_deref(),
+            Some("Rule violation: Soul level above 1")
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Split naming templates
+//!
+//! `AutosplitterConfig::split_name_template` renders a display name for every
+//! boss split and split trigger when it happens, e.g. `"{name} ({kill_count})"`
+//! or `"{area} - {name}"`. The result is set as `display_name` on the
+//! `boss_defeated`/`trigger_matched` event and used by chapters, highlights,
+//! announcements and the position trail, so hosts get consistent names
+//! without post-processing events.
+//!
+//! Placeholders:
+//! - `{name}` (`{boss_name}`): the boss or trigger name
+//! - `{id}` (`{boss_id}`): the boss or trigger id
+//! - `{kill_count}`: the boss kill count (NG+ cycle), 1 for triggers
+//! - `{igt}`: the in-game time as `h:mm:ss`
+//! - any other key of the split's metadata, e.g. `{area}`
+//!
+//! Unknown placeholders are kept as written, so typos show in the name.
+
+use crate::config::SplitMetadata;
+
+/// What a template can refer to
+pub struct SplitContext<'a> {
+    pub name: &'a str,
+    pub id: &'a str,
+    pub kill_count: u32,
+    pub igt_ms: Option<i64>,
+    pub metadata: &'a SplitMetadata,
+}
+
+/// Check a template for unclosed placeholders
+pub fn validate(template: &str) -> Result<(), String> {
+    let mut open = false;
+    for c in template.chars() {
+        match c {
+            '{' if open => return Err(format!("Nested '{{' in split name template: {}", template)),
+            '{' => open = true,
+            '}' => open = false,
+            _ => {}
+        }
+    }
+    if open {
+        return Err(format!("Unclosed '{{' in split name template: {}", template));
+    }
+    Ok(())
+}
+
+/// Render `template` for a split
+pub fn render(template: &str, context: &SplitContext) -> String {
+    let mut name = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        name.push_str(&rest[..start]);
+        let key = &rest[start + 1..start + len];
+        match value(key, context) {
+            Some(value) => name.push_str(&value),
+            None => name.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    name.push_str(rest);
+    name
+}
+
+fn value(key: &str, context: &SplitContext) -> Option<String> {
+    match key {
+        "name" | "boss_name" => Some(context.name.to_string()),
+        "id" | "boss_id" => Some(context.id.to_string()),
+        "kill_count" => Some(context.kill_count.to_string()),
+        "igt" => context.igt_ms.map(format_igt),
+        _ => match context.metadata.get(key)? {
+            serde_json::Value::String(value) => Some(value.clone()),
+            value => Some(value.to_string()),
+        },
+    }
+}
+
+fn format_igt(igt_ms: i64) -> String {
+    let seconds = igt_ms.max(0) / 1000;
+    format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let metadata = SplitMetadata::from([("area".to_string(), serde_json::json!("High Wall of Lothric"))]);
+        let context = SplitContext {
+            name: "Vordt of the Boreal Valley",
+            id: "vordt",
+            kill_count: 2,
+            igt_ms: Some(3_725_000),
+            metadata: &metadata,
+        };
+
+        assert_eq!(
+            render("{boss_name} ({kill_count})", &context),
+            "Vordt of the Boreal Valley (2)"
+        );
+        assert_eq!(
+            render("{area} — {name} @ {igt}", &context),
+            "High Wall of Lothric — Vordt of the Boreal Valley @ 1:02:05"
+        );
+        // Unknown placeholders and stray braces are kept
+        assert_eq!(render("{zone}: {id} }{", &context), "{zone}: vordt }{");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate("{name} ({kill_count})").is_ok());
+        assert!(validate("{name").is_err());
+        assert!(validate("{{name}}").is_err());
+    }
+}
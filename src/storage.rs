@@ -0,0 +1,448 @@
+//! Persistence of run logs, golds and statistics
+//!
+//! Completed runs are handed to a [`Storage`] backend, which keeps the run
+//! log, the best segment per split ("golds") and per-game statistics. Two
+//! backends are built in: [`JsonFileStorage`] (a directory of JSON files) and,
+//! with the `sqlite` feature, [`SqliteStorage`]. Embedded hosts can implement
+//! [`Storage`] themselves to keep everything in their own database.
+
+use crate::run_log::{RunEvent, RunLogEntry};
+use crate::speech::split_key;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Best segment IGT per split, keyed by boss or trigger id
+pub type Golds = HashMap<String, i64>;
+
+/// A finished (or reset) run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredRun {
+    pub game_id: String,
+    /// Unix time in milliseconds when the run was stored
+    pub saved_at_ms: u64,
+    pub entries: Vec<RunLogEntry>,
+}
+
+/// Per-game totals
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunStatistics {
+    /// Stored runs
+    pub attempts: u64,
+    /// How many runs reached each split
+    #[serde(default)]
+    pub split_counts: HashMap<String, u64>,
+}
+
+/// Storage backend for runs, golds and statistics
+pub trait Storage: Send {
+    fn save_run(&mut self, run: &StoredRun) -> Result<(), String>;
+    fn load_runs(&self, game_id: &str) -> Result<Vec<StoredRun>, String>;
+    fn save_golds(&mut self, game_id: &str, golds: &Golds) -> Result<(), String>;
+    fn load_golds(&self, game_id: &str) -> Result<Golds, String>;
+    fn save_statistics(&mut self, game_id: &str, statistics: &RunStatistics) -> Result<(), String>;
+    fn load_statistics(&self, game_id: &str) -> Result<RunStatistics, String>;
+}
+
+/// Segment times of a run: IGT between consecutive splits, in split order
+pub fn segment_times(entries: &[RunLogEntry]) -> Vec<(String, i64)> {
+    let mut previous_igt = 0;
+    let mut segments = Vec::new();
+
+    for entry in entries {
+        let (Some(key), Some(igt)) = (split_key(&entry.event), entry.igt_ms) else {
+            continue;
+        };
+        segments.push((key.to_string(), igt - previous_igt));
+        previous_igt = igt;
+    }
+
+    segments
+}
+
+/// Entries of the current attempt: everything after the last reset
+pub fn current_attempt(entries: &[RunLogEntry]) -> &[RunLogEntry] {
+    let start = entries
+        .iter()
+        .rposition(|e| matches!(e.event, RunEvent::Reset))
+        .map_or(0, |i| i + 1);
+    &entries[start..]
+}
+
+/// Store a run and fold it into the golds and statistics; returns the splits
+/// that set a new gold
+pub fn record_run(storage: &mut dyn Storage, run: &StoredRun) -> Result<Vec<String>, String> {
+    storage.save_run(run)?;
+
+    let segments = segment_times(&run.entries);
+
+    let mut golds = storage.load_golds(&run.game_id)?;
+    let mut new_golds = Vec::new();
+    for (key, time) in &segments {
+        if *time > 0 && golds.get(key).is_none_or(|best| time < best) {
+            golds.insert(key.clone(), *time);
+            new_golds.push(key.clone());
+        }
+    }
+    if !new_golds.is_empty() {
+        storage.save_golds(&run.game_id, &golds)?;
+    }
+
+    let mut statistics = storage.load_statistics(&run.game_id)?;
+    statistics.attempts += 1;
+    for (key, _) in &segments {
+        *statistics.split_counts.entry(key.clone()).or_default() += 1;
+    }
+    storage.save_statistics(&run.game_id, &statistics)?;
+
+    Ok(new_golds)
+}
+
+/// Storage kept in memory (for tests and hosts that persist on their own)
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    runs: Vec<StoredRun>,
+    golds: HashMap<String, Golds>,
+    statistics: HashMap<String, RunStatistics>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn save_run(&mut self, run: &StoredRun) -> Result<(), String> {
+        self.runs.push(run.clone());
+        Ok(())
+    }
+
+    fn load_runs(&self, game_id: &str) -> Result<Vec<StoredRun>, String> {
+        Ok(self.runs.iter().filter(|r| r.game_id == game_id).cloned().collect())
+    }
+
+    fn save_golds(&mut self, game_id: &str, golds: &Golds) -> Result<(), String> {
+        self.golds.insert(game_id.to_string(), golds.clone());
+        Ok(())
+    }
+
+    fn load_golds(&self, game_id: &str) -> Result<Golds, String> {
+        Ok(self.golds.get(game_id).cloned().unwrap_or_default())
+    }
+
+    fn save_statistics(&mut self, game_id: &str, statistics: &RunStatistics) -> Result<(), String> {
+        self.statistics.insert(game_id.to_string(), statistics.clone());
+        Ok(())
+    }
+
+    fn load_statistics(&self, game_id: &str) -> Result<RunStatistics, String> {
+        Ok(self.statistics.get(game_id).cloned().unwrap_or_default())
+    }
+}
+
+/// JSON files under a directory: `<game>/runs/<saved_at_ms>.json`,
+/// `<game>/golds.json` and `<game>/statistics.json`
+#[derive(Debug, Clone)]
+pub struct JsonFileStorage {
+    dir: PathBuf,
+}
+
+impl JsonFileStorage {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn game_dir(&self, game_id: &str) -> PathBuf {
+        let name: String = game_id
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+            .collect();
+        self.dir.join(name)
+    }
+
+    fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+        std::fs::write(path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    fn read_json<T: for<'de> Deserialize<'de> + Default>(path: &Path) -> Result<T, String> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(T::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+}
+
+impl Storage for JsonFileStorage {
+    fn save_run(&mut self, run: &StoredRun) -> Result<(), String> {
+        let path = self
+            .game_dir(&run.game_id)
+            .join("runs")
+            .join(format!("{}.json", run.saved_at_ms));
+        Self::write_json(&path, run)
+    }
+
+    fn load_runs(&self, game_id: &str) -> Result<Vec<StoredRun>, String> {
+        let dir = self.game_dir(game_id).join("runs");
+        let read_dir = match std::fs::read_dir(&dir) {
+            Ok(read_dir) => read_dir,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read {}: {}", dir.display(), e)),
+        };
+
+        let mut runs: Vec<StoredRun> = Vec::new();
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                let json = std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                runs.push(
+                    serde_json::from_str(&json)
+                        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?,
+                );
+            }
+        }
+        runs.sort_by_key(|r| r.saved_at_ms);
+        Ok(runs)
+    }
+
+    fn save_golds(&mut self, game_id: &str, golds: &Golds) -> Result<(), String> {
+        Self::write_json(&self.game_dir(game_id).join("golds.json"), golds)
+    }
+
+    fn load_golds(&self, game_id: &str) -> Result<Golds, String> {
+        Self::read_json(&self.game_dir(game_id).join("golds.json"))
+    }
+
+    fn save_statistics(&mut self, game_id: &str, statistics: &RunStatistics) -> Result<(), String> {
+        Self::write_json(&self.game_dir(game_id).join("statistics.json"), statistics)
+    }
+
+    fn load_statistics(&self, game_id: &str) -> Result<RunStatistics, String> {
+        Self::read_json(&self.game_dir(game_id).join("statistics.json"))
+    }
+}
+
+/// SQLite database with `runs`, `golds` and `statistics` tables
+#[cfg(feature = "sqlite")]
+pub struct SqliteStorage {
+    connection: rusqlite::Connection,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteStorage {
+    /// Open (or create) a database file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let connection = rusqlite::Connection::open(path.as_ref())
+            .map_err(|e| format!("Failed to open {}: {}", path.as_ref().display(), e))?;
+        Self::with_connection(connection)
+    }
+
+    /// Use an existing connection (e.g., the host's database or `open_in_memory`)
+    pub fn with_connection(connection: rusqlite::Connection) -> Result<Self, String> {
+        connection
+            .execute_batch(
+                "CREATE TABLE IF NOT EXISTS runs (
+                     game_id TEXT NOT NULL,
+                     saved_at_ms INTEGER NOT NULL,
+                     entries TEXT NOT NULL
+                 );
+                 CREATE TABLE IF NOT EXISTS golds (
+                     game_id TEXT NOT NULL,
+                     split TEXT NOT NULL,
+                     igt_ms INTEGER NOT NULL,
+                     PRIMARY KEY (game_id, split)
+                 );
+                 CREATE TABLE IF NOT EXISTS statistics (
+                     game_id TEXT PRIMARY KEY,
+                     data TEXT NOT NULL
+                 );",
+            )
+            .map_err(|e| format!("Failed to create tables: {}", e))?;
+        Ok(Self { connection })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl Storage for SqliteStorage {
+    fn save_run(&mut self, run: &StoredRun) -> Result<(), String> {
+        let entries = serde_json::to_string(&run.entries).map_err(|e| e.to_string())?;
+        self.connection
+            .execute(
+                "INSERT INTO runs (game_id, saved_at_ms, entries) VALUES (?1, ?2, ?3)",
+                rusqlite::params![run.game_id, run.saved_at_ms as i64, entries],
+            )
+            .map_err(|e| format!("Failed to save run: {}", e))?;
+        Ok(())
+    }
+
+    fn load_runs(&self, game_id: &str) -> Result<Vec<StoredRun>, String> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT saved_at_ms, entries FROM runs WHERE game_id = ?1 ORDER BY saved_at_ms")
+            .map_err(|e| e.to_string())?;
+        let rows = statement
+            .query_map([game_id], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+            .map_err(|e| format!("Failed to load runs: {}", e))?;
+
+        let mut runs = Vec::new();
+        for row in rows {
+            let (saved_at_ms, entries) = row.map_err(|e| e.to_string())?;
+            runs.push(StoredRun {
+                game_id: game_id.to_string(),
+                saved_at_ms: saved_at_ms as u64,
+                entries: serde_json::from_str(&entries).map_err(|e| e.to_string())?,
+            });
+        }
+        Ok(runs)
+    }
+
+    fn save_golds(&mut self, game_id: &str, golds: &Golds) -> Result<(), String> {
+        let transaction = self.connection.transaction().map_err(|e| e.to_string())?;
+        for (split, igt_ms) in golds {
+            transaction
+                .execute(
+                    "INSERT OR REPLACE INTO golds (game_id, split, igt_ms) VALUES (?1, ?2, ?3)",
+                    rusqlite::params![game_id, split, igt_ms],
+                )
+                .map_err(|e| format!("Failed to save golds: {}", e))?;
+        }
+        transaction.commit().map_err(|e| e.to_string())
+    }
+
+    fn load_golds(&self, game_id: &str) -> Result<Golds, String> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT split, igt_ms FROM golds WHERE game_id = ?1")
+            .map_err(|e| e.to_string())?;
+        let rows = statement
+            .query_map([game_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))
+            .map_err(|e| format!("Failed to load golds: {}", e))?;
+        rows.collect::<Result<Golds, _>>().map_err(|e| e.to_string())
+    }
+
+    fn save_statistics(&mut self, game_id: &str, statistics: &RunStatistics) -> Result<(), String> {
+        let data = serde_json::to_string(statistics).map_err(|e| e.to_string())?;
+        self.connection
+            .execute(
+                "INSERT OR REPLACE INTO statistics (game_id, data) VALUES (?1, ?2)",
+                rusqlite::params![game_id, data],
+            )
+            .map_err(|e| format!("Failed to save statistics: {}", e))?;
+        Ok(())
+    }
+
+    fn load_statistics(&self, game_id: &str) -> Result<RunStatistics, String> {
+        let data: Option<String> = self
+            .connection
+            .query_row("SELECT data FROM statistics WHERE game_id = ?1", [game_id], |row| row.get(0))
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                e => Err(format!("Failed to load statistics: {}", e)),
+            })?;
+        match data {
+            Some(data) => serde_json::from_str(&data).map_err(|e| e.to_string()),
+            None => Ok(RunStatistics::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(boss_id: &str, igt_ms: Option<i64>) -> RunLogEntry {
+        RunLogEntry {
+            elapsed_ms: 0,
+            igt_ms,
+            host_ms: None,
+            menu_time_ms: None,
+            event: RunEvent::BossDefeated {
+                boss_id: boss_id.to_string(),
+                boss_name: boss_id.to_string(),
+                flag_id: 0,
+                suspicion: None,
+                display_name: None,
+                metadata: Default::default(),
+            },
+        }
+    }
+
+    fn run(saved_at_ms: u64, times: &[(&str, i64)]) -> StoredRun {
+        StoredRun {
+            game_id: "DarkSouls3".to_string(),
+            saved_at_ms,
+            entries: times.iter().map(|(id, igt)| split(id, Some(*igt))).collect(),
+        }
+    }
+
+    fn exercise(storage: &mut dyn Storage) {
+        let new_golds = record_run(storage, &run(1, &[("iudex", 60_000), ("vordt", 300_000)])).unwrap();
+        assert_eq!(new_golds, vec!["iudex".to_string(), "vordt".to_string()]);
+
+        // Slower Iudex, faster Vordt segment
+        let new_golds = record_run(storage, &run(2, &[("iudex", 70_000), ("vordt", 250_000)])).unwrap();
+        assert_eq!(new_golds, vec!["vordt".to_string()]);
+
+        let golds = storage.load_golds("DarkSouls3").unwrap();
+        assert_eq!(golds.get("iudex"), Some(&60_000));
+        assert_eq!(golds.get("vordt"), Some(&180_000));
+
+        let statistics = storage.load_statistics("DarkSouls3").unwrap();
+        assert_eq!(statistics.attempts, 2);
+        assert_eq!(statistics.split_counts.get("vordt"), Some(&2));
+
+        let runs = storage.load_runs("DarkSouls3").unwrap();
+        assert_eq!(runs.iter().map(|r| r.saved_at_ms).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(storage.load_runs("EldenRing").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_segment_times_and_current_attempt() {
+        let entries = vec![
+            split("iudex", Some(50_000)),
+            RunLogEntry { elapsed_ms: 0, igt_ms: None, host_ms: None, menu_time_ms: None, event: RunEvent::Reset },
+            split("iudex", Some(60_000)),
+            split("no_igt", None),
+            split("vordt", Some(300_000)),
+        ];
+
+        let attempt = current_attempt(&entries);
+        assert_eq!(attempt.len(), 3);
+        assert_eq!(
+            segment_times(attempt),
+            vec![("iudex".to_string(), 60_000), ("vordt".to_string(), 240_000)]
+        );
+    }
+
+    #[test]
+    fn test_memory_storage() {
+        exercise(&mut MemoryStorage::new());
+    }
+
+    #[test]
+    fn test_json_file_storage() {
+        let dir = std::env::temp_dir().join(format!("nyacore-storage-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+
+        exercise(&mut JsonFileStorage::new(&dir));
+        assert!(dir.join("DarkSouls3").join("golds.json").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_storage() {
+        let connection = rusqlite::Connection::open_in_memory().unwrap();
+        exercise(&mut SqliteStorage::with_connection(connection).unwrap());
+    }
+}
@@ -0,0 +1,295 @@
+//! Timing rules: segments between a start and an end anchor
+//!
+//! Boss flags and custom triggers split at the instant a flag or condition
+//! changes. Some community categories time segments differently, e.g. "from
+//! the Vordt kill flag to the first load after the Dancer kill". A timing
+//! rule declares both boundaries as anchors; the segment starts at the first
+//! start anchor and ends at the first end anchor after that, and is logged
+//! as a [`RunEvent::SegmentTimed`] split.
+//!
+//! Anchors are edges observed between ticks: a flag becoming set, the
+//! "loading" or "blackscreen" attribute turning on or off, or the player
+//! entering an area. An anchor can additionally require a flag to be set
+//! when the edge happens, which expresses "first load after X". Each rule is
+//! timed once per attempt. A segment in progress survives a game restart;
+//! edges are only detected from the second tick after an attach, so a flag
+//! that is already set in the save does not start or end a segment.
+
+use crate::run_log::RunEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Edge that marks a segment boundary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum AnchorEvent {
+    /// Event flag went from unset to set
+    FlagSet { flag_id: u32 },
+    /// "loading" attribute turned on
+    LoadStart,
+    /// "loading" attribute turned off
+    LoadEnd,
+    /// "blackscreen" attribute turned on
+    BlackscreenStart,
+    /// "blackscreen" attribute turned off
+    BlackscreenEnd,
+    /// Player entered an area (games exposing a map id, e.g. Elden Ring
+    /// `area` 10 = Stormveil Castle); `block` narrows it to one map
+    AreaEntered {
+        area: u8,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        block: Option<u8>,
+    },
+}
+
+/// A segment boundary
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Anchor {
+    pub on: AnchorEvent,
+    /// Only count the edge while this flag is set ("first load after X")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_flag: Option<u32>,
+}
+
+/// A segment timed from its start anchor to its end anchor
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct TimingRule {
+    /// Split key of the segment (comparisons, attempt history)
+    pub id: String,
+    pub name: String,
+    pub start: Anchor,
+    pub end: Anchor,
+}
+
+impl TimingRule {
+    /// Flags read to evaluate the rule's anchors
+    pub fn flag_ids(&self) -> Vec<u32> {
+        [&self.start, &self.end]
+            .iter()
+            .flat_map(|anchor| {
+                let edge = match anchor.on {
+                    AnchorEvent::FlagSet { flag_id } => Some(flag_id),
+                    _ => None,
+                };
+                edge.into_iter().chain(anchor.after_flag)
+            })
+            .collect()
+    }
+}
+
+/// What the anchors are evaluated against on one tick
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Observation {
+    pub loading: Option<bool>,
+    pub blackscreen: Option<bool>,
+    /// (area, block) of the current map
+    pub area: Option<(u8, u8)>,
+    pub flags: HashMap<u32, bool>,
+}
+
+impl Observation {
+    /// Whether `anchor` fires between the previous observation and this one
+    fn fires(&self, previous: &Observation, anchor: &Anchor) -> bool {
+        if let Some(flag_id) = anchor.after_flag {
+            if self.flags.get(&flag_id) != Some(&true) {
+                return false;
+            }
+        }
+
+        let turned = |before: Option<bool>, after: Option<bool>, on: bool| {
+            matches!((before, after), (Some(b), Some(a)) if b != on && a == on)
+        };
+        match anchor.on {
+            AnchorEvent::FlagSet { flag_id } => turned(
+                previous.flags.get(&flag_id).copied(),
+                self.flags.get(&flag_id).copied(),
+                true,
+            ),
+            AnchorEvent::LoadStart => turned(previous.loading, self.loading, true),
+            AnchorEvent::LoadEnd => turned(previous.loading, self.loading, false),
+            AnchorEvent::BlackscreenStart => turned(previous.blackscreen, self.blackscreen, true),
+            AnchorEvent::BlackscreenEnd => turned(previous.blackscreen, self.blackscreen, false),
+            AnchorEvent::AreaEntered { area, block } => {
+                let inside = |current: Option<(u8, u8)>| {
+                    current.map(|(a, b)| a == area && block.is_none_or(|block| block == b))
+                };
+                turned(inside(previous.area), inside(self.area), true)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Progress {
+    Started { elapsed_ms: u64, igt_ms: Option<i64> },
+    Finished,
+}
+
+/// Per-attempt progress of the timing rules
+#[derive(Debug, Default)]
+pub struct RuleTimer {
+    previous: Option<Observation>,
+    progress: HashMap<String, Progress>,
+}
+
+impl RuleTimer {
+    /// Forget the previous observation (after attaching); segments in
+    /// progress keep running
+    pub fn detach(&mut self) {
+        self.previous = None;
+    }
+
+    /// Start over for a new attempt
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether any rule still needs observations
+    pub fn is_pending(&self, rules: &[TimingRule]) -> bool {
+        rules.iter().any(|rule| self.progress.get(&rule.id) != Some(&Progress::Finished))
+    }
+
+    /// Evaluate the rules against a new observation; returns the segments
+    /// that ended. A rule's end anchor is only checked from the tick after
+    /// its segment started.
+    pub fn observe(
+        &mut self,
+        rules: &[TimingRule],
+        observation: Observation,
+        elapsed_ms: u64,
+        igt_ms: Option<i64>,
+    ) -> Vec<RunEvent> {
+        let Some(previous) = self.previous.replace(observation) else {
+            return Vec::new();
+        };
+        let current = self.previous.as_ref().unwrap();
+
+        let mut segments = Vec::new();
+        for rule in rules {
+            match self.progress.get(&rule.id).copied() {
+                None => {
+                    if current.fires(&previous, &rule.start) {
+                        log::info!("Segment '{}' started", rule.name);
+                        self.progress.insert(rule.id.clone(), Progress::Started { elapsed_ms, igt_ms });
+                    }
+                }
+                Some(Progress::Started { elapsed_ms: start_ms, igt_ms: start_igt }) => {
+                    if current.fires(&previous, &rule.end) {
+                        let duration_ms = elapsed_ms.saturating_sub(start_ms);
+                        log::info!("Segment '{}' ended after {} ms", rule.name, duration_ms);
+                        self.progress.insert(rule.id.clone(), Progress::Finished);
+                        segments.push(RunEvent::SegmentTimed {
+                            rule_id: rule.id.clone(),
+                            name: rule.name.clone(),
+                            start_elapsed_ms: start_ms,
+                            duration_ms,
+                            igt_duration_ms: igt_ms.zip(start_igt).map(|(end, start)| end - start),
+                        });
+                    }
+                }
+                Some(Progress::Finished) => {}
+            }
+        }
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(start: Anchor, end: Anchor) -> TimingRule {
+        TimingRule {
+            id: "vordt_to_dancer".to_string(),
+            name: "Vordt to Dancer".to_string(),
+            start,
+            end,
+        }
+    }
+
+    fn observation(loading: bool, flags: &[(u32, bool)]) -> Observation {
+        Observation {
+            loading: Some(loading),
+            flags: flags.iter().copied().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_flag_to_first_load_after_flag() {
+        let rules = [rule(
+            Anchor { on: AnchorEvent::FlagSet { flag_id: 14000800 }, after_flag: None },
+            Anchor { on: AnchorEvent::LoadStart, after_flag: Some(14100800) },
+        )];
+        let mut timer = RuleTimer::default();
+
+        assert!(timer.observe(&rules, observation(false, &[(14000800, false), (14100800, false)]), 0, Some(0)).is_empty());
+        // Vordt killed: segment starts
+        assert!(timer.observe(&rules, observation(false, &[(14000800, true), (14100800, false)]), 1000, Some(900)).is_empty());
+        // A load before Dancer does not end it
+        assert!(timer.observe(&rules, observation(true, &[(14000800, true), (14100800, false)]), 2000, Some(1800)).is_empty());
+        assert!(timer.observe(&rules, observation(false, &[(14000800, true), (14100800, true)]), 5000, Some(4500)).is_empty());
+        // First load after Dancer
+        let segments = timer.observe(&rules, observation(true, &[(14000800, true), (14100800, true)]), 6000, Some(5400));
+        assert_eq!(
+            segments,
+            vec![RunEvent::SegmentTimed {
+                rule_id: "vordt_to_dancer".to_string(),
+                name: "Vordt to Dancer".to_string(),
+                start_elapsed_ms: 1000,
+                duration_ms: 5000,
+                igt_duration_ms: Some(4500),
+            }]
+        );
+        assert!(!timer.is_pending(&rules));
+
+        // Timed once per attempt
+        assert!(timer.observe(&rules, observation(false, &[(14000800, true), (14100800, true)]), 7000, None).is_empty());
+        assert!(timer.observe(&rules, observation(true, &[(14000800, true), (14100800, true)]), 8000, None).is_empty());
+    }
+
+    #[test]
+    fn test_flags_set_on_attach_do_not_fire() {
+        let rules = [rule(
+            Anchor { on: AnchorEvent::FlagSet { flag_id: 14000800 }, after_flag: None },
+            Anchor { on: AnchorEvent::LoadEnd, after_flag: None },
+        )];
+        let mut timer = RuleTimer::default();
+
+        timer.observe(&rules, observation(true, &[(14000800, true)]), 0, None);
+        assert!(timer.observe(&rules, observation(false, &[(14000800, true)]), 100, None).is_empty());
+        assert!(timer.is_pending(&rules));
+    }
+
+    #[test]
+    fn test_area_entry_and_blackscreen() {
+        let rules = [rule(
+            Anchor { on: AnchorEvent::AreaEntered { area: 10, block: None }, after_flag: None },
+            Anchor { on: AnchorEvent::BlackscreenEnd, after_flag: None },
+        )];
+        let mut timer = RuleTimer::default();
+        let at = |area: u8, blackscreen: bool| Observation {
+            blackscreen: Some(blackscreen),
+            area: Some((area, 0)),
+            ..Default::default()
+        };
+
+        timer.observe(&rules, at(60, false), 0, None);
+        assert!(timer.observe(&rules, at(10, true), 500, None).is_empty());
+        // Detaching keeps the segment running but needs a fresh observation
+        timer.detach();
+        assert!(timer.observe(&rules, at(10, false), 900, None).is_empty());
+        assert!(timer.observe(&rules, at(10, true), 1000, None).is_empty());
+        let segments = timer.observe(&rules, at(10, false), 1500, None);
+        assert!(matches!(
+            segments.as_slice(),
+            [RunEvent::SegmentTimed { start_elapsed_ms: 500, duration_ms: 1000, igt_duration_ms: None, .. }]
+        ));
+
+        timer.reset();
+        assert!(timer.is_pending(&rules));
+    }
+}
@@ -0,0 +1,222 @@
+//! TypeScript definitions for the JSON models
+//!
+//! Generates a `.d.ts` file from the serde models returned over FFI (state,
+//! triggers, config, run log, position trail and capabilities) so web-based hosts stay in sync
+//! with the Rust types. Only built with the `typescript` feature.
+//!
+//! The definitions are written to `bindings/nyacore-autosplitter.d.ts` by
+//! `cargo test --features typescript`.
+
+use crate::ac6_arena::ArenaOpponent;
+use crate::bundle::BundleManifest;
+use crate::capabilities::Capabilities;
+use crate::config::{AutosplitterConfig, AutosplitterState, CustomTrigger};
+use crate::demo::DemoScript;
+use crate::ds2_triggers::Ds2Landmark;
+use crate::flag_history::FlagHistorySegment;
+use crate::flag_ids::{Ds3FlagId, ErFlagId};
+use crate::output::OutputMessage;
+use crate::position_trail::TrailExport;
+use crate::run_log::RunLogEntry;
+use crate::sekiro_gauntlet::Gauntlet;
+use crate::sekiro_idols::SculptorsIdol;
+use crate::watches::Watch;
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+
+/// Path of the generated definitions, relative to the crate root
+pub const OUTPUT_PATH: &str = "bindings/nyacore-autosplitter.d.ts";
+
+/// Generate TypeScript definitions for every JSON model
+pub fn generate() -> String {
+    let settings = SchemaSettings::draft07().with(|s| {
+        s.option_nullable = false;
+        s.option_add_null_type = true;
+    });
+    let mut gen = SchemaGenerator::new(settings);
+
+    // Registers each root type (and everything it references) as a definition
+    gen.subschema_for::<AutosplitterState>();
+    gen.subschema_for::<AutosplitterConfig>();
+    gen.subschema_for::<CustomTrigger>();
+    gen.subschema_for::<RunLogEntry>();
+    gen.subschema_for::<Capabilities>();
+    gen.subschema_for::<TrailExport>();
+    gen.subschema_for::<ArenaOpponent>();
+    gen.subschema_for::<OutputMessage>();
+    gen.subschema_for::<Gauntlet>();
+    gen.subschema_for::<SculptorsIdol>();
+    gen.subschema_for::<Ds2Landmark>();
+    gen.subschema_for::<Ds3FlagId>();
+    gen.subschema_for::<ErFlagId>();
+    gen.subschema_for::<BundleManifest>();
+    gen.subschema_for::<FlagHistorySegment>();
+    gen.subschema_for::<Watch>();
+    gen.subschema_for::<DemoScript>();
+
+    let mut definitions: Vec<_> = gen.definitions().iter().collect();
+    definitions.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut out = String::new();
+    out.push_str("// Generated by nyacore-autosplitter. Do not edit by hand.\n");
+    out.push_str("// Regenerate with `cargo test --features typescript`.\n");
+
+    for (name, schema) in definitions {
+        out.push('\n');
+        if let Some(description) = description(schema) {
+            out.push_str(&format!("/** {} */\n", description));
+        }
+        out.push_str(&format!("export type {} = {};\n", name, ts_type(schema)));
+    }
+
+    out
+}
+
+fn description(schema: &Schema) -> Option<String> {
+    match schema {
+        Schema::Object(obj) => obj
+            .metadata
+            .as_ref()
+            .and_then(|m| m.description.as_ref())
+            .map(|d| d.replace("*/", "*\\/").replace('\n', " ")),
+        Schema::Bool(_) => None,
+    }
+}
+
+fn ts_type(schema: &Schema) -> String {
+    match schema {
+        Schema::Bool(true) => "unknown".to_string(),
+        Schema::Bool(false) => "never".to_string(),
+        Schema::Object(obj) => object_type(obj),
+    }
+}
+
+fn object_type(obj: &SchemaObject) -> String {
+    if let Some(reference) = &obj.reference {
+        return reference.rsplit('/').next().unwrap_or(reference).to_string();
+    }
+
+    if let Some(value) = &obj.const_value {
+        return value.to_string();
+    }
+
+    if let Some(values) = &obj.enum_values {
+        return union(values.iter().map(|v| v.to_string()).collect());
+    }
+
+    if let Some(subschemas) = &obj.subschemas {
+        let variants = subschemas
+            .one_of
+            .as_ref()
+            .or(subschemas.any_of.as_ref())
+            .map(|schemas| union(schemas.iter().map(ts_type).collect()));
+        let all_of = subschemas
+            .all_of
+            .as_ref()
+            .map(|schemas| schemas.iter().map(ts_type).collect::<Vec<_>>().join(" & "));
+
+        match (variants, all_of) {
+            (Some(v), Some(a)) => return format!("({}) & {}", v, a),
+            (Some(v), None) => return v,
+            (None, Some(a)) => return a,
+            (None, None) => {}
+        }
+    }
+
+    match &obj.instance_type {
+        Some(SingleOrVec::Single(instance_type)) => instance_type_ts(obj, instance_type),
+        Some(SingleOrVec::Vec(instance_types)) => union(
+            instance_types
+                .iter()
+                .map(|t| instance_type_ts(obj, t))
+                .collect(),
+        ),
+        None => "unknown".to_string(),
+    }
+}
+
+fn instance_type_ts(obj: &SchemaObject, instance_type: &InstanceType) -> String {
+    match instance_type {
+        InstanceType::Null => "null".to_string(),
+        InstanceType::Boolean => "boolean".to_string(),
+        InstanceType::Integer | InstanceType::Number => "number".to_string(),
+        InstanceType::String => "string".to_string(),
+        InstanceType::Array => {
+            let item = match obj.array.as_ref().and_then(|a| a.items.as_ref()) {
+                Some(SingleOrVec::Single(item)) => ts_type(item),
+                Some(SingleOrVec::Vec(items)) => {
+                    return format!(
+                        "[{}]",
+                        items.iter().map(ts_type).collect::<Vec<_>>().join(", ")
+                    )
+                }
+                None => "unknown".to_string(),
+            };
+            if item.contains(' ') {
+                format!("({})[]", item)
+            } else {
+                format!("{}[]", item)
+            }
+        }
+        InstanceType::Object => object_literal(obj),
+    }
+}
+
+fn object_literal(obj: &SchemaObject) -> String {
+    let Some(object) = &obj.object else {
+        return "Record<string, unknown>".to_string();
+    };
+
+    if object.properties.is_empty() {
+        let value = object
+            .additional_properties
+            .as_deref()
+            .map(ts_type)
+            .unwrap_or_else(|| "unknown".to_string());
+        return format!("Record<string, {}>", value);
+    }
+
+    let fields: Vec<String> = object
+        .properties
+        .iter()
+        .map(|(name, schema)| {
+            let optional = if object.required.contains(name) { "" } else { "?" };
+            let doc = description(schema)
+                .map(|d| format!("/** {} */ ", d))
+                .unwrap_or_default();
+            format!("{}{}{}: {}", doc, name, optional, ts_type(schema))
+        })
+        .collect();
+
+    format!("{{ {} }}", fields.join("; "))
+}
+
+fn union(mut variants: Vec<String>) -> String {
+    variants.dedup();
+    if variants.is_empty() {
+        "never".to_string()
+    } else {
+        variants.join(" | ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_contains_models() {
+        let ts = generate();
+        assert!(ts.contains("export type AutosplitterState = {"));
+        assert!(ts.contains("export type CompareOp = \"eq\" | \"ne\""));
+        assert!(ts.contains("type: \"level_increased\""));
+        assert!(ts.contains("process_id?: number | null"));
+    }
+
+    #[test]
+    fn test_export_bindings() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(OUTPUT_PATH);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, generate()).unwrap();
+    }
+}
@@ -0,0 +1,108 @@
+//! Warp detection
+//!
+//! Dark Souls 3 and Elden Ring store the bonfire or grace the player last
+//! rested at, and overwrite it with the destination when a warp is chosen
+//! from the travel menu. A change of that value alone is not a warp (resting
+//! at a new bonfire changes it too), so a warp is reported when the value
+//! changes within [`WARP_LOAD_WINDOW_MS`] of a load or blackscreen starting.
+//!
+//! Warping to the bonfire the player last rested at does not change the value
+//! and is not detected. Resting at a new bonfire and quitting out right after
+//! can be reported as a warp.
+
+use crate::run_log::RunEvent;
+
+/// Max time between the destination change and the start of the load
+pub const WARP_LOAD_WINDOW_MS: u64 = 5000;
+
+/// Per-attach warp bookkeeping
+#[derive(Debug, Default)]
+pub struct WarpTracker {
+    destination: Option<u32>,
+    /// Destination change not yet matched to a load: (destination, elapsed_ms)
+    changed: Option<(u32, u64)>,
+    in_transition: bool,
+    transition_started_ms: Option<u64>,
+}
+
+impl WarpTracker {
+    /// Feed the last rested/warp destination id (None while unreadable) and
+    /// whether a load or blackscreen is active; returns a warp event when the
+    /// destination changed around the start of a transition
+    pub fn observe(&mut self, destination: Option<u32>, transition: bool, elapsed_ms: u64) -> Option<RunEvent> {
+        if transition && !self.in_transition {
+            self.transition_started_ms = Some(elapsed_ms);
+        }
+        self.in_transition = transition;
+
+        // An unreadable value (e.g., during the load) keeps the last one
+        if let Some(destination) = destination {
+            if self.destination.replace(destination).is_some_and(|previous| previous != destination) {
+                self.changed = Some((destination, elapsed_ms));
+            }
+        }
+
+        let (destination, changed_ms) = self.changed?;
+        if elapsed_ms.saturating_sub(changed_ms) > WARP_LOAD_WINDOW_MS {
+            self.changed = None;
+            return None;
+        }
+        // The destination changes while choosing the warp or during its load
+        let started_ms = self.transition_started_ms.filter(|_| self.in_transition)?;
+        if started_ms.abs_diff(changed_ms) > WARP_LOAD_WINDOW_MS {
+            return None;
+        }
+
+        self.changed = None;
+        self.transition_started_ms = None;
+        Some(RunEvent::WarpInitiated { destination })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIRELINK: u32 = 4001950;
+    const HIGH_WALL: u32 = 4001951;
+
+    #[test]
+    fn test_destination_change_then_load() {
+        let mut tracker = WarpTracker::default();
+        assert_eq!(tracker.observe(Some(HIGH_WALL), false, 0), None);
+        // Warp chosen: the destination changes, the load follows the fade
+        assert_eq!(tracker.observe(Some(FIRELINK), false, 1000), None);
+        assert_eq!(
+            tracker.observe(Some(FIRELINK), true, 2500),
+            Some(RunEvent::WarpInitiated { destination: FIRELINK })
+        );
+        // Reported once
+        assert_eq!(tracker.observe(None, true, 3000), None);
+        assert_eq!(tracker.observe(Some(FIRELINK), false, 9000), None);
+    }
+
+    #[test]
+    fn test_load_then_destination_change() {
+        let mut tracker = WarpTracker::default();
+        tracker.observe(Some(HIGH_WALL), false, 0);
+        assert_eq!(tracker.observe(Some(HIGH_WALL), true, 1000), None);
+        assert_eq!(
+            tracker.observe(Some(FIRELINK), true, 1200),
+            Some(RunEvent::WarpInitiated { destination: FIRELINK })
+        );
+    }
+
+    #[test]
+    fn test_resting_and_reloading_are_not_warps() {
+        let mut tracker = WarpTracker::default();
+        // First sample after attaching
+        assert_eq!(tracker.observe(Some(HIGH_WALL), true, 0), None);
+        tracker.observe(Some(HIGH_WALL), false, 100);
+        // Resting at a new bonfire, no load
+        assert_eq!(tracker.observe(Some(FIRELINK), false, 1000), None);
+        assert_eq!(tracker.observe(Some(FIRELINK), false, 5000), None);
+        // A death reload long after the rest
+        assert_eq!(tracker.observe(Some(FIRELINK), false, 7000), None);
+        assert_eq!(tracker.observe(Some(FIRELINK), true, 8000), None);
+    }
+}
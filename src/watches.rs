@@ -0,0 +1,256 @@
+//! Host-registered value watches
+//!
+//! Advanced hosts can watch arbitrary memory without waiting for a new
+//! trigger type: a [`Watch`] names a pointer chain from the main module, the
+//! type of the value at its end and optionally a comparison. Watches are
+//! added and removed at runtime over FFI; every change of a watched value
+//! (that passes the comparison, if any) is logged as a `value_changed` event,
+//! which hosts receive with the other run events.
+//!
+//! Chains resolve like `memory::search::PointerChain`, so chains found with
+//! the pointer scanner can be watched directly. Links are read with the
+//! pointer width of the game's image (4 bytes for 32-bit games such as DS1
+//! PTDE, 8 otherwise), like `memory::Pointer` does with `is_64_bit`.
+
+use crate::config::CompareOp;
+use crate::run_log::RunEvent;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Max offsets in a watch's pointer chain
+pub const MAX_CHAIN_DEPTH: usize = 8;
+
+/// How the bytes at the end of a chain are read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub enum WatchType {
+    U8,
+    U16,
+    I32,
+    U32,
+    I64,
+    /// Truncated to an integer
+    F32,
+}
+
+impl WatchType {
+    fn size(self) -> usize {
+        match self {
+            WatchType::U8 => 1,
+            WatchType::U16 => 2,
+            WatchType::I32 | WatchType::U32 | WatchType::F32 => 4,
+            WatchType::I64 => 8,
+        }
+    }
+
+    fn decode(self, bytes: &[u8]) -> i64 {
+        let mut raw = [0u8; 8];
+        raw[..bytes.len()].copy_from_slice(bytes);
+        match self {
+            WatchType::U8 => raw[0] as i64,
+            WatchType::U16 => u16::from_le_bytes([raw[0], raw[1]]) as i64,
+            WatchType::I32 => i32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as i64,
+            WatchType::U32 => u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as i64,
+            WatchType::I64 => i64::from_le_bytes(raw),
+            WatchType::F32 => f32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as i64,
+        }
+    }
+}
+
+/// A value watched for changes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "typescript", derive(schemars::JsonSchema))]
+pub struct Watch {
+    pub id: String,
+    /// Offset of the static pointer from the main module base
+    pub module_offset: usize,
+    /// Each offset but the last is added and dereferenced; the last is added
+    /// to get the value address (empty reads the value at the module offset)
+    #[serde(default)]
+    pub offsets: Vec<usize>,
+    pub value_type: WatchType,
+    /// Only report changes to values passing `compare` against `value`
+    #[serde(default)]
+    pub compare: Option<CompareOp>,
+    #[serde(default)]
+    pub value: i64,
+}
+
+impl Watch {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.is_empty() {
+            return Err("Watch id must not be empty".to_string());
+        }
+        if self.offsets.len() > MAX_CHAIN_DEPTH {
+            return Err(format!(
+                "Watch '{}': pointer chain has {} offsets (max {})",
+                self.id,
+                self.offsets.len(),
+                MAX_CHAIN_DEPTH
+            ));
+        }
+        Ok(())
+    }
+
+    /// Follow the chain from `module_base` with `pointer_size`-byte links
+    /// and read the value
+    pub fn read(
+        &self,
+        module_base: usize,
+        pointer_size: usize,
+        read: &impl Fn(usize, usize) -> Option<Vec<u8>>,
+    ) -> Option<i64> {
+        read_chain(module_base, pointer_size, self.module_offset, &self.offsets, self.value_type, read)
+    }
+}
+
+/// Follow a pointer chain from `module_base + module_offset` and read the
+/// value at its end (see `Watch::offsets`); links are 4-byte pointers when
+/// `pointer_size` is 4 and 8-byte pointers otherwise
+pub(crate) fn read_chain(
+    module_base: usize,
+    pointer_size: usize,
+    module_offset: usize,
+    offsets: &[usize],
+    value_type: WatchType,
+    read: &impl Fn(usize, usize) -> Option<Vec<u8>>,
+) -> Option<i64> {
+    let mut address = module_base.checked_add(module_offset)?;
+    for offset in offsets {
+        let pointer = match pointer_size {
+            4 => u32::from_le_bytes(read(address, 4)?.try_into().ok()?) as usize,
+            _ => u64::from_le_bytes(read(address, 8)?.try_into().ok()?) as usize,
+        };
+        if pointer == 0 {
+            return None;
+        }
+        address = pointer.checked_add(*offset)?;
+    }
+
+    let size = value_type.size();
+    let bytes = read(address, size)?;
+    (bytes.len() == size).then(|| value_type.decode(&bytes))
+}
+
+/// Per-attach watch bookkeeping
+#[derive(Debug, Default)]
+pub struct WatchTracker {
+    module_base: usize,
+    /// Pointer size of the game (see `memory::arch::module_pointer_size`)
+    pointer_size: usize,
+    /// Last readable value of each watch
+    values: HashMap<String, i64>,
+}
+
+impl WatchTracker {
+    pub fn new(module_base: usize, pointer_size: usize) -> Self {
+        Self {
+            module_base,
+            pointer_size,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Read every watch; returns a `value_changed` event per reported
+    /// change. The first value read of a watch is its baseline, and an
+    /// unreadable value keeps the last one.
+    pub fn observe(&mut self, watches: &[Watch], read: impl Fn(usize, usize) -> Option<Vec<u8>>) -> Vec<RunEvent> {
+        self.values.retain(|id, _| watches.iter().any(|watch| &watch.id == id));
+
+        let mut events = Vec::new();
+        for watch in watches {
+            let Some(value) = watch.read(self.module_base, self.pointer_size, &read) else {
+                continue;
+            };
+            let Some(previous) = self.values.insert(watch.id.clone(), value) else {
+                continue;
+            };
+            if previous == value || watch.compare.is_some_and(|op| !op.compare(value, watch.value)) {
+                continue;
+            }
+            events.push(RunEvent::ValueChanged {
+                watch_id: watch.id.clone(),
+                from: previous,
+                to: value,
+            });
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: usize = 0x140000000;
+
+    fn memory(health: u32) -> HashMap<usize, Vec<u8>> {
+        HashMap::from([
+            (BASE + 0x3d5000, 0x7ff000000u64.to_le_bytes().to_vec()),
+            (0x7ff000080, 0x7ff100000u64.to_le_bytes().to_vec()),
+            (0x7ff1000d8, health.to_le_bytes().to_vec()),
+        ])
+    }
+
+    fn health_watch(compare: Option<CompareOp>) -> Watch {
+        Watch {
+            id: "hp".to_string(),
+            module_offset: 0x3d5000,
+            offsets: vec![0x80, 0xd8],
+            value_type: WatchType::U32,
+            compare,
+            value: 0,
+        }
+    }
+
+    #[test]
+    fn test_pointer_chain_read() {
+        let memory = memory(454);
+        let read = |address: usize, size: usize| memory.get(&address).map(|bytes| bytes[..size].to_vec());
+        assert_eq!(health_watch(None).read(BASE, 8, &read), Some(454));
+        // Broken chain
+        assert_eq!(health_watch(None).read(BASE + 8, 8, &read), None);
+    }
+
+    #[test]
+    fn test_pointer_chain_read_32_bit() {
+        const BASE_32: usize = 0x400000;
+        // The next 4 bytes after each link are garbage a 64-bit read would pick up
+        let memory = HashMap::from([
+            (BASE_32 + 0xf7d7c, vec![0x00, 0x10, 0x20, 0x05, 0xff, 0xff, 0xff, 0xff]),
+            (0x5201080, vec![0x00, 0x30, 0x40, 0x05, 0xff, 0xff, 0xff, 0xff]),
+            (0x54030d8, 454u32.to_le_bytes().to_vec()),
+        ]);
+        let read = |address: usize, size: usize| memory.get(&address).and_then(|bytes| bytes.get(..size)).map(<[u8]>::to_vec);
+        let watch = Watch {
+            module_offset: 0xf7d7c,
+            ..health_watch(None)
+        };
+        assert_eq!(watch.read(BASE_32, 4, &read), Some(454));
+        assert_eq!(watch.read(BASE_32, 8, &read), None);
+    }
+
+    #[test]
+    fn test_change_events() {
+        let watches = [health_watch(Some(CompareOp::Eq))];
+        let mut tracker = WatchTracker::new(BASE, 8);
+        let observe = |tracker: &mut WatchTracker, health: u32| {
+            let memory = memory(health);
+            tracker.observe(&watches, |address, size| memory.get(&address).map(|bytes| bytes[..size].to_vec()))
+        };
+
+        // Baseline, then a change that fails the comparison (== 0)
+        assert!(observe(&mut tracker, 454).is_empty());
+        assert!(observe(&mut tracker, 120).is_empty());
+        assert_eq!(
+            observe(&mut tracker, 0),
+            [RunEvent::ValueChanged {
+                watch_id: "hp".to_string(),
+                from: 120,
+                to: 0,
+            }]
+        );
+        assert!(observe(&mut tracker, 0).is_empty());
+    }
+}